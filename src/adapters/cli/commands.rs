@@ -3,10 +3,17 @@
 //! Implementation of all CLI commands for the Butters trading bot.
 
 use clap::{Parser, Subcommand};
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use std::path::PathBuf;
 
+use crate::adapters::market_data::ReplayPriceSource;
+use crate::application::{
+    BacktestConfig, Backtester, McTrade, MonteCarloConfig, MonteCarloSimulator, OptimizerConfig, ParamRange,
+    SearchMode, WalkForwardOptimizer,
+};
+use crate::config::load_config;
 use crate::meme::commands::MemeCmd;
+use crate::strategy::StrategyConfig;
 
 /// Butters - Mean Reversion DEX Trading Bot for Solana/Jupiter
 #[derive(Parser, Debug)]
@@ -50,6 +57,12 @@ pub enum Command {
     /// Run backtesting on historical data
     Backtest(BacktestCmd),
 
+    /// Walk-forward parameter search over a recording
+    Optimize(OptimizeCmd),
+
+    /// Monte Carlo robustness check of backtest or paper trades
+    MonteCarlo(MonteCarloCmd),
+
     /// Resume trading after BalanceGuard halt
     Resume(ResumeCmd),
 
@@ -100,6 +113,14 @@ pub struct StatusCmd {
     /// Output format (text, json, table)
     #[arg(short, long, value_name = "FORMAT", default_value = "text")]
     pub format: String,
+
+    /// Show the paper trading wallet (`[paper] state_file`) instead of the real one
+    #[arg(long)]
+    pub paper: bool,
+
+    /// Write the paper trade history to a JSON file (with --paper)
+    #[arg(long, value_name = "FILE", requires = "paper")]
+    pub export_trades: Option<PathBuf>,
 }
 
 /// Get swap quote
@@ -204,6 +225,122 @@ pub struct BacktestCmd {
     /// Export results to JSON
     #[arg(long, value_name = "FILE")]
     pub export_json: Option<PathBuf>,
+
+    /// Export the ensemble vote breakdown per candle to CSV
+    #[arg(long, value_name = "FILE")]
+    pub export_votes: Option<PathBuf>,
+
+    /// Replay a recording file or directory instead of fetching history
+    #[arg(long, value_name = "PATH")]
+    pub replay: Option<PathBuf>,
+
+    /// Compare the simple, EWMA, robust, Kalman and OU z-score estimators on the same data
+    #[arg(long)]
+    pub compare_zscore: bool,
+}
+
+/// Walk-forward parameter optimization
+#[derive(Parser, Debug)]
+pub struct OptimizeCmd {
+    /// Recording file or directory to optimize on
+    #[arg(long, value_name = "PATH")]
+    pub replay: PathBuf,
+
+    /// Path to configuration file
+    #[arg(short, long, value_name = "FILE", default_value = "config/mainnet.toml")]
+    pub config: PathBuf,
+
+    /// Parameter range as name=min:max:step (repeatable, e.g. z_threshold=1.5:3.0:0.5)
+    #[arg(long = "param", value_name = "RANGE")]
+    pub params: Vec<String>,
+
+    /// Random search with this many samples instead of the full grid
+    #[arg(long, value_name = "SAMPLES")]
+    pub random: Option<usize>,
+
+    /// Seed for random search
+    #[arg(long, value_name = "SEED", default_value = "42")]
+    pub seed: u64,
+
+    /// Score to maximize: return, sharpe, return_over_drawdown
+    #[arg(long, value_name = "OBJECTIVE", default_value = "sharpe")]
+    pub objective: String,
+
+    /// In-sample (fitting) window length
+    #[arg(long, value_name = "HOURS", default_value = "72")]
+    pub in_sample_hours: u64,
+
+    /// Out-of-sample (validation) window length, also the step between windows
+    #[arg(long, value_name = "HOURS", default_value = "24")]
+    pub out_of_sample_hours: u64,
+
+    /// Minimum in-sample trades for a candidate to be scored
+    #[arg(long, value_name = "TRADES", default_value = "5")]
+    pub min_trades: usize,
+
+    /// Starting capital for each backtest
+    #[arg(long, value_name = "AMOUNT", default_value = "10000")]
+    pub capital: f64,
+
+    /// Export the full report to JSON
+    #[arg(long, value_name = "FILE")]
+    pub export_json: Option<PathBuf>,
+}
+
+/// Monte Carlo robustness analysis
+#[derive(Parser, Debug)]
+pub struct MonteCarloCmd {
+    /// Backtest this recording file or directory and resample its trades
+    #[arg(long, value_name = "PATH", conflicts_with = "paper_trades")]
+    pub replay: Option<PathBuf>,
+
+    /// Resample paper trades (trade list or engine state JSON export)
+    #[arg(long, value_name = "FILE", required_unless_present = "replay")]
+    pub paper_trades: Option<PathBuf>,
+
+    /// Path to configuration file
+    #[arg(short, long, value_name = "FILE", default_value = "config/mainnet.toml")]
+    pub config: PathBuf,
+
+    /// Number of simulated trade sequences
+    #[arg(long, value_name = "RUNS", default_value = "10000")]
+    pub runs: usize,
+
+    /// Trades per sequence (default: as many as the source)
+    #[arg(long, value_name = "TRADES")]
+    pub trades_per_run: Option<usize>,
+
+    /// Random seed
+    #[arg(long, value_name = "SEED", default_value = "42")]
+    pub seed: u64,
+
+    /// Starting account in SOL
+    #[arg(long, value_name = "SOL", default_value = "10")]
+    pub capital: f64,
+
+    /// Stake per trade in SOL (default: risk.trade_size_sol)
+    #[arg(long, value_name = "SOL")]
+    pub trade_size: Option<f64>,
+
+    /// Mean slippage per fill (default: half of jupiter.slippage_bps, drawn up to the full tolerance)
+    #[arg(long, value_name = "BPS")]
+    pub slippage_bps: Option<f64>,
+
+    /// Mean fee per fill
+    #[arg(long, value_name = "BPS", default_value = "15")]
+    pub fee_bps: f64,
+
+    /// Probability an entry is missed through latency
+    #[arg(long, value_name = "PROB", default_value = "0.05")]
+    pub miss_probability: f64,
+
+    /// Loss from the starting account that counts as ruin
+    #[arg(long, value_name = "PCT", default_value = "50")]
+    pub ruin_loss_pct: f64,
+
+    /// Export the report to JSON
+    #[arg(long, value_name = "FILE")]
+    pub export_json: Option<PathBuf>,
 }
 
 /// Resume trading after BalanceGuard halt
@@ -237,6 +374,8 @@ pub async fn execute(app: CliApp) -> Result<()> {
         Command::Quote(cmd) => quote_command(cmd).await,
         Command::Swap(cmd) => swap_command(cmd).await,
         Command::Backtest(cmd) => backtest_command(cmd).await,
+        Command::Optimize(cmd) => optimize_command(cmd).await,
+        Command::MonteCarlo(cmd) => monte_carlo_command(cmd).await,
         Command::Resume(cmd) => resume_command(cmd).await,
         Command::Meme(cmd) => crate::meme::execute_meme_command(cmd).await,
     }
@@ -412,6 +551,227 @@ async fn backtest_command(cmd: BacktestCmd) -> Result<()> {
     Ok(())
 }
 
+/// Handle optimize command: walk-forward search over a recording
+pub async fn optimize_command(cmd: OptimizeCmd) -> Result<()> {
+    let config = load_config(&cmd.config)?;
+    let strategy_config = StrategyConfig::from(&config);
+
+    let mut optimizer_config = OptimizerConfig {
+        objective: cmd.objective.parse().map_err(anyhow::Error::msg)?,
+        in_sample: std::time::Duration::from_secs(cmd.in_sample_hours * 3600),
+        out_of_sample: std::time::Duration::from_secs(cmd.out_of_sample_hours * 3600),
+        min_trades: cmd.min_trades,
+        ..Default::default()
+    };
+    if !cmd.params.is_empty() {
+        optimizer_config.ranges = cmd
+            .params
+            .iter()
+            .map(|spec| spec.parse::<ParamRange>().map_err(anyhow::Error::msg))
+            .collect::<Result<_>>()?;
+    }
+    if let Some(samples) = cmd.random {
+        optimizer_config.search = SearchMode::Random { samples, seed: cmd.seed };
+    }
+
+    let source = ReplayPriceSource::from_path(&cmd.replay, &config.tokens.base_mint)
+        .context(format!("Failed to load recording: {}", cmd.replay.display()))?;
+
+    let backtest_config = BacktestConfig {
+        timeframe: config.strategy.timeframe_duration()?,
+        initial_capital: cmd.capital,
+        ..Default::default()
+    };
+
+    // Same regime filter as live trading, on 1-minute candles
+    let optimizer = WalkForwardOptimizer::new(strategy_config, backtest_config, optimizer_config)
+        .with_regime(config.regime.clone(), std::time::Duration::from_secs(60));
+    let candidates = optimizer.candidates()?.len();
+
+    println!(
+        "Walk-forward over {} samples from {}: {} candidates, {}h in-sample / {}h out-of-sample",
+        source.len(),
+        cmd.replay.display(),
+        candidates,
+        cmd.in_sample_hours,
+        cmd.out_of_sample_hours
+    );
+
+    let report = optimizer.run(source.samples())?;
+
+    println!();
+    println!(
+        "  {:<16} {:>9} {:>7} {:>9} {:>9} {:>7} {:>9}  Params",
+        "Out-of-sample", "IS score", "IS n", "IS ret", "OOS score", "OOS n", "OOS ret"
+    );
+    for window in &report.windows {
+        let params: Vec<String> = window.params.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        println!(
+            "  {:<16} {:>9.2} {:>7} {:>+8.2}% {:>9.2} {:>7} {:>+8.2}%  {}",
+            window.out_of_sample_start.format("%Y-%m-%d %H:%M"),
+            window.in_sample.score,
+            window.in_sample.trades,
+            window.in_sample.return_pct,
+            window.out_of_sample.score,
+            window.out_of_sample.trades,
+            window.out_of_sample.return_pct,
+            params.join(" ")
+        );
+    }
+    if report.skipped_windows > 0 {
+        println!("  Skipped {} windows with fewer than {} in-sample trades", report.skipped_windows, cmd.min_trades);
+    }
+
+    println!();
+    println!("  {:<20} {:>10} {:>10} {:>10} {:>12}", "Parameter", "Mean", "Std dev", "Stability", "Recommended");
+    for param in &report.parameters {
+        println!(
+            "  {:<20} {:>10.3} {:>10.3} {:>10.2} {:>12}",
+            param.param.name(),
+            param.mean,
+            param.std_dev,
+            param.stability,
+            param.recommended
+        );
+    }
+
+    println!();
+    println!("Out-of-sample return: {:+.2}%", report.out_of_sample_return_pct);
+    if let Some(efficiency) = report.efficiency {
+        println!("Walk-forward efficiency (OOS / IS score): {:.2}", efficiency);
+    }
+
+    if report.windows.is_empty() {
+        println!("No window produced {} in-sample trades; widen the ranges or lower --min-trades", cmd.min_trades);
+    } else {
+        if let Some(ref error) = report.recommendation_error {
+            println!();
+            println!("WARNING: the recommended values are not a valid config together: {}", error);
+            println!("Each parameter's median is picked on its own; adjust the fragment before using it");
+        }
+        println!();
+        print!("{}", report.config_fragment());
+    }
+
+    if let Some(ref path) = cmd.export_json {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(path, json).context("Failed to write JSON export")?;
+        println!("Exported JSON to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Handle monte-carlo command: resample backtest or paper trades
+pub async fn monte_carlo_command(cmd: MonteCarloCmd) -> Result<()> {
+    use crate::meme::paper_trading::{PaperTrade, PaperTradingEngine};
+
+    let config = load_config(&cmd.config)?;
+
+    let trades = if let Some(ref path) = cmd.paper_trades {
+        let json = std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+        // Accept both the trade list and the full engine state export
+        let paper_trades: Vec<PaperTrade> = match serde_json::from_str(&json) {
+            Ok(trades) => trades,
+            Err(_) => PaperTradingEngine::from_json(&json)
+                .map_err(anyhow::Error::msg)
+                .context("Expected a paper trade list or engine state")?
+                .get_trades()
+                .to_vec(),
+        };
+        println!("Loaded {} paper trades from {}", paper_trades.len(), path.display());
+        McTrade::from_paper(&paper_trades)
+    } else if let Some(ref replay_path) = cmd.replay {
+        let strategy_config = StrategyConfig::from(&config);
+        strategy_config.validate().context("Invalid strategy parameters")?;
+        let source = ReplayPriceSource::from_path(replay_path, &config.tokens.base_mint)
+            .context(format!("Failed to load recording: {}", replay_path.display()))?;
+        let backtest_config = BacktestConfig {
+            timeframe: config.strategy.timeframe_duration()?,
+            ..Default::default()
+        };
+        let mut backtester = Backtester::new(strategy_config, backtest_config);
+        let report = backtester.run(&source).await.context("Backtest failed")?;
+        println!("Backtested {} samples from {}", report.samples, replay_path.display());
+        McTrade::from_backtest(&report.trades)
+    } else {
+        bail!("Pass --replay <PATH> or --paper-trades <FILE>");
+    };
+
+    // Slippage is drawn between 0 and twice the mean; by default that spans
+    // the configured Jupiter tolerance
+    let slippage_bps = cmd.slippage_bps.unwrap_or(config.jupiter.slippage_bps as f64 / 2.0);
+    let mc_config = MonteCarloConfig {
+        runs: cmd.runs,
+        trades_per_run: cmd.trades_per_run,
+        seed: cmd.seed,
+        capital: cmd.capital,
+        trade_size: cmd.trade_size.unwrap_or(config.risk.trade_size_sol),
+        slippage_bps,
+        slippage_jitter_bps: slippage_bps,
+        fee_bps: cmd.fee_bps,
+        fee_jitter_bps: cmd.fee_bps / 3.0,
+        miss_probability: cmd.miss_probability,
+        ruin_loss_pct: cmd.ruin_loss_pct,
+    };
+
+    let report = MonteCarloSimulator::new(mc_config).run(&trades)?;
+    let config = &report.config;
+
+    println!();
+    println!(
+        "Monte Carlo: {} runs x {} trades | {} SOL per trade on {} SOL",
+        config.runs,
+        config.trades_per_run.unwrap_or(report.source_trades),
+        config.trade_size,
+        config.capital
+    );
+    println!(
+        "  Costs per fill: slippage {:.1} +/- {:.1} bps, fees {:.1} +/- {:.1} bps | missed fills {:.1}%",
+        config.slippage_bps,
+        config.slippage_jitter_bps,
+        config.fee_bps,
+        config.fee_jitter_bps,
+        report.missed_fill_rate * 100.0
+    );
+    println!(
+        "  Source: {} trades, win rate {:.1}% (95% CI {:.1}%-{:.1}%)",
+        report.source_trades,
+        report.source_win_rate * 100.0,
+        report.win_rate_interval.0 * 100.0,
+        report.win_rate_interval.1 * 100.0
+    );
+    println!();
+    println!("  {:<14} {:>10} {:>10} {:>10} {:>10} {:>10}", "", "p5", "p25", "median", "p75", "p95");
+    let pnl = &report.final_pnl;
+    println!(
+        "  {:<14} {:>+10.4} {:>+10.4} {:>+10.4} {:>+10.4} {:>+10.4}",
+        "PnL (SOL)", pnl.p5, pnl.p25, pnl.median, pnl.p75, pnl.p95
+    );
+    let dd = &report.max_drawdown_pct;
+    println!(
+        "  {:<14} {:>9.2}% {:>9.2}% {:>9.2}% {:>9.2}% {:>9.2}%",
+        "Max drawdown", dd.p5, dd.p25, dd.median, dd.p75, dd.p95
+    );
+    println!();
+    println!("  Probability of loss: {:.1}%", report.probability_of_loss * 100.0);
+    println!("  Risk of ruin (-{}%): {:.2}%", config.ruin_loss_pct, report.risk_of_ruin * 100.0);
+    if report.source_trades < 30 {
+        println!(
+            "  Only {} trades: the distributions mostly reshuffle the same few outcomes",
+            report.source_trades
+        );
+    }
+
+    if let Some(ref path) = cmd.export_json {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(path, json).context("Failed to write JSON export")?;
+        println!("Exported JSON to {}", path.display());
+    }
+
+    Ok(())
+}
+
 /// Handle resume command
 async fn resume_command(cmd: ResumeCmd) -> Result<()> {
     use crate::domain::GuardStatus;
@@ -637,9 +997,28 @@ mod tests {
             Command::Status(cmd) => {
                 assert!(cmd.detailed);
                 assert_eq!(cmd.format, "json");
+                assert!(!cmd.paper);
+            }
+            _ => panic!("Expected Status command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_status_paper_export() {
+        let args = vec!["butters", "status", "--paper", "--export-trades", "trades.json"];
+        let app = CliApp::try_parse_from(args).unwrap();
+
+        match app.command {
+            Command::Status(cmd) => {
+                assert!(cmd.paper);
+                assert_eq!(cmd.export_trades, Some(PathBuf::from("trades.json")));
             }
             _ => panic!("Expected Status command"),
         }
+
+        // Export needs the paper wallet
+        let args = vec!["butters", "status", "--export-trades", "trades.json"];
+        assert!(CliApp::try_parse_from(args).is_err());
     }
 
     #[test]
@@ -761,6 +1140,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_app_parse_backtest_replay() {
+        let args = vec![
+            "butters", "backtest",
+            "--pair", "SOL/USDC",
+            "--replay", "data/recordings",
+            "--compare-zscore"
+        ];
+        let app = CliApp::try_parse_from(args).unwrap();
+
+        match app.command {
+            Command::Backtest(cmd) => {
+                assert_eq!(cmd.replay, Some(PathBuf::from("data/recordings")));
+                assert!(cmd.compare_zscore);
+            }
+            _ => panic!("Expected Backtest command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_app_parse_optimize() {
+        let args = vec![
+            "butters", "optimize",
+            "--replay", "data/recordings",
+            "--param", "z_threshold=1.5:3.0:0.5",
+            "--param", "adx_period=10:20:5",
+            "--random", "50",
+            "--objective", "return"
+        ];
+        let app = CliApp::try_parse_from(args).unwrap();
+
+        match app.command {
+            Command::Optimize(cmd) => {
+                assert_eq!(cmd.replay, PathBuf::from("data/recordings"));
+                assert_eq!(cmd.params.len(), 2);
+                assert_eq!(cmd.random, Some(50));
+                assert_eq!(cmd.objective, "return");
+                assert_eq!(cmd.in_sample_hours, 72);
+                assert_eq!(cmd.out_of_sample_hours, 24);
+                assert_eq!(cmd.min_trades, 5);
+            }
+            _ => panic!("Expected Optimize command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_app_parse_monte_carlo() {
+        let args = vec![
+            "butters", "monte-carlo",
+            "--paper-trades", "data/paper_trades.json",
+            "--runs", "2000",
+            "--trade-size", "0.37"
+        ];
+        let app = CliApp::try_parse_from(args).unwrap();
+
+        match app.command {
+            Command::MonteCarlo(cmd) => {
+                assert_eq!(cmd.paper_trades, Some(PathBuf::from("data/paper_trades.json")));
+                assert_eq!(cmd.runs, 2000);
+                assert_eq!(cmd.trade_size, Some(0.37));
+                assert_eq!(cmd.slippage_bps, None);
+                assert_eq!(cmd.miss_probability, 0.05);
+            }
+            _ => panic!("Expected MonteCarlo command"),
+        }
+
+        // A trade source is required, and only one
+        assert!(CliApp::try_parse_from(vec!["butters", "monte-carlo"]).is_err());
+        assert!(CliApp::try_parse_from(vec![
+            "butters", "monte-carlo", "--replay", "data", "--paper-trades", "trades.json"
+        ]).is_err());
+    }
+
     #[test]
     fn test_global_flags() {
         let args = vec!["butters", "-v", "--debug", "status"];
@@ -5,7 +5,8 @@
 
 mod commands;
 
-pub use commands::{CliApp, Command, RunCmd, StatusCmd, QuoteCmd, SwapCmd, BacktestCmd, ResumeCmd};
+pub use commands::{CliApp, Command, RunCmd, StatusCmd, QuoteCmd, SwapCmd, BacktestCmd, OptimizeCmd, MonteCarloCmd, ResumeCmd};
+pub use commands::{monte_carlo_command, optimize_command};
 pub use crate::meme::commands::MemeCmd;
 
 use anyhow::Result;
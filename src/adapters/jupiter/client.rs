@@ -2,16 +2,19 @@
 //!
 //! HTTP client for Jupiter DEX aggregator V6 API.
 //! Handles quote fetching, swap building, and transaction execution.
+//! Requests go through a `JupiterGateway`, which may be shared with the
+//! other Jupiter adapters.
 
 use std::time::Duration;
 use async_trait::async_trait;
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use serde::Deserialize;
 
 use crate::ports::execution::{
     ExecutionPort, ExecutionError, SwapQuoteRequest, SwapQuoteResponse,
     ExecuteSwapRequest, ExecuteSwapResponse,
 };
+use super::gateway::{GatewayConfig, GatewayResponse, JupiterGateway, RequestPriority};
 use super::quote::{QuoteRequest, QuoteResponse};
 use super::swap::{SwapRequest, SwapResponse};
 
@@ -46,7 +49,7 @@ impl Default for JupiterConfig {
 #[derive(Debug, Clone)]
 pub struct JupiterClient {
     config: JupiterConfig,
-    http: Client,
+    gateway: JupiterGateway,
 }
 
 impl JupiterClient {
@@ -55,14 +58,16 @@ impl JupiterClient {
         Self::with_config(JupiterConfig::default())
     }
 
-    /// Create a new Jupiter client with custom configuration
+    /// Create a new Jupiter client with custom configuration and its own gateway
     pub fn with_config(config: JupiterConfig) -> Result<Self, ExecutionError> {
-        let http = Client::builder()
-            .timeout(config.timeout)
-            .build()
-            .map_err(|e| ExecutionError::ApiError(format!("Failed to create HTTP client: {}", e)))?;
+        let gateway = JupiterGateway::new(GatewayConfig {
+            timeout: config.timeout,
+            max_retries: config.max_retries,
+            ..GatewayConfig::for_api_key(config.api_key.clone())
+        })
+        .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
 
-        Ok(Self { config, http })
+        Ok(Self { config, gateway })
     }
 
     /// Create a new Jupiter client with API key
@@ -72,127 +77,95 @@ impl JupiterClient {
         Self::with_config(config)
     }
 
-    /// Get a quote for a token swap
+    /// Create a client that sends requests through a shared gateway
+    pub fn with_gateway(gateway: JupiterGateway) -> Self {
+        let config = JupiterConfig {
+            api_key: gateway.api_key().map(str::to_string),
+            timeout: gateway.config().timeout,
+            max_retries: gateway.config().max_retries,
+            ..JupiterConfig::default()
+        };
+
+        Self { config, gateway }
+    }
+
+    /// Gateway used for requests
+    pub fn gateway(&self) -> &JupiterGateway {
+        &self.gateway
+    }
+
+    /// Get a quote for a token swap in the entry lane
     pub async fn get_quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ExecutionError> {
+        self.get_quote_with_priority(request, RequestPriority::Entry).await
+    }
+
+    /// Get a quote for a token swap in the given priority lane
+    ///
+    /// Identical quotes are served from the gateway cache for a short TTL,
+    /// except in the exit lane.
+    pub async fn get_quote_with_priority(
+        &self,
+        request: &QuoteRequest,
+        priority: RequestPriority,
+    ) -> Result<QuoteResponse, ExecutionError> {
         let url = format!("{}/quote", self.config.api_base_url);
 
-        let mut req = self.http
-            .get(&url)
-            .query(&[
-                ("inputMint", &request.input_mint),
-                ("outputMint", &request.output_mint),
-                ("amount", &request.amount.to_string()),
-                ("slippageBps", &request.slippage_bps.to_string()),
-            ]);
+        let mut query = vec![
+            ("inputMint", request.input_mint.clone()),
+            ("outputMint", request.output_mint.clone()),
+            ("amount", request.amount.to_string()),
+            ("slippageBps", request.slippage_bps.to_string()),
+        ];
 
         if request.only_direct_routes {
-            req = req.query(&[("onlyDirectRoutes", "true")]);
-        }
-
-        if let Some(ref api_key) = self.config.api_key {
-            req = req.header("x-api-key", api_key);
+            query.push(("onlyDirectRoutes", "true".to_string()));
         }
 
-        let response: reqwest::Response = self.execute_with_retry(|| async {
-            req.try_clone()
-                .ok_or_else(|| ExecutionError::ApiError("Failed to clone request".into()))?
-                .send()
-                .await
-                .map_err(|e| ExecutionError::ApiError(e.to_string()))
-        }).await?;
+        let response = self.gateway
+            .get(&url, &query, priority, Some(self.gateway.quote_cache_ttl()))
+            .await
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
 
-        self.handle_response(response).await
+        self.handle_response(response)
     }
 
-    /// Build and get swap transaction
+    /// Build and get swap transaction in the entry lane
     pub async fn get_swap_transaction(
         &self,
         request: &SwapRequest,
     ) -> Result<SwapResponse, ExecutionError> {
-        let url = format!("{}/swap", self.config.api_base_url);
-
-        let mut req = self.http
-            .post(&url)
-            .json(request);
-
-        if let Some(ref api_key) = self.config.api_key {
-            req = req.header("x-api-key", api_key);
-        }
-
-        let response: reqwest::Response = self.execute_with_retry(|| async {
-            req.try_clone()
-                .ok_or_else(|| ExecutionError::ApiError("Failed to clone request".into()))?
-                .send()
-                .await
-                .map_err(|e| ExecutionError::ApiError(e.to_string()))
-        }).await?;
-
-        self.handle_response(response).await
+        self.get_swap_transaction_with_priority(request, RequestPriority::Entry).await
     }
 
-    /// Execute request with retry logic and rate limit handling
-    async fn execute_with_retry<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response, ExecutionError>
-    where
-        F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<reqwest::Response, ExecutionError>>,
-    {
-        let mut last_error = None;
-
-        for attempt in 0..self.config.max_retries {
-            match request_fn().await {
-                Ok(response) => {
-                    if response.status().is_success() || response.status() == StatusCode::BAD_REQUEST {
-                        return Ok(response);
-                    }
-
-                    // Handle rate limiting (429) with exponential backoff
-                    if response.status() == StatusCode::TOO_MANY_REQUESTS {
-                        let backoff = Duration::from_secs(2u64.pow(attempt + 1)); // 2s, 4s, 8s
-                        tracing::warn!(
-                            "Rate limited (429), backing off for {:?} (attempt {}/{})",
-                            backoff, attempt + 1, self.config.max_retries
-                        );
-                        last_error = Some(ExecutionError::ApiError(
-                            "Rate limit exceeded - backing off".into()
-                        ));
-                        tokio::time::sleep(backoff).await;
-                        continue;
-                    }
-
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() {
-                        last_error = Some(ExecutionError::ApiError(
-                            format!("Server error: {}", response.status())
-                        ));
-                        tokio::time::sleep(Duration::from_millis(500 * (attempt as u64 + 1))).await;
-                        continue;
-                    }
-
-                    return Ok(response);
-                }
-                Err(e) => {
-                    last_error = Some(e);
-                    tokio::time::sleep(Duration::from_millis(500 * (attempt as u64 + 1))).await;
-                }
-            }
-        }
+    /// Build and get swap transaction in the given priority lane
+    pub async fn get_swap_transaction_with_priority(
+        &self,
+        request: &SwapRequest,
+        priority: RequestPriority,
+    ) -> Result<SwapResponse, ExecutionError> {
+        let url = format!("{}/swap", self.config.api_base_url);
+
+        let response = self.gateway
+            .post_json(&url, request, priority)
+            .await
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
 
-        Err(last_error.unwrap_or_else(|| ExecutionError::ApiError("Max retries exceeded".into())))
+        self.handle_response(response)
     }
 
     /// Handle API response and deserialize
-    async fn handle_response<T: for<'de> Deserialize<'de>>(
+    fn handle_response<T: for<'de> Deserialize<'de>>(
         &self,
-        response: reqwest::Response,
+        response: GatewayResponse,
     ) -> Result<T, ExecutionError> {
-        let status = response.status();
+        let status = response.status;
 
         if status == StatusCode::TOO_MANY_REQUESTS {
             return Err(ExecutionError::ApiError("Rate limit exceeded".into()));
         }
 
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
+            let error_text = response.body;
 
             // Check for slippage error
             if error_text.contains("SlippageToleranceExceeded") || error_text.contains("6001") {
@@ -208,7 +181,6 @@ impl JupiterClient {
 
         response
             .json()
-            .await
             .map_err(|e| ExecutionError::ApiError(format!("Failed to parse response: {}", e)))
     }
 
@@ -299,4 +271,13 @@ mod tests {
         let client = JupiterClient::with_api_key("test-key".to_string());
         assert!(client.is_ok());
     }
+
+    #[test]
+    fn test_jupiter_client_with_shared_gateway() {
+        let gateway = JupiterGateway::for_api_key(Some("test-key".to_string())).unwrap();
+        let client = JupiterClient::with_gateway(gateway.clone());
+
+        assert_eq!(client.config.api_key.as_deref(), Some("test-key"));
+        assert!(std::ptr::eq(client.gateway().limiter(), gateway.limiter()));
+    }
 }
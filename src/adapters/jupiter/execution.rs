@@ -0,0 +1,104 @@
+//! Jupiter Execution Port
+//!
+//! `ExecutionPort` over the raw Jupiter quote and swap endpoints, sending
+//! every request through a `JupiterGateway`.
+
+use crate::ports::execution::{
+    ExecuteSwapRequest, ExecuteSwapResponse, ExecutionError, ExecutionPort, SwapQuoteRequest,
+    SwapQuoteResponse,
+};
+
+use super::gateway::{JupiterGateway, RequestPriority};
+
+/// `ExecutionPort` calling the quote and swap endpoints at `api_base_url`
+pub struct JupiterExecutionPort {
+    api_base_url: String,
+    api_key: Option<String>,
+    gateway: JupiterGateway,
+}
+
+impl JupiterExecutionPort {
+    /// Create a port with its own gateway sized to `api_key`
+    pub fn new(api_base_url: String, api_key: Option<String>) -> Result<Self, ExecutionError> {
+        let gateway = JupiterGateway::for_api_key(api_key.clone())
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
+        Ok(Self {
+            api_base_url,
+            api_key,
+            gateway,
+        })
+    }
+
+    /// Create a port that sends requests through a shared gateway
+    pub fn with_gateway(api_base_url: String, gateway: JupiterGateway) -> Self {
+        Self {
+            api_base_url,
+            api_key: gateway.api_key().map(str::to_string),
+            gateway,
+        }
+    }
+
+    fn require_api_key(&self) -> Result<(), ExecutionError> {
+        self.api_key
+            .as_ref()
+            .map(|_| ())
+            .ok_or_else(|| ExecutionError::InvalidParameters("API key required".to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionPort for JupiterExecutionPort {
+    async fn get_swap_quote(
+        &self,
+        request: SwapQuoteRequest,
+    ) -> Result<SwapQuoteResponse, ExecutionError> {
+        self.require_api_key()?;
+        let url = format!("{}/quote", self.api_base_url);
+
+        let mut query = vec![
+            ("input_mint", request.input_mint),
+            ("output_mint", request.output_mint),
+            ("amount", request.amount.to_string()),
+            ("slippage_bps", request.slippage_bps.to_string()),
+        ];
+        if let Some(fee_bps) = request.platform_fee_bps {
+            query.push(("platform_fee_bps", fee_bps.to_string()));
+        }
+
+        let response = self
+            .gateway
+            .get(&url, &query, RequestPriority::Entry, Some(self.gateway.quote_cache_ttl()))
+            .await
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
+
+        if !response.is_success() {
+            return Err(ExecutionError::ApiError(response.body));
+        }
+
+        response
+            .json()
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))
+    }
+
+    async fn execute_swap(
+        &self,
+        request: ExecuteSwapRequest,
+    ) -> Result<ExecuteSwapResponse, ExecutionError> {
+        self.require_api_key()?;
+        let url = format!("{}/swap", self.api_base_url);
+
+        let response = self
+            .gateway
+            .post_json(&url, &request, RequestPriority::Entry)
+            .await
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
+
+        if !response.is_success() {
+            return Err(ExecutionError::ApiError(response.body));
+        }
+
+        response
+            .json()
+            .map_err(|e| ExecutionError::ApiError(e.to_string()))
+    }
+}
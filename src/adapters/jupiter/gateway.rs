@@ -0,0 +1,664 @@
+//! Jupiter Gateway
+//!
+//! Single HTTP front door shared by every Jupiter adapter (swap client,
+//! token/price fetcher, legacy price client, execution port).
+//!
+//! # Features
+//! - Token-bucket rate limiter sized to the API key tier
+//! - Priority lanes: exits > entries > price polling
+//! - 429 handling that honours `Retry-After` and pauses every lane
+//! - Short-TTL cache for identical GET requests (quotes, token metadata)
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Sustained request rate without an API key (lite-api free tier)
+pub const FREE_TIER_REQUESTS_PER_SECOND: f64 = 1.0;
+
+/// Sustained request rate with an API key (portal.jup.ag basic tier)
+pub const KEYED_TIER_REQUESTS_PER_SECOND: f64 = 10.0;
+
+/// Longest `Retry-After` we are willing to honour
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Upper bound on a single limiter sleep so waiters re-check lanes promptly
+const MAX_LIMITER_SLEEP: Duration = Duration::from_millis(250);
+
+/// Errors returned by the gateway
+#[derive(Debug, Error)]
+pub enum GatewayError {
+    #[error("HTTP request failed: {0}")]
+    HttpError(String),
+
+    #[error("Rate limited, retries exhausted")]
+    RateLimited,
+
+    #[error("Server error: {0}")]
+    ServerError(u16),
+}
+
+/// Request priority lane, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    /// Closing a position
+    Exit = 0,
+    /// Opening a position
+    Entry = 1,
+    /// Price polling and metadata lookups
+    Polling = 2,
+}
+
+impl RequestPriority {
+    const COUNT: usize = 3;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Gateway configuration
+#[derive(Debug, Clone)]
+pub struct GatewayConfig {
+    /// API key sent as `x-api-key` on every request
+    pub api_key: Option<String>,
+    /// Sustained request rate
+    pub requests_per_second: f64,
+    /// Bucket capacity (requests that may be sent back to back)
+    pub burst: u32,
+    /// Request timeout
+    pub timeout: Duration,
+    /// Attempts per request, including the first
+    pub max_retries: u32,
+    /// Base delay for retry backoff
+    pub retry_base_delay: Duration,
+    /// TTL for cached quotes
+    pub quote_cache_ttl: Duration,
+    /// TTL for cached token metadata
+    pub metadata_cache_ttl: Duration,
+    /// Maximum cached responses
+    pub max_cache_entries: usize,
+}
+
+impl GatewayConfig {
+    /// Config sized to the tier implied by `api_key`
+    pub fn for_api_key(api_key: Option<String>) -> Self {
+        let (requests_per_second, burst) = if api_key.is_some() {
+            (KEYED_TIER_REQUESTS_PER_SECOND, 10)
+        } else {
+            (FREE_TIER_REQUESTS_PER_SECOND, 2)
+        };
+
+        Self {
+            api_key,
+            requests_per_second,
+            burst,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            retry_base_delay: Duration::from_millis(500),
+            quote_cache_ttl: Duration::from_secs(2),
+            metadata_cache_ttl: Duration::from_secs(300),
+            max_cache_entries: 1_000,
+        }
+    }
+
+    /// Override the sustained request rate
+    pub fn with_requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.requests_per_second = requests_per_second;
+        self
+    }
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self::for_api_key(None)
+    }
+}
+
+/// Response body and status of a completed request
+#[derive(Debug, Clone)]
+pub struct GatewayResponse {
+    pub status: StatusCode,
+    pub body: String,
+    /// Served from the response cache
+    pub cached: bool,
+}
+
+impl GatewayResponse {
+    /// Whether the status is 2xx
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Deserialize the body as JSON
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    /// Every lane waits until this instant after a 429
+    paused_until: Option<Instant>,
+    /// Callers currently waiting in each lane
+    waiting: [usize; RequestPriority::COUNT],
+}
+
+/// Token-bucket rate limiter with priority lanes
+///
+/// A lane only takes a token when no higher-priority lane is waiting, so
+/// exits are never queued behind price polling.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+impl RateLimiter {
+    /// Create a full bucket refilling at `requests_per_second`
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            rate: requests_per_second.max(f64::EPSILON),
+            burst,
+            state: Mutex::new(BucketState {
+                tokens: burst,
+                last_refill: Instant::now(),
+                paused_until: None,
+                waiting: [0; RequestPriority::COUNT],
+            }),
+        }
+    }
+
+    /// Wait for a token in the given lane
+    pub async fn acquire(&self, priority: RequestPriority) {
+        let _waiting = LaneGuard::enter(self, priority);
+
+        loop {
+            match self.try_acquire(priority, Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait.min(MAX_LIMITER_SLEEP)).await,
+            }
+        }
+    }
+
+    /// Take a token if one is available to this lane, otherwise return how long to wait
+    fn try_acquire(&self, priority: RequestPriority, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        // The bucket does not refill while paused, so a 429 is not followed by a burst
+        if let Some(until) = state.paused_until {
+            if until > now {
+                state.last_refill = now;
+                return Err(until - now);
+            }
+            state.paused_until = None;
+            state.last_refill = state.last_refill.max(until);
+        }
+
+        let elapsed = now.saturating_duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.burst);
+        state.last_refill = now;
+
+        let token_wait = Duration::from_secs_f64(((1.0 - state.tokens) / self.rate).max(0.0));
+
+        if state.waiting[..priority.index()].iter().any(|&n| n > 0) {
+            // Let the higher lane take the next token first
+            return Err(token_wait.max(Duration::from_millis(5)));
+        }
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(token_wait)
+        }
+    }
+
+    /// Stop handing out tokens in every lane for `duration`
+    pub fn pause_for(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.paused_until = Some(state.paused_until.map_or(until, |current| current.max(until)));
+        state.tokens = 0.0;
+    }
+
+    /// Tokens currently in the bucket (not refilled)
+    pub fn available(&self) -> f64 {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).tokens
+    }
+
+    fn set_waiting(&self, priority: RequestPriority, entering: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let count = &mut state.waiting[priority.index()];
+        *count = if entering { *count + 1 } else { count.saturating_sub(1) };
+    }
+}
+
+/// Marks a caller as waiting in a lane; dropping it (even on cancellation) leaves the lane
+struct LaneGuard<'a> {
+    limiter: &'a RateLimiter,
+    priority: RequestPriority,
+}
+
+impl<'a> LaneGuard<'a> {
+    fn enter(limiter: &'a RateLimiter, priority: RequestPriority) -> Self {
+        limiter.set_waiting(priority, true);
+        Self { limiter, priority }
+    }
+}
+
+impl Drop for LaneGuard<'_> {
+    fn drop(&mut self) {
+        self.limiter.set_waiting(self.priority, false);
+    }
+}
+
+/// Short-TTL cache of successful GET response bodies
+#[derive(Debug)]
+struct ResponseCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl ResponseCache {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &str, now: Instant) -> Option<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(expires, _)| *expires > now)
+            .map(|(_, body)| body.clone())
+    }
+
+    fn insert(&self, key: String, body: String, ttl: Duration, now: Instant) {
+        if self.max_entries == 0 || ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, (expires, _)| *expires > now);
+        }
+        if entries.len() >= self.max_entries {
+            // Still full of live entries: evict the one closest to expiry
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (expires, _))| *expires)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (now + ttl, body));
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[derive(Debug)]
+struct GatewayInner {
+    config: GatewayConfig,
+    http: Client,
+    limiter: RateLimiter,
+    cache: ResponseCache,
+}
+
+/// Shared Jupiter HTTP gateway
+///
+/// Cloning is cheap; clones share the limiter and cache.
+#[derive(Debug, Clone)]
+pub struct JupiterGateway {
+    inner: Arc<GatewayInner>,
+}
+
+impl JupiterGateway {
+    /// Create a gateway with its own limiter and cache
+    pub fn new(config: GatewayConfig) -> Result<Self, GatewayError> {
+        let http = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| GatewayError::HttpError(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            inner: Arc::new(GatewayInner {
+                limiter: RateLimiter::new(config.requests_per_second, config.burst),
+                cache: ResponseCache::new(config.max_cache_entries),
+                http,
+                config,
+            }),
+        })
+    }
+
+    /// Create a gateway sized to the tier implied by `api_key`
+    pub fn for_api_key(api_key: Option<String>) -> Result<Self, GatewayError> {
+        Self::new(GatewayConfig::for_api_key(api_key))
+    }
+
+    /// Gateway configuration
+    pub fn config(&self) -> &GatewayConfig {
+        &self.inner.config
+    }
+
+    /// API key sent with every request
+    pub fn api_key(&self) -> Option<&str> {
+        self.inner.config.api_key.as_deref()
+    }
+
+    /// Shared rate limiter
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.inner.limiter
+    }
+
+    /// TTL for cached quotes
+    pub fn quote_cache_ttl(&self) -> Duration {
+        self.inner.config.quote_cache_ttl
+    }
+
+    /// TTL for cached token metadata
+    pub fn metadata_cache_ttl(&self) -> Duration {
+        self.inner.config.metadata_cache_ttl
+    }
+
+    /// Number of cached responses (including expired ones not yet evicted)
+    pub fn cached_responses(&self) -> usize {
+        self.inner.cache.len()
+    }
+
+    /// Send a GET request
+    ///
+    /// With `cache_ttl`, an identical successful response from within the TTL
+    /// is returned without touching the network. Exits always go to the
+    /// network but still refresh the cache.
+    pub async fn get(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+        priority: RequestPriority,
+        cache_ttl: Option<Duration>,
+    ) -> Result<GatewayResponse, GatewayError> {
+        let cache_key = cache_key(url, query);
+
+        if cache_ttl.is_some() && priority != RequestPriority::Exit {
+            if let Some(body) = self.inner.cache.get(&cache_key, Instant::now()) {
+                return Ok(GatewayResponse {
+                    status: StatusCode::OK,
+                    body,
+                    cached: true,
+                });
+            }
+        }
+
+        let response = self
+            .send(priority, || self.inner.http.get(url).query(query))
+            .await?;
+
+        if let Some(ttl) = cache_ttl {
+            if response.is_success() {
+                self.inner.cache.insert(cache_key, response.body.clone(), ttl, Instant::now());
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send a POST request with a JSON body (never cached)
+    pub async fn post_json<B: Serialize + ?Sized>(
+        &self,
+        url: &str,
+        body: &B,
+        priority: RequestPriority,
+    ) -> Result<GatewayResponse, GatewayError> {
+        self.send(priority, || self.inner.http.post(url).json(body)).await
+    }
+
+    /// Rate-limited send with retry on 429, 5xx and transport errors
+    async fn send<F>(&self, priority: RequestPriority, build: F) -> Result<GatewayResponse, GatewayError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let config = &self.inner.config;
+        let mut last_error = None;
+
+        for attempt in 0..config.max_retries.max(1) {
+            self.inner.limiter.acquire(priority).await;
+
+            let mut request = build();
+            if let Some(ref api_key) = config.api_key {
+                request = request.header("x-api-key", api_key);
+            }
+
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    // Rate limited: pause every lane, not just this caller
+                    if status == StatusCode::TOO_MANY_REQUESTS {
+                        let backoff = retry_after(response.headers(), Utc::now())
+                            .unwrap_or(config.retry_base_delay * 2u32.pow(attempt + 1));
+                        tracing::warn!(
+                            "Jupiter rate limited (429), pausing {:?} (attempt {}/{}, {:?})",
+                            backoff,
+                            attempt + 1,
+                            config.max_retries,
+                            priority
+                        );
+                        self.inner.limiter.pause_for(backoff);
+                        last_error = Some(GatewayError::RateLimited);
+                        continue;
+                    }
+
+                    // Retry on server errors (5xx)
+                    if status.is_server_error() {
+                        last_error = Some(GatewayError::ServerError(status.as_u16()));
+                        tokio::time::sleep(config.retry_base_delay * (attempt + 1)).await;
+                        continue;
+                    }
+
+                    let body = response
+                        .text()
+                        .await
+                        .map_err(|e| GatewayError::HttpError(e.to_string()))?;
+
+                    return Ok(GatewayResponse {
+                        status,
+                        body,
+                        cached: false,
+                    });
+                }
+                Err(e) => {
+                    last_error = Some(GatewayError::HttpError(e.to_string()));
+                    tokio::time::sleep(config.retry_base_delay * (attempt + 1)).await;
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(GatewayError::RateLimited))
+    }
+}
+
+impl Default for JupiterGateway {
+    fn default() -> Self {
+        Self::new(GatewayConfig::default()).expect("Failed to create default JupiterGateway")
+    }
+}
+
+/// Cache key for a GET request; query order is part of the key
+fn cache_key(url: &str, query: &[(&str, String)]) -> String {
+    let mut key = url.to_string();
+    for (i, (name, value)) in query.iter().enumerate() {
+        key.push(if i == 0 { '?' } else { '&' });
+        key.push_str(name);
+        key.push('=');
+        key.push_str(value);
+    }
+    key
+}
+
+/// Parse `Retry-After` as delta-seconds or an HTTP date, capped at `MAX_RETRY_AFTER`
+fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    let wait = if let Ok(secs) = value.parse::<u64>() {
+        Duration::from_secs(secs)
+    } else {
+        let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+        (at - now).to_std().unwrap_or(Duration::ZERO)
+    };
+
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_config_tiers() {
+        let free = GatewayConfig::for_api_key(None);
+        assert_eq!(free.requests_per_second, FREE_TIER_REQUESTS_PER_SECOND);
+
+        let keyed = GatewayConfig::for_api_key(Some("key".to_string()));
+        assert_eq!(keyed.requests_per_second, KEYED_TIER_REQUESTS_PER_SECOND);
+        assert!(keyed.burst > free.burst);
+
+        let custom = free.with_requests_per_second(5.0);
+        assert_eq!(custom.requests_per_second, 5.0);
+    }
+
+    #[test]
+    fn test_bucket_burst_then_refill() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire(RequestPriority::Polling, now).is_ok());
+        assert!(limiter.try_acquire(RequestPriority::Polling, now).is_ok());
+
+        let wait = limiter.try_acquire(RequestPriority::Polling, now).unwrap_err();
+        assert!(wait <= Duration::from_millis(100));
+
+        // 100ms at 10 rps refills one token
+        let later = now + Duration::from_millis(100);
+        assert!(limiter.try_acquire(RequestPriority::Polling, later).is_ok());
+    }
+
+    #[test]
+    fn test_lower_lane_yields_to_waiting_exit() {
+        let limiter = RateLimiter::new(10.0, 5);
+        let now = Instant::now();
+
+        let _exit = LaneGuard::enter(&limiter, RequestPriority::Exit);
+        assert!(limiter.try_acquire(RequestPriority::Polling, now).is_err());
+        assert!(limiter.try_acquire(RequestPriority::Entry, now).is_err());
+        assert!(limiter.try_acquire(RequestPriority::Exit, now).is_ok());
+
+        drop(_exit);
+        assert!(limiter.try_acquire(RequestPriority::Polling, now).is_ok());
+    }
+
+    #[test]
+    fn test_pause_blocks_every_lane() {
+        let limiter = RateLimiter::new(100.0, 5);
+        limiter.pause_for(Duration::from_secs(2));
+
+        let wait = limiter.try_acquire(RequestPriority::Exit, Instant::now()).unwrap_err();
+        assert!(wait > Duration::from_secs(1));
+        assert_eq!(limiter.available(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_serves_exit_before_polling() {
+        let limiter = Arc::new(RateLimiter::new(20.0, 1));
+        limiter.acquire(RequestPriority::Polling).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for priority in [RequestPriority::Polling, RequestPriority::Polling, RequestPriority::Exit] {
+            let limiter = limiter.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                limiter.acquire(priority).await;
+                order.lock().unwrap().push(priority);
+            }));
+            tokio::task::yield_now().await;
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(order.lock().unwrap()[0], RequestPriority::Exit);
+    }
+
+    #[test]
+    fn test_cache_ttl_and_eviction() {
+        let cache = ResponseCache::new(2);
+        let now = Instant::now();
+
+        cache.insert("a".into(), "1".into(), Duration::from_secs(1), now);
+        assert_eq!(cache.get("a", now), Some("1".to_string()));
+        assert_eq!(cache.get("a", now + Duration::from_secs(2)), None);
+
+        cache.insert("b".into(), "2".into(), Duration::from_secs(10), now);
+        cache.insert("c".into(), "3".into(), Duration::from_secs(10), now);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a", now), None);
+
+        // Zero TTL is never stored
+        cache.insert("d".into(), "4".into(), Duration::ZERO, now);
+        assert_eq!(cache.get("d", now), None);
+    }
+
+    #[test]
+    fn test_cache_key_includes_query() {
+        let a = cache_key("https://x/quote", &[("inputMint", "A".into()), ("amount", "1".into())]);
+        let b = cache_key("https://x/quote", &[("inputMint", "A".into()), ("amount", "2".into())]);
+        assert_eq!(a, "https://x/quote?inputMint=A&amount=1");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_retry_after_parsing() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, now), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(3)));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2026 07:28:05 GMT"));
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(5)));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(retry_after(&headers, now), Some(MAX_RETRY_AFTER));
+    }
+
+    #[test]
+    fn test_gateway_clones_share_state() {
+        let gateway = JupiterGateway::default();
+        let clone = gateway.clone();
+        assert!(std::ptr::eq(gateway.limiter(), clone.limiter()));
+        assert!(gateway.api_key().is_none());
+    }
+}
@@ -3,14 +3,22 @@
 //! Implementation of the ExecutionPort for Jupiter DEX aggregator.
 //! Handles quote fetching, swap building, and transaction execution.
 //! Also provides token list fetching and price APIs.
+//! All adapters can share one `JupiterGateway` for rate limiting and caching.
 
 mod client;
+mod execution;
+mod gateway;
 mod quote;
 mod swap;
 mod token_list;
 
 pub use client::JupiterClient;
-pub use quote::QuoteRequest;
+pub use execution::JupiterExecutionPort;
+pub use gateway::{
+    GatewayConfig, GatewayError, GatewayResponse, JupiterGateway,
+    RateLimiter, RequestPriority,
+};
+pub use quote::{QuoteRequest, QuoteResponse};
 pub use swap::SwapRequest;
 pub use token_list::{
     JupiterToken, JupiterTokenFetcher,
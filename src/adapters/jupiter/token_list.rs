@@ -12,13 +12,18 @@
 //! - Get verified token list
 //! - Get trending/top traded tokens
 //! - Get token prices (single or batch)
+//!
+//! Requests go through a `JupiterGateway`; token metadata is cached there
+//! and price lookups run in the polling lane.
 
 use std::collections::HashMap;
 use std::time::Duration;
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::gateway::{GatewayConfig, GatewayError, GatewayResponse, JupiterGateway, RequestPriority};
+
 /// Errors that can occur when fetching token data
 #[derive(Debug, Error)]
 pub enum JupiterTokenError {
@@ -41,6 +46,15 @@ pub enum JupiterTokenError {
     ApiError(String),
 }
 
+impl From<GatewayError> for JupiterTokenError {
+    fn from(e: GatewayError) -> Self {
+        match e {
+            GatewayError::RateLimited => JupiterTokenError::RateLimited,
+            other => JupiterTokenError::ApiError(other.to_string()),
+        }
+    }
+}
+
 /// Configuration for the Jupiter token fetcher
 #[derive(Debug, Clone)]
 pub struct JupiterTokenConfig {
@@ -207,7 +221,7 @@ impl TokenCategory {
 #[derive(Debug, Clone)]
 pub struct JupiterTokenFetcher {
     config: JupiterTokenConfig,
-    http: Client,
+    gateway: JupiterGateway,
 }
 
 impl JupiterTokenFetcher {
@@ -221,21 +235,37 @@ impl JupiterTokenFetcher {
         Self::with_config(JupiterTokenConfig::with_api_key(api_key))
     }
 
-    /// Create a new fetcher with custom configuration
+    /// Create a new fetcher with custom configuration and its own gateway
     pub fn with_config(config: JupiterTokenConfig) -> Result<Self, JupiterTokenError> {
-        let http = Client::builder()
-            .timeout(config.timeout)
-            .build()?;
+        let gateway = JupiterGateway::new(GatewayConfig {
+            timeout: config.timeout,
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            ..GatewayConfig::for_api_key(config.api_key.clone())
+        })?;
+
+        Ok(Self { config, gateway })
+    }
 
-        Ok(Self { config, http })
+    /// Create a fetcher that sends requests through a shared gateway
+    ///
+    /// Endpoints follow the gateway's API key (api.jup.ag with a key,
+    /// lite-api.jup.ag without).
+    pub fn with_gateway(gateway: JupiterGateway) -> Self {
+        let config = match gateway.api_key() {
+            Some(api_key) => JupiterTokenConfig::with_api_key(api_key),
+            None => JupiterTokenConfig::default(),
+        };
+
+        Self { config, gateway }
     }
 
     /// Get token info by mint address
     pub async fn get_token(&self, mint: &str) -> Result<JupiterToken, JupiterTokenError> {
         let url = format!("{}/token/{}", self.config.token_api_url, mint);
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, self.metadata_cache_ttl()).await?;
 
-        response.json::<JupiterToken>().await.map_err(|e| {
+        response.json::<JupiterToken>().map_err(|e| {
             if e.to_string().contains("404") || e.to_string().contains("null") {
                 JupiterTokenError::TokenNotFound(mint.to_string())
             } else {
@@ -247,10 +277,10 @@ impl JupiterTokenFetcher {
     /// Search tokens by name or symbol
     pub async fn search_tokens(&self, query: &str) -> Result<Vec<JupiterToken>, JupiterTokenError> {
         let url = format!("{}/search?query={}", self.config.token_api_url, query);
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, self.metadata_cache_ttl()).await?;
 
         // The API returns an array directly
-        let tokens: Vec<JupiterToken> = response.json().await.map_err(|e| {
+        let tokens: Vec<JupiterToken> = response.json().map_err(|e| {
             JupiterTokenError::ParseError(format!("Failed to parse search results: {}", e))
         })?;
 
@@ -260,10 +290,10 @@ impl JupiterTokenFetcher {
     /// Get list of verified token mints
     pub async fn get_verified_tokens(&self) -> Result<Vec<String>, JupiterTokenError> {
         let url = format!("{}/tag?query=verified", self.config.token_api_url);
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, self.metadata_cache_ttl()).await?;
 
         // Returns array of mint addresses
-        let mints: Vec<String> = response.json().await.map_err(|e| {
+        let mints: Vec<String> = response.json().map_err(|e| {
             JupiterTokenError::ParseError(format!("Failed to parse verified tokens: {}", e))
         })?;
 
@@ -291,9 +321,9 @@ impl JupiterTokenFetcher {
             category.as_str(),
             interval.as_str()
         );
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, self.metadata_cache_ttl()).await?;
 
-        let tokens: Vec<JupiterToken> = response.json().await.map_err(|e| {
+        let tokens: Vec<JupiterToken> = response.json().map_err(|e| {
             JupiterTokenError::ParseError(format!("Failed to parse trending tokens: {}", e))
         })?;
 
@@ -303,9 +333,9 @@ impl JupiterTokenFetcher {
     /// Get price for a single token (in USD)
     pub async fn get_price(&self, mint: &str) -> Result<TokenPrice, JupiterTokenError> {
         let url = format!("{}?ids={}", self.config.price_api_url, mint);
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, None).await?;
 
-        let price_response: PriceV3Response = response.json().await.map_err(|e| {
+        let price_response: PriceV3Response = response.json().map_err(|e| {
             JupiterTokenError::ParseError(format!("Failed to parse price response: {}", e))
         })?;
 
@@ -340,9 +370,9 @@ impl JupiterTokenFetcher {
 
         let ids = mints.join(",");
         let url = format!("{}?ids={}", self.config.price_api_url, ids);
-        let response = self.execute_request(&url).await?;
+        let response = self.execute_request(&url, None).await?;
 
-        let price_response: PriceV3Response = response.json().await.map_err(|e| {
+        let price_response: PriceV3Response = response.json().map_err(|e| {
             JupiterTokenError::ParseError(format!("Failed to parse prices response: {}", e))
         })?;
 
@@ -369,80 +399,40 @@ impl JupiterTokenFetcher {
         Ok(prices)
     }
 
-    /// Execute request with retry logic
-    async fn execute_request(&self, url: &str) -> Result<reqwest::Response, JupiterTokenError> {
-        let mut last_error = None;
+    /// Gateway used for requests
+    pub fn gateway(&self) -> &JupiterGateway {
+        &self.gateway
+    }
 
-        for attempt in 0..self.config.max_retries {
-            let mut req = self.http.get(url);
+    fn metadata_cache_ttl(&self) -> Option<Duration> {
+        Some(self.gateway.metadata_cache_ttl())
+    }
 
-            // Add API key header if configured
-            if let Some(ref api_key) = self.config.api_key {
-                req = req.header("x-api-key", api_key);
-            }
+    /// Execute a polling-lane GET through the gateway (which handles retries and 429s)
+    async fn execute_request(
+        &self,
+        url: &str,
+        cache_ttl: Option<Duration>,
+    ) -> Result<GatewayResponse, JupiterTokenError> {
+        let response = self
+            .gateway
+            .get(url, &[], RequestPriority::Polling, cache_ttl)
+            .await?;
+
+        // Handle 404 for token not found
+        if response.status == StatusCode::NOT_FOUND {
+            return Err(JupiterTokenError::TokenNotFound(url.to_string()));
+        }
 
-            match req.send().await {
-                Ok(response) => {
-                    let status = response.status();
-
-                    // Handle rate limiting with exponential backoff
-                    if status == StatusCode::TOO_MANY_REQUESTS {
-                        let backoff = Duration::from_millis(
-                            self.config.retry_base_delay_ms * 2u64.pow(attempt + 1),
-                        );
-                        tracing::warn!(
-                            "Rate limited (429), backing off for {:?} (attempt {}/{})",
-                            backoff,
-                            attempt + 1,
-                            self.config.max_retries
-                        );
-                        last_error = Some(JupiterTokenError::RateLimited);
-                        tokio::time::sleep(backoff).await;
-                        continue;
-                    }
-
-                    // Retry on server errors (5xx)
-                    if status.is_server_error() {
-                        let backoff = Duration::from_millis(
-                            self.config.retry_base_delay_ms * (attempt as u64 + 1),
-                        );
-                        last_error = Some(JupiterTokenError::ApiError(format!(
-                            "Server error: {}",
-                            status
-                        )));
-                        tokio::time::sleep(backoff).await;
-                        continue;
-                    }
-
-                    // Handle 404 for token not found
-                    if status == StatusCode::NOT_FOUND {
-                        return Err(JupiterTokenError::TokenNotFound(url.to_string()));
-                    }
-
-                    // Handle other client errors
-                    if status.is_client_error() {
-                        let error_text = response.text().await.unwrap_or_default();
-                        return Err(JupiterTokenError::ApiError(format!(
-                            "API error {}: {}",
-                            status, error_text
-                        )));
-                    }
-
-                    return Ok(response);
-                }
-                Err(e) => {
-                    last_error = Some(JupiterTokenError::HttpError(e));
-                    let backoff = Duration::from_millis(
-                        self.config.retry_base_delay_ms * (attempt as u64 + 1),
-                    );
-                    tokio::time::sleep(backoff).await;
-                }
-            }
+        // Handle other client errors
+        if response.status.is_client_error() {
+            return Err(JupiterTokenError::ApiError(format!(
+                "API error {}: {}",
+                response.status, response.body
+            )));
         }
 
-        Err(last_error.unwrap_or_else(|| {
-            JupiterTokenError::ApiError("Max retries exceeded".into())
-        }))
+        Ok(response)
     }
 }
 
@@ -547,6 +537,21 @@ mod tests {
         assert!((price.price - 150.50).abs() < 0.001);
     }
 
+    #[test]
+    fn test_fetcher_with_shared_gateway() {
+        let gateway = JupiterGateway::for_api_key(Some("test-key".to_string())).unwrap();
+        let fetcher = JupiterTokenFetcher::with_gateway(gateway.clone());
+
+        assert_eq!(fetcher.config.price_api_url, "https://api.jup.ag/price/v3");
+        assert!(std::ptr::eq(fetcher.gateway().limiter(), gateway.limiter()));
+    }
+
+    #[test]
+    fn test_gateway_rate_limit_maps_to_token_error() {
+        let err: JupiterTokenError = GatewayError::RateLimited.into();
+        assert!(matches!(err, JupiterTokenError::RateLimited));
+    }
+
     #[test]
     fn test_default_fetcher() {
         let _fetcher = JupiterTokenFetcher::default();
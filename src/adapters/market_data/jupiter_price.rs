@@ -1,8 +1,9 @@
 use std::time::Duration;
-use reqwest::Client;
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::adapters::jupiter::{GatewayConfig, GatewayError, JupiterGateway, RequestPriority};
+
 const JUPITER_PRICE_API: &str = "https://price.jup.ag/v6/price";
 
 #[derive(Debug, Error)]
@@ -13,33 +14,45 @@ pub enum PriceError {
     NoPriceData(String),
     #[error("Parse error: {0}")]
     ParseError(String),
+    #[error("Gateway error: {0}")]
+    GatewayError(#[from] GatewayError),
 }
 
 #[derive(Debug, Clone)]
 pub struct JupiterPriceClient {
-    http: Client,
+    gateway: JupiterGateway,
     timeout: Duration,
 }
 
 impl JupiterPriceClient {
     pub fn new() -> Result<Self, PriceError> {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()?;
-        Ok(Self { http, timeout: Duration::from_secs(10) })
+        let gateway = JupiterGateway::new(GatewayConfig {
+            timeout: Duration::from_secs(10),
+            ..Default::default()
+        })?;
+        Ok(Self::with_gateway(gateway))
     }
 
-    /// Get price for a single token in USDC
-    pub async fn get_price(&self, mint: &str) -> Result<f64, PriceError> {
-        let url = format!("{}?ids={}", JUPITER_PRICE_API, mint);
+    /// Create a client that sends requests through a shared gateway
+    pub fn with_gateway(gateway: JupiterGateway) -> Self {
+        let timeout = gateway.config().timeout;
+        Self { gateway, timeout }
+    }
 
-        let response: PriceResponse = self.http
-            .get(&url)
-            .send()
-            .await?
-            .json()
+    /// Fetch and parse a price response in the polling lane
+    async fn fetch(&self, ids: &str) -> Result<PriceResponse, PriceError> {
+        let url = format!("{}?ids={}", JUPITER_PRICE_API, ids);
+        let response = self.gateway
+            .get(&url, &[], RequestPriority::Polling, None)
             .await?;
 
+        response.json().map_err(|e| PriceError::ParseError(e.to_string()))
+    }
+
+    /// Get price for a single token in USDC
+    pub async fn get_price(&self, mint: &str) -> Result<f64, PriceError> {
+        let response = self.fetch(mint).await?;
+
         response.data
             .get(mint)
             .map(|p| p.price)
@@ -48,14 +61,7 @@ impl JupiterPriceClient {
 
     /// Get price of base token in terms of quote token
     pub async fn get_pair_price(&self, base_mint: &str, quote_mint: &str) -> Result<f64, PriceError> {
-        let url = format!("{}?ids={},{}", JUPITER_PRICE_API, base_mint, quote_mint);
-
-        let response: PriceResponse = self.http
-            .get(&url)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let response = self.fetch(&format!("{},{}", base_mint, quote_mint)).await?;
 
         let base_price = response.data
             .get(base_mint)
@@ -112,6 +118,13 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_client_with_shared_gateway() {
+        let gateway = JupiterGateway::default();
+        let client = JupiterPriceClient::with_gateway(gateway.clone());
+        assert!(std::ptr::eq(client.gateway.limiter(), gateway.limiter()));
+    }
+
     #[test]
     fn test_default_client() {
         let _client = JupiterPriceClient::default();
@@ -1,2 +1,11 @@
 mod jupiter_price;
+mod pool_price;
+mod quote_price;
+pub mod recorder;
+mod replay;
+
 pub use jupiter_price::JupiterPriceClient;
+pub use pool_price::OnchainPoolPriceSource;
+pub use quote_price::JupiterQuotePriceSource;
+pub use recorder::{MarketRecorder, QuoteSummary, RecordedEvent, SharedRecorder};
+pub use replay::ReplayPriceSource;
@@ -0,0 +1,152 @@
+//! On-chain Pool Price Source
+//!
+//! Live `PricePort` that prices a token from AMM pool state read directly
+//! over RPC, with no dependency on the Jupiter API. The price is the
+//! median mid price across the configured pools, so a single stale or
+//! manipulated pool cannot move it on its own.
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use super::recorder::{record_shared, RecordedEvent, SharedRecorder};
+use crate::adapters::pools::{OnchainPoolReader, PoolSnapshot};
+use crate::ports::price::{PricePort, PricePortError, PriceSample};
+
+/// Prices a token from on-chain pools
+#[derive(Clone)]
+pub struct OnchainPoolPriceSource {
+    reader: OnchainPoolReader,
+    pools: Vec<String>,
+    base_mint: String,
+    quote_mint: String,
+    recorder: Option<SharedRecorder>,
+}
+
+impl OnchainPoolPriceSource {
+    /// Create a source pricing `base_mint` in `quote_mint` from `pools`
+    pub fn new(reader: OnchainPoolReader, pools: Vec<String>, base_mint: String, quote_mint: String) -> Self {
+        Self {
+            reader,
+            pools,
+            base_mint,
+            quote_mint,
+            recorder: None,
+        }
+    }
+
+    /// Source pricing SOL in USDC from the default SOL/USDC pools
+    pub fn sol_usdc(reader: OnchainPoolReader, sol_mint: String, usdc_mint: String) -> Self {
+        Self::new(reader, OnchainPoolReader::sol_usdc_pools(), sol_mint, usdc_mint)
+    }
+
+    /// Record every price sample
+    pub fn with_recorder(mut self, recorder: SharedRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Pools this source reads
+    pub fn pools(&self) -> &[String] {
+        &self.pools
+    }
+}
+
+/// Median mid price of `base_mint` across pools that trade the pair
+fn median_mid_price(snapshots: &[PoolSnapshot], base_mint: &str, quote_mint: &str) -> Option<f64> {
+    let mut prices: Vec<f64> = snapshots
+        .iter()
+        .filter(|s| s.contains(base_mint) && s.contains(quote_mint))
+        .filter_map(|s| s.mid_price(base_mint))
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .collect();
+
+    if prices.is_empty() {
+        return None;
+    }
+
+    prices.sort_by(|a, b| a.total_cmp(b));
+    let mid = prices.len() / 2;
+    if prices.len().is_multiple_of(2) {
+        Some((prices[mid - 1] + prices[mid]) / 2.0)
+    } else {
+        Some(prices[mid])
+    }
+}
+
+#[async_trait]
+impl PricePort for OnchainPoolPriceSource {
+    async fn next_price(&self) -> Result<PriceSample, PricePortError> {
+        let mut snapshots = Vec::with_capacity(self.pools.len());
+        for (address, result) in self.pools.iter().zip(self.reader.read_pools(&self.pools).await) {
+            match result {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => warn!("Failed to read pool {}: {}", address, e),
+            }
+        }
+
+        let price = median_mid_price(&snapshots, &self.base_mint, &self.quote_mint).ok_or_else(|| {
+            PricePortError::FetchError(format!(
+                "No readable pool prices {} in {}",
+                self.base_mint, self.quote_mint
+            ))
+        })?;
+
+        let sample = PriceSample::now(self.base_mint.clone(), price);
+
+        if let Some(ref recorder) = self.recorder {
+            record_shared(recorder, RecordedEvent::Price(sample.clone()));
+        }
+
+        Ok(sample)
+    }
+
+    fn mint(&self) -> &str {
+        &self.base_mint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::pools::{PoolKind, PoolLiquidity};
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn pool(usdc_reserve: u64) -> PoolSnapshot {
+        PoolSnapshot {
+            address: "pool".to_string(),
+            kind: PoolKind::RaydiumAmmV4,
+            mint_a: SOL.to_string(),
+            mint_b: USDC.to_string(),
+            decimals_a: 9,
+            decimals_b: 6,
+            fee_rate: 0.0025,
+            liquidity: PoolLiquidity::ConstantProduct {
+                reserve_a: 1_000_000_000_000,
+                reserve_b: usdc_reserve,
+            },
+        }
+    }
+
+    #[test]
+    fn test_median_ignores_outlier_pool() {
+        // 150, 151 and a manipulated 300 USDC/SOL
+        let snapshots = vec![pool(150_000_000_000), pool(300_000_000_000), pool(151_000_000_000)];
+        let price = median_mid_price(&snapshots, SOL, USDC).unwrap();
+        assert!((price - 151.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_median_skips_other_pairs() {
+        let other = PoolSnapshot {
+            mint_b: "other".to_string(),
+            ..pool(900_000_000_000)
+        };
+        let snapshots = vec![pool(150_000_000_000), other, pool(152_000_000_000)];
+        let price = median_mid_price(&snapshots, SOL, USDC).unwrap();
+        assert!((price - 151.0).abs() < 1e-9);
+
+        assert!(median_mid_price(&[], SOL, USDC).is_none());
+    }
+}
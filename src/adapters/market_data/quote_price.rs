@@ -0,0 +1,100 @@
+//! Jupiter Quote Price Source
+//!
+//! Live `PricePort` that prices one whole base token by requesting a
+//! Jupiter quote into the quote token (e.g. 1 SOL -> USDC). Every sample
+//! and quote summary can be handed to a `MarketRecorder` for later replay.
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use super::recorder::{record_shared, QuoteSummary, RecordedEvent, SharedRecorder};
+use crate::adapters::jupiter::{JupiterClient, QuoteRequest, RequestPriority};
+use crate::ports::price::{PricePort, PricePortError, PriceSample};
+
+/// SOL decimals
+const SOL_DECIMALS: u8 = 9;
+/// USDC decimals
+const USDC_DECIMALS: u8 = 6;
+
+/// Prices a token from Jupiter quotes
+#[derive(Clone)]
+pub struct JupiterQuotePriceSource {
+    jupiter: JupiterClient,
+    base_mint: String,
+    quote_mint: String,
+    base_decimals: u8,
+    quote_decimals: u8,
+    slippage_bps: u16,
+    recorder: Option<SharedRecorder>,
+}
+
+impl JupiterQuotePriceSource {
+    /// Create a source pricing `base_mint` in `quote_mint` (SOL/USDC decimals by default)
+    pub fn new(jupiter: JupiterClient, base_mint: String, quote_mint: String, slippage_bps: u16) -> Self {
+        Self {
+            jupiter,
+            base_mint,
+            quote_mint,
+            base_decimals: SOL_DECIMALS,
+            quote_decimals: USDC_DECIMALS,
+            slippage_bps,
+            recorder: None,
+        }
+    }
+
+    /// Set token decimals for non SOL/USDC pairs
+    pub fn with_decimals(mut self, base_decimals: u8, quote_decimals: u8) -> Self {
+        self.base_decimals = base_decimals;
+        self.quote_decimals = quote_decimals;
+        self
+    }
+
+    /// Record every price sample and quote summary
+    pub fn with_recorder(mut self, recorder: SharedRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Amount of one whole base token in base units
+    fn one_base_token(&self) -> u64 {
+        10u64.pow(self.base_decimals as u32)
+    }
+}
+
+#[async_trait]
+impl PricePort for JupiterQuotePriceSource {
+    async fn next_price(&self) -> Result<PriceSample, PricePortError> {
+        let amount = self.one_base_token();
+        let request = QuoteRequest::new(
+            self.base_mint.clone(),
+            self.quote_mint.clone(),
+            amount,
+            self.slippage_bps,
+        );
+
+        let started = Instant::now();
+        let quote = self.jupiter.get_quote_with_priority(&request, RequestPriority::Polling).await
+            .map_err(|e| PricePortError::FetchError(format!("Failed to get quote: {}", e)))?;
+        let latency = started.elapsed();
+
+        let output_amount = quote.output_amount();
+        if output_amount == 0 {
+            return Err(PricePortError::ParseError("Quote returned zero output".to_string()));
+        }
+
+        let price = output_amount as f64 / 10f64.powi(self.quote_decimals as i32);
+        let sample = PriceSample::now(self.base_mint.clone(), price);
+
+        if let Some(ref recorder) = self.recorder {
+            record_shared(recorder, RecordedEvent::Quote(QuoteSummary::from_quote(&quote, latency)));
+            record_shared(recorder, RecordedEvent::Price(sample.clone()));
+        }
+
+        Ok(sample)
+    }
+
+    fn mint(&self) -> &str {
+        &self.base_mint
+    }
+}
@@ -0,0 +1,372 @@
+//! Market Data Recorder
+//!
+//! Appends every price sample and quote summary the bot observes to
+//! JSON-lines files so a live session can be replayed later.
+//!
+//! Files rotate at UTC day boundaries and when they exceed a size limit:
+//! `<dir>/market-20260118.jsonl`, `<dir>/market-20260118.1.jsonl`, ...
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::adapters::jupiter::QuoteResponse;
+use crate::ports::price::PriceSample;
+
+/// File name prefix for recordings
+pub const RECORDING_PREFIX: &str = "market";
+
+/// Default rotation size (64 MB)
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Recorder shared between the price source and the orchestrator
+pub type SharedRecorder = Arc<Mutex<MarketRecorder>>;
+
+#[derive(Debug, Error)]
+pub enum RecorderError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    SerializeError(#[from] serde_json::Error),
+}
+
+/// Summary of a Jupiter quote as seen by the bot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteSummary {
+    pub timestamp: DateTime<Utc>,
+    pub input_mint: String,
+    pub output_mint: String,
+    /// Input amount in base units
+    pub in_amount: u64,
+    /// Output amount in base units
+    pub out_amount: u64,
+    /// Price impact percentage
+    pub price_impact_pct: f64,
+    /// DEX labels of each route step (e.g., "Raydium", "Orca")
+    pub route_labels: Vec<String>,
+    /// Round-trip latency of the quote request
+    pub latency_ms: u64,
+}
+
+impl QuoteSummary {
+    /// Summarize a quote response received after `latency`
+    pub fn from_quote(quote: &QuoteResponse, latency: Duration) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            input_mint: quote.input_mint.clone(),
+            output_mint: quote.output_mint.clone(),
+            in_amount: quote.input_amount(),
+            out_amount: quote.output_amount(),
+            price_impact_pct: quote.price_impact(),
+            route_labels: quote
+                .route_plan
+                .iter()
+                .map(|step| step.swap_info.label.clone())
+                .collect(),
+            latency_ms: latency.as_millis() as u64,
+        }
+    }
+}
+
+/// One line of a recording file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedEvent {
+    Price(PriceSample),
+    Quote(QuoteSummary),
+}
+
+impl RecordedEvent {
+    /// Time the event was observed
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            RecordedEvent::Price(sample) => sample.timestamp,
+            RecordedEvent::Quote(quote) => quote.timestamp,
+        }
+    }
+}
+
+/// Appends recorded events to rotating JSON-lines files
+#[derive(Debug)]
+pub struct MarketRecorder {
+    /// Directory recordings are written to
+    dir: PathBuf,
+    /// Rotate once a file reaches this size
+    max_file_bytes: u64,
+    /// Currently open file
+    file: Option<File>,
+    /// UTC day of the open file
+    current_day: Option<NaiveDate>,
+    /// Rotation index within the current day
+    current_index: u32,
+    /// Bytes in the open file
+    current_bytes: u64,
+}
+
+impl MarketRecorder {
+    /// Create a recorder writing to `dir` (created on first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            file: None,
+            current_day: None,
+            current_index: 0,
+            current_bytes: 0,
+        }
+    }
+
+    /// Set the rotation size
+    pub fn with_max_file_bytes(mut self, max_file_bytes: u64) -> Self {
+        self.max_file_bytes = max_file_bytes.max(1);
+        self
+    }
+
+    /// Wrap in a shared handle
+    pub fn shared(self) -> SharedRecorder {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Record a price sample
+    pub fn record_price(&mut self, sample: &PriceSample) -> Result<(), RecorderError> {
+        self.record(&RecordedEvent::Price(sample.clone()))
+    }
+
+    /// Record a quote summary
+    pub fn record_quote(&mut self, quote: &QuoteSummary) -> Result<(), RecorderError> {
+        self.record(&RecordedEvent::Quote(quote.clone()))
+    }
+
+    /// Append one event, rotating the file if needed
+    pub fn record(&mut self, event: &RecordedEvent) -> Result<(), RecorderError> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        let day = event.timestamp().date_naive();
+        let needs_rotation = self.file.is_none()
+            || self.current_day != Some(day)
+            || self.current_bytes + line.len() as u64 > self.max_file_bytes;
+
+        if needs_rotation {
+            self.rotate(day, line.len() as u64)?;
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes())?;
+            file.flush()?;
+            self.current_bytes += line.len() as u64;
+        }
+
+        Ok(())
+    }
+
+    /// Path of the file currently being written
+    pub fn current_path(&self) -> Option<PathBuf> {
+        self.current_day
+            .filter(|_| self.file.is_some())
+            .map(|day| self.file_path(day, self.current_index))
+    }
+
+    /// Directory recordings are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Open the first file for `day` that still has room for `incoming` bytes
+    fn rotate(&mut self, day: NaiveDate, incoming: u64) -> Result<(), RecorderError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let mut index = if self.current_day == Some(day) {
+            self.current_index
+        } else {
+            0
+        };
+
+        loop {
+            let path = self.file_path(day, index);
+            let existing = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+            // Always accept an empty file, even if one line exceeds the limit
+            if existing == 0 || existing + incoming <= self.max_file_bytes {
+                let file = OpenOptions::new().create(true).append(true).open(&path)?;
+                self.file = Some(file);
+                self.current_day = Some(day);
+                self.current_index = index;
+                self.current_bytes = existing;
+                return Ok(());
+            }
+
+            index += 1;
+        }
+    }
+
+    fn file_path(&self, day: NaiveDate, index: u32) -> PathBuf {
+        let date = day.format("%Y%m%d");
+        if index == 0 {
+            self.dir.join(format!("{}-{}.jsonl", RECORDING_PREFIX, date))
+        } else {
+            self.dir.join(format!("{}-{}.{}.jsonl", RECORDING_PREFIX, date, index))
+        }
+    }
+}
+
+/// Record through a shared handle, logging instead of failing
+/// Recording must never interrupt trading
+pub fn record_shared(recorder: &SharedRecorder, event: RecordedEvent) {
+    match recorder.lock() {
+        Ok(mut recorder) => {
+            if let Err(e) = recorder.record(&event) {
+                tracing::warn!("Failed to record market data: {}", e);
+            }
+        }
+        Err(_) => tracing::warn!("Market recorder lock poisoned, event dropped"),
+    }
+}
+
+/// Load all events from a recording file or a directory of recordings,
+/// sorted by timestamp. Lines that fail to parse (e.g. a partial line
+/// left by a crash) are skipped with a warning.
+pub fn load_recording(path: impl AsRef<Path>) -> Result<Vec<RecordedEvent>, RecorderError> {
+    let path = path.as_ref();
+
+    let mut files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().map(|ext| ext == "jsonl").unwrap_or(false))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut events = Vec::new();
+    for file in files.drain(..) {
+        let reader = BufReader::new(File::open(&file)?);
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<RecordedEvent>(&line) {
+                Ok(event) => events.push(event),
+                Err(e) => tracing::warn!(
+                    "Skipping malformed line {} in {}: {}",
+                    line_no + 1,
+                    file.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    // Stable sort keeps file order for identical timestamps
+    events.sort_by_key(|event| event.timestamp());
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn sample_at(secs: i64, price: f64) -> PriceSample {
+        PriceSample {
+            timestamp: Utc.timestamp_opt(1_768_694_400 + secs, 0).unwrap(), // 2026-01-18 00:00 UTC
+            mint: "SOL".to_string(),
+            price,
+        }
+    }
+
+    fn quote_summary() -> QuoteSummary {
+        QuoteSummary {
+            timestamp: Utc.timestamp_opt(1_768_694_400, 0).unwrap(),
+            input_mint: "SOL".to_string(),
+            output_mint: "USDC".to_string(),
+            in_amount: 1_000_000_000,
+            out_amount: 142_500_000,
+            price_impact_pct: 0.01,
+            route_labels: vec!["Raydium".to_string(), "Orca".to_string()],
+            latency_ms: 180,
+        }
+    }
+
+    #[test]
+    fn test_record_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let mut recorder = MarketRecorder::new(dir.path());
+
+        recorder.record_price(&sample_at(0, 142.5)).unwrap();
+        recorder.record_quote(&quote_summary()).unwrap();
+        recorder.record_price(&sample_at(15, 142.7)).unwrap();
+
+        let path = recorder.current_path().unwrap();
+        assert!(path.ends_with("market-20260118.jsonl"));
+
+        let events = load_recording(dir.path()).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], RecordedEvent::Price(sample_at(0, 142.5)));
+        assert!(matches!(events[1], RecordedEvent::Quote(ref q) if q.route_labels.len() == 2));
+    }
+
+    #[test]
+    fn test_event_json_is_tagged() {
+        let json = serde_json::to_string(&RecordedEvent::Price(sample_at(0, 1.0))).unwrap();
+        assert!(json.contains("\"type\":\"price\""));
+
+        let json = serde_json::to_string(&RecordedEvent::Quote(quote_summary())).unwrap();
+        assert!(json.contains("\"type\":\"quote\""));
+        assert!(json.contains("\"latency_ms\":180"));
+    }
+
+    #[test]
+    fn test_rotates_on_day_change() {
+        let dir = TempDir::new().unwrap();
+        let mut recorder = MarketRecorder::new(dir.path());
+
+        recorder.record_price(&sample_at(0, 1.0)).unwrap();
+        recorder.record_price(&sample_at(86_400, 2.0)).unwrap();
+
+        assert!(dir.path().join("market-20260118.jsonl").exists());
+        assert!(dir.path().join("market-20260119.jsonl").exists());
+    }
+
+    #[test]
+    fn test_rotates_on_size() {
+        let dir = TempDir::new().unwrap();
+        let mut recorder = MarketRecorder::new(dir.path()).with_max_file_bytes(150);
+
+        for i in 0..6 {
+            recorder.record_price(&sample_at(i, 100.0 + i as f64)).unwrap();
+        }
+
+        assert!(dir.path().join("market-20260118.jsonl").exists());
+        assert!(dir.path().join("market-20260118.1.jsonl").exists());
+
+        // Everything is still loaded back in order
+        let events = load_recording(dir.path()).unwrap();
+        assert_eq!(events.len(), 6);
+        assert!(events.windows(2).all(|w| w[0].timestamp() <= w[1].timestamp()));
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = TempDir::new().unwrap();
+        let mut recorder = MarketRecorder::new(dir.path());
+        recorder.record_price(&sample_at(0, 1.0)).unwrap();
+
+        let path = recorder.current_path().unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"price\",\"timest").unwrap();
+
+        let events = load_recording(&path).unwrap();
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -0,0 +1,140 @@
+//! Replay Price Source
+//!
+//! Serves price samples captured by the `MarketRecorder` back through the
+//! `PricePort`, in recorded order, so a live session can be re-run offline.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+
+use super::recorder::{load_recording, RecordedEvent, RecorderError};
+use crate::ports::price::{PricePort, PricePortError, PriceSample};
+
+/// Price port backed by recorded samples
+#[derive(Debug)]
+pub struct ReplayPriceSource {
+    /// Mint being replayed
+    mint: String,
+    /// Samples for `mint`, sorted by timestamp
+    samples: Vec<PriceSample>,
+    /// Index of the next sample to serve
+    cursor: AtomicUsize,
+}
+
+impl ReplayPriceSource {
+    /// Create from samples; samples for other mints are dropped
+    pub fn new(mint: impl Into<String>, samples: Vec<PriceSample>) -> Self {
+        let mint = mint.into();
+        let mut samples: Vec<PriceSample> = samples
+            .into_iter()
+            .filter(|s| s.mint == mint)
+            .collect();
+        samples.sort_by_key(|s| s.timestamp);
+
+        Self {
+            mint,
+            samples,
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    /// Load price samples for `mint` from a recording file or directory
+    pub fn from_path(path: impl AsRef<Path>, mint: &str) -> Result<Self, RecorderError> {
+        let samples = load_recording(path)?
+            .into_iter()
+            .filter_map(|event| match event {
+                RecordedEvent::Price(sample) => Some(sample),
+                RecordedEvent::Quote(_) => None,
+            })
+            .collect();
+
+        Ok(Self::new(mint, samples))
+    }
+
+    /// All samples in replay order
+    pub fn samples(&self) -> &[PriceSample] {
+        &self.samples
+    }
+
+    /// Total number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether there is nothing to replay
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Samples not yet served
+    pub fn remaining(&self) -> usize {
+        self.samples.len().saturating_sub(self.cursor.load(Ordering::SeqCst))
+    }
+
+    /// Start over from the first sample
+    pub fn rewind(&self) {
+        self.cursor.store(0, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl PricePort for ReplayPriceSource {
+    async fn next_price(&self) -> Result<PriceSample, PricePortError> {
+        let index = self.cursor.fetch_add(1, Ordering::SeqCst);
+        self.samples
+            .get(index)
+            .cloned()
+            .ok_or(PricePortError::Exhausted)
+    }
+
+    fn mint(&self) -> &str {
+        &self.mint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::market_data::recorder::MarketRecorder;
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn samples() -> Vec<PriceSample> {
+        let start = Utc::now();
+        vec![
+            PriceSample { timestamp: start + Duration::seconds(30), mint: "SOL".into(), price: 101.0 },
+            PriceSample { timestamp: start, mint: "SOL".into(), price: 100.0 },
+            PriceSample { timestamp: start + Duration::seconds(15), mint: "BONK".into(), price: 0.00002 },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_replay_in_timestamp_order() {
+        let source = ReplayPriceSource::new("SOL", samples());
+        assert_eq!(source.len(), 2);
+
+        assert_eq!(source.next_price().await.unwrap().price, 100.0);
+        assert_eq!(source.next_price().await.unwrap().price, 101.0);
+        assert!(matches!(source.next_price().await, Err(PricePortError::Exhausted)));
+        assert_eq!(source.remaining(), 0);
+
+        source.rewind();
+        assert_eq!(source.remaining(), 2);
+        assert_eq!(source.next_price().await.unwrap().price, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_from_recording_dir() {
+        let dir = TempDir::new().unwrap();
+        let mut recorder = MarketRecorder::new(dir.path());
+        for sample in samples() {
+            recorder.record_price(&sample).unwrap();
+        }
+
+        let source = ReplayPriceSource::from_path(dir.path(), "SOL").unwrap();
+        assert_eq!(source.mint(), "SOL");
+        assert_eq!(source.len(), 2);
+        assert_eq!(source.next_price().await.unwrap().price, 100.0);
+    }
+}
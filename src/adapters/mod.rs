@@ -8,6 +8,8 @@
 //! - Jito: MEV-protected bundle submission
 //! - Token Metadata: Mint authority, freeze authority, supply info
 //! - Pump.fun: Real-time token launch monitoring via WebSocket
+//! - Pools: On-chain AMM pool reader for Jupiter-independent pricing
+//! - Perp: Perp venues for true shorts (local in-memory stand-in)
 
 pub mod jupiter;
 pub mod solana;
@@ -16,4 +18,6 @@ pub mod market_data;
 pub mod jito;
 pub mod token_metadata;
 pub mod pump_fun;
+pub mod pools;
+pub mod perp;
 
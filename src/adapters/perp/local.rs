@@ -0,0 +1,175 @@
+//! Local Perp Exchange
+//!
+//! In-memory stand-in for a perp DEX: fills every order at the requested
+//! price less a taker fee and tracks a single position. Used in tests and
+//! paper mode where no perp venue is connected.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::domain::position::Side;
+use crate::ports::perp::{PerpFill, PerpPort, PerpPortError, PerpPosition};
+
+#[derive(Debug, Default)]
+struct LocalState {
+    position: Option<PerpPosition>,
+    fills: Vec<PerpFill>,
+}
+
+/// In-memory perp venue
+#[derive(Debug)]
+pub struct LocalPerpExchange {
+    market: String,
+    /// Taker fee in basis points of notional
+    fee_bps: f64,
+    state: Mutex<LocalState>,
+}
+
+impl LocalPerpExchange {
+    pub fn new(market: impl Into<String>) -> Self {
+        Self {
+            market: market.into(),
+            fee_bps: 5.0,
+            state: Mutex::new(LocalState::default()),
+        }
+    }
+
+    pub fn with_fee_bps(mut self, fee_bps: f64) -> Self {
+        self.fee_bps = fee_bps;
+        self
+    }
+
+    /// All fills in execution order
+    pub fn fills(&self) -> Vec<PerpFill> {
+        self.state.lock().unwrap().fills.clone()
+    }
+
+    /// Realized PnL net of fees, in quote units
+    pub fn realized_pnl(&self) -> f64 {
+        self.state
+            .lock()
+            .unwrap()
+            .fills
+            .iter()
+            .map(|fill| fill.realized_pnl - fill.fee)
+            .sum()
+    }
+
+    fn fee(&self, size: f64, price: f64) -> f64 {
+        size * price * self.fee_bps / 10_000.0
+    }
+}
+
+fn check_order(size: f64, price: f64) -> Result<(), PerpPortError> {
+    if size <= 0.0 || price <= 0.0 {
+        return Err(PerpPortError::Rejected(format!(
+            "size and price must be > 0, got {} @ {}",
+            size, price
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl PerpPort for LocalPerpExchange {
+    fn market(&self) -> &str {
+        &self.market
+    }
+
+    async fn increase(&self, side: Side, size: f64, price: f64) -> Result<PerpFill, PerpPortError> {
+        check_order(size, price)?;
+        let mut state = self.state.lock().unwrap();
+
+        let position = match state.position {
+            Some(open) if open.side != side => {
+                return Err(PerpPortError::Rejected(format!(
+                    "{:?} position open, reduce it before going {:?}",
+                    open.side, side
+                )));
+            }
+            Some(open) => PerpPosition {
+                side,
+                size: open.size + size,
+                entry_price: (open.entry_price * open.size + price * size) / (open.size + size),
+            },
+            None => PerpPosition { side, size, entry_price: price },
+        };
+
+        let fill = PerpFill { side, size, price, fee: self.fee(size, price), realized_pnl: 0.0 };
+        state.position = Some(position);
+        state.fills.push(fill);
+        Ok(fill)
+    }
+
+    async fn reduce(&self, size: f64, price: f64) -> Result<PerpFill, PerpPortError> {
+        check_order(size, price)?;
+        let mut state = self.state.lock().unwrap();
+
+        let open = state.position.ok_or(PerpPortError::NoPosition)?;
+        if size > open.size + 1e-9 {
+            return Err(PerpPortError::Rejected(format!(
+                "cannot reduce {:.4} of a {:.4} position",
+                size, open.size
+            )));
+        }
+
+        let size = size.min(open.size);
+        let realized_pnl = match open.side {
+            Side::Long => (price - open.entry_price) * size,
+            Side::Short => (open.entry_price - price) * size,
+        };
+        let remaining = open.size - size;
+        state.position = (remaining > 1e-9).then_some(PerpPosition { size: remaining, ..open });
+
+        let fill = PerpFill { side: open.side, size, price, fee: self.fee(size, price), realized_pnl };
+        state.fills.push(fill);
+        Ok(fill)
+    }
+
+    async fn position(&self) -> Result<Option<PerpPosition>, PerpPortError> {
+        Ok(self.state.lock().unwrap().position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_short_round_trip() {
+        let exchange = LocalPerpExchange::new("SOL-PERP").with_fee_bps(0.0);
+
+        exchange.increase(Side::Short, 1.0, 100.0).await.unwrap();
+        exchange.increase(Side::Short, 1.0, 110.0).await.unwrap();
+        let position = exchange.position().await.unwrap().unwrap();
+        assert_eq!(position.side, Side::Short);
+        assert!((position.entry_price - 105.0).abs() < 1e-9);
+
+        // Half off at 100: +5 per SOL on 1 SOL
+        let fill = exchange.reduce(1.0, 100.0).await.unwrap();
+        assert!((fill.realized_pnl - 5.0).abs() < 1e-9);
+        assert!((exchange.position().await.unwrap().unwrap().size - 1.0).abs() < 1e-9);
+
+        exchange.reduce(1.0, 95.0).await.unwrap();
+        assert!(exchange.position().await.unwrap().is_none());
+        assert!((exchange.realized_pnl() - 15.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_orders() {
+        let exchange = LocalPerpExchange::new("SOL-PERP");
+
+        assert!(matches!(exchange.reduce(1.0, 100.0).await, Err(PerpPortError::NoPosition)));
+        assert!(exchange.increase(Side::Short, 0.0, 100.0).await.is_err());
+
+        exchange.increase(Side::Short, 1.0, 100.0).await.unwrap();
+        assert!(exchange.increase(Side::Long, 1.0, 100.0).await.is_err());
+        assert!(exchange.reduce(2.0, 100.0).await.is_err());
+
+        // Fees are charged on every fill
+        let fill = exchange.reduce(1.0, 100.0).await.unwrap();
+        assert!((fill.fee - 0.05).abs() < 1e-9);
+        assert!((exchange.realized_pnl() + 0.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,5 @@
+//! Perp venue adapters implementing `PerpPort`
+
+mod local;
+
+pub use local::LocalPerpExchange;
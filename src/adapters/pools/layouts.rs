@@ -0,0 +1,369 @@
+//! Pool Account Layouts
+//!
+//! Byte-offset decoders for the pool accounts of each supported program,
+//! plus the SPL token account and mint fields the reader needs. Only the
+//! fields used for pricing are decoded.
+//!
+//! | Program        | Account     | Pricing fields                                   |
+//! |----------------|-------------|--------------------------------------------------|
+//! | Raydium AMM v4 | AmmInfo     | decimals, swap fee, pending PnL, vaults, mints   |
+//! | Raydium CPMM   | PoolState   | amm_config, vaults, mints, decimals, owed fees   |
+//! | Raydium CLMM   | PoolState   | amm_config, mints, decimals, liquidity, sqrt px  |
+//! | Orca Whirlpool | Whirlpool   | fee rate, liquidity, sqrt px, mints, vaults      |
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::reader::PoolReaderError;
+use super::types::PoolKind;
+
+/// Fee denominator used by Raydium CPMM/CLMM configs and Whirlpools
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+
+/// SPL token account: `amount` offset
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// SPL mint: `decimals` offset
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Where the swap fee for a pool comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeeSource {
+    /// Stored on the pool account
+    Fixed(f64),
+    /// Stored on a separate AMM config account
+    AmmConfig(Pubkey),
+}
+
+/// Current price state of a concentrated-liquidity pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcentratedState {
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub tick_current: i32,
+}
+
+/// Pool account fields needed for pricing
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolLayout {
+    pub kind: PoolKind,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    /// Token decimals when stored on the pool (Whirlpools do not)
+    pub decimals: Option<(u8, u8)>,
+    pub fee: FeeSource,
+    /// Vault balances owed to the protocol rather than LPs (constant product only)
+    pub pending_fees: (u64, u64),
+    /// Present for concentrated-liquidity pools
+    pub concentrated: Option<ConcentratedState>,
+}
+
+impl PoolLayout {
+    /// Accounts that must also be read to price this pool
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        let mut accounts = Vec::new();
+        if self.concentrated.is_none() {
+            accounts.push(self.vault_a);
+            accounts.push(self.vault_b);
+        }
+        if self.decimals.is_none() {
+            accounts.push(self.mint_a);
+            accounts.push(self.mint_b);
+        }
+        if let FeeSource::AmmConfig(config) = self.fee {
+            accounts.push(config);
+        }
+        accounts
+    }
+}
+
+/// Decode a pool account of the given kind
+pub fn decode_pool(kind: PoolKind, data: &[u8]) -> Result<PoolLayout, PoolReaderError> {
+    match kind {
+        PoolKind::RaydiumAmmV4 => decode_raydium_amm_v4(data),
+        PoolKind::RaydiumCpmm => decode_raydium_cpmm(data),
+        PoolKind::RaydiumClmm => decode_raydium_clmm(data),
+        PoolKind::OrcaWhirlpool => decode_whirlpool(data),
+    }
+}
+
+/// Raydium AMM v4 `AmmInfo` (752 bytes, no discriminator)
+fn decode_raydium_amm_v4(data: &[u8]) -> Result<PoolLayout, PoolReaderError> {
+    require_len(data, 752, "Raydium AMM v4 pool")?;
+
+    let fee_numerator = read_u64(data, 176)?;
+    let fee_denominator = read_u64(data, 184)?;
+    let fee_rate = if fee_denominator == 0 {
+        0.0
+    } else {
+        fee_numerator as f64 / fee_denominator as f64
+    };
+
+    Ok(PoolLayout {
+        kind: PoolKind::RaydiumAmmV4,
+        decimals: Some((read_u64(data, 32)? as u8, read_u64(data, 40)? as u8)),
+        fee: FeeSource::Fixed(fee_rate),
+        pending_fees: (read_u64(data, 192)?, read_u64(data, 200)?),
+        vault_a: read_pubkey(data, 336)?,
+        vault_b: read_pubkey(data, 368)?,
+        mint_a: read_pubkey(data, 400)?,
+        mint_b: read_pubkey(data, 432)?,
+        concentrated: None,
+    })
+}
+
+/// Raydium CPMM `PoolState` (Anchor, 8-byte discriminator)
+fn decode_raydium_cpmm(data: &[u8]) -> Result<PoolLayout, PoolReaderError> {
+    require_len(data, 373, "Raydium CPMM pool")?;
+
+    let protocol_fees = (read_u64(data, 341)?, read_u64(data, 349)?);
+    let fund_fees = (read_u64(data, 357)?, read_u64(data, 365)?);
+
+    Ok(PoolLayout {
+        kind: PoolKind::RaydiumCpmm,
+        fee: FeeSource::AmmConfig(read_pubkey(data, 8)?),
+        vault_a: read_pubkey(data, 72)?,
+        vault_b: read_pubkey(data, 104)?,
+        mint_a: read_pubkey(data, 168)?,
+        mint_b: read_pubkey(data, 200)?,
+        decimals: Some((read_u8(data, 331)?, read_u8(data, 332)?)),
+        pending_fees: (
+            protocol_fees.0.saturating_add(fund_fees.0),
+            protocol_fees.1.saturating_add(fund_fees.1),
+        ),
+        concentrated: None,
+    })
+}
+
+/// Raydium CLMM `PoolState` (Anchor, 8-byte discriminator)
+fn decode_raydium_clmm(data: &[u8]) -> Result<PoolLayout, PoolReaderError> {
+    require_len(data, 273, "Raydium CLMM pool")?;
+
+    Ok(PoolLayout {
+        kind: PoolKind::RaydiumClmm,
+        fee: FeeSource::AmmConfig(read_pubkey(data, 9)?),
+        mint_a: read_pubkey(data, 73)?,
+        mint_b: read_pubkey(data, 105)?,
+        vault_a: read_pubkey(data, 137)?,
+        vault_b: read_pubkey(data, 169)?,
+        decimals: Some((read_u8(data, 233)?, read_u8(data, 234)?)),
+        pending_fees: (0, 0),
+        concentrated: Some(ConcentratedState {
+            liquidity: read_u128(data, 237)?,
+            sqrt_price_x64: read_u128(data, 253)?,
+            tick_current: read_i32(data, 269)?,
+        }),
+    })
+}
+
+/// Orca `Whirlpool` (Anchor, 8-byte discriminator)
+fn decode_whirlpool(data: &[u8]) -> Result<PoolLayout, PoolReaderError> {
+    require_len(data, 245, "Orca Whirlpool")?;
+
+    Ok(PoolLayout {
+        kind: PoolKind::OrcaWhirlpool,
+        fee: FeeSource::Fixed(read_u16(data, 45)? as f64 / FEE_RATE_DENOMINATOR),
+        mint_a: read_pubkey(data, 101)?,
+        vault_a: read_pubkey(data, 133)?,
+        mint_b: read_pubkey(data, 181)?,
+        vault_b: read_pubkey(data, 213)?,
+        decimals: None,
+        pending_fees: (0, 0),
+        concentrated: Some(ConcentratedState {
+            liquidity: read_u128(data, 49)?,
+            sqrt_price_x64: read_u128(data, 65)?,
+            tick_current: read_i32(data, 81)?,
+        }),
+    })
+}
+
+/// Trade fee rate from a Raydium AMM config account
+pub fn decode_amm_config_fee(kind: PoolKind, data: &[u8]) -> Result<f64, PoolReaderError> {
+    let raw = match kind {
+        // CPMM AmmConfig: bump u8, disable_create_pool bool, index u16, trade_fee_rate u64
+        PoolKind::RaydiumCpmm => read_u64(data, 12)?,
+        // CLMM AmmConfig: bump u8, index u16, owner Pubkey, protocol_fee_rate u32, trade_fee_rate u32
+        PoolKind::RaydiumClmm => read_u32(data, 47)? as u64,
+        other => {
+            return Err(PoolReaderError::InvalidAccountData(format!(
+                "{} pools have no AMM config",
+                other.label()
+            )))
+        }
+    };
+
+    Ok(raw as f64 / FEE_RATE_DENOMINATOR)
+}
+
+/// Balance of an SPL token account (Token and Token-2022 share the base layout)
+pub fn decode_token_account_amount(data: &[u8]) -> Result<u64, PoolReaderError> {
+    read_u64(data, TOKEN_ACCOUNT_AMOUNT_OFFSET)
+}
+
+/// Decimals of an SPL mint
+pub fn decode_mint_decimals(data: &[u8]) -> Result<u8, PoolReaderError> {
+    read_u8(data, MINT_DECIMALS_OFFSET)
+}
+
+fn require_len(data: &[u8], len: usize, what: &str) -> Result<(), PoolReaderError> {
+    if data.len() < len {
+        return Err(PoolReaderError::InvalidAccountData(format!(
+            "{} account too short: {} < {} bytes",
+            what,
+            data.len(),
+            len
+        )));
+    }
+    Ok(())
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], PoolReaderError> {
+    data.get(offset..offset + N)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(|| {
+            PoolReaderError::InvalidAccountData(format!(
+                "read of {} bytes at offset {} past end of {}-byte account",
+                N,
+                offset,
+                data.len()
+            ))
+        })
+}
+
+fn read_u8(data: &[u8], offset: usize) -> Result<u8, PoolReaderError> {
+    Ok(read_bytes::<1>(data, offset)?[0])
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, PoolReaderError> {
+    read_bytes(data, offset).map(u16::from_le_bytes)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, PoolReaderError> {
+    read_bytes(data, offset).map(u32::from_le_bytes)
+}
+
+fn read_i32(data: &[u8], offset: usize) -> Result<i32, PoolReaderError> {
+    read_bytes(data, offset).map(i32::from_le_bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, PoolReaderError> {
+    read_bytes(data, offset).map(u64::from_le_bytes)
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Result<u128, PoolReaderError> {
+    read_bytes(data, offset).map(u128::from_le_bytes)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey, PoolReaderError> {
+    read_bytes::<32>(data, offset).map(Pubkey::new_from_array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    #[test]
+    fn test_decode_raydium_amm_v4() {
+        let mut data = vec![0u8; 752];
+        let (vault_a, vault_b, mint_a, mint_b) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        put(&mut data, 32, &9u64.to_le_bytes());
+        put(&mut data, 40, &6u64.to_le_bytes());
+        put(&mut data, 176, &25u64.to_le_bytes());
+        put(&mut data, 184, &10_000u64.to_le_bytes());
+        put(&mut data, 192, &7u64.to_le_bytes());
+        put(&mut data, 336, vault_a.as_ref());
+        put(&mut data, 368, vault_b.as_ref());
+        put(&mut data, 400, mint_a.as_ref());
+        put(&mut data, 432, mint_b.as_ref());
+
+        let layout = decode_pool(PoolKind::RaydiumAmmV4, &data).unwrap();
+        assert_eq!(layout.decimals, Some((9, 6)));
+        assert_eq!(layout.fee, FeeSource::Fixed(0.0025));
+        assert_eq!(layout.pending_fees, (7, 0));
+        assert_eq!((layout.mint_a, layout.mint_b), (mint_a, mint_b));
+        assert_eq!(layout.dependencies(), vec![vault_a, vault_b]);
+    }
+
+    #[test]
+    fn test_decode_raydium_cpmm() {
+        let mut data = vec![0u8; 637];
+        let config = Pubkey::new_unique();
+        put(&mut data, 8, config.as_ref());
+        put(&mut data, 331, &[9, 6]);
+        put(&mut data, 341, &3u64.to_le_bytes());
+        put(&mut data, 357, &2u64.to_le_bytes());
+
+        let layout = decode_pool(PoolKind::RaydiumCpmm, &data).unwrap();
+        assert_eq!(layout.fee, FeeSource::AmmConfig(config));
+        assert_eq!(layout.pending_fees, (5, 0));
+        assert_eq!(layout.dependencies().last(), Some(&config));
+
+        let mut config_data = vec![0u8; 236];
+        put(&mut config_data, 12, &2_500u64.to_le_bytes());
+        assert_eq!(decode_amm_config_fee(PoolKind::RaydiumCpmm, &config_data).unwrap(), 0.0025);
+    }
+
+    #[test]
+    fn test_decode_raydium_clmm() {
+        let mut data = vec![0u8; 1544];
+        put(&mut data, 233, &[9, 6]);
+        put(&mut data, 237, &1_000u128.to_le_bytes());
+        put(&mut data, 253, &(2u128 << 64).to_le_bytes());
+        put(&mut data, 269, &(-42i32).to_le_bytes());
+
+        let layout = decode_pool(PoolKind::RaydiumClmm, &data).unwrap();
+        let state = layout.concentrated.unwrap();
+        assert_eq!(state.liquidity, 1_000);
+        assert_eq!(state.sqrt_price_x64, 2u128 << 64);
+        assert_eq!(state.tick_current, -42);
+
+        let mut config_data = vec![0u8; 117];
+        put(&mut config_data, 47, &400u32.to_le_bytes());
+        assert_eq!(decode_amm_config_fee(PoolKind::RaydiumClmm, &config_data).unwrap(), 0.0004);
+    }
+
+    #[test]
+    fn test_decode_whirlpool_needs_mint_decimals() {
+        let mut data = vec![0u8; 653];
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        put(&mut data, 45, &3_000u16.to_le_bytes());
+        put(&mut data, 49, &5_000u128.to_le_bytes());
+        put(&mut data, 65, &(1u128 << 64).to_le_bytes());
+        put(&mut data, 101, mint_a.as_ref());
+        put(&mut data, 181, mint_b.as_ref());
+
+        let layout = decode_pool(PoolKind::OrcaWhirlpool, &data).unwrap();
+        assert_eq!(layout.fee, FeeSource::Fixed(0.003));
+        assert!(layout.decimals.is_none());
+        assert_eq!(layout.dependencies(), vec![mint_a, mint_b]);
+    }
+
+    #[test]
+    fn test_short_account_rejected() {
+        let data = vec![0u8; 100];
+        for kind in PoolKind::ALL {
+            assert!(matches!(
+                decode_pool(kind, &data),
+                Err(PoolReaderError::InvalidAccountData(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_token_account_and_mint() {
+        let mut account = vec![0u8; 165];
+        put(&mut account, 64, &123_456u64.to_le_bytes());
+        assert_eq!(decode_token_account_amount(&account).unwrap(), 123_456);
+
+        let mut mint = vec![0u8; 82];
+        mint[44] = 6;
+        assert_eq!(decode_mint_decimals(&mint).unwrap(), 6);
+
+        assert!(decode_token_account_amount(&[0u8; 10]).is_err());
+    }
+}
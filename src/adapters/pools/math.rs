@@ -0,0 +1,147 @@
+//! AMM Pricing Math
+//!
+//! Mid price and swap output for constant-product (x*y=k) and
+//! concentrated-liquidity pools, computed locally from pool state.
+//! All amounts are raw token units; prices are raw `b per a` unless noted.
+
+/// 2^64, the fixed-point scale of Q64.64 sqrt prices
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Output of a constant-product swap after the input fee
+///
+/// Returns 0 for empty pools or zero input.
+pub fn constant_product_out(amount_in: u64, reserve_in: u64, reserve_out: u64, fee_rate: f64) -> u64 {
+    if amount_in == 0 || reserve_in == 0 || reserve_out == 0 {
+        return 0;
+    }
+
+    // Fee in parts per million keeps the product in integer math
+    let fee_ppm = (fee_rate.clamp(0.0, 1.0) * 1_000_000.0).round() as u128;
+    let in_after_fee = amount_in as u128 * (1_000_000 - fee_ppm) / 1_000_000;
+
+    let out = reserve_out as u128 * in_after_fee / (reserve_in as u128 + in_after_fee);
+    out as u64
+}
+
+/// Raw mid price of a constant-product pool (`reserve_b / reserve_a`)
+pub fn constant_product_price(reserve_a: u64, reserve_b: u64) -> Option<f64> {
+    if reserve_a == 0 {
+        return None;
+    }
+    Some(reserve_b as f64 / reserve_a as f64)
+}
+
+/// Convert a Q64.64 sqrt price to a plain sqrt price
+pub fn sqrt_price_from_x64(sqrt_price_x64: u128) -> f64 {
+    sqrt_price_x64 as f64 / Q64
+}
+
+/// Raw mid price of a concentrated-liquidity pool (`b per a`)
+pub fn concentrated_price(sqrt_price_x64: u128) -> f64 {
+    let sqrt_price = sqrt_price_from_x64(sqrt_price_x64);
+    sqrt_price * sqrt_price
+}
+
+/// Output of a concentrated-liquidity swap within the active tick range
+///
+/// Uses the active liquidity only, so the result is exact while the swap
+/// does not cross an initialized tick and optimistic beyond that. Callers
+/// pricing sizes that are large relative to `liquidity` should treat it
+/// as an upper bound.
+pub fn concentrated_out(
+    amount_in: u64,
+    sqrt_price_x64: u128,
+    liquidity: u128,
+    fee_rate: f64,
+    a_to_b: bool,
+) -> u64 {
+    if amount_in == 0 || liquidity == 0 || sqrt_price_x64 == 0 {
+        return 0;
+    }
+
+    let input = amount_in as f64 * (1.0 - fee_rate.clamp(0.0, 1.0));
+    let liquidity = liquidity as f64;
+    let sqrt_price = sqrt_price_from_x64(sqrt_price_x64);
+
+    let out = if a_to_b {
+        // Token a in pushes the price down: sqrt_p' = L * sqrt_p / (L + dx * sqrt_p)
+        let next = liquidity * sqrt_price / (liquidity + input * sqrt_price);
+        liquidity * (sqrt_price - next)
+    } else {
+        // Token b in pushes the price up: sqrt_p' = sqrt_p + dy / L
+        let next = sqrt_price + input / liquidity;
+        liquidity * (next - sqrt_price) / (sqrt_price * next)
+    };
+
+    out.max(0.0).floor() as u64
+}
+
+/// Scale a raw price to UI units given the token decimals
+pub fn ui_price(raw_price: f64, decimals_a: u8, decimals_b: u8) -> f64 {
+    raw_price * 10f64.powi(decimals_a as i32 - decimals_b as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_product_out() {
+        // 1_000 in against 1_000_000 / 2_000_000 with no fee
+        let out = constant_product_out(1_000, 1_000_000, 2_000_000, 0.0);
+        assert_eq!(out, 1_998);
+
+        // 0.25% fee reduces output
+        let with_fee = constant_product_out(1_000, 1_000_000, 2_000_000, 0.0025);
+        assert!(with_fee < out);
+
+        assert_eq!(constant_product_out(0, 1, 1, 0.0), 0);
+        assert_eq!(constant_product_out(1, 0, 1, 0.0), 0);
+    }
+
+    #[test]
+    fn test_constant_product_price_impact_grows_with_size() {
+        let small = constant_product_out(1_000, 1_000_000_000, 150_000_000_000, 0.0) as f64 / 1_000.0;
+        let large = constant_product_out(100_000_000, 1_000_000_000, 150_000_000_000, 0.0) as f64 / 100_000_000.0;
+
+        assert!((small - 150.0).abs() < 0.5);
+        assert!(large < small * 0.95);
+    }
+
+    #[test]
+    fn test_concentrated_price() {
+        // sqrt(4) = 2 in Q64.64
+        let sqrt_price_x64 = 2u128 << 64;
+        assert!((concentrated_price(sqrt_price_x64) - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_concentrated_out_small_trade_matches_mid() {
+        let sqrt_price_x64 = 2u128 << 64; // price 4 b per a
+        let liquidity = 1_000_000_000_000u128;
+
+        let b_out = concentrated_out(1_000, sqrt_price_x64, liquidity, 0.0, true);
+        assert!((b_out as f64 - 4_000.0).abs() <= 1.0);
+
+        let a_out = concentrated_out(4_000, sqrt_price_x64, liquidity, 0.0, false);
+        assert!((a_out as f64 - 1_000.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_concentrated_out_slippage_and_fee() {
+        let sqrt_price_x64 = 2u128 << 64;
+        let liquidity = 1_000_000u128;
+
+        let out = concentrated_out(1_000_000, sqrt_price_x64, liquidity, 0.0, true);
+        assert!((out as f64) < 4_000_000.0 * 0.9);
+
+        let with_fee = concentrated_out(1_000_000, sqrt_price_x64, liquidity, 0.003, true);
+        assert!(with_fee < out);
+    }
+
+    #[test]
+    fn test_ui_price() {
+        // 1 SOL (9 decimals) = 150 USDC (6 decimals): raw price 0.15
+        assert!((ui_price(0.15, 9, 6) - 150.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,14 @@
+//! On-chain Pool Adapter
+//!
+//! Reads SOL/USDC (or any pair) pool state straight from the AMM programs
+//! over RPC and prices it locally, so prices stay available when the
+//! Jupiter API is down or rate limited. Supports Raydium AMM v4, CPMM,
+//! CLMM and Orca Whirlpool pools.
+
+mod layouts;
+pub mod math;
+mod reader;
+mod types;
+
+pub use reader::{OnchainPoolReader, PoolReaderError, SOL_USDC_POOLS};
+pub use types::{PoolKind, PoolLiquidity, PoolSnapshot};
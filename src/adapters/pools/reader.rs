@@ -0,0 +1,294 @@
+//! On-chain Pool Reader
+//!
+//! Reads pool accounts over RPC and turns them into `PoolSnapshot`s that
+//! can be priced locally. Pool kind is taken from the account owner, so a
+//! wrong or migrated address fails loudly instead of being mis-decoded.
+//! Each call makes two `getMultipleAccounts` requests: one for the pools,
+//! one for their vaults, mints and fee configs.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+use super::layouts::{
+    decode_amm_config_fee, decode_mint_decimals, decode_pool, decode_token_account_amount,
+    FeeSource, PoolLayout,
+};
+use super::types::{PoolKind, PoolLiquidity, PoolSnapshot};
+use crate::adapters::solana::SolanaClient;
+use crate::domain::known_programs::KNOWN_DEX_PROGRAMS;
+
+/// Main SOL/USDC pools of the supported DEX programs
+pub const SOL_USDC_POOLS: &[(&str, &str)] = &[
+    ("Raydium AMM v4 SOL/USDC", "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2"),
+    ("Orca Whirlpool SOL/USDC", "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE"),
+    ("Raydium CLMM SOL/USDC", "3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv"),
+];
+
+/// Errors that can occur when reading pools
+#[derive(Debug, Error)]
+pub enum PoolReaderError {
+    #[error("RPC error: {0}")]
+    RpcError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Account not found: {0}")]
+    AccountNotFound(String),
+
+    #[error("Unsupported pool program {program} for {address}")]
+    UnsupportedProgram { address: String, program: String },
+
+    #[error("Invalid account data: {0}")]
+    InvalidAccountData(String),
+}
+
+/// Reads AMM pool state directly from chain
+#[derive(Clone)]
+pub struct OnchainPoolReader {
+    solana: SolanaClient,
+}
+
+impl OnchainPoolReader {
+    /// Create a reader using the given RPC client
+    pub fn new(solana: SolanaClient) -> Self {
+        Self { solana }
+    }
+
+    /// Addresses of the default SOL/USDC pools
+    pub fn sol_usdc_pools() -> Vec<String> {
+        SOL_USDC_POOLS.iter().map(|(_, address)| address.to_string()).collect()
+    }
+
+    /// Read a single pool
+    pub async fn read_pool(&self, address: &str) -> Result<PoolSnapshot, PoolReaderError> {
+        self.read_pools(&[address.to_string()])
+            .await
+            .pop()
+            .unwrap_or_else(|| Err(PoolReaderError::AccountNotFound(address.to_string())))
+    }
+
+    /// Read several pools; results are in input order and fail independently
+    pub async fn read_pools(&self, addresses: &[String]) -> Vec<Result<PoolSnapshot, PoolReaderError>> {
+        let parsed: Vec<Result<Pubkey, PoolReaderError>> = addresses
+            .iter()
+            .map(|a| Pubkey::from_str(a).map_err(|e| PoolReaderError::InvalidAddress(format!("{}: {}", a, e))))
+            .collect();
+        let keys: Vec<Pubkey> = parsed.iter().filter_map(|p| p.as_ref().ok().copied()).collect();
+
+        let pool_accounts = match self.fetch_accounts(&keys).await {
+            Ok(accounts) => accounts,
+            Err(e) => return addresses.iter().map(|_| Err(PoolReaderError::RpcError(e.to_string()))).collect(),
+        };
+
+        // Decode pools, then read every dependency in one batch
+        let layouts: Vec<Result<(String, PoolLayout), PoolReaderError>> = parsed
+            .into_iter()
+            .zip(addresses)
+            .map(|(key, address)| {
+                let key = key?;
+                let account = pool_accounts
+                    .get(&key)
+                    .ok_or_else(|| PoolReaderError::AccountNotFound(address.clone()))?;
+                Ok((address.clone(), decode_pool_account(address, account)?))
+            })
+            .collect();
+
+        let mut dependencies: Vec<Pubkey> = layouts
+            .iter()
+            .filter_map(|l| l.as_ref().ok())
+            .flat_map(|(_, layout)| layout.dependencies())
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+
+        let dependency_accounts = match self.fetch_accounts(&dependencies).await {
+            Ok(accounts) => accounts,
+            Err(e) => return addresses.iter().map(|_| Err(PoolReaderError::RpcError(e.to_string()))).collect(),
+        };
+
+        layouts
+            .into_iter()
+            .map(|layout| {
+                let (address, layout) = layout?;
+                build_snapshot(address, &layout, &dependency_accounts)
+            })
+            .collect()
+    }
+
+    async fn fetch_accounts(&self, keys: &[Pubkey]) -> Result<HashMap<Pubkey, Account>, PoolReaderError> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let accounts = self
+            .solana
+            .get_multiple_accounts(keys)
+            .await
+            .map_err(|e| PoolReaderError::RpcError(e.to_string()))?;
+
+        Ok(keys
+            .iter()
+            .zip(accounts)
+            .filter_map(|(key, account)| account.map(|a| (*key, a)))
+            .collect())
+    }
+}
+
+/// Decode a pool account, choosing the layout from its owner program
+fn decode_pool_account(address: &str, account: &Account) -> Result<PoolLayout, PoolReaderError> {
+    let owner = account.owner.to_string();
+
+    let kind = PoolKind::from_program_id(&owner).ok_or_else(|| PoolReaderError::UnsupportedProgram {
+        address: address.to_string(),
+        program: if KNOWN_DEX_PROGRAMS.contains(&owner.as_str()) {
+            format!("{} (known DEX, layout not implemented)", owner)
+        } else {
+            owner.clone()
+        },
+    })?;
+
+    decode_pool(kind, &account.data)
+}
+
+/// Combine a decoded pool with its dependency accounts
+fn build_snapshot(
+    address: String,
+    layout: &PoolLayout,
+    accounts: &HashMap<Pubkey, Account>,
+) -> Result<PoolSnapshot, PoolReaderError> {
+    let account = |key: &Pubkey| {
+        accounts
+            .get(key)
+            .ok_or_else(|| PoolReaderError::AccountNotFound(key.to_string()))
+    };
+
+    let (decimals_a, decimals_b) = match layout.decimals {
+        Some(decimals) => decimals,
+        None => (
+            decode_mint_decimals(&account(&layout.mint_a)?.data)?,
+            decode_mint_decimals(&account(&layout.mint_b)?.data)?,
+        ),
+    };
+
+    let fee_rate = match layout.fee {
+        FeeSource::Fixed(rate) => rate,
+        FeeSource::AmmConfig(config) => decode_amm_config_fee(layout.kind, &account(&config)?.data)?,
+    };
+
+    let liquidity = match layout.concentrated {
+        Some(state) => PoolLiquidity::Concentrated {
+            sqrt_price_x64: state.sqrt_price_x64,
+            liquidity: state.liquidity,
+            tick_current: state.tick_current,
+        },
+        None => {
+            let vault_a = decode_token_account_amount(&account(&layout.vault_a)?.data)?;
+            let vault_b = decode_token_account_amount(&account(&layout.vault_b)?.data)?;
+            PoolLiquidity::ConstantProduct {
+                reserve_a: vault_a.saturating_sub(layout.pending_fees.0),
+                reserve_b: vault_b.saturating_sub(layout.pending_fees.1),
+            }
+        }
+    };
+
+    Ok(PoolSnapshot {
+        address,
+        kind: layout.kind,
+        mint_a: layout.mint_a.to_string(),
+        mint_b: layout.mint_b.to_string(),
+        decimals_a,
+        decimals_b,
+        fee_rate,
+        liquidity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(owner: &str, data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1,
+            data,
+            owner: Pubkey::from_str(owner).unwrap(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn token_account(amount: u64) -> Account {
+        let mut data = vec![0u8; 165];
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        account("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", data)
+    }
+
+    #[test]
+    fn test_default_pools_parse() {
+        for address in OnchainPoolReader::sol_usdc_pools() {
+            assert!(Pubkey::from_str(&address).is_ok(), "{}", address);
+        }
+    }
+
+    #[test]
+    fn test_owner_selects_layout() {
+        let unsupported = account("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo", vec![0u8; 900]);
+        match decode_pool_account("pool", &unsupported) {
+            Err(PoolReaderError::UnsupportedProgram { program, .. }) => {
+                assert!(program.contains("known DEX"));
+            }
+            other => panic!("expected unsupported program, got {:?}", other),
+        }
+
+        let amm = account(PoolKind::RaydiumAmmV4.program_id(), vec![0u8; 752]);
+        assert_eq!(decode_pool_account("pool", &amm).unwrap().kind, PoolKind::RaydiumAmmV4);
+    }
+
+    #[test]
+    fn test_build_constant_product_snapshot() {
+        let mut data = vec![0u8; 752];
+        let (vault_a, vault_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        data[32..40].copy_from_slice(&9u64.to_le_bytes());
+        data[40..48].copy_from_slice(&6u64.to_le_bytes());
+        data[176..184].copy_from_slice(&25u64.to_le_bytes());
+        data[184..192].copy_from_slice(&10_000u64.to_le_bytes());
+        data[192..200].copy_from_slice(&1_000_000_000u64.to_le_bytes());
+        data[336..368].copy_from_slice(vault_a.as_ref());
+        data[368..400].copy_from_slice(vault_b.as_ref());
+
+        let layout = decode_pool_account("pool", &account(PoolKind::RaydiumAmmV4.program_id(), data)).unwrap();
+
+        // 101 SOL in the vault, 1 SOL owed as PnL => 100 SOL / 15_000 USDC
+        let accounts = HashMap::from([
+            (vault_a, token_account(101_000_000_000)),
+            (vault_b, token_account(15_000_000_000)),
+        ]);
+        let snapshot = build_snapshot("pool".to_string(), &layout, &accounts).unwrap();
+
+        assert_eq!(
+            snapshot.liquidity,
+            PoolLiquidity::ConstantProduct { reserve_a: 100_000_000_000, reserve_b: 15_000_000_000 }
+        );
+        assert!((snapshot.mid_price(&snapshot.mint_a.clone()).unwrap() - 150.0).abs() < 1e-9);
+        assert_eq!(snapshot.fee_rate, 0.0025);
+    }
+
+    #[test]
+    fn test_missing_dependency_is_reported() {
+        let layout = decode_pool_account(
+            "pool",
+            &account(PoolKind::RaydiumAmmV4.program_id(), vec![0u8; 752]),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            build_snapshot("pool".to_string(), &layout, &HashMap::new()),
+            Err(PoolReaderError::AccountNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+//! Pool Types
+//!
+//! Supported AMM program kinds and the decoded pool snapshot used for
+//! local pricing.
+
+use serde::Serialize;
+
+use super::math;
+
+/// Raydium AMM v4 program
+pub const RAYDIUM_AMM_V4_PROGRAM: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+/// Raydium constant-product (CPMM) program
+pub const RAYDIUM_CPMM_PROGRAM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+/// Raydium concentrated-liquidity (CLMM) program
+pub const RAYDIUM_CLMM_PROGRAM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+/// Orca Whirlpool program
+pub const ORCA_WHIRLPOOL_PROGRAM: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+
+/// Pool program kinds that can be decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum PoolKind {
+    /// Raydium AMM v4 (constant product, reserves in vaults)
+    RaydiumAmmV4,
+    /// Raydium CPMM (constant product, reserves in vaults)
+    RaydiumCpmm,
+    /// Raydium CLMM (concentrated liquidity)
+    RaydiumClmm,
+    /// Orca Whirlpool (concentrated liquidity)
+    OrcaWhirlpool,
+}
+
+impl PoolKind {
+    /// All decodable kinds
+    pub const ALL: [PoolKind; 4] = [
+        PoolKind::RaydiumAmmV4,
+        PoolKind::RaydiumCpmm,
+        PoolKind::RaydiumClmm,
+        PoolKind::OrcaWhirlpool,
+    ];
+
+    /// Program that owns pools of this kind
+    pub fn program_id(&self) -> &'static str {
+        match self {
+            PoolKind::RaydiumAmmV4 => RAYDIUM_AMM_V4_PROGRAM,
+            PoolKind::RaydiumCpmm => RAYDIUM_CPMM_PROGRAM,
+            PoolKind::RaydiumClmm => RAYDIUM_CLMM_PROGRAM,
+            PoolKind::OrcaWhirlpool => ORCA_WHIRLPOOL_PROGRAM,
+        }
+    }
+
+    /// Kind for an owning program, if supported
+    pub fn from_program_id(program_id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.program_id() == program_id)
+    }
+
+    /// Whether pricing uses sqrt price and active liquidity
+    pub fn is_concentrated(&self) -> bool {
+        matches!(self, PoolKind::RaydiumClmm | PoolKind::OrcaWhirlpool)
+    }
+
+    /// Human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoolKind::RaydiumAmmV4 => "Raydium AMM v4",
+            PoolKind::RaydiumCpmm => "Raydium CPMM",
+            PoolKind::RaydiumClmm => "Raydium CLMM",
+            PoolKind::OrcaWhirlpool => "Orca Whirlpool",
+        }
+    }
+}
+
+/// Liquidity state needed to price a swap
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum PoolLiquidity {
+    /// x*y=k reserves, net of fees owed to the protocol
+    ConstantProduct { reserve_a: u64, reserve_b: u64 },
+    /// Current Q64.64 sqrt price (b per a) and active liquidity
+    Concentrated {
+        sqrt_price_x64: u128,
+        liquidity: u128,
+        tick_current: i32,
+    },
+}
+
+/// Pool state read from chain
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolSnapshot {
+    /// Pool account address
+    pub address: String,
+    pub kind: PoolKind,
+    /// Token a (Raydium coin/token 0, Whirlpool token a)
+    pub mint_a: String,
+    /// Token b (Raydium pc/token 1, Whirlpool token b)
+    pub mint_b: String,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// Swap fee as a fraction of input
+    pub fee_rate: f64,
+    pub liquidity: PoolLiquidity,
+}
+
+impl PoolSnapshot {
+    /// Whether the pool trades `mint`
+    pub fn contains(&self, mint: &str) -> bool {
+        self.mint_a == mint || self.mint_b == mint
+    }
+
+    /// Raw mid price of a in b units
+    fn raw_price(&self) -> Option<f64> {
+        match self.liquidity {
+            PoolLiquidity::ConstantProduct { reserve_a, reserve_b } => {
+                math::constant_product_price(reserve_a, reserve_b)
+            }
+            PoolLiquidity::Concentrated { sqrt_price_x64, .. } => {
+                Some(math::concentrated_price(sqrt_price_x64)).filter(|p| *p > 0.0)
+            }
+        }
+    }
+
+    /// Mid price of `base_mint` in whole units of the other token
+    pub fn mid_price(&self, base_mint: &str) -> Option<f64> {
+        let price_a = math::ui_price(self.raw_price()?, self.decimals_a, self.decimals_b);
+
+        if base_mint == self.mint_a {
+            Some(price_a)
+        } else if base_mint == self.mint_b && price_a > 0.0 {
+            Some(1.0 / price_a)
+        } else {
+            None
+        }
+    }
+
+    /// Raw output amount for swapping `amount_in` raw units of `input_mint`
+    pub fn quote_out(&self, input_mint: &str, amount_in: u64) -> Option<u64> {
+        let a_to_b = if input_mint == self.mint_a {
+            true
+        } else if input_mint == self.mint_b {
+            false
+        } else {
+            return None;
+        };
+
+        let out = match self.liquidity {
+            PoolLiquidity::ConstantProduct { reserve_a, reserve_b } => {
+                let (reserve_in, reserve_out) = if a_to_b {
+                    (reserve_a, reserve_b)
+                } else {
+                    (reserve_b, reserve_a)
+                };
+                math::constant_product_out(amount_in, reserve_in, reserve_out, self.fee_rate)
+            }
+            PoolLiquidity::Concentrated { sqrt_price_x64, liquidity, .. } => {
+                math::concentrated_out(amount_in, sqrt_price_x64, liquidity, self.fee_rate, a_to_b)
+            }
+        };
+
+        Some(out)
+    }
+
+    /// Executable price of `input_mint` for a trade of `amount_in` raw units,
+    /// in whole units of the other token (includes fee and price impact)
+    pub fn effective_price(&self, input_mint: &str, amount_in: u64) -> Option<f64> {
+        if amount_in == 0 {
+            return None;
+        }
+
+        let out = self.quote_out(input_mint, amount_in)?;
+        let (decimals_in, decimals_out) = if input_mint == self.mint_a {
+            (self.decimals_a, self.decimals_b)
+        } else {
+            (self.decimals_b, self.decimals_a)
+        };
+
+        Some(math::ui_price(out as f64 / amount_in as f64, decimals_in, decimals_out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn cp_pool() -> PoolSnapshot {
+        PoolSnapshot {
+            address: "pool".to_string(),
+            kind: PoolKind::RaydiumAmmV4,
+            mint_a: SOL.to_string(),
+            mint_b: USDC.to_string(),
+            decimals_a: 9,
+            decimals_b: 6,
+            fee_rate: 0.0025,
+            // 10_000 SOL / 1_500_000 USDC
+            liquidity: PoolLiquidity::ConstantProduct {
+                reserve_a: 10_000_000_000_000,
+                reserve_b: 1_500_000_000_000,
+            },
+        }
+    }
+
+    #[test]
+    fn test_pool_kind_program_roundtrip() {
+        for kind in PoolKind::ALL {
+            assert_eq!(PoolKind::from_program_id(kind.program_id()), Some(kind));
+        }
+        assert!(PoolKind::from_program_id("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo").is_none());
+        assert!(PoolKind::OrcaWhirlpool.is_concentrated());
+        assert!(!PoolKind::RaydiumCpmm.is_concentrated());
+    }
+
+    #[test]
+    fn test_supported_programs_are_known_dex_programs() {
+        use crate::domain::known_programs::KNOWN_DEX_PROGRAMS;
+        for kind in PoolKind::ALL {
+            assert!(KNOWN_DEX_PROGRAMS.contains(&kind.program_id()));
+        }
+    }
+
+    #[test]
+    fn test_constant_product_mid_price() {
+        let pool = cp_pool();
+        assert!((pool.mid_price(SOL).unwrap() - 150.0).abs() < 1e-9);
+        assert!((pool.mid_price(USDC).unwrap() - 1.0 / 150.0).abs() < 1e-12);
+        assert!(pool.mid_price("other").is_none());
+    }
+
+    #[test]
+    fn test_effective_price_includes_fee_and_impact() {
+        let pool = cp_pool();
+
+        let small = pool.effective_price(SOL, 1_000_000_000).unwrap();
+        assert!(small < 150.0 && small > 149.5);
+
+        let large = pool.effective_price(SOL, 500_000_000_000_000 / 1_000).unwrap();
+        assert!(large < small);
+
+        assert!(pool.quote_out("other", 1).is_none());
+    }
+
+    #[test]
+    fn test_concentrated_mid_price() {
+        // raw price 0.15 USDC units per lamport => 150 USDC/SOL
+        let sqrt_price_x64 = (0.15f64.sqrt() * 18_446_744_073_709_551_616.0) as u128;
+        let pool = PoolSnapshot {
+            kind: PoolKind::OrcaWhirlpool,
+            liquidity: PoolLiquidity::Concentrated {
+                sqrt_price_x64,
+                liquidity: 50_000_000_000_000,
+                tick_current: -18_971,
+            },
+            ..cp_pool()
+        };
+
+        assert!((pool.mid_price(SOL).unwrap() - 150.0).abs() < 1e-6);
+        let price = pool.effective_price(USDC, 150_000_000).unwrap();
+        assert!((price - 1.0 / 150.0).abs() < 1e-4);
+    }
+}
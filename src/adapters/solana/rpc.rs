@@ -1,6 +1,8 @@
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
     signature::Signature,
     transaction::Transaction,
 };
@@ -23,6 +25,9 @@ pub enum SolanaClientError {
     ConfirmationTimeout,
 }
 
+/// Maximum accounts per `getMultipleAccounts` request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
 /// Wrapper around Solana RPC client with async-compatible methods
 #[derive(Clone)]
 pub struct SolanaClient {
@@ -148,6 +153,28 @@ impl SolanaClient {
         .map_err(|e| SolanaClientError::RpcError(format!("Task join error: {}", e)))?
     }
 
+    /// Get raw account data for many accounts, in request order
+    /// Chunks the request to the RPC limit of 100 accounts per call
+    pub async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>, SolanaClientError> {
+        let pubkeys = pubkeys.to_vec();
+        let client = Arc::clone(&self.client);
+        tokio::task::spawn_blocking(move || {
+            let mut accounts = Vec::with_capacity(pubkeys.len());
+            for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+                let batch = client
+                    .get_multiple_accounts(chunk)
+                    .map_err(|e| SolanaClientError::RpcError(e.to_string()))?;
+                accounts.extend(batch);
+            }
+            Ok(accounts)
+        })
+        .await
+        .map_err(|e| SolanaClientError::RpcError(format!("Task join error: {}", e)))?
+    }
+
     /// Get transaction details by signature
     pub async fn get_transaction(
         &self,
@@ -0,0 +1,701 @@
+//! Backtester
+//!
+//! Runs a `PricePort` (typically a replay of recorded ticks) through any
+//! `Strategy` with the same tick/candle logic as the live orchestrator. Time is taken from sample timestamps rather than the wall
+//! clock, so candles, cooldowns and time stops match the recorded session.
+//!
+//! `Backtester::compare` runs several strategy configs over the same samples,
+//! e.g. the z-score estimators via `compare_zscore_estimators`.
+//!
+//! Scale-in legs add to the open position at the average entry price; each
+//! partial exit is reported as its own trade against that average.
+//!
+//! Ensemble strategies also get their vote breakdown recorded on every closed
+//! candle (`BacktestReport::votes`).
+//!
+//! `with_regime` applies the live regime filter: detectors run on their own
+//! candles and their multiplier scales entries, blocking them at 0.
+//! `with_warmup` feeds samples before a cut-off to the strategy and detectors
+//! without trading or reporting them (walk-forward out-of-sample windows).
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::application::orchestrator::WARMUP_MULTIPLIER;
+use crate::domain::position::Side;
+use crate::ports::price::{PricePort, PricePortError, PriceSample};
+use crate::strategy::{
+    build_strategy, CandleBuilder, CompositeRegimeDetector, EnsembleTally, RegimeDetector, Strategy, StrategyConfig, TimeframeState, TradeAction,
+    ZScoreEstimator,
+};
+
+#[derive(Debug, Error)]
+pub enum BacktestError {
+    #[error("Price source error: {0}")]
+    PriceSourceError(#[from] PricePortError),
+    #[error("No price data to backtest")]
+    NoData,
+}
+
+/// Backtest settings
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    /// Strategy candle timeframe
+    pub timeframe: Duration,
+    /// Starting capital in quote units (USDC)
+    pub initial_capital: f64,
+    /// Fee charged on each fill in basis points
+    pub fee_bps: f64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            timeframe: Duration::from_secs(60),
+            initial_capital: 10_000.0,
+            fee_bps: 0.0,
+        }
+    }
+}
+
+/// A completed round trip, or the closed part of a position for partial exits
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestTrade {
+    pub side: Side,
+    pub entry_time: DateTime<Utc>,
+    pub entry_price: f64,
+    pub exit_time: DateTime<Utc>,
+    pub exit_price: f64,
+    /// Price move captured in percent, before fees
+    pub pnl_pct: f64,
+    /// Equity change in quote units, after fees
+    pub pnl: f64,
+}
+
+/// Backtest results
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestReport {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    /// Price samples processed
+    pub samples: usize,
+    /// Strategy candles closed
+    pub candles: usize,
+    pub trades: Vec<BacktestTrade>,
+    pub initial_capital: f64,
+    pub final_equity: f64,
+    pub total_return_pct: f64,
+    /// Fraction of winning trades (0.0-1.0)
+    pub win_rate: f64,
+    pub max_drawdown_pct: f64,
+    /// Multi-timeframe confirmation state at the end of the run
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub timeframes: Vec<TimeframeState>,
+    /// Entries and scale-ins blocked by a regime multiplier of 0
+    pub regime_blocked: usize,
+    /// Ensemble vote breakdown per closed candle, empty for other strategies
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub votes: Vec<BacktestVote>,
+}
+
+/// Ensemble votes on one closed candle and the signal they produced
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestVote {
+    pub time: DateTime<Utc>,
+    pub action: TradeAction,
+    pub tally: EnsembleTally,
+}
+
+/// One variant of a side-by-side comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestComparison {
+    pub label: String,
+    pub report: BacktestReport,
+}
+
+/// Position opened by the backtester
+#[derive(Debug, Clone)]
+struct OpenTrade {
+    side: Side,
+    entry_time: DateTime<Utc>,
+    /// Average entry price over all legs
+    entry_price: f64,
+    /// Base units still open
+    quantity: f64,
+    /// Base units of the initial entry, the unit scale-in legs are sized in
+    unit_quantity: f64,
+}
+
+/// Drives a strategy over timestamped price samples
+#[derive(Debug)]
+pub struct Backtester {
+    config: BacktestConfig,
+    strategy: Box<dyn Strategy>,
+    candle_builder: CandleBuilder,
+    /// Maps sample timestamps onto the monotonic clock used by strategy and candles
+    clock_origin: Option<(DateTime<Utc>, Instant)>,
+    /// Regime detectors and the candles they run on (None = no regime filter)
+    regime: Option<(CandleBuilder, CompositeRegimeDetector)>,
+    regime_blocked: usize,
+    /// Samples before this are warm-up only
+    warmup_until: Option<DateTime<Utc>>,
+    /// Fraction of equity committed per trade
+    position_fraction: f64,
+    /// Scale-in leg size relative to the initial entry
+    scale_in_size: f64,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    samples: usize,
+    candles: usize,
+    open_trade: Option<OpenTrade>,
+    trades: Vec<BacktestTrade>,
+    votes: Vec<BacktestVote>,
+    equity: f64,
+    peak_equity: f64,
+    max_drawdown_pct: f64,
+}
+
+impl Backtester {
+    /// Create a backtester for the strategy selected by `strategy_config.kind`
+    pub fn new(strategy_config: StrategyConfig, config: BacktestConfig) -> Self {
+        let position_fraction = strategy_config.risk.max_position_pct / 100.0;
+        let scale_in_size = strategy_config.scaling.scale_in_size;
+        Self {
+            candle_builder: CandleBuilder::new(config.timeframe),
+            strategy: build_strategy(&strategy_config),
+            clock_origin: None,
+            regime: None,
+            regime_blocked: 0,
+            warmup_until: None,
+            position_fraction,
+            scale_in_size,
+            start: None,
+            end: None,
+            samples: 0,
+            candles: 0,
+            open_trade: None,
+            trades: Vec::new(),
+            votes: Vec::new(),
+            equity: config.initial_capital,
+            peak_equity: config.initial_capital,
+            max_drawdown_pct: 0.0,
+            config,
+        }
+    }
+
+    /// Backtest a custom strategy, keeping the position sizing from `new`
+    pub fn with_strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Gate and scale entries by regime detectors running on `candle_period` candles
+    pub fn with_regime(mut self, detector: CompositeRegimeDetector, candle_period: Duration) -> Self {
+        self.regime = Some((CandleBuilder::new(candle_period), detector));
+        self
+    }
+
+    /// Only warm up strategy, candles and detectors on samples before `until`
+    pub fn with_warmup(mut self, until: DateTime<Utc>) -> Self {
+        self.warmup_until = Some(until);
+        self
+    }
+
+    /// Run every sample from `source` until it is exhausted
+    pub async fn run(&mut self, source: &dyn PricePort) -> Result<BacktestReport, BacktestError> {
+        loop {
+            match source.next_price().await {
+                Ok(sample) => {
+                    self.on_sample(&sample);
+                }
+                Err(PricePortError::Exhausted) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if self.samples == 0 {
+            return Err(BacktestError::NoData);
+        }
+
+        Ok(self.report())
+    }
+
+    /// Run each labelled strategy config over the same samples
+    pub fn compare(
+        variants: impl IntoIterator<Item = (String, StrategyConfig)>,
+        config: &BacktestConfig,
+        samples: &[PriceSample],
+    ) -> Vec<BacktestComparison> {
+        variants
+            .into_iter()
+            .map(|(label, strategy_config)| {
+                let mut backtester = Backtester::new(strategy_config, config.clone());
+                for sample in samples {
+                    backtester.on_sample(sample);
+                }
+                BacktestComparison {
+                    label,
+                    report: backtester.report(),
+                }
+            })
+            .collect()
+    }
+
+    /// Compare every z-score estimator, all other settings from `strategy_config`
+    pub fn compare_zscore_estimators(
+        strategy_config: &StrategyConfig,
+        config: &BacktestConfig,
+        samples: &[PriceSample],
+    ) -> Vec<BacktestComparison> {
+        let variants = ZScoreEstimator::ALL.iter().map(|&estimator| {
+            (
+                estimator.name().to_string(),
+                strategy_config.clone().with_zscore_estimator(estimator),
+            )
+        });
+        Self::compare(variants, config, samples)
+    }
+
+    /// Process one price sample, filling any signal immediately at the sample price
+    pub fn on_sample(&mut self, sample: &PriceSample) -> Option<TradeAction> {
+        let now = self.instant_for(sample.timestamp);
+        self.strategy.set_clock(now);
+
+        if let Some((ref mut builder, ref mut detector)) = self.regime {
+            if let Some(candle) = builder.update_at(sample.price, now) {
+                detector.update(&candle);
+            }
+        }
+
+        let closed_candle = self.candle_builder.update_at(sample.price, now);
+        let action = self.strategy.update_tick(sample.price, closed_candle.as_ref());
+
+        if self.warmup_until.is_some_and(|until| sample.timestamp < until) {
+            return None;
+        }
+
+        self.samples += 1;
+        self.start.get_or_insert(sample.timestamp);
+        self.end = Some(sample.timestamp);
+        if closed_candle.is_some() {
+            self.candles += 1;
+            if let Some(tally) = self.strategy.snapshot().ensemble {
+                self.votes.push(BacktestVote {
+                    time: sample.timestamp,
+                    action: action.unwrap_or(TradeAction::Hold),
+                    tally,
+                });
+            }
+        }
+
+        if let Some(action) = action {
+            match action {
+                TradeAction::EnterLong | TradeAction::EnterShort | TradeAction::ScaleIn
+                    if self.regime_multiplier() <= 0.0 =>
+                {
+                    self.regime_blocked += 1;
+                    return Some(TradeAction::Hold);
+                }
+                TradeAction::EnterLong | TradeAction::EnterShort => {
+                    self.open(action, sample);
+                    self.strategy.confirm_trade(action, sample.price);
+                }
+                TradeAction::ScaleIn => {
+                    self.scale_in(sample);
+                    self.strategy.confirm_trade(action, sample.price);
+                }
+                TradeAction::PartialExit { fraction } => {
+                    self.realize(sample, fraction);
+                    self.strategy.confirm_trade(action, sample.price);
+                }
+                TradeAction::Exit => {
+                    self.realize(sample, 1.0);
+                    self.open_trade = None;
+                    self.strategy.confirm_trade(action, sample.price);
+                }
+                TradeAction::Hold => {}
+            }
+        }
+
+        action
+    }
+
+    /// Snapshot of the results so far
+    pub fn report(&self) -> BacktestReport {
+        let wins = self.trades.iter().filter(|t| t.pnl > 0.0).count();
+        let win_rate = if self.trades.is_empty() {
+            0.0
+        } else {
+            wins as f64 / self.trades.len() as f64
+        };
+
+        BacktestReport {
+            start: self.start,
+            end: self.end,
+            samples: self.samples,
+            candles: self.candles,
+            trades: self.trades.clone(),
+            initial_capital: self.config.initial_capital,
+            final_equity: self.equity,
+            total_return_pct: (self.equity - self.config.initial_capital) / self.config.initial_capital * 100.0,
+            win_rate,
+            max_drawdown_pct: self.max_drawdown_pct,
+            timeframes: self.strategy.snapshot().timeframes,
+            regime_blocked: self.regime_blocked,
+            votes: self.votes.clone(),
+        }
+    }
+
+    /// Strategy being backtested
+    pub fn strategy(&self) -> &dyn Strategy {
+        self.strategy.as_ref()
+    }
+
+    /// Entry size multiplier from the regime detectors (1.0 without a regime filter)
+    fn regime_multiplier(&self) -> f64 {
+        match self.regime {
+            Some((_, ref detector)) if detector.is_ready() => detector.get_position_multiplier(),
+            Some(_) => WARMUP_MULTIPLIER,
+            None => 1.0,
+        }
+    }
+
+    fn instant_for(&mut self, timestamp: DateTime<Utc>) -> Instant {
+        let (origin_ts, origin) = *self.clock_origin.get_or_insert((timestamp, Instant::now()));
+        // Out-of-order samples collapse onto the origin rather than going back in time
+        let offset = (timestamp - origin_ts).to_std().unwrap_or(Duration::ZERO);
+        origin + offset
+    }
+
+    fn open(&mut self, action: TradeAction, sample: &PriceSample) {
+        let side = if action == TradeAction::EnterLong { Side::Long } else { Side::Short };
+        let notional = self.equity * self.position_fraction * self.regime_multiplier() * self.strategy.size_multiplier();
+        self.equity -= notional * self.config.fee_bps / 10_000.0;
+
+        let quantity = notional / sample.price;
+        self.open_trade = Some(OpenTrade {
+            side,
+            entry_time: sample.timestamp,
+            entry_price: sample.price,
+            quantity,
+            unit_quantity: quantity,
+        });
+    }
+
+    fn scale_in(&mut self, sample: &PriceSample) {
+        let Some(open) = self.open_trade.as_mut() else {
+            return;
+        };
+
+        let leg = open.unit_quantity * self.scale_in_size;
+        self.equity -= leg * sample.price * self.config.fee_bps / 10_000.0;
+        open.entry_price = (open.entry_price * open.quantity + sample.price * leg) / (open.quantity + leg);
+        open.quantity += leg;
+    }
+
+    /// Close `fraction` of the open position and record it as a trade
+    fn realize(&mut self, sample: &PriceSample, fraction: f64) {
+        let Some(open) = self.open_trade.as_mut() else {
+            return;
+        };
+
+        let quantity = open.quantity * fraction.clamp(0.0, 1.0);
+        open.quantity -= quantity;
+        let open = open.clone();
+
+        let pnl_pct = match open.side {
+            Side::Long => (sample.price - open.entry_price) / open.entry_price * 100.0,
+            Side::Short => (open.entry_price - sample.price) / open.entry_price * 100.0,
+        };
+        let notional = quantity * open.entry_price;
+        let exit_notional = notional * (1.0 + pnl_pct / 100.0);
+        let entry_fee = notional * self.config.fee_bps / 10_000.0;
+        let exit_fee = exit_notional * self.config.fee_bps / 10_000.0;
+        let gross = notional * pnl_pct / 100.0;
+
+        self.equity += gross - exit_fee;
+        self.peak_equity = self.peak_equity.max(self.equity);
+        if self.peak_equity > 0.0 {
+            let drawdown = (self.peak_equity - self.equity) / self.peak_equity * 100.0;
+            self.max_drawdown_pct = self.max_drawdown_pct.max(drawdown);
+        }
+
+        self.trades.push(BacktestTrade {
+            side: open.side,
+            entry_time: open.entry_time,
+            entry_price: open.entry_price,
+            exit_time: sample.timestamp,
+            exit_price: sample.price,
+            pnl_pct,
+            pnl: gross - entry_fee - exit_fee,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::market_data::ReplayPriceSource;
+    use chrono::TimeZone;
+
+    fn strategy_config() -> StrategyConfig {
+        StrategyConfig {
+            lookback_period: 10,
+            z_threshold: 1.5,
+            z_exit_threshold: 0.0,
+            cooldown_seconds: 0,
+            ..Default::default()
+        }
+    }
+
+    /// 15-second ticks of an oscillating price with occasional shocks
+    fn samples(count: usize) -> Vec<PriceSample> {
+        let start = Utc.timestamp_opt(1_768_694_400, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                let wave = (i as f64 / 12.0).sin() * 0.4;
+                let shock = if i % 97 == 50 { -1.2 } else if i % 89 == 40 { 1.1 } else { 0.0 };
+                PriceSample {
+                    timestamp: start + chrono::Duration::seconds(15 * i as i64),
+                    mint: "SOL".to_string(),
+                    price: 100.0 + wave + shock,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_candles_follow_sample_timestamps() {
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+
+        // 41 ticks at 15s = 10 minutes = 10 closed 1m candles
+        for sample in samples(41) {
+            backtester.on_sample(&sample);
+        }
+
+        let report = backtester.report();
+        assert_eq!(report.samples, 41);
+        assert_eq!(report.candles, 10);
+        assert!(backtester.strategy().is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_replay_is_deterministic() {
+        let source = ReplayPriceSource::new("SOL", samples(2_000));
+
+        let mut first = Backtester::new(strategy_config(), BacktestConfig::default());
+        let report_a = first.run(&source).await.unwrap();
+
+        source.rewind();
+        let mut second = Backtester::new(strategy_config(), BacktestConfig::default());
+        let report_b = second.run(&source).await.unwrap();
+
+        assert!(!report_a.trades.is_empty());
+        assert_eq!(report_a.trades.len(), report_b.trades.len());
+        assert_eq!(report_a.final_equity, report_b.final_equity);
+        for (a, b) in report_a.trades.iter().zip(&report_b.trades) {
+            assert_eq!(a.entry_time, b.entry_time);
+            assert_eq!(a.exit_price, b.exit_price);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_source() {
+        let source = ReplayPriceSource::new("SOL", Vec::new());
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+
+        assert!(matches!(backtester.run(&source).await, Err(BacktestError::NoData)));
+    }
+
+    #[test]
+    fn test_runs_selected_strategy() {
+        use crate::strategy::StrategyKind;
+
+        let config = strategy_config().with_kind(StrategyKind::OuReversion);
+        let mut backtester = Backtester::new(config, BacktestConfig::default());
+        for sample in samples(500) {
+            backtester.on_sample(&sample);
+        }
+
+        assert_eq!(backtester.strategy().name(), "ou_reversion");
+        assert_eq!(backtester.report().samples, 500);
+    }
+
+    #[test]
+    fn test_scale_in_and_partial_exits() {
+        use crate::strategy::ScalingConfig;
+
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+        for sample in samples(2_000) {
+            backtester.on_sample(&sample);
+        }
+        let plain = backtester.report();
+
+        let config = StrategyConfig {
+            scaling: ScalingConfig {
+                scale_in_levels: vec![2.0],
+                partial_exit_levels: vec![0.75],
+                ..Default::default()
+            },
+            ..strategy_config()
+        };
+        let mut backtester = Backtester::new(config, BacktestConfig::default());
+        for sample in samples(2_000) {
+            backtester.on_sample(&sample);
+        }
+        let layered = backtester.report();
+
+        // Partial exits show up as extra trades sharing the position's entry time
+        assert!(layered.trades.len() > plain.trades.len());
+        assert!(layered.trades.windows(2).any(|w| w[0].entry_time == w[1].entry_time));
+        assert_ne!(layered.final_equity, plain.final_equity);
+    }
+
+    #[test]
+    fn test_fees_reduce_equity() {
+        let config = BacktestConfig { fee_bps: 30.0, ..Default::default() };
+        let mut backtester = Backtester::new(strategy_config(), config);
+
+        for sample in samples(2_000) {
+            backtester.on_sample(&sample);
+        }
+        let with_fees = backtester.report();
+
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+        for sample in samples(2_000) {
+            backtester.on_sample(&sample);
+        }
+        let without_fees = backtester.report();
+
+        assert_eq!(with_fees.trades.len(), without_fees.trades.len());
+        assert!(with_fees.final_equity < without_fees.final_equity);
+    }
+
+    #[test]
+    fn test_report_includes_timeframe_state() {
+        use crate::strategy::TimeframeRule;
+
+        let run = |config: StrategyConfig| {
+            let mut backtester = Backtester::new(config, BacktestConfig::default());
+            for sample in samples(2_000) {
+                backtester.on_sample(&sample);
+            }
+            backtester.report()
+        };
+
+        let unfiltered = run(strategy_config());
+        assert!(unfiltered.timeframes.is_empty());
+
+        let filtered = run(StrategyConfig {
+            timeframes: vec![TimeframeRule { lookback: 5, ..TimeframeRule::new(300).with_max_abs_zscore(0.5) }],
+            ..strategy_config()
+        });
+        assert_eq!(filtered.timeframes.len(), 1);
+        let state = &filtered.timeframes[0];
+        assert_eq!(state.timeframe, "5m");
+        assert_eq!(state.candles, 99);
+        assert!(state.blocked_entries > 0);
+        // Vetoed signals enter later, once the 5m timeframe agrees
+        let entries = |report: &BacktestReport| report.trades.iter().map(|t| t.entry_time).collect::<Vec<_>>();
+        assert_ne!(entries(&filtered), entries(&unfiltered));
+        assert!(filtered.trades[0].entry_time >= unfiltered.trades[0].entry_time);
+
+        let json = serde_json::to_value(&filtered).unwrap();
+        assert_eq!(json["timeframes"][0]["timeframe"], "5m");
+    }
+
+    #[test]
+    fn test_regime_filter_blocks_entries() {
+        use crate::strategy::RegimeConfig;
+
+        // Ranging ticks, then a steady climb with pullbacks that ADX reads as a strong trend
+        let mut data = samples(2_000);
+        let start = data[data.len() - 1].timestamp;
+        data.extend((1..2_000).map(|i| PriceSample {
+            timestamp: start + chrono::Duration::seconds(15 * i as i64),
+            mint: "SOL".to_string(),
+            price: 100.0 + i as f64 * 0.02 + if i % 40 < 4 { -0.6 } else { 0.0 },
+        }));
+        let run = |regime: Option<RegimeConfig>| {
+            let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+            if let Some(regime) = regime {
+                backtester = backtester.with_regime(regime.build(), Duration::from_secs(60));
+            }
+            for sample in &data {
+                backtester.on_sample(sample);
+            }
+            backtester.report()
+        };
+
+        let unfiltered = run(None);
+        assert_eq!(unfiltered.regime_blocked, 0);
+
+        let filtered = run(Some(RegimeConfig::default()));
+        assert!(filtered.regime_blocked > 0);
+        let entries = |report: &BacktestReport| report.trades.iter().map(|t| t.entry_time).collect::<Vec<_>>();
+        assert_ne!(entries(&filtered), entries(&unfiltered));
+    }
+
+    #[test]
+    fn test_warmup_samples_not_traded() {
+        let data = samples(2_000);
+        let cutoff = data[1_000].timestamp;
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default()).with_warmup(cutoff);
+        for sample in &data {
+            backtester.on_sample(sample);
+        }
+        let report = backtester.report();
+
+        assert_eq!(report.samples, 1_000);
+        assert_eq!(report.start, Some(cutoff));
+        assert!(!report.trades.is_empty());
+        assert!(report.trades.iter().all(|t| t.entry_time >= cutoff));
+    }
+
+    #[test]
+    fn test_ensemble_votes_exported() {
+        use crate::strategy::StrategyKind;
+
+        let run = |config: StrategyConfig| {
+            let mut backtester = Backtester::new(config, BacktestConfig::default());
+            for sample in samples(2_000) {
+                backtester.on_sample(&sample);
+            }
+            backtester.report()
+        };
+
+        assert!(run(strategy_config()).votes.is_empty());
+
+        let mut config = strategy_config().with_kind(StrategyKind::Ensemble);
+        config.ensemble.entry_score = 0.3;
+        let report = run(config);
+        // One record per closed candle once every member is warm
+        assert!(!report.votes.is_empty() && report.votes.len() < report.candles);
+        assert!(report.votes.iter().all(|v| v.tally.votes.len() == 4));
+        assert!(report.votes.iter().any(|v| v.action == TradeAction::EnterLong));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["votes"][0]["tally"]["votes"][0]["signal"], "zscore");
+    }
+
+    #[test]
+    fn test_compare_zscore_estimators() {
+        let data = samples(2_000);
+        let results = Backtester::compare_zscore_estimators(&strategy_config(), &BacktestConfig::default(), &data);
+
+        let labels: Vec<&str> = results.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["simple", "ewma", "robust", "kalman", "ou"]);
+        assert!(results.iter().all(|r| r.report.samples == 2_000));
+
+        // The simple variant is the plain backtest
+        let mut backtester = Backtester::new(strategy_config(), BacktestConfig::default());
+        for sample in &data {
+            backtester.on_sample(sample);
+        }
+        assert_eq!(results[0].report.trades.len(), backtester.report().trades.len());
+        assert_eq!(results[0].report.final_equity, backtester.report().final_equity);
+    }
+}
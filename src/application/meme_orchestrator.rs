@@ -2,7 +2,8 @@
 //!
 //! Multi-token trading loop for meme coins using the OU-GBM strategy.
 //! Key features:
-//! - HashMap of OUProcess per token for signal generation
+//! - One `OuReversionStrategy` per token deciding entries and exits,
+//!   including stop loss, take profit and time stop
 //! - Token discovery via Jupiter API
 //! - Single active position at a time
 //! - Always settles to USDC
@@ -19,12 +20,20 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio::task::JoinSet;
 
-use crate::adapters::jupiter::{JupiterClient, QuoteRequest, SwapRequest};
+use crate::adapters::jupiter::{
+    JupiterClient, JupiterTokenFetcher, QuoteRequest, RequestPriority, SwapRequest,
+};
 use crate::adapters::solana::{SolanaClient, WalletManager};
 use crate::domain::{BalanceGuard, ExpectedDelta};
-use crate::strategy::ou_process::{OUProcess, OUSignal, OUParams};
+use crate::strategy::mean_reversion::{PositionState, TradeAction};
+use crate::strategy::ou_process::OUParams;
+use crate::strategy::params::{OuStrategyConfig, RiskConfig, StrategyConfig, StrategyKind};
+use crate::strategy::regime::Candle;
+use crate::strategy::ou_reversion::OuReversionStrategy;
+use crate::strategy::traits::{Strategy, StrategySnapshot};
 
 /// USDC mint address on Solana
 pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
@@ -295,12 +304,40 @@ pub struct MemeOrchestratorConfig {
     pub min_half_life_minutes: f64,
     /// Maximum half-life in minutes
     pub max_half_life_minutes: f64,
+    /// Maximum mints per Price API request
+    #[serde(default = "default_price_batch_size")]
+    pub price_batch_size: usize,
+    /// Maximum Jupiter quotes in flight during a tick
+    #[serde(default = "default_quote_concurrency")]
+    pub quote_concurrency: usize,
+    /// Tokens within this z-score distance of the entry threshold get a quote price
+    #[serde(default = "default_quote_z_margin")]
+    pub quote_z_margin: f64,
+    /// Seconds without a fresh price before a token is considered stale
+    #[serde(default = "default_max_price_age_secs")]
+    pub max_price_age_secs: u64,
 }
 
 fn default_enabled() -> bool {
     false
 }
 
+fn default_price_batch_size() -> usize {
+    50
+}
+
+fn default_quote_concurrency() -> usize {
+    4
+}
+
+fn default_quote_z_margin() -> f64 {
+    0.5
+}
+
+fn default_max_price_age_secs() -> u64 {
+    180
+}
+
 impl Default for MemeOrchestratorConfig {
     fn default() -> Self {
         Self {
@@ -321,6 +358,10 @@ impl Default for MemeOrchestratorConfig {
             min_ou_confidence: 0.3,
             min_half_life_minutes: 5.0,
             max_half_life_minutes: 120.0,
+            price_batch_size: default_price_batch_size(),
+            quote_concurrency: default_quote_concurrency(),
+            quote_z_margin: default_quote_z_margin(),
+            max_price_age_secs: default_max_price_age_secs(),
         }
     }
 }
@@ -370,8 +411,69 @@ impl MemeOrchestratorConfig {
             ));
         }
 
+        if self.price_batch_size == 0 {
+            return Err(MemeOrchestratorError::ConfigError(
+                "price_batch_size must be > 0".to_string(),
+            ));
+        }
+
+        if self.quote_concurrency == 0 {
+            return Err(MemeOrchestratorError::ConfigError(
+                "quote_concurrency must be > 0".to_string(),
+            ));
+        }
+
+        if self.quote_z_margin < 0.0 {
+            return Err(MemeOrchestratorError::ConfigError(
+                "quote_z_margin must be >= 0".to_string(),
+            ));
+        }
+
+        if self.max_price_age_secs == 0 {
+            return Err(MemeOrchestratorError::ConfigError(
+                "max_price_age_secs must be > 0".to_string(),
+            ));
+        }
+
         Ok(())
     }
+
+    /// Price age after which a token is considered stale
+    pub fn max_price_age(&self) -> Duration {
+        Duration::from_secs(self.max_price_age_secs)
+    }
+
+    /// `OuReversionStrategy` settings each tracked token trades with
+    ///
+    /// Every polled price is one OU sample. The meme loop has no cooldown or
+    /// daily limits, so those are left open.
+    pub fn strategy_config(&self) -> StrategyConfig {
+        let defaults = StrategyConfig::default();
+        StrategyConfig {
+            kind: StrategyKind::OuReversion,
+            lookback_period: self.ou_lookback,
+            cooldown_seconds: 0,
+            risk: RiskConfig {
+                stop_loss_pct: self.stop_loss_pct,
+                take_profit_pct: self.take_profit_pct,
+                time_stop_hours: self.max_position_hours,
+                max_daily_trades: u32::MAX,
+                max_daily_loss_pct: f64::INFINITY,
+                ..defaults.risk
+            },
+            ou: OuStrategyConfig {
+                dt_minutes: self.ou_dt_minutes,
+                z_entry_threshold: self.z_entry_threshold,
+                z_exit_threshold: self.z_exit_threshold,
+                min_confidence: self.min_ou_confidence,
+                min_half_life_minutes: self.min_half_life_minutes,
+                max_half_life_minutes: self.max_half_life_minutes,
+                allow_short: false,
+                ..defaults.ou
+            },
+            ..defaults
+        }
+    }
 }
 
 /// Per-token tracking state
@@ -379,10 +481,14 @@ impl MemeOrchestratorConfig {
 pub struct TokenTracker {
     /// Token info
     pub info: TokenInfo,
-    /// OU process estimator for this token
-    pub ou_process: OUProcess,
-    /// Last price update time
+    /// Strategy deciding entries and exits for this token
+    pub strategy: OuReversionStrategy,
+    /// Action the strategy returned for the latest price (None while warming up)
+    pub action: Option<TradeAction>,
+    /// Last time a price was fed to the strategy
     pub last_price_time: Option<Instant>,
+    /// Last time the observed price changed
+    pub price_changed_time: Option<Instant>,
     /// Price history for additional analysis
     pub price_history: Vec<f64>,
     /// Maximum history to keep
@@ -390,18 +496,21 @@ pub struct TokenTracker {
 }
 
 impl TokenTracker {
-    pub fn new(info: TokenInfo, ou_lookback: usize, ou_dt_minutes: f64) -> Self {
+    pub fn new(info: TokenInfo, strategy_config: &StrategyConfig) -> Self {
+        let lookback = strategy_config.lookback_period;
         Self {
             info,
-            ou_process: OUProcess::new(ou_lookback, ou_dt_minutes),
+            strategy: OuReversionStrategy::new(strategy_config.clone()),
+            action: None,
             last_price_time: None,
-            price_history: Vec::with_capacity(ou_lookback),
-            max_history: ou_lookback * 2,
+            price_changed_time: None,
+            price_history: Vec::with_capacity(lookback),
+            max_history: lookback * 2,
         }
     }
 
-    /// Update with new price, returns OU signal
-    pub fn update_price(&mut self, price: f64) -> OUSignal {
+    /// Feed a new price to the strategy as one closed sample
+    pub fn update_price(&mut self, price: f64) -> Option<TradeAction> {
         self.info.price_usdc = Some(price);
         self.info.last_updated = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -415,30 +524,76 @@ impl TokenTracker {
             self.price_history.remove(0);
         }
 
-        // Update OU process
-        self.ou_process.update(price)
+        let candle = Candle::new(price, price, price, price, 0.0);
+        self.action = self.strategy.update_tick(price, Some(&candle));
+        self.action
     }
 
-    /// Check if this token has valid OU parameters for trading
-    pub fn is_tradeable(&self, config: &MemeOrchestratorConfig) -> bool {
-        if let Some(params) = self.ou_process.params() {
-            if !params.is_valid() {
-                return false;
-            }
-            if params.confidence < config.min_ou_confidence {
-                return false;
-            }
-            if let Some(half_life_min) = self.ou_process.half_life_minutes() {
-                if half_life_min < config.min_half_life_minutes
-                    || half_life_min > config.max_half_life_minutes
-                {
-                    return false;
-                }
-            }
-            true
-        } else {
-            false
+    /// Run only the strategy's stops on the last known price
+    ///
+    /// Used when no fresh price arrived, so an open position still hits its
+    /// time stop without feeding the OU fit a repeated sample.
+    pub fn check_stops(&mut self) -> Option<TradeAction> {
+        self.action = self
+            .info
+            .price_usdc
+            .and_then(|price| self.strategy.update_tick(price, None));
+        self.action
+    }
+
+    /// Observe a polled price, feeding the strategy only while the feed is live
+    ///
+    /// A price that has not moved for longer than `max_age` is treated as a
+    /// frozen feed: it stays visible in `info` and stops are still checked,
+    /// but it is not fed to the OU fit, so repeated cached values do not
+    /// collapse the variance. Returns whether the price was fed.
+    pub fn observe_price(&mut self, price: f64, max_age: Duration) -> bool {
+        let now = Instant::now();
+        let unchanged = self.info.price_usdc == Some(price);
+
+        if !unchanged || self.price_changed_time.is_none() {
+            self.price_changed_time = Some(now);
+        }
+
+        let frozen = unchanged
+            && self
+                .price_changed_time
+                .is_some_and(|changed| now.saturating_duration_since(changed) > max_age);
+        if frozen {
+            self.check_stops();
+            return false;
         }
+
+        self.update_price(price);
+        true
+    }
+
+    /// Whether the strategy has not been fed a price within `max_age`
+    pub fn is_price_stale(&self, max_age: Duration) -> bool {
+        match self.last_price_time {
+            Some(t) => t.elapsed() > max_age,
+            None => true,
+        }
+    }
+
+    /// Resume `position` in the strategy as if it had been open since its entry
+    pub fn adopt(&mut self, position: &ActivePosition) {
+        let snapshot = StrategySnapshot {
+            position: PositionState::long(position.entry_price),
+            position_age_secs: Some(position.age_seconds() as f64),
+            ..self.strategy.snapshot()
+        };
+        self.strategy.restore(&snapshot);
+    }
+
+    /// Current OU z-score of the strategy
+    pub fn z_score(&self) -> Option<f64> {
+        self.strategy.current_z_score()
+    }
+
+    /// OU parameters the strategy currently trades on
+    pub fn ou_params(&self) -> Option<OUParams> {
+        self.strategy.ou_params().cloned()
     }
 }
 
@@ -448,6 +603,8 @@ pub struct MemeOrchestrator {
     config: MemeOrchestratorConfig,
     /// Jupiter client for swaps
     jupiter: JupiterClient,
+    /// Jupiter Price API client for batched prices
+    token_fetcher: JupiterTokenFetcher,
     /// Solana RPC client
     solana: SolanaClient,
     /// Wallet manager
@@ -475,10 +632,13 @@ impl MemeOrchestrator {
         wallet: WalletManager,
     ) -> Result<Self, MemeOrchestratorError> {
         let balance_guard = BalanceGuard::new(wallet.pubkey());
+        // Price polling shares the swap client's rate limiter
+        let token_fetcher = JupiterTokenFetcher::with_gateway(jupiter.gateway().clone());
 
         Ok(Self {
             config,
             jupiter,
+            token_fetcher,
             solana,
             wallet: wallet.clone(),
             tokens: Arc::new(RwLock::new(HashMap::new())),
@@ -490,6 +650,12 @@ impl MemeOrchestrator {
         })
     }
 
+    /// Use a custom Price API client (e.g. one with an API key)
+    pub fn with_token_fetcher(mut self, token_fetcher: JupiterTokenFetcher) -> Self {
+        self.token_fetcher = token_fetcher;
+        self
+    }
+
     /// Load persisted state on startup
     pub async fn load_persisted_state(&self) -> Result<(), MemeOrchestratorError> {
         let path = self.config.data_dir.join(POSITION_FILE);
@@ -536,11 +702,7 @@ impl MemeOrchestrator {
 
     /// Add a token to track
     pub async fn add_token(&self, info: TokenInfo) {
-        let tracker = TokenTracker::new(
-            info.clone(),
-            self.config.ou_lookback,
-            self.config.ou_dt_minutes,
-        );
+        let tracker = TokenTracker::new(info.clone(), &self.config.strategy_config());
         self.tokens.write().await.insert(info.mint.clone(), tracker);
         tracing::info!("Added token: {} ({})", info.symbol, info.mint);
     }
@@ -556,65 +718,155 @@ impl MemeOrchestrator {
         &self,
         mint: &str,
         price: f64,
-    ) -> Result<OUSignal, MemeOrchestratorError> {
+    ) -> Result<Option<TradeAction>, MemeOrchestratorError> {
         let mut tokens = self.tokens.write().await;
         let tracker = tokens
             .get_mut(mint)
             .ok_or_else(|| MemeOrchestratorError::TokenNotFound(mint.to_string()))?;
 
-        let signal = tracker.update_price(price);
-        Ok(signal)
+        Ok(tracker.update_price(price))
     }
 
     /// Get current OU parameters for a token
     pub async fn get_token_ou_params(&self, mint: &str) -> Option<OUParams> {
         let tokens = self.tokens.read().await;
-        tokens
-            .get(mint)
-            .and_then(|t| t.ou_process.params().cloned())
+        tokens.get(mint).and_then(TokenTracker::ou_params)
     }
 
-    /// Check if a token is ready for trading
-    pub async fn is_token_tradeable(&self, mint: &str) -> bool {
+    /// Check if a token's strategy has enough data to signal
+    pub async fn is_token_ready(&self, mint: &str) -> bool {
         let tokens = self.tokens.read().await;
-        if let Some(tracker) = tokens.get(mint) {
-            tracker.is_tradeable(&self.config)
-        } else {
-            false
-        }
+        tokens.get(mint).is_some_and(|t| t.strategy.is_ready())
     }
 
     /// Fetch price for a token using Jupiter quote
     pub async fn fetch_token_price(&self, mint: &str) -> Result<f64, MemeOrchestratorError> {
-        // Quote 1 unit of token to USDC
-        let tracker = {
+        let decimals = {
             let tokens = self.tokens.read().await;
             tokens
                 .get(mint)
                 .ok_or_else(|| MemeOrchestratorError::TokenNotFound(mint.to_string()))?
                 .info
-                .clone()
+                .decimals
         };
 
-        let amount = 10u64.pow(tracker.decimals as u32); // 1 token
+        quote_token_price(&self.jupiter, mint, decimals, self.config.slippage_bps).await
+    }
 
-        let quote_request = QuoteRequest::new(
-            mint.to_string(),
-            USDC_MINT.to_string(),
-            amount,
-            self.config.slippage_bps,
-        );
+    /// Fetch Price API prices for many tokens, `price_batch_size` mints per request
+    ///
+    /// Chunks that fail are logged and left out; callers fall back to quotes.
+    pub async fn fetch_batch_prices(&self, mints: &[String]) -> HashMap<String, f64> {
+        let mut prices = HashMap::new();
+
+        for chunk in mints.chunks(self.config.price_batch_size) {
+            let ids: Vec<&str> = chunk.iter().map(String::as_str).collect();
+            match self.token_fetcher.get_prices(&ids).await {
+                Ok(batch) => {
+                    prices.extend(
+                        batch
+                            .into_iter()
+                            .filter(|(_, p)| p.price > 0.0)
+                            .map(|(mint, p)| (mint, p.price)),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Batch price fetch failed for {} tokens: {}", chunk.len(), e);
+                }
+            }
+        }
 
-        let quote = self
-            .jupiter
-            .get_quote(&quote_request)
+        prices
+    }
+
+    /// Tokens that need an executable quote price this tick
+    ///
+    /// The open position is always quoted for exits. Tokens whose strategy is
+    /// ready are quoted when their batch price puts them within
+    /// `quote_z_margin` of the entry threshold, or when the batch had no price
+    /// for them.
+    async fn quote_candidates(
+        &self,
+        mints: &[String],
+        batch_prices: &HashMap<String, f64>,
+    ) -> Vec<(String, u8)> {
+        let position_mint = self
+            .active_position
+            .read()
             .await
-            .map_err(|e| MemeOrchestratorError::MarketDataError(e.to_string()))?;
+            .as_ref()
+            .map(|p| p.token_mint.clone());
+        let entry_cutoff = self.config.z_entry_threshold + self.config.quote_z_margin;
 
-        // USDC has 6 decimals
-        let price = quote.output_amount() as f64 / 1_000_000.0;
+        let tokens = self.tokens.read().await;
+        mints
+            .iter()
+            .filter_map(|mint| {
+                let tracker = tokens.get(mint)?;
+
+                let needs_quote = if position_mint.as_deref() == Some(mint.as_str()) {
+                    true
+                } else if position_mint.is_some() || !tracker.strategy.is_ready() {
+                    false
+                } else {
+                    match (batch_prices.get(mint), tracker.ou_params()) {
+                        (Some(&price), Some(params)) => params.z_score(price.ln()) < entry_cutoff,
+                        _ => true,
+                    }
+                };
 
-        Ok(price)
+                needs_quote.then(|| (mint.clone(), tracker.info.decimals))
+            })
+            .collect()
+    }
+
+    /// Quote several tokens concurrently, at most `quote_concurrency` at a time
+    pub async fn fetch_quote_prices(&self, tokens: Vec<(String, u8)>) -> HashMap<String, f64> {
+        let semaphore = Arc::new(Semaphore::new(self.config.quote_concurrency));
+        let mut tasks = JoinSet::new();
+
+        for (mint, decimals) in tokens {
+            let jupiter = self.jupiter.clone();
+            let semaphore = semaphore.clone();
+            let slippage_bps = self.config.slippage_bps;
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = quote_token_price(&jupiter, &mint, decimals, slippage_bps).await;
+                (mint, result)
+            });
+        }
+
+        let mut prices = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((mint, Ok(price))) => {
+                    prices.insert(mint, price);
+                }
+                Ok((mint, Err(e))) => {
+                    tracing::warn!("Failed to quote price for {}: {}", mint, e);
+                }
+                Err(e) => {
+                    tracing::warn!("Quote task failed: {}", e);
+                }
+            }
+        }
+
+        prices
+    }
+
+    /// Prices for this tick: batched Price API prices, overridden by quotes
+    /// for tokens near a trade
+    async fn fetch_tick_prices(&self, mints: &[String]) -> HashMap<String, f64> {
+        let mut prices = self.fetch_batch_prices(mints).await;
+
+        let candidates = self.quote_candidates(mints, &prices).await;
+        if !candidates.is_empty() {
+            tracing::debug!("Quoting {} of {} tokens", candidates.len(), mints.len());
+            prices.extend(self.fetch_quote_prices(candidates).await);
+        }
+
+        prices
     }
 
     /// Check for entry signal on a specific token
@@ -629,23 +881,19 @@ impl MemeOrchestrator {
             .get(mint)
             .ok_or_else(|| MemeOrchestratorError::TokenNotFound(mint.to_string()))?;
 
-        // Check if tradeable
-        if !tracker.is_tradeable(&self.config) {
+        // Never enter on a signal computed from an old price
+        if tracker.is_price_stale(self.config.max_price_age()) {
+            tracing::debug!("Skipping entry check for {}: price is stale", tracker.info.symbol);
             return Ok(false);
         }
 
-        // Check OU signal
-        if let Some(z_score) = tracker.ou_process.current_z_score() {
-            // Entry on oversold (z < threshold, e.g., z < -3.5)
-            if z_score < self.config.z_entry_threshold {
-                tracing::info!(
-                    "Entry signal for {}: z={:.2} < {:.2}",
-                    tracker.info.symbol,
-                    z_score,
-                    self.config.z_entry_threshold
-                );
-                return Ok(true);
-            }
+        if tracker.action == Some(TradeAction::EnterLong) {
+            tracing::info!(
+                "Entry signal for {}: z={:.2}",
+                tracker.info.symbol,
+                tracker.z_score().unwrap_or(0.0)
+            );
+            return Ok(true);
         }
 
         Ok(false)
@@ -659,66 +907,68 @@ impl MemeOrchestrator {
         };
 
         let tokens = self.tokens.read().await;
-        let tracker = tokens.get(&position.token_mint);
-
-        if let Some(tracker) = tracker {
-            let current_price = tracker.info.price_usdc.unwrap_or(position.entry_price);
-            let pnl_pct = position.pnl_pct(current_price);
-            let age_hours = position.age_seconds() as f64 / 3600.0;
-
-            // Check stop loss
-            if pnl_pct <= -self.config.stop_loss_pct {
-                tracing::warn!(
-                    "Stop loss triggered for {}: {:.2}%",
-                    position.token_symbol,
-                    pnl_pct
-                );
-                return Ok(true);
-            }
-
-            // Check take profit
-            if pnl_pct >= self.config.take_profit_pct {
-                tracing::info!(
-                    "Take profit triggered for {}: {:.2}%",
-                    position.token_symbol,
-                    pnl_pct
-                );
-                return Ok(true);
-            }
-
-            // Check time stop
-            if age_hours >= self.config.max_position_hours {
-                tracing::info!(
-                    "Time stop triggered for {}: {:.1}h",
-                    position.token_symbol,
-                    age_hours
-                );
-                return Ok(true);
-            }
-
-            // Check z-score mean reversion exit
-            if let Some(z_score) = tracker.ou_process.current_z_score() {
-                if z_score >= self.config.z_exit_threshold {
-                    tracing::info!(
-                        "Mean reversion exit for {}: z={:.2}",
-                        position.token_symbol,
-                        z_score
-                    );
-                    return Ok(true);
-                }
-            }
-        } else {
+        let Some(tracker) = tokens.get(&position.token_mint) else {
             // Token no longer tracked - force exit
             tracing::warn!(
                 "Token {} no longer tracked, forcing exit",
                 position.token_symbol
             );
             return Ok(true);
+        };
+
+        if tracker.is_price_stale(self.config.max_price_age()) {
+            tracing::warn!(
+                "Price for {} is stale, exit checks use the last known price",
+                position.token_symbol
+            );
+        }
+
+        // Stop loss, take profit, time stop and the z-score exit all come from the strategy
+        if tracker.action == Some(TradeAction::Exit) {
+            let current_price = tracker.info.price_usdc.unwrap_or(position.entry_price);
+            tracing::info!(
+                "Exit signal for {}: PnL {:.2}%, z={:.2}, age {:.1}h",
+                position.token_symbol,
+                position.pnl_pct(current_price),
+                tracker.z_score().unwrap_or(0.0),
+                position.age_seconds() as f64 / 3600.0
+            );
+            return Ok(true);
         }
 
         Ok(false)
     }
 
+    /// Hand a position recovered from disk to its token's strategy
+    ///
+    /// The time stop keeps counting from the original entry and today's
+    /// trade counters are left as they are.
+    async fn adopt_recovered_position(&self) {
+        let Some(position) = self.active_position.read().await.clone() else {
+            return;
+        };
+        let mut tokens = self.tokens.write().await;
+        let Some(tracker) = tokens.get_mut(&position.token_mint) else {
+            return;
+        };
+        if tracker.strategy.position() == PositionState::Flat {
+            tracing::info!(
+                "Strategy for {} adopting recovered position at ${:.8}",
+                position.token_symbol,
+                position.entry_price
+            );
+            tracker.adopt(&position);
+        }
+    }
+
+    /// Record an executed trade on the token's strategy
+    async fn confirm_strategy(&self, mint: &str, action: TradeAction, price: f64) {
+        if let Some(tracker) = self.tokens.write().await.get_mut(mint) {
+            tracker.strategy.confirm_trade(action, price);
+            tracker.action = None;
+        }
+    }
+
     /// Execute entry trade (USDC -> Token)
     pub async fn execute_entry(
         &self,
@@ -751,8 +1001,8 @@ impl MemeOrchestrator {
                 .ok_or_else(|| MemeOrchestratorError::TokenNotFound(mint.to_string()))?;
             (
                 tracker.info.clone(),
-                tracker.ou_process.current_z_score().unwrap_or(0.0),
-                tracker.ou_process.params().cloned(),
+                tracker.z_score().unwrap_or(0.0),
+                tracker.ou_params(),
             )
         };
 
@@ -781,6 +1031,7 @@ impl MemeOrchestrator {
             );
 
             *self.active_position.write().await = Some(position.clone());
+            self.confirm_strategy(mint, TradeAction::EnterLong, price).await;
             self.persist_state().await?;
 
             return Ok(position);
@@ -879,6 +1130,7 @@ impl MemeOrchestrator {
         }
 
         *self.active_position.write().await = Some(position.clone());
+        self.confirm_strategy(mint, TradeAction::EnterLong, price).await;
         self.persist_state().await?;
 
         Ok(position)
@@ -919,6 +1171,8 @@ impl MemeOrchestrator {
             );
 
             *self.active_position.write().await = None;
+            self.confirm_strategy(&position.token_mint, TradeAction::Exit, current_price)
+                .await;
             self.persist_state().await?;
 
             return Ok(pnl_pct);
@@ -941,7 +1195,7 @@ impl MemeOrchestrator {
 
         let quote = self
             .jupiter
-            .get_quote(&quote_request)
+            .get_quote_with_priority(&quote_request, RequestPriority::Exit)
             .await
             .map_err(|e| MemeOrchestratorError::ExecutionError(e.to_string()))?;
 
@@ -975,7 +1229,7 @@ impl MemeOrchestrator {
 
         let swap_response = self
             .jupiter
-            .get_swap_transaction(&swap_request)
+            .get_swap_transaction_with_priority(&swap_request, RequestPriority::Exit)
             .await
             .map_err(|e| MemeOrchestratorError::ExecutionError(e.to_string()))?;
 
@@ -1012,6 +1266,9 @@ impl MemeOrchestrator {
         }
 
         *self.active_position.write().await = None;
+        let exit_price = position.entry_price * (1.0 + pnl_pct / 100.0);
+        self.confirm_strategy(&position.token_mint, TradeAction::Exit, exit_price)
+            .await;
         self.persist_state().await?;
 
         Ok(pnl_pct)
@@ -1061,34 +1318,40 @@ impl MemeOrchestrator {
 
     /// Execute one trading cycle
     pub async fn tick(&self) -> Result<(), MemeOrchestratorError> {
+        self.adopt_recovered_position().await;
+
         // Update prices for all tracked tokens
         let token_mints: Vec<String> = {
             let tokens = self.tokens.read().await;
             tokens.keys().cloned().collect()
         };
 
-        for mint in &token_mints {
-            match self.fetch_token_price(mint).await {
-                Ok(price) => {
-                    let signal = self.update_token_price(mint, price).await?;
+        let prices = self.fetch_tick_prices(&token_mints).await;
+        let max_age = self.config.max_price_age();
+
+        {
+            let mut tokens = self.tokens.write().await;
+            for mint in &token_mints {
+                let Some(tracker) = tokens.get_mut(mint) else {
+                    continue;
+                };
 
-                    // Log status
-                    let tokens = self.tokens.read().await;
-                    if let Some(tracker) = tokens.get(mint) {
-                        let z_score = tracker.ou_process.current_z_score();
+                match prices.get(mint) {
+                    Some(&price) => {
+                        tracker.observe_price(price, max_age);
 
                         tracing::debug!(
-                            "{} ${:.8} | z={:.2} | half_life={:.1}min | signal={:?}",
+                            "{} ${:.8} | z={:.2} | action={:?}",
                             tracker.info.symbol,
                             price,
-                            z_score.unwrap_or(0.0),
-                            tracker.ou_process.half_life_minutes().unwrap_or(0.0),
-                            signal
+                            tracker.z_score().unwrap_or(0.0),
+                            tracker.action
                         );
                     }
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to fetch price for {}: {}", mint, e);
+                    None => {
+                        tracing::warn!("No price for {} this tick", tracker.info.symbol);
+                        tracker.check_stops();
+                    }
                 }
             }
         }
@@ -1153,6 +1416,13 @@ impl MemeOrchestrator {
             .map(|t| t.info.symbol.clone())
             .collect();
 
+        let max_age = self.config.max_price_age();
+        let stale_tokens: Vec<String> = tokens
+            .values()
+            .filter(|t| t.is_price_stale(max_age))
+            .map(|t| t.info.symbol.clone())
+            .collect();
+
         let current_price = position.as_ref().and_then(|p| {
             tokens.get(&p.token_mint).and_then(|t| t.info.price_usdc)
         });
@@ -1166,6 +1436,7 @@ impl MemeOrchestrator {
             is_running: *self.is_running.blocking_read(),
             paper_mode: self.config.paper_mode,
             tracked_tokens,
+            stale_tokens,
             active_position: position.map(|p| p.token_symbol),
             current_pnl_pct: pnl_pct,
             balance_guard_halted: self.balance_guard.blocking_read().is_halted(),
@@ -1183,12 +1454,41 @@ impl MemeOrchestrator {
     }
 }
 
+/// Quote one whole token into USDC and return its price
+async fn quote_token_price(
+    jupiter: &JupiterClient,
+    mint: &str,
+    decimals: u8,
+    slippage_bps: u16,
+) -> Result<f64, MemeOrchestratorError> {
+    let amount = 10u64.pow(decimals as u32); // 1 token
+
+    let quote_request = QuoteRequest::new(
+        mint.to_string(),
+        USDC_MINT.to_string(),
+        amount,
+        slippage_bps,
+    );
+
+    let quote = jupiter
+        .get_quote_with_priority(&quote_request, RequestPriority::Polling)
+        .await
+        .map_err(|e| MemeOrchestratorError::MarketDataError(e.to_string()))?;
+
+    // USDC has 6 decimals
+    let price = quote.output_amount() as f64 / 1_000_000.0;
+
+    Ok(price)
+}
+
 /// Status snapshot
 #[derive(Debug, Clone)]
 pub struct MemeOrchestratorStatus {
     pub is_running: bool,
     pub paper_mode: bool,
     pub tracked_tokens: Vec<String>,
+    /// Tokens without a fresh price within `max_price_age_secs`
+    pub stale_tokens: Vec<String>,
     pub active_position: Option<String>,
     pub current_pnl_pct: Option<f64>,
     pub balance_guard_halted: bool,
@@ -1211,6 +1511,14 @@ mod tests {
         }
     }
 
+    fn create_test_tracker(ou_lookback: usize) -> TokenTracker {
+        let config = MemeOrchestratorConfig {
+            ou_lookback,
+            ..Default::default()
+        };
+        TokenTracker::new(create_test_token_info(), &config.strategy_config())
+    }
+
     #[test]
     fn test_token_info_creation() {
         let info = TokenInfo::new("mint123".to_string(), "TEST".to_string(), 9);
@@ -1248,20 +1556,64 @@ mod tests {
         assert_eq!(config.z_entry_threshold, -3.5);
         assert_eq!(config.z_exit_threshold, 0.0);
         assert!(config.paper_mode);
+        assert_eq!(config.price_batch_size, 50);
+        assert_eq!(config.max_price_age(), Duration::from_secs(180));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_rejects_zero_quote_concurrency() {
+        let config = MemeOrchestratorConfig {
+            quote_concurrency: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_token_tracker_staleness() {
+        let mut tracker = create_test_tracker(50);
+        let max_age = Duration::from_millis(20);
+
+        assert!(tracker.is_price_stale(max_age));
+        assert!(tracker.observe_price(0.002, max_age));
+        assert!(!tracker.is_price_stale(max_age));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(tracker.is_price_stale(max_age));
+    }
+
+    #[test]
+    fn test_token_tracker_skips_frozen_price() {
+        let mut tracker = create_test_tracker(50);
+        let max_age = Duration::from_millis(20);
+
+        assert!(tracker.observe_price(0.002, max_age));
+        // Repeated value within max_age is still fed
+        assert!(tracker.observe_price(0.002, max_age));
+        assert_eq!(tracker.price_history.len(), 2);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!tracker.observe_price(0.002, max_age));
+        assert_eq!(tracker.price_history.len(), 2);
+
+        // A new price revives the feed
+        assert!(tracker.observe_price(0.0021, max_age));
+        assert_eq!(tracker.price_history.len(), 3);
+        assert!(!tracker.is_price_stale(max_age));
     }
 
     #[test]
     fn test_token_tracker_creation() {
-        let info = create_test_token_info();
-        let tracker = TokenTracker::new(info.clone(), 100, 1.0);
+        let tracker = create_test_tracker(100);
         assert_eq!(tracker.info.symbol, "TEST");
-        assert!(!tracker.ou_process.is_ready());
+        assert_eq!(tracker.strategy.name(), "ou_reversion");
+        assert!(!tracker.strategy.is_ready());
     }
 
     #[test]
     fn test_token_tracker_price_update() {
-        let info = create_test_token_info();
-        let mut tracker = TokenTracker::new(info, 50, 1.0);
+        let mut tracker = create_test_tracker(50);
 
         // Update prices
         for i in 0..60 {
@@ -1330,12 +1682,80 @@ mod tests {
     }
 
     #[test]
-    fn test_token_tracker_is_tradeable() {
+    fn test_token_tracker_not_ready_without_data() {
+        let mut tracker = create_test_tracker(50);
+        tracker.update_price(0.001);
+
+        // No signal without enough data
+        assert!(tracker.action.is_none());
+        assert!(tracker.ou_params().is_none());
+    }
+
+    #[test]
+    fn test_strategy_config_mirrors_meme_rules() {
         let config = MemeOrchestratorConfig::default();
-        let info = create_test_token_info();
-        let tracker = TokenTracker::new(info, 50, 1.0);
+        let strategy_config = config.strategy_config();
+
+        assert_eq!(strategy_config.kind, StrategyKind::OuReversion);
+        assert_eq!(strategy_config.lookback_period, config.ou_lookback);
+        assert_eq!(strategy_config.ou.z_entry_threshold, config.z_entry_threshold);
+        assert_eq!(strategy_config.risk.stop_loss_pct, config.stop_loss_pct);
+        assert_eq!(strategy_config.risk.time_stop_hours, config.max_position_hours);
+        assert!(!strategy_config.ou.allow_short);
+    }
+
+    #[test]
+    fn test_adopted_position_keeps_its_age() {
+        let mut tracker = create_test_tracker(50);
+        let mut position = ActivePosition::new(
+            "mint".to_string(),
+            "TEST".to_string(),
+            0.001,
+            1_000_000_000,
+            50.0,
+            -3.5,
+            None,
+        );
+        // Entered 4h01m ago, past the 4h max_position_hours
+        position.entry_timestamp -= 4 * 3600 + 60;
+
+        tracker.adopt(&position);
+        let snapshot = tracker.strategy.snapshot();
+        assert_eq!(snapshot.position, PositionState::long(0.001));
+        assert!(snapshot.position_age_secs.unwrap() >= (4 * 3600 + 60) as f64);
+        assert_eq!(snapshot.daily_trades, 0);
+
+        // The time stop fires at once instead of restarting at adoption
+        tracker.info.price_usdc = Some(0.001);
+        assert_eq!(tracker.check_stops(), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_token_tracker_trades_through_strategy() {
+        let config = MemeOrchestratorConfig {
+            ou_lookback: 60,
+            z_entry_threshold: -2.0,
+            min_ou_confidence: 0.0,
+            min_half_life_minutes: 0.5,
+            max_half_life_minutes: 600.0,
+            ..Default::default()
+        };
+        let mut tracker = TokenTracker::new(create_test_token_info(), &config.strategy_config());
+
+        // Deterministic AR(1) around 0.001 (rho ~0.8)
+        let mut x: f64 = 0.0;
+        for i in 0..120 {
+            let shock = ((i * 7919) % 13) as f64 / 13.0 - 0.5;
+            x = 0.8 * x + shock * 0.01;
+            tracker.update_price(0.001 * x.exp());
+        }
+        assert!(tracker.ou_params().is_some());
+
+        assert_eq!(tracker.update_price(0.00097), Some(TradeAction::EnterLong));
+        tracker.strategy.confirm_trade(TradeAction::EnterLong, 0.00097);
 
-        // Not tradeable without enough data
-        assert!(!tracker.is_tradeable(&config));
+        // Stop loss from the meme config, checked without feeding the OU fit
+        tracker.info.price_usdc = Some(0.00085);
+        assert_eq!(tracker.check_stops(), Some(TradeAction::Exit));
     }
 }
@@ -1,7 +1,25 @@
 pub mod orchestrator;
 pub mod meme_orchestrator;
+pub mod backtest;
+pub mod multi_pair;
+pub mod spread_trader;
+pub mod optimizer;
+pub mod monte_carlo;
+pub mod paper_portfolio;
+pub mod state_file;
 
 pub use orchestrator::TradingOrchestrator;
+pub use multi_pair::{MultiPairOrchestrator, MultiPairStatus, PairStatusRow};
+pub use spread_trader::{LegExecutor, LegOrder, OpenSpread, SpreadLeg, SpreadTrader};
+pub use backtest::{Backtester, BacktestComparison, BacktestConfig, BacktestReport, BacktestVote};
+pub use monte_carlo::{
+    Distribution, McTrade, MonteCarloConfig, MonteCarloError, MonteCarloReport, MonteCarloSimulator,
+};
+pub use paper_portfolio::{PaperPortfolio, PaperPortfolioError};
+pub use optimizer::{
+    Objective, OptimizationReport, OptimizeParam, OptimizerConfig, OptimizerError,
+    ParamRange, ParameterStability, SearchMode, WalkForwardOptimizer, WindowResult,
+};
 pub use meme_orchestrator::{
     MemeOrchestrator, MemeOrchestratorConfig,
     TokenInfo, PersistedState,
@@ -0,0 +1,478 @@
+//! Monte Carlo Robustness Analysis
+//!
+//! Stress-tests a trade list from a backtest (`BacktestTrade`) or paper
+//! trading (`PaperTrade` sells) by replaying random variations of it:
+//! - Trade sequences are bootstrapped (drawn with replacement), so order and
+//!   luck of the draw vary between runs
+//! - Slippage and fees are drawn per fill around their configured means
+//! - Each entry can be missed with `miss_probability` (latency, failed
+//!   transactions), skipping the trade
+//!
+//! Every trade stakes `trade_size` on a `capital` account; the report gives
+//! the distribution of final PnL and max drawdown and the fraction of runs
+//! that hit the ruin level. Trades are reduced to their gross return, so
+//! costs already baked into the source are stripped before being re-applied.
+//!
+//! A handful of winning trades bootstraps into a handful of outcomes: check
+//! `win_rate_interval` and `source_trades` before trusting the distributions.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::application::backtest::BacktestTrade;
+use crate::meme::paper_trading::{PaperTrade, TradeSide};
+
+#[derive(Debug, Error)]
+pub enum MonteCarloError {
+    #[error("No closed trades to resample")]
+    NoTrades,
+    #[error("Invalid Monte Carlo parameters: {0}")]
+    InvalidConfig(String),
+}
+
+/// Closed trade reduced to what the simulation resamples
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct McTrade {
+    /// Price move captured in percent, before slippage and fees
+    pub gross_return_pct: f64,
+}
+
+impl McTrade {
+    pub fn new(gross_return_pct: f64) -> Self {
+        Self { gross_return_pct }
+    }
+
+    /// Backtest trades already report the move before fees
+    pub fn from_backtest(trades: &[BacktestTrade]) -> Vec<McTrade> {
+        trades.iter().map(|t| McTrade::new(t.pnl_pct)).collect()
+    }
+
+    /// Closed paper round trips (sells), with the sell's recorded slippage
+    /// removed from both fills and the buy and sell `fee_sol` added back
+    ///
+    /// The engine folds buy fees into the holding's average cost, so the
+    /// buys are replayed per mint to know which share of a sell's cost basis
+    /// was fees. Sells without their buys in the list only get the sell fee back.
+    pub fn from_paper(trades: &[PaperTrade]) -> Vec<McTrade> {
+        // Open tokens, SOL spent and fees paid per mint
+        let mut holdings: HashMap<&str, (u64, f64, f64)> = HashMap::new();
+        let mut result = Vec::new();
+        for trade in trades {
+            let (tokens, spent, fees) = holdings.entry(trade.mint.as_str()).or_default();
+            if trade.side == TradeSide::Buy {
+                *tokens += trade.token_amount;
+                *spent += trade.sol_amount;
+                *fees += trade.fee_sol;
+                continue;
+            }
+
+            let fee_share = if *spent + *fees > 0.0 { *fees / (*spent + *fees) } else { 0.0 };
+            let sold = if *tokens > 0 { (trade.token_amount as f64 / *tokens as f64).min(1.0) } else { 1.0 };
+            *tokens = tokens.saturating_sub(trade.token_amount);
+            *spent *= 1.0 - sold;
+            *fees *= 1.0 - sold;
+
+            // Sell `sol_amount` is net of its fee and `pnl_sol` is against the fee-inclusive basis
+            let Some(pnl_sol) = trade.pnl_sol else { continue };
+            let cost_basis = (trade.sol_amount - pnl_sol) * (1.0 - fee_share);
+            if cost_basis <= 0.0 {
+                continue;
+            }
+            let net = (trade.sol_amount + trade.fee_sol) / cost_basis - 1.0;
+            let slippage = trade.slippage_bps as f64 / 10_000.0;
+            let gross = (1.0 + net) * (1.0 + slippage) / (1.0 - slippage) - 1.0;
+            result.push(McTrade::new(gross * 100.0));
+        }
+        result
+    }
+}
+
+/// Simulation settings
+#[derive(Debug, Clone, Serialize)]
+pub struct MonteCarloConfig {
+    /// Number of simulated trade sequences
+    pub runs: usize,
+    /// Trades per sequence (None = as many as the source)
+    pub trades_per_run: Option<usize>,
+    pub seed: u64,
+    /// Starting account in stake units (SOL)
+    pub capital: f64,
+    /// Stake per trade (`risk.trade_size_sol`)
+    pub trade_size: f64,
+    /// Mean slippage per fill in basis points
+    pub slippage_bps: f64,
+    /// Slippage is drawn uniformly within +/- this of the mean (floored at 0)
+    pub slippage_jitter_bps: f64,
+    /// Mean fee per fill in basis points (DEX + network, as a fraction of the stake)
+    pub fee_bps: f64,
+    /// Fee is drawn uniformly within +/- this of the mean (floored at 0)
+    pub fee_jitter_bps: f64,
+    /// Probability an entry is not filled in time and the trade is skipped
+    pub miss_probability: f64,
+    /// A run is ruined once equity falls this far below `capital`, in percent
+    pub ruin_loss_pct: f64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            runs: 10_000,
+            trades_per_run: None,
+            seed: 42,
+            capital: 1.0,
+            trade_size: 0.1,
+            slippage_bps: 25.0,
+            slippage_jitter_bps: 25.0,
+            fee_bps: 15.0,
+            fee_jitter_bps: 5.0,
+            miss_probability: 0.05,
+            ruin_loss_pct: 50.0,
+        }
+    }
+}
+
+impl MonteCarloConfig {
+    pub fn validate(&self) -> Result<(), MonteCarloError> {
+        let invalid = |msg: String| Err(MonteCarloError::InvalidConfig(msg));
+        if self.runs == 0 {
+            return invalid("runs must be > 0".to_string());
+        }
+        if self.trades_per_run == Some(0) {
+            return invalid("trades_per_run must be > 0".to_string());
+        }
+        if self.capital <= 0.0 || self.trade_size <= 0.0 {
+            return invalid(format!(
+                "capital {} and trade_size {} must be > 0",
+                self.capital, self.trade_size
+            ));
+        }
+        if [self.slippage_bps, self.slippage_jitter_bps, self.fee_bps, self.fee_jitter_bps]
+            .iter()
+            .any(|bps| !(0.0..10_000.0).contains(bps))
+        {
+            return invalid("slippage and fee bps must be in [0, 10000)".to_string());
+        }
+        if !(0.0..1.0).contains(&self.miss_probability) {
+            return invalid(format!("miss_probability must be in [0, 1), got {}", self.miss_probability));
+        }
+        if self.ruin_loss_pct <= 0.0 || self.ruin_loss_pct > 100.0 {
+            return invalid(format!("ruin_loss_pct must be in (0, 100], got {}", self.ruin_loss_pct));
+        }
+        Ok(())
+    }
+}
+
+/// Summary of one simulated quantity across runs
+#[derive(Debug, Clone, Serialize)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    fn new(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let percentile = |p: f64| values[((n - 1.0) * p).round() as usize];
+        Self {
+            mean,
+            std_dev,
+            min: values[0],
+            p5: percentile(0.05),
+            p25: percentile(0.25),
+            median: percentile(0.5),
+            p75: percentile(0.75),
+            p95: percentile(0.95),
+            max: values[values.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:+.4} | p5 {:+.4} | median {:+.4} | p95 {:+.4}",
+            self.mean, self.p5, self.median, self.p95
+        )
+    }
+}
+
+/// Monte Carlo results
+#[derive(Debug, Clone, Serialize)]
+pub struct MonteCarloReport {
+    pub config: MonteCarloConfig,
+    /// Closed trades in the source list
+    pub source_trades: usize,
+    pub source_win_rate: f64,
+    /// 95% Wilson interval of the source win rate
+    pub win_rate_interval: (f64, f64),
+    /// Final PnL in stake units (SOL)
+    pub final_pnl: Distribution,
+    /// Max peak-to-trough drawdown in percent of equity
+    pub max_drawdown_pct: Distribution,
+    /// Fraction of runs that reached the ruin level
+    pub risk_of_ruin: f64,
+    /// Fraction of runs that ended below `capital`
+    pub probability_of_loss: f64,
+    /// Mean fraction of entries missed per run
+    pub missed_fill_rate: f64,
+}
+
+/// Bootstrap simulator over a closed trade list
+#[derive(Debug, Clone)]
+pub struct MonteCarloSimulator {
+    config: MonteCarloConfig,
+}
+
+impl MonteCarloSimulator {
+    pub fn new(config: MonteCarloConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &MonteCarloConfig {
+        &self.config
+    }
+
+    /// Run all simulations (deterministic for a seed)
+    pub fn run(&self, trades: &[McTrade]) -> Result<MonteCarloReport, MonteCarloError> {
+        self.config.validate()?;
+        if trades.is_empty() {
+            return Err(MonteCarloError::NoTrades);
+        }
+
+        let config = &self.config;
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let per_run = config.trades_per_run.unwrap_or(trades.len());
+        let ruin_equity = config.capital * (1.0 - config.ruin_loss_pct / 100.0);
+
+        let mut final_pnl = Vec::with_capacity(config.runs);
+        let mut drawdowns = Vec::with_capacity(config.runs);
+        let mut ruined = 0;
+        let mut missed = 0;
+
+        for _ in 0..config.runs {
+            let mut equity = config.capital;
+            let mut peak = equity;
+            let mut max_drawdown: f64 = 0.0;
+            let mut is_ruined = false;
+
+            for _ in 0..per_run {
+                let trade = trades[rng.gen_range(0..trades.len())];
+                if rng.gen::<f64>() < config.miss_probability {
+                    missed += 1;
+                    continue;
+                }
+
+                // Never stake more than what is left
+                let stake = config.trade_size.min(equity);
+                let entry_cost = self.draw_cost(&mut rng);
+                let exit_cost = self.draw_cost(&mut rng);
+                let net = (1.0 + trade.gross_return_pct / 100.0) * (1.0 - exit_cost) / (1.0 + entry_cost) - 1.0;
+                equity += stake * net;
+
+                peak = peak.max(equity);
+                max_drawdown = max_drawdown.max((peak - equity) / peak * 100.0);
+                if equity <= ruin_equity {
+                    is_ruined = true;
+                    break;
+                }
+            }
+
+            if is_ruined {
+                ruined += 1;
+            }
+            final_pnl.push(equity - config.capital);
+            drawdowns.push(max_drawdown);
+        }
+
+        let runs = config.runs as f64;
+        let wins = trades.iter().filter(|t| t.gross_return_pct > 0.0).count();
+        Ok(MonteCarloReport {
+            config: config.clone(),
+            source_trades: trades.len(),
+            source_win_rate: wins as f64 / trades.len() as f64,
+            win_rate_interval: wilson_interval(wins, trades.len()),
+            probability_of_loss: final_pnl.iter().filter(|&&pnl| pnl < 0.0).count() as f64 / runs,
+            final_pnl: Distribution::new(final_pnl),
+            max_drawdown_pct: Distribution::new(drawdowns),
+            risk_of_ruin: ruined as f64 / runs,
+            missed_fill_rate: missed as f64 / (runs * per_run as f64),
+        })
+    }
+
+    /// Slippage + fee for one fill, as a fraction
+    fn draw_cost(&self, rng: &mut StdRng) -> f64 {
+        let mut draw = |mean: f64, jitter: f64| {
+            if jitter > 0.0 {
+                (mean + rng.gen_range(-jitter..=jitter)).max(0.0)
+            } else {
+                mean
+            }
+        };
+        let slippage = draw(self.config.slippage_bps, self.config.slippage_jitter_bps);
+        let fee = draw(self.config.fee_bps, self.config.fee_jitter_bps);
+        (slippage + fee) / 10_000.0
+    }
+}
+
+/// 95% Wilson score interval for `wins` out of `n`
+fn wilson_interval(wins: usize, n: usize) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    const Z: f64 = 1.96;
+    let n = n as f64;
+    let p = wins as f64 / n;
+    let denominator = 1.0 + Z * Z / n;
+    let center = (p + Z * Z / (2.0 * n)) / denominator;
+    let half_width = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+    ((center - half_width).max(0.0), (center + half_width).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_costs() -> MonteCarloConfig {
+        MonteCarloConfig {
+            runs: 500,
+            slippage_bps: 0.0,
+            slippage_jitter_bps: 0.0,
+            fee_bps: 0.0,
+            fee_jitter_bps: 0.0,
+            miss_probability: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_identical_trades_are_deterministic() {
+        let trades = vec![McTrade::new(1.0); 10];
+        let report = MonteCarloSimulator::new(no_costs()).run(&trades).unwrap();
+
+        // 10 trades x 0.1 SOL x 1%
+        assert!((report.final_pnl.mean - 0.01).abs() < 1e-9);
+        assert!(report.final_pnl.std_dev < 1e-9);
+        assert_eq!(report.max_drawdown_pct.max, 0.0);
+        assert_eq!(report.risk_of_ruin, 0.0);
+        assert_eq!(report.probability_of_loss, 0.0);
+    }
+
+    #[test]
+    fn test_costs_and_missed_fills_reduce_pnl() {
+        let trades: Vec<McTrade> = [0.8, 0.8, 0.8, -0.5, 0.8, -0.5].into_iter().map(McTrade::new).collect();
+        let clean = MonteCarloSimulator::new(no_costs()).run(&trades).unwrap();
+
+        let costly = MonteCarloSimulator::new(MonteCarloConfig { runs: 500, ..Default::default() })
+            .run(&trades)
+            .unwrap();
+        assert!(costly.final_pnl.mean < clean.final_pnl.mean);
+        assert!(costly.missed_fill_rate > 0.0 && costly.missed_fill_rate < 0.2);
+        assert!(costly.probability_of_loss > clean.probability_of_loss);
+
+        let again = MonteCarloSimulator::new(MonteCarloConfig { runs: 500, ..Default::default() })
+            .run(&trades)
+            .unwrap();
+        assert_eq!(again.final_pnl.mean, costly.final_pnl.mean);
+    }
+
+    #[test]
+    fn test_risk_of_ruin_at_trade_size() {
+        let trades: Vec<McTrade> = [5.0, -10.0].into_iter().map(McTrade::new).collect();
+        let config = MonteCarloConfig {
+            trades_per_run: Some(200),
+            capital: 1.0,
+            ruin_loss_pct: 20.0,
+            ..no_costs()
+        };
+
+        let small = MonteCarloSimulator::new(MonteCarloConfig { trade_size: 0.1, ..config.clone() })
+            .run(&trades)
+            .unwrap();
+        let large = MonteCarloSimulator::new(MonteCarloConfig { trade_size: 1.0, ..config })
+            .run(&trades)
+            .unwrap();
+        assert!(large.risk_of_ruin > small.risk_of_ruin);
+        assert!(large.risk_of_ruin > 0.9);
+        assert!(large.max_drawdown_pct.median >= 20.0);
+    }
+
+    #[test]
+    fn test_few_trades_give_wide_win_rate_interval() {
+        let two = MonteCarloSimulator::new(no_costs()).run(&[McTrade::new(1.0), McTrade::new(2.0)]).unwrap();
+        assert_eq!(two.source_win_rate, 1.0);
+        assert!(two.win_rate_interval.0 < 0.35);
+        assert!(two.win_rate_interval.1 > 0.99);
+
+        let many: Vec<McTrade> = (0..200).map(|i| McTrade::new(if i % 10 < 7 { 1.0 } else { -1.0 })).collect();
+        let report = MonteCarloSimulator::new(no_costs()).run(&many).unwrap();
+        assert!(report.win_rate_interval.0 > 0.6 && report.win_rate_interval.1 < 0.8);
+
+        assert!(matches!(MonteCarloSimulator::new(no_costs()).run(&[]), Err(MonteCarloError::NoTrades)));
+        let invalid = MonteCarloConfig { miss_probability: 1.0, ..no_costs() };
+        assert!(MonteCarloSimulator::new(invalid).run(&many).is_err());
+    }
+
+    fn paper(side: TradeSide, sol_amount: f64, slippage_bps: u16, fee_sol: f64, pnl_sol: Option<f64>) -> PaperTrade {
+        PaperTrade {
+            id: 1,
+            mint: "mint".to_string(),
+            symbol: "MEME".to_string(),
+            side,
+            token_amount: 1_000,
+            decimals: 6,
+            sol_amount,
+            price: 0.0001,
+            slippage_bps,
+            fee_sol,
+            timestamp: 0,
+            pnl_sol,
+            pnl_pct: pnl_sol.map(|p| p / sol_amount * 100.0),
+            quote_currency: "SOL".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_paper_trades_strip_engine_slippage() {
+        // Flat price round trip with 50 bps slippage on each side: 0.1 SOL in, 0.1 * 0.995 / 1.005 out
+        let out = 0.1 * 0.995 / 1.005;
+        let trades = McTrade::from_paper(&[
+            paper(TradeSide::Buy, 0.1, 50, 0.0, None),
+            paper(TradeSide::Sell, out, 50, 0.0, Some(out - 0.1)),
+        ]);
+        assert_eq!(trades.len(), 1);
+        assert!(trades[0].gross_return_pct.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_paper_trades_strip_fees() {
+        // Flat price, 0.001 SOL fee on each fill: the engine books 0.099 against a 0.101 basis
+        let trades = McTrade::from_paper(&[
+            paper(TradeSide::Buy, 0.1, 0, 0.001, None),
+            paper(TradeSide::Sell, 0.099, 0, 0.001, Some(-0.002)),
+        ]);
+        assert_eq!(trades.len(), 1);
+        assert!(trades[0].gross_return_pct.abs() < 1e-9, "{}", trades[0].gross_return_pct);
+
+        // Half sold at +10% before fees; the rest stays open with half the buy fee
+        let mut half = paper(TradeSide::Sell, 0.055 - 0.001, 0, 0.001, Some(0.054 - 0.0505));
+        half.token_amount = 500;
+        let trades = McTrade::from_paper(&[paper(TradeSide::Buy, 0.1, 0, 0.001, None), half]);
+        assert!((trades[0].gross_return_pct - 10.0).abs() < 1e-9, "{}", trades[0].gross_return_pct);
+    }
+}
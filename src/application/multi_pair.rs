@@ -0,0 +1,299 @@
+//! Multi-Pair Orchestrator
+//!
+//! Runs one `TradingOrchestrator` per configured pair from a single wallet.
+//! Each pair keeps its own strategy, ADX and candle state and per-pair
+//! config; all pairs share:
+//! - the wallet's balance guard, with swaps executed one at a time
+//! - a `PairRiskBook` enforcing the global exposure cap and the
+//!   cross-pair daily loss limit on every entry
+//!
+//! Daily counters of every pair and the shared daily loss reset when the
+//! UTC day changes.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+
+use super::orchestrator::{OrchestratorError, OrchestratorStatus, TradingOrchestrator};
+use crate::domain::{PairRiskBook, PortfolioLimits};
+
+/// Interval between status tables in the log while running
+const STATUS_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Interval between checks for a new UTC trading day
+const DAY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Status of one pair
+#[derive(Debug, Clone)]
+pub struct PairStatusRow {
+    pub status: OrchestratorStatus,
+    /// Open exposure, percent of equity
+    pub exposure_pct: f64,
+    /// Realized PnL today, percent of equity
+    pub realized_pnl_pct: f64,
+}
+
+/// Status of every pair plus the shared limits
+#[derive(Debug, Clone)]
+pub struct MultiPairStatus {
+    pub pairs: Vec<PairStatusRow>,
+    pub total_exposure_pct: f64,
+    pub max_total_exposure_pct: f64,
+    pub daily_pnl_pct: f64,
+    pub max_daily_loss_pct: f64,
+}
+
+impl fmt::Display for MultiPairStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<12} {:<28} {:>7} {:>9} {:>9} {:>7}",
+            "PAIR", "POSITION", "Z", "EXPOSURE", "PNL", "TRADES"
+        )?;
+        for row in &self.pairs {
+            let z = row
+                .status
+                .current_zscore
+                .map(|z| format!("{:+.2}", z))
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(
+                f,
+                "{:<12} {:<28} {:>7} {:>8.2}% {:>+8.3}% {:>7}",
+                row.status.symbol,
+                row.status.position,
+                z,
+                row.exposure_pct,
+                row.realized_pnl_pct,
+                row.status.daily_trades
+            )?;
+        }
+        write!(
+            f,
+            "TOTAL exposure {:.2}% / {:.2}% | daily PnL {:+.3}% (limit -{:.2}%)",
+            self.total_exposure_pct, self.max_total_exposure_pct, self.daily_pnl_pct, self.max_daily_loss_pct
+        )
+    }
+}
+
+/// Trades several pairs from one wallet
+pub struct MultiPairOrchestrator {
+    pairs: Vec<TradingOrchestrator>,
+    risk_book: Arc<RwLock<PairRiskBook>>,
+}
+
+impl MultiPairOrchestrator {
+    pub fn new(limits: PortfolioLimits) -> Self {
+        Self {
+            pairs: Vec::new(),
+            risk_book: Arc::new(RwLock::new(PairRiskBook::new(limits))),
+        }
+    }
+
+    /// Add a pair; it shares the wallet of the first pair and the risk book
+    pub fn with_pair(mut self, orchestrator: TradingOrchestrator) -> Self {
+        let orchestrator = match self.pairs.first() {
+            Some(first) => orchestrator.with_shared_wallet(first),
+            None => orchestrator,
+        };
+        self.pairs.push(orchestrator.with_risk_book(Arc::clone(&self.risk_book)));
+        self
+    }
+
+    /// Orchestrators in the order the pairs were added
+    pub fn pairs(&self) -> &[TradingOrchestrator] {
+        &self.pairs
+    }
+
+    /// Exposure and daily PnL shared by all pairs
+    pub fn risk_book(&self) -> Arc<RwLock<PairRiskBook>> {
+        Arc::clone(&self.risk_book)
+    }
+
+    /// Run every pair until all have stopped, logging a status table periodically
+    pub async fn run(&self) -> Result<(), OrchestratorError> {
+        if self.pairs.is_empty() {
+            return Err(OrchestratorError::ConfigError("no pairs configured".to_string()));
+        }
+
+        tracing::info!("Starting multi-pair orchestrator with {} pairs", self.pairs.len());
+
+        let mut tasks = JoinSet::new();
+        for pair in &self.pairs {
+            let pair = pair.clone();
+            tasks.spawn(async move { pair.run().await });
+        }
+
+        let mut status_timer = tokio::time::interval(STATUS_INTERVAL);
+        status_timer.tick().await; // first tick fires immediately
+        let mut day_timer = tokio::time::interval(DAY_CHECK_INTERVAL);
+        let mut day = Utc::now().date_naive();
+        loop {
+            tokio::select! {
+                joined = tasks.join_next() => match joined {
+                    Some(Ok(Ok(()))) => {}
+                    Some(Ok(Err(e))) => tracing::error!("Pair stopped with error: {}", e),
+                    Some(Err(e)) => tracing::error!("Pair task failed: {}", e),
+                    None => break,
+                },
+                _ = status_timer.tick() => {
+                    tracing::info!("Pairs status:\n{}", self.status().await);
+                }
+                _ = day_timer.tick() => {
+                    self.roll_day(&mut day, Utc::now()).await;
+                }
+            }
+        }
+
+        tracing::info!("Multi-pair orchestrator stopped");
+        Ok(())
+    }
+
+    /// Stop every pair
+    pub async fn stop(&self) {
+        for pair in &self.pairs {
+            pair.stop().await;
+        }
+    }
+
+    /// Reset daily counters of every pair and the shared daily loss
+    pub async fn reset_daily(&self) {
+        for pair in &self.pairs {
+            pair.reset_daily().await;
+        }
+        self.risk_book.write().await.reset_daily();
+    }
+
+    /// Reset daily counters once `now` falls on a later UTC day than `day`
+    async fn roll_day(&self, day: &mut NaiveDate, now: DateTime<Utc>) -> bool {
+        let today = now.date_naive();
+        if today <= *day {
+            return false;
+        }
+        tracing::info!("New trading day {}, resetting daily counters", today);
+        *day = today;
+        self.reset_daily().await;
+        true
+    }
+
+    /// Status row per pair plus the shared limits
+    pub async fn status(&self) -> MultiPairStatus {
+        let mut pairs = Vec::with_capacity(self.pairs.len());
+        for pair in &self.pairs {
+            let status = pair.status().await;
+            let book = self.risk_book.read().await.pair(&status.symbol);
+            pairs.push(PairStatusRow {
+                status,
+                exposure_pct: book.exposure_pct,
+                realized_pnl_pct: book.daily_pnl_pct,
+            });
+        }
+
+        let book = self.risk_book.read().await;
+        MultiPairStatus {
+            pairs,
+            total_exposure_pct: book.total_exposure_pct(),
+            max_total_exposure_pct: book.limits().max_total_exposure_pct,
+            daily_pnl_pct: book.daily_pnl_pct(),
+            max_daily_loss_pct: book.limits().max_daily_loss_pct,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::jupiter::JupiterClient;
+    use crate::adapters::solana::{SolanaClient, WalletManager};
+    use crate::strategy::StrategyConfig;
+
+    fn pair(symbol: &str, base_mint: &str, wallet: &WalletManager) -> TradingOrchestrator {
+        TradingOrchestrator::new(
+            StrategyConfig::default(),
+            JupiterClient::new().unwrap(),
+            SolanaClient::new("https://api.devnet.solana.com".to_string()),
+            wallet.clone(),
+            base_mint.to_string(),
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            50,
+            true,
+            0.1,
+            5000,
+        )
+        .unwrap()
+        .with_symbol(symbol)
+    }
+
+    fn multi_pair() -> MultiPairOrchestrator {
+        let wallet = WalletManager::new_random();
+        MultiPairOrchestrator::new(PortfolioLimits::default())
+            .with_pair(pair("SOL/USDC", "So11111111111111111111111111111111111111112", &wallet))
+            .with_pair(
+                pair("JUP/USDC", "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", &wallet).with_decimals(6, 6),
+            )
+    }
+
+    #[tokio::test]
+    async fn test_status_rows_per_pair() {
+        let orchestrator = multi_pair();
+        orchestrator.risk_book().write().await.add_exposure("JUP/USDC", 5.0);
+        orchestrator.risk_book().write().await.reduce("JUP/USDC", 0.5, -2.0);
+
+        let status = orchestrator.status().await;
+        let symbols: Vec<&str> = status.pairs.iter().map(|row| row.status.symbol.as_str()).collect();
+        assert_eq!(symbols, ["SOL/USDC", "JUP/USDC"]);
+        assert_eq!(status.pairs[0].exposure_pct, 0.0);
+        assert!((status.pairs[1].exposure_pct - 2.5).abs() < 1e-12);
+        assert!((status.daily_pnl_pct + 0.05).abs() < 1e-12);
+
+        let table = status.to_string();
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().nth(2).unwrap().starts_with("JUP/USDC"));
+        assert!(table.contains("TOTAL exposure 2.50% / 15.00%"), "{}", table);
+    }
+
+    #[tokio::test]
+    async fn test_reset_daily_clears_shared_loss() {
+        let orchestrator = multi_pair();
+        {
+            let book = orchestrator.risk_book();
+            let mut book = book.write().await;
+            book.add_exposure("SOL/USDC", 5.0);
+            book.reduce("SOL/USDC", 1.0, -80.0);
+            assert!(book.check_entry(1.0).is_err());
+        }
+
+        orchestrator.reset_daily().await;
+        assert!(orchestrator.risk_book().read().await.check_entry(1.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_day_change_resets_daily() {
+        let orchestrator = multi_pair();
+        {
+            let book = orchestrator.risk_book();
+            let mut book = book.write().await;
+            book.add_exposure("SOL/USDC", 5.0);
+            book.reduce("SOL/USDC", 1.0, -80.0);
+        }
+
+        let now = Utc::now();
+        let mut day = now.date_naive();
+        assert!(!orchestrator.roll_day(&mut day, now).await);
+        assert!(orchestrator.risk_book().read().await.check_entry(1.0).is_err());
+
+        let tomorrow = now + chrono::Duration::days(1);
+        assert!(orchestrator.roll_day(&mut day, tomorrow).await);
+        assert_eq!(day, tomorrow.date_naive());
+        assert!(orchestrator.risk_book().read().await.check_entry(1.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_run_without_pairs() {
+        let orchestrator = MultiPairOrchestrator::new(PortfolioLimits::default());
+        assert!(orchestrator.run().await.is_err());
+    }
+}
@@ -0,0 +1,883 @@
+//! Walk-Forward Optimizer
+//!
+//! Tunes strategy, risk and ADX parameters without fitting the whole history:
+//! - The samples are cut into rolling windows: `in_sample` to fit, followed
+//!   by `out_of_sample` to validate; the next window starts `out_of_sample` later
+//! - Every candidate (full grid, or random draws from it) is backtested on
+//!   the in-sample part and scored by the `Objective`
+//! - The best candidate is replayed on the out-of-sample part, warmed up on
+//!   the in-sample samples so indicators are ready at the cut
+//!
+//! A parameter whose best value jumps around between windows is fitting noise;
+//! `ParameterStability` reports how much each one moved, and the recommended
+//! value is the median choice snapped to the search grid. Medians are taken per
+//! parameter, so the recommended set is validated again as a whole and the
+//! report flags it when the values do not form a valid config.
+//!
+//! Backtests size entries by the regime position multiplier exactly like the
+//! live bot. The ADX entry/exit thresholds only drive the detector's
+//! `is_trading_enabled` hysteresis, which that multiplier does not read, so
+//! they are not searchable: only the ADX period moves scores.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::application::backtest::{BacktestConfig, BacktestReport, Backtester};
+use crate::ports::price::PriceSample;
+use crate::strategy::params::ConfigError;
+use crate::strategy::{AdxConfig, RegimeConfig, StrategyConfig};
+use crate::strategy::regime::RegimeDetectorKind;
+
+/// (in-sample start, out-of-sample start, out-of-sample end)
+type Window = (DateTime<Utc>, DateTime<Utc>, DateTime<Utc>);
+
+#[derive(Debug, Error)]
+pub enum OptimizerError {
+    #[error("Invalid parameter range: {0}")]
+    InvalidRange(String),
+    #[error("Not enough data for one window: need {needed_hours:.1}h, have {available_hours:.1}h")]
+    NotEnoughData { needed_hours: f64, available_hours: f64 },
+    #[error("No valid parameter combination to search")]
+    NoCandidates,
+    #[error("ADX parameters need the adx regime detector")]
+    NoAdxDetector,
+}
+
+/// Parameter the optimizer can search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizeParam {
+    LookbackPeriod,
+    ZThreshold,
+    ZExitThreshold,
+    StopLossPct,
+    TakeProfitPct,
+    AdxPeriod,
+}
+
+impl OptimizeParam {
+    pub const ALL: [OptimizeParam; 6] = [
+        Self::LookbackPeriod,
+        Self::ZThreshold,
+        Self::ZExitThreshold,
+        Self::StopLossPct,
+        Self::TakeProfitPct,
+        Self::AdxPeriod,
+    ];
+
+    /// Name used on the command line and in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptimizeParam::LookbackPeriod => "lookback_period",
+            OptimizeParam::ZThreshold => "z_threshold",
+            OptimizeParam::ZExitThreshold => "z_exit_threshold",
+            OptimizeParam::StopLossPct => "stop_loss_pct",
+            OptimizeParam::TakeProfitPct => "take_profit_pct",
+            OptimizeParam::AdxPeriod => "adx_period",
+        }
+    }
+
+    /// config.toml table and key the parameter is set in
+    pub fn config_key(&self) -> (&'static str, &'static str) {
+        match self {
+            OptimizeParam::LookbackPeriod => ("strategy", "lookback_period"),
+            OptimizeParam::ZThreshold => ("strategy", "z_threshold"),
+            OptimizeParam::ZExitThreshold => ("strategy", "z_exit_threshold"),
+            OptimizeParam::StopLossPct => ("risk", "stop_loss_pct"),
+            OptimizeParam::TakeProfitPct => ("risk", "take_profit_pct"),
+            OptimizeParam::AdxPeriod => ("regime.adx", "period"),
+        }
+    }
+
+    /// Whether values are whole numbers (candle counts)
+    pub fn is_integer(&self) -> bool {
+        matches!(self, OptimizeParam::LookbackPeriod | OptimizeParam::AdxPeriod)
+    }
+
+    fn is_adx(&self) -> bool {
+        matches!(self, OptimizeParam::AdxPeriod)
+    }
+
+    fn apply(&self, value: f64, strategy: &mut StrategyConfig, adx: &mut AdxConfig) {
+        match self {
+            OptimizeParam::LookbackPeriod => strategy.lookback_period = value as usize,
+            OptimizeParam::ZThreshold => strategy.z_threshold = value,
+            OptimizeParam::ZExitThreshold => strategy.z_exit_threshold = value,
+            OptimizeParam::StopLossPct => strategy.risk.stop_loss_pct = value,
+            OptimizeParam::TakeProfitPct => strategy.risk.take_profit_pct = value,
+            OptimizeParam::AdxPeriod => adx.period = value as usize,
+        }
+    }
+
+    /// Format a value the way config.toml expects it
+    fn format_value(&self, value: f64) -> String {
+        if self.is_integer() {
+            format!("{}", value.round() as i64)
+        } else {
+            format!("{:?}", value)
+        }
+    }
+}
+
+impl FromStr for OptimizeParam {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if matches!(s.trim(), "adx_entry_threshold" | "adx_exit_threshold") {
+            return Err(format!(
+                "{} cannot be optimized: it only drives the ADX trading hysteresis, which backtests do not use",
+                s.trim()
+            ));
+        }
+        Self::ALL.into_iter().find(|p| p.name() == s.trim()).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|p| p.name()).collect();
+            format!("unknown parameter \"{}\", expected one of [{}]", s, names.join(", "))
+        })
+    }
+}
+
+/// Inclusive grid `min, min + step, ..., max` for one parameter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamRange {
+    pub param: OptimizeParam,
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+}
+
+impl ParamRange {
+    pub fn new(param: OptimizeParam, min: f64, max: f64, step: f64) -> Self {
+        Self { param, min, max, step }
+    }
+
+    pub fn validate(&self) -> Result<(), OptimizerError> {
+        if !(self.min.is_finite() && self.max.is_finite()) || self.min > self.max {
+            return Err(OptimizerError::InvalidRange(format!(
+                "{}: min {} must be <= max {}",
+                self.param.name(),
+                self.min,
+                self.max
+            )));
+        }
+        if self.step <= 0.0 && self.max > self.min {
+            return Err(OptimizerError::InvalidRange(format!(
+                "{}: step must be > 0, got {}",
+                self.param.name(),
+                self.step
+            )));
+        }
+        Ok(())
+    }
+
+    /// Grid values, rounded to remove float drift from repeated steps
+    pub fn values(&self) -> Vec<f64> {
+        if self.max <= self.min || self.step <= 0.0 {
+            return vec![self.min];
+        }
+        let count = ((self.max - self.min) / self.step + 1e-9).floor() as usize + 1;
+        (0..count)
+            .map(|i| ((self.min + i as f64 * self.step) * 1e9).round() / 1e9)
+            .map(|v| if self.param.is_integer() { v.round() } else { v })
+            .collect()
+    }
+
+    /// Grid value nearest to `value`
+    fn snap(&self, value: f64) -> f64 {
+        self.values()
+            .into_iter()
+            .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+            .unwrap_or(value)
+    }
+}
+
+impl FromStr for ParamRange {
+    type Err = String;
+
+    /// `name=min:max:step`, or `name=value` for a fixed value
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, spec) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=min:max:step, got \"{}\"", s))?;
+        let param: OptimizeParam = name.parse()?;
+        let numbers: Vec<f64> = spec
+            .split(':')
+            .map(|part| part.trim().parse::<f64>().map_err(|e| format!("{}: {}", name, e)))
+            .collect::<Result<_, _>>()?;
+        let range = match numbers[..] {
+            [value] => ParamRange::new(param, value, value, 0.0),
+            [min, max, step] => ParamRange::new(param, min, max, step),
+            _ => return Err(format!("expected {}=min:max:step, got \"{}\"", name, s)),
+        };
+        range.validate().map_err(|e| e.to_string())?;
+        Ok(range)
+    }
+}
+
+/// How candidates are drawn from the parameter grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Every combination of grid values
+    Grid,
+    /// `samples` random combinations of grid values (deterministic for a seed)
+    Random { samples: usize, seed: u64 },
+}
+
+/// What the in-sample search maximizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    /// Total return in percent
+    Return,
+    /// Mean over std dev of per-trade returns, times sqrt(trades)
+    #[default]
+    Sharpe,
+    /// Total return over max drawdown (drawdown floored at 0.1%)
+    ReturnOverDrawdown,
+}
+
+impl Objective {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Objective::Return => "return",
+            Objective::Sharpe => "sharpe",
+            Objective::ReturnOverDrawdown => "return_over_drawdown",
+        }
+    }
+
+    /// Score a backtest report (higher is better)
+    pub fn score(&self, report: &BacktestReport) -> f64 {
+        match self {
+            Objective::Return => report.total_return_pct,
+            Objective::Sharpe => {
+                let n = report.trades.len();
+                if n < 2 {
+                    return 0.0;
+                }
+                let mean = report.trades.iter().map(|t| t.pnl_pct).sum::<f64>() / n as f64;
+                let variance = report.trades.iter().map(|t| (t.pnl_pct - mean).powi(2)).sum::<f64>() / n as f64;
+                if variance <= 0.0 {
+                    return 0.0;
+                }
+                mean / variance.sqrt() * (n as f64).sqrt()
+            }
+            Objective::ReturnOverDrawdown => report.total_return_pct / report.max_drawdown_pct.max(0.1),
+        }
+    }
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Objective::Return, Objective::Sharpe, Objective::ReturnOverDrawdown]
+            .into_iter()
+            .find(|o| o.name() == s.trim())
+            .ok_or_else(|| format!("unknown objective \"{}\", expected return, sharpe or return_over_drawdown", s))
+    }
+}
+
+/// Walk-forward search settings
+#[derive(Debug, Clone)]
+pub struct OptimizerConfig {
+    pub ranges: Vec<ParamRange>,
+    pub search: SearchMode,
+    pub objective: Objective,
+    /// Length of the fitting part of each window
+    pub in_sample: Duration,
+    /// Length of the validation part, and the step between windows
+    pub out_of_sample: Duration,
+    /// In-sample trades a candidate needs to be considered
+    pub min_trades: usize,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            ranges: vec![
+                ParamRange::new(OptimizeParam::LookbackPeriod, 20.0, 80.0, 20.0),
+                ParamRange::new(OptimizeParam::ZThreshold, 1.5, 3.0, 0.5),
+                ParamRange::new(OptimizeParam::ZExitThreshold, 0.0, 0.5, 0.25),
+            ],
+            search: SearchMode::Grid,
+            objective: Objective::Sharpe,
+            in_sample: Duration::from_secs(3 * 86_400),
+            out_of_sample: Duration::from_secs(86_400),
+            min_trades: 5,
+        }
+    }
+}
+
+/// Results of one backtest within a window
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowStats {
+    pub score: f64,
+    pub trades: usize,
+    pub return_pct: f64,
+    pub win_rate: f64,
+    pub max_drawdown_pct: f64,
+}
+
+impl WindowStats {
+    fn new(report: &BacktestReport, objective: Objective) -> Self {
+        Self {
+            score: objective.score(report),
+            trades: report.trades.len(),
+            return_pct: report.total_return_pct,
+            win_rate: report.win_rate,
+            max_drawdown_pct: report.max_drawdown_pct,
+        }
+    }
+}
+
+/// Best in-sample candidate of one window and how it did out of sample
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowResult {
+    pub in_sample_start: DateTime<Utc>,
+    pub out_of_sample_start: DateTime<Utc>,
+    pub out_of_sample_end: DateTime<Utc>,
+    /// Chosen value per parameter name
+    pub params: BTreeMap<String, f64>,
+    /// Candidates with enough in-sample trades
+    pub scored_candidates: usize,
+    pub in_sample: WindowStats,
+    pub out_of_sample: WindowStats,
+}
+
+/// How consistently the windows agreed on one parameter
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterStability {
+    pub param: OptimizeParam,
+    /// Chosen value in each window
+    pub values: Vec<f64>,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// 1.0 = same value in every window, 0.0 = spread over the whole range
+    pub stability: f64,
+    /// Median choice snapped to the grid
+    pub recommended: f64,
+}
+
+/// Walk-forward results
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizationReport {
+    pub objective: Objective,
+    /// Candidate parameter sets searched in each window
+    pub candidates: usize,
+    pub windows: Vec<WindowResult>,
+    /// Windows where no candidate reached `min_trades`
+    pub skipped_windows: usize,
+    pub parameters: Vec<ParameterStability>,
+    /// Out-of-sample returns compounded over all windows
+    pub out_of_sample_return_pct: f64,
+    /// Mean out-of-sample score over mean in-sample score (None if in-sample <= 0)
+    pub efficiency: Option<f64>,
+    /// Why the recommended values are invalid together (None if they form a valid config)
+    pub recommendation_error: Option<String>,
+}
+
+impl OptimizationReport {
+    /// config.toml fragment with the recommended values, grouped by table
+    pub fn config_fragment(&self) -> String {
+        let mut tables: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for stability in &self.parameters {
+            let (table, key) = stability.param.config_key();
+            tables.entry(table).or_default().push(format!(
+                "{} = {}  # stability {:.2}",
+                key,
+                stability.param.format_value(stability.recommended),
+                stability.stability
+            ));
+        }
+
+        let mut fragment = format!(
+            "# Walk-forward recommendation: {} windows, objective {}\n",
+            self.windows.len(),
+            self.objective.name()
+        );
+        if let Some(ref error) = self.recommendation_error {
+            fragment.push_str(&format!("# WARNING: not a valid combination ({}); adjust before use\n", error));
+        }
+        // config.toml order rather than alphabetical
+        for table in ["strategy", "risk", "regime.adx"] {
+            if let Some(lines) = tables.get(table) {
+                fragment.push_str(&format!("\n[{}]\n", table));
+                for line in lines {
+                    fragment.push_str(line);
+                    fragment.push('\n');
+                }
+            }
+        }
+        fragment
+    }
+}
+
+impl fmt::Display for OptimizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.config_fragment())
+    }
+}
+
+/// Walk-forward grid / random search over `StrategyConfig` and ADX settings
+#[derive(Debug, Clone)]
+pub struct WalkForwardOptimizer {
+    strategy_config: StrategyConfig,
+    backtest: BacktestConfig,
+    config: OptimizerConfig,
+    /// Regime filter applied to every backtest, with its candle period
+    regime: Option<(RegimeConfig, Duration)>,
+}
+
+impl WalkForwardOptimizer {
+    /// Search around `strategy_config`; parameters not in a range keep its values
+    pub fn new(strategy_config: StrategyConfig, backtest: BacktestConfig, config: OptimizerConfig) -> Self {
+        Self {
+            strategy_config,
+            backtest,
+            config,
+            regime: None,
+        }
+    }
+
+    /// Apply the regime filter in every backtest (required for ADX parameters)
+    pub fn with_regime(mut self, regime: RegimeConfig, candle_period: Duration) -> Self {
+        self.regime = Some((regime, candle_period));
+        self
+    }
+
+    /// Candidate parameter sets that produce valid configs
+    pub fn candidates(&self) -> Result<Vec<Vec<f64>>, OptimizerError> {
+        for range in &self.config.ranges {
+            range.validate()?;
+        }
+        let grids: Vec<Vec<f64>> = self.config.ranges.iter().map(|r| r.values()).collect();
+
+        let combinations: Vec<Vec<f64>> = match self.config.search {
+            SearchMode::Grid => grids.iter().fold(vec![Vec::new()], |acc, values| {
+                acc.iter()
+                    .flat_map(|prefix| {
+                        values.iter().map(move |&v| {
+                            let mut combination = prefix.clone();
+                            combination.push(v);
+                            combination
+                        })
+                    })
+                    .collect()
+            }),
+            SearchMode::Random { samples, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut drawn: Vec<Vec<f64>> = Vec::new();
+                for _ in 0..samples {
+                    let combination: Vec<f64> =
+                        grids.iter().map(|values| values[rng.gen_range(0..values.len())]).collect();
+                    if !drawn.contains(&combination) {
+                        drawn.push(combination);
+                    }
+                }
+                drawn
+            }
+        };
+
+        let candidates: Vec<Vec<f64>> =
+            combinations.into_iter().filter(|values| self.configs(values).is_ok()).collect();
+        if candidates.is_empty() {
+            return Err(OptimizerError::NoCandidates);
+        }
+        Ok(candidates)
+    }
+
+    /// Run the walk-forward search over time-ordered samples
+    pub fn run(&self, samples: &[PriceSample]) -> Result<OptimizationReport, OptimizerError> {
+        let uses_adx = self.config.ranges.iter().any(|r| r.param.is_adx());
+        let has_adx = self
+            .regime
+            .as_ref()
+            .is_some_and(|(regime, _)| regime.detectors.contains(&RegimeDetectorKind::Adx));
+        if uses_adx && !has_adx {
+            return Err(OptimizerError::NoAdxDetector);
+        }
+
+        let candidates = self.candidates()?;
+        let windows = self.windows(samples)?;
+        let window_count = windows.len();
+        let objective = self.config.objective;
+
+        let mut results = Vec::new();
+        for (in_start, oos_start, oos_end) in windows {
+            let in_sample = slice(samples, in_start, oos_start);
+            let full = slice(samples, in_start, oos_end);
+
+            let mut scored = 0;
+            let mut best: Option<(f64, &Vec<f64>, BacktestReport)> = None;
+            for values in &candidates {
+                let report = self.backtest(values, in_sample, None);
+                if report.trades.len() < self.config.min_trades {
+                    continue;
+                }
+                scored += 1;
+                let score = objective.score(&report);
+                if best.as_ref().is_none_or(|(best_score, _, _)| score > *best_score) {
+                    best = Some((score, values, report));
+                }
+            }
+
+            let Some((_, values, in_report)) = best else {
+                continue;
+            };
+            let oos_report = self.backtest(values, full, Some(oos_start));
+            results.push(WindowResult {
+                in_sample_start: in_start,
+                out_of_sample_start: oos_start,
+                out_of_sample_end: oos_end,
+                params: self
+                    .config
+                    .ranges
+                    .iter()
+                    .zip(values)
+                    .map(|(range, &v)| (range.param.name().to_string(), v))
+                    .collect(),
+                scored_candidates: scored,
+                in_sample: WindowStats::new(&in_report, objective),
+                out_of_sample: WindowStats::new(&oos_report, objective),
+            });
+        }
+
+        let skipped_windows = window_count - results.len();
+        let parameters = self.stability(&results);
+        let recommendation_error = self.check_recommendation(&parameters);
+        let out_of_sample_return_pct = (results
+            .iter()
+            .map(|w| 1.0 + w.out_of_sample.return_pct / 100.0)
+            .product::<f64>()
+            - 1.0)
+            * 100.0;
+        let efficiency = if results.is_empty() {
+            None
+        } else {
+            let n = results.len() as f64;
+            let in_score = results.iter().map(|w| w.in_sample.score).sum::<f64>() / n;
+            let oos_score = results.iter().map(|w| w.out_of_sample.score).sum::<f64>() / n;
+            (in_score > 0.0).then(|| oos_score / in_score)
+        };
+
+        Ok(OptimizationReport {
+            objective,
+            candidates: candidates.len(),
+            windows: results,
+            skipped_windows,
+            parameters,
+            out_of_sample_return_pct,
+            efficiency,
+            recommendation_error,
+        })
+    }
+
+    /// Validate the per-parameter recommendations as one candidate
+    fn check_recommendation(&self, parameters: &[ParameterStability]) -> Option<String> {
+        if parameters.is_empty() {
+            return None;
+        }
+        let values: Vec<f64> = parameters.iter().map(|p| p.recommended).collect();
+        self.configs(&values).err().map(|e| e.to_string())
+    }
+
+    /// Strategy and ADX configs for one candidate, an error if the combination is invalid
+    fn configs(&self, values: &[f64]) -> Result<(StrategyConfig, Option<RegimeConfig>), ConfigError> {
+        let mut strategy = self.strategy_config.clone();
+        let mut regime = self.regime.as_ref().map(|(regime, _)| regime.clone());
+        let mut adx = regime.as_ref().map(|r| r.adx.clone()).unwrap_or_else(AdxConfig::crypto_optimized);
+        for (range, &value) in self.config.ranges.iter().zip(values) {
+            range.param.apply(value, &mut strategy, &mut adx);
+        }
+        strategy.validate()?;
+        if let Some(ref mut regime) = regime {
+            regime.adx = adx;
+            regime.validate()?;
+        }
+        Ok((strategy, regime))
+    }
+
+    /// Backtest a candidate; samples before `warmup_until` only warm it up
+    fn backtest(&self, values: &[f64], samples: &[PriceSample], warmup_until: Option<DateTime<Utc>>) -> BacktestReport {
+        let (strategy, regime) = self.configs(values).expect("candidates are validated");
+        let mut backtester = Backtester::new(strategy, self.backtest.clone());
+        if let (Some(regime), Some((_, period))) = (regime, self.regime.as_ref()) {
+            backtester = backtester.with_regime(regime.build(), *period);
+        }
+        if let Some(until) = warmup_until {
+            backtester = backtester.with_warmup(until);
+        }
+        for sample in samples {
+            backtester.on_sample(sample);
+        }
+        backtester.report()
+    }
+
+    /// Every full window that fits in the samples
+    fn windows(&self, samples: &[PriceSample]) -> Result<Vec<Window>, OptimizerError> {
+        let in_sample = chrono::Duration::from_std(self.config.in_sample).unwrap_or(chrono::Duration::MAX);
+        let out_of_sample = chrono::Duration::from_std(self.config.out_of_sample).unwrap_or(chrono::Duration::MAX);
+        let needed_hours = (self.config.in_sample + self.config.out_of_sample).as_secs_f64() / 3600.0;
+
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return Err(OptimizerError::NotEnoughData { needed_hours, available_hours: 0.0 });
+        };
+
+        let mut windows = Vec::new();
+        let mut start = first.timestamp;
+        while let Some(oos_end) = start.checked_add_signed(in_sample).and_then(|t| t.checked_add_signed(out_of_sample)) {
+            if oos_end > last.timestamp || out_of_sample <= chrono::Duration::zero() {
+                break;
+            }
+            windows.push((start, start + in_sample, oos_end));
+            start += out_of_sample;
+        }
+
+        if windows.is_empty() {
+            let available_hours = (last.timestamp - first.timestamp).num_seconds() as f64 / 3600.0;
+            return Err(OptimizerError::NotEnoughData { needed_hours, available_hours });
+        }
+        Ok(windows)
+    }
+
+    fn stability(&self, windows: &[WindowResult]) -> Vec<ParameterStability> {
+        if windows.is_empty() {
+            return Vec::new();
+        }
+        self.config
+            .ranges
+            .iter()
+            .map(|range| {
+                let values: Vec<f64> = windows.iter().map(|w| w.params[range.param.name()]).collect();
+                let n = values.len() as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+                let half_span = (range.max - range.min) / 2.0;
+                let stability = if half_span > 0.0 {
+                    1.0 - (std_dev / half_span).min(1.0)
+                } else {
+                    1.0
+                };
+
+                let mut sorted = values.clone();
+                sorted.sort_by(f64::total_cmp);
+                let mid = sorted.len() / 2;
+                let median = if sorted.len().is_multiple_of(2) {
+                    (sorted[mid - 1] + sorted[mid]) / 2.0
+                } else {
+                    sorted[mid]
+                };
+
+                ParameterStability {
+                    param: range.param,
+                    values,
+                    mean,
+                    std_dev,
+                    stability,
+                    recommended: range.snap(median),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Samples with `start <= timestamp < end`
+fn slice(samples: &[PriceSample], start: DateTime<Utc>, end: DateTime<Utc>) -> &[PriceSample] {
+    let from = samples.partition_point(|s| s.timestamp < start);
+    let to = samples.partition_point(|s| s.timestamp < end);
+    &samples[from..to]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn strategy_config() -> StrategyConfig {
+        StrategyConfig {
+            lookback_period: 10,
+            z_threshold: 1.5,
+            z_exit_threshold: 0.0,
+            cooldown_seconds: 0,
+            ..Default::default()
+        }
+    }
+
+    /// One day of 1-minute ticks oscillating around 100 with periodic shocks
+    fn samples() -> Vec<PriceSample> {
+        let start = Utc.timestamp_opt(1_768_694_400, 0).unwrap();
+        (0..1_440)
+            .map(|i| {
+                let wave = (i as f64 / 8.0).sin() * 0.4;
+                let shock = if i % 53 == 20 { -1.5 } else if i % 47 == 30 { 1.3 } else { 0.0 };
+                PriceSample {
+                    timestamp: start + chrono::Duration::minutes(i),
+                    mint: "SOL".to_string(),
+                    price: 100.0 + wave + shock,
+                }
+            })
+            .collect()
+    }
+
+    fn optimizer_config() -> OptimizerConfig {
+        OptimizerConfig {
+            ranges: vec![
+                ParamRange::new(OptimizeParam::LookbackPeriod, 10.0, 30.0, 10.0),
+                ParamRange::new(OptimizeParam::ZThreshold, 1.0, 2.0, 0.5),
+            ],
+            in_sample: Duration::from_secs(6 * 3600),
+            out_of_sample: Duration::from_secs(2 * 3600),
+            min_trades: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_param_range_parsing() {
+        let range: ParamRange = "z_threshold=1.5:3.0:0.5".parse().unwrap();
+        assert_eq!(range.values(), vec![1.5, 2.0, 2.5, 3.0]);
+
+        let range: ParamRange = "z_exit_threshold=0.0:0.3:0.1".parse().unwrap();
+        assert_eq!(range.values(), vec![0.0, 0.1, 0.2, 0.3]);
+
+        let fixed: ParamRange = "lookback_period=40".parse().unwrap();
+        assert_eq!(fixed.values(), vec![40.0]);
+        assert_eq!(fixed.snap(55.0), 40.0);
+
+        assert!("z_threshold=3.0:1.5:0.5".parse::<ParamRange>().is_err());
+        assert!("z_threshold=1.5:3.0:0".parse::<ParamRange>().is_err());
+        assert!("zscore=1:2:1".parse::<ParamRange>().is_err());
+        let err = "adx_exit_threshold=25:35:5".parse::<ParamRange>().unwrap_err();
+        assert!(err.contains("cannot be optimized"));
+        assert!("z_threshold".parse::<ParamRange>().is_err());
+        assert_eq!("sharpe".parse::<Objective>(), Ok(Objective::Sharpe));
+    }
+
+    #[test]
+    fn test_candidates_skip_invalid_combinations() {
+        let config = OptimizerConfig {
+            ranges: vec![
+                ParamRange::new(OptimizeParam::ZThreshold, 0.5, 1.5, 0.5),
+                ParamRange::new(OptimizeParam::ZExitThreshold, 0.0, 1.0, 0.5),
+            ],
+            ..Default::default()
+        };
+        let optimizer = WalkForwardOptimizer::new(strategy_config(), BacktestConfig::default(), config.clone());
+        // z_exit_threshold must stay below z_threshold: 1 + 2 + 3 of the 9 combinations are valid
+        let candidates = optimizer.candidates().unwrap();
+        assert_eq!(candidates.len(), 6);
+        assert!(candidates.iter().all(|c| c[1] < c[0]));
+
+        let random = OptimizerConfig { search: SearchMode::Random { samples: 20, seed: 7 }, ..config };
+        let optimizer = WalkForwardOptimizer::new(strategy_config(), BacktestConfig::default(), random);
+        let drawn = optimizer.candidates().unwrap();
+        assert!(!drawn.is_empty() && drawn.len() <= 6);
+        assert_eq!(drawn, optimizer.candidates().unwrap());
+    }
+
+    #[test]
+    fn test_walk_forward_windows_and_report() {
+        let data = samples();
+        let optimizer = WalkForwardOptimizer::new(strategy_config(), BacktestConfig::default(), optimizer_config());
+        let report = optimizer.run(&data).unwrap();
+
+        // 24h of data (last tick at 23:59), 6h + 2h windows stepping by 2h
+        assert_eq!(report.windows.len() + report.skipped_windows, 8);
+        assert!(!report.windows.is_empty());
+        assert_eq!(report.candidates, 9);
+        for window in &report.windows {
+            assert_eq!(window.out_of_sample_start - window.in_sample_start, chrono::Duration::hours(6));
+            assert_eq!(window.out_of_sample_end - window.out_of_sample_start, chrono::Duration::hours(2));
+            assert!(window.in_sample.trades >= 1);
+            assert!(window.scored_candidates <= report.candidates);
+        }
+
+        assert_eq!(report.parameters.len(), 2);
+        for stability in &report.parameters {
+            assert_eq!(stability.values.len(), report.windows.len());
+            assert!((0.0..=1.0).contains(&stability.stability));
+        }
+        let lookback = &report.parameters[0];
+        assert!([10.0, 20.0, 30.0].contains(&lookback.recommended));
+
+        let fragment = report.config_fragment();
+        assert!(fragment.contains("[strategy]\n"));
+        assert!(fragment.contains(&format!("lookback_period = {}  #", lookback.recommended as i64)));
+        assert!(!fragment.contains("[risk]"));
+        let _: toml::Value = toml::from_str(&fragment).unwrap();
+
+        // Deterministic for the same data
+        let again = optimizer.run(&data).unwrap();
+        assert_eq!(again.out_of_sample_return_pct, report.out_of_sample_return_pct);
+    }
+
+    #[test]
+    fn test_recommendation_validated_as_a_whole() {
+        let config = OptimizerConfig {
+            ranges: vec![
+                ParamRange::new(OptimizeParam::ZThreshold, 0.5, 1.5, 0.5),
+                ParamRange::new(OptimizeParam::ZExitThreshold, 0.0, 1.0, 0.5),
+            ],
+            ..Default::default()
+        };
+        let optimizer = WalkForwardOptimizer::new(strategy_config(), BacktestConfig::default(), config);
+        let stability = |param, values: Vec<f64>, recommended| ParameterStability {
+            param,
+            values,
+            mean: recommended,
+            std_dev: 0.0,
+            stability: 1.0,
+            recommended,
+        };
+
+        // Windows chose (1.0, 0.5), (1.0, 0.5), (1.5, 1.0), (1.5, 1.0): each median alone
+        // is on the grid, but exit 1.0 with entry 1.0 never passes validation
+        let mut parameters = vec![
+            stability(OptimizeParam::ZThreshold, vec![1.0, 1.0, 1.5, 1.5], 1.0),
+            stability(OptimizeParam::ZExitThreshold, vec![0.5, 0.5, 1.0, 1.0], 1.0),
+        ];
+        let error = optimizer.check_recommendation(&parameters).unwrap();
+        assert!(error.contains("exit"), "{}", error);
+
+        let mut report = OptimizationReport {
+            objective: Objective::Sharpe,
+            candidates: 6,
+            windows: Vec::new(),
+            skipped_windows: 0,
+            parameters: parameters.clone(),
+            out_of_sample_return_pct: 0.0,
+            efficiency: None,
+            recommendation_error: Some(error),
+        };
+        assert!(report.config_fragment().contains("# WARNING: not a valid combination"));
+        let _: toml::Value = toml::from_str(&report.config_fragment()).unwrap();
+
+        parameters[1].recommended = 0.5;
+        assert!(optimizer.check_recommendation(&parameters).is_none());
+        report.recommendation_error = None;
+        assert!(!report.config_fragment().contains("WARNING"));
+    }
+
+    #[test]
+    fn test_adx_params_need_regime() {
+        let mut config = optimizer_config();
+        config.ranges.push(ParamRange::new(OptimizeParam::AdxPeriod, 10.0, 20.0, 5.0));
+        let optimizer = WalkForwardOptimizer::new(strategy_config(), BacktestConfig::default(), config);
+        assert!(matches!(optimizer.run(&samples()), Err(OptimizerError::NoAdxDetector)));
+
+        let optimizer = optimizer.with_regime(RegimeConfig::default(), Duration::from_secs(60));
+        let report = optimizer.run(&samples()).unwrap();
+        assert_eq!(report.candidates, 27);
+        assert!(report.config_fragment().contains("[regime.adx]\nperiod = "));
+
+        let short = &samples()[..300];
+        assert!(matches!(optimizer.run(short), Err(OptimizerError::NotEnoughData { .. })));
+    }
+}
@@ -1,23 +1,42 @@
 //! Trading Orchestrator
 //!
-//! Coordinates the mean reversion strategy with Jupiter execution.
+//! Coordinates the configured strategy with Jupiter execution.
 //! Main trading loop that fetches prices, updates strategy, and executes trades.
+//!
+//! With a state file (`with_state_file`) the strategy snapshot and the open
+//! size are saved after every tick and resumed on the next `run`, so a
+//! restart keeps the open position and its trailing stop.
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use thiserror::Error;
+use serde::{Deserialize, Serialize};
 use base64::Engine;
 use solana_sdk::transaction::VersionedTransaction;
 
 use crate::strategy::{
-    MeanReversionStrategy, StrategyConfig, TradeAction, PositionState,
+    Strategy, StrategyConfig, StrategySnapshot, TradeAction, PositionState, KalmanState, TimeframeState, build_strategy,
     AdxRegimeDetector, AdxConfig, CandleBuilder, RegimeDetector,
+    CompositeRegimeDetector, RegimeComponent, VolatilityRegimeDetector, VolatilityState,
+    PositionSizer, SizingConfig, SizingDecision, SizingInputs,
 };
-use crate::adapters::jupiter::{JupiterClient, QuoteRequest, SwapRequest};
+use crate::strategy::regime::Candle;
+use crate::strategy::regime::{CombineMode, TrendRegime};
+use crate::adapters::jupiter::{JupiterClient, QuoteRequest, RequestPriority, SwapRequest};
+use crate::adapters::market_data::{JupiterQuotePriceSource, QuoteSummary, RecordedEvent, SharedRecorder};
+use crate::adapters::market_data::recorder::record_shared;
 use crate::adapters::solana::{SolanaClient, WalletManager};
+use crate::application::paper_portfolio::PaperPortfolio;
+use crate::application::state_file::write_atomic;
+use crate::meme::paper_trading::TradeSide;
+use crate::ports::perp::PerpPort;
+use crate::ports::price::PricePort;
+use crate::domain::position::Side;
 use crate::domain::{
-    BalanceGuard, ExpectedDelta,
+    BalanceGuard, ExpectedDelta, Inventory, InventoryConfig, PairRiskBook,
     TransactionValidator,
 };
 
@@ -33,42 +52,98 @@ pub enum OrchestratorError {
     WalletError(String),
     #[error("Security violation: {0}")]
     SecurityViolation(String),
+    #[error("Inventory error: {0}")]
+    InventoryError(String),
+    #[error("Risk limit exceeded: {0}")]
+    RiskLimitExceeded(String),
+    #[error("State file error: {0}")]
+    StateError(String),
 }
 
 /// Main trading orchestrator that coordinates strategy and execution
 pub struct TradingOrchestrator {
-    strategy: Arc<RwLock<MeanReversionStrategy>>,
+    strategy: Arc<RwLock<Box<dyn Strategy>>>,
     jupiter: JupiterClient,
+    /// Source of price ticks (live Jupiter quotes by default)
+    price_source: Arc<dyn PricePort>,
+    /// Optional recorder for execution quotes
+    recorder: Option<SharedRecorder>,
     solana: SolanaClient,
     wallet: WalletManager,
+    /// Pair label for logs and the shared risk book (e.g. "SOL/USDC")
+    symbol: String,
     base_mint: String,
     quote_mint: String,
+    base_decimals: u8,
+    quote_decimals: u8,
     slippage_bps: u16,
     is_running: Arc<RwLock<bool>>,
     paper_mode: bool,
+    /// Virtual wallet paper trades fill against; without one they are only logged
+    paper_portfolio: Option<Arc<RwLock<PaperPortfolio>>>,
+    /// Where the strategy snapshot and open size are saved after every tick
+    state_file: Option<PathBuf>,
     poll_interval: Duration,
     /// Trade size in SOL (e.g., 0.1 = trade 0.1 SOL per signal)
+    /// Used when equity sizing is disabled or wallet equity is unavailable
     trade_size_sol: f64,
+    /// Maximum position size as percentage of equity (`RiskConfig::max_position_pct`)
+    max_position_pct: f64,
+    /// Equity-based position sizer (fixed `trade_size_sol` unless enabled)
+    sizer: Arc<RwLock<PositionSizer>>,
+    /// Scale-in leg size relative to the initial entry (`ScalingConfig::scale_in_size`)
+    scale_in_size: f64,
+    /// Size of the open position in SOL, so exits unwind what was entered
+    open_size: Arc<RwLock<Option<OpenSize>>>,
+    /// Latest entry sizing decision
+    last_sizing: Arc<RwLock<Option<SizingDecision>>>,
+    /// Base inventory against the neutral allocation, decides where shorts go
+    inventory: Inventory,
+    /// Perp venue for shorts when `ShortMode::Perp`
+    perp: Option<Arc<dyn PerpPort>>,
+    /// Exposure and daily loss shared with the other pairs on this wallet
+    risk_book: Option<Arc<RwLock<PairRiskBook>>>,
+    /// Held while a swap executes, shared by pairs on the same wallet so
+    /// balance guard snapshots only ever see one trade
+    trade_lock: Arc<tokio::sync::Mutex<()>>,
     /// Priority fee in lamports for faster transaction inclusion
     priority_fee_lamports: u64,
     balance_guard: Arc<RwLock<BalanceGuard>>,
     tx_validator: TransactionValidator,
-    /// ADX regime detector for filtering trending markets
-    adx_detector: Arc<RwLock<AdxRegimeDetector>>,
+    /// Regime detectors (ADX by default) filtering markets unfit for the strategy
+    regime_detector: Arc<RwLock<CompositeRegimeDetector>>,
+    /// Realized volatility detector scaling position size and stop distances (optional)
+    volatility_detector: Arc<RwLock<Option<VolatilityRegimeDetector>>>,
     /// Candle builder to create OHLC from price ticks
     candle_builder: Arc<RwLock<CandleBuilder>>,
-    /// Position multiplier from ADX regime detection (0.0-1.0)
+    /// Candle builder at the strategy timeframe - z-scores only see closed candles
+    strategy_candle_builder: Arc<RwLock<CandleBuilder>>,
+    /// Position multiplier from regime detection (0.0-1.0)
     /// During warmup this defaults to WARMUP_MULTIPLIER for cautious trading
     regime_multiplier: Arc<RwLock<f64>>,
 }
 
-/// Position multiplier during ADX warmup (trade cautiously until ADX is ready)
-const WARMUP_MULTIPLIER: f64 = 0.5;
+/// Position multiplier during regime warmup (trade cautiously until a detector is ready)
+pub const WARMUP_MULTIPLIER: f64 = 0.5;
+
+/// Default strategy candle timeframe when none is configured
+const DEFAULT_TIMEFRAME: Duration = Duration::from_secs(60);
+
+/// Native SOL mint
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// SOL decimals (default base token)
+const SOL_DECIMALS: u8 = 9;
+/// USDC decimals (default quote token)
+const USDC_DECIMALS: u8 = 6;
 
 /// Status snapshot of the orchestrator
 #[derive(Debug, Clone)]
 pub struct OrchestratorStatus {
     pub is_running: bool,
+    /// Pair traded, e.g. "SOL/USDC"
+    pub symbol: String,
+    /// Name of the running strategy
+    pub strategy: String,
     pub position: String,  // "Flat", "Long", "Short"
     pub daily_trades: u32,
     pub daily_pnl_pct: f64,
@@ -76,12 +151,24 @@ pub struct OrchestratorStatus {
     pub current_zscore: Option<f64>,
     /// Current ADX value (0-100), None if warming up
     pub adx_value: Option<f64>,
-    /// Current trend regime from ADX
+    /// Current trend regime from ADX (Unknown when ADX is not configured)
     pub trend_regime: String,
     /// Position size multiplier from regime detection (0.0-1.0)
     pub regime_multiplier: f64,
-    /// Whether ADX has enough data
+    /// Whether any regime detector has enough data
     pub adx_ready: bool,
+    /// Per-detector regime state
+    pub regime_components: Vec<RegimeComponent>,
+    /// Realized volatility state, None unless the volatility detector is enabled and ready
+    pub volatility: Option<VolatilityState>,
+    /// Strategy indicator values (z-score, RSI, MACD, moving averages, ...)
+    pub indicators: BTreeMap<String, f64>,
+    /// Kalman fair value filter state, None unless the Kalman estimator is selected
+    pub kalman: Option<KalmanState>,
+    /// Multi-timeframe entry confirmation state, empty when no rules are configured
+    pub timeframes: Vec<TimeframeState>,
+    /// Latest entry sizing decision, None before the first entry
+    pub sizing: Option<SizingDecision>,
 }
 
 impl TradingOrchestrator {
@@ -98,38 +185,138 @@ impl TradingOrchestrator {
         trade_size_sol: f64,
         priority_fee_lamports: u64,
     ) -> Result<Self, OrchestratorError> {
-        let strategy = MeanReversionStrategy::new(strategy_config);
+        let strategy = build_strategy(&strategy_config);
+        let max_position_pct = strategy_config.risk.max_position_pct;
+        let scale_in_size = strategy_config.scaling.scale_in_size;
 
         // Initialize ADX with crypto-optimized settings (period=10, faster response)
-        let adx_detector = AdxRegimeDetector::new(AdxConfig::crypto_optimized());
+        let regime_detector = adx_only(AdxConfig::crypto_optimized());
 
         // Build 1-minute candles from price ticks for ADX
         let candle_builder = CandleBuilder::one_minute();
 
+        let price_source = JupiterQuotePriceSource::new(
+            jupiter.clone(),
+            base_mint.clone(),
+            quote_mint.clone(),
+            slippage_bps,
+        );
+
         Ok(Self {
             strategy: Arc::new(RwLock::new(strategy)),
             jupiter,
+            price_source: Arc::new(price_source),
+            recorder: None,
             solana,
             wallet: wallet.clone(),
+            symbol: "SOL".to_string(),
             base_mint,
             quote_mint,
+            base_decimals: SOL_DECIMALS,
+            quote_decimals: USDC_DECIMALS,
             slippage_bps,
             is_running: Arc::new(RwLock::new(false)),
             paper_mode,
+            paper_portfolio: None,
+            state_file: None,
             poll_interval: Duration::from_secs(15), // 15 second poll to avoid API rate limits
             trade_size_sol,
+            max_position_pct,
+            sizer: Arc::new(RwLock::new(PositionSizer::new(SizingConfig::default()))),
+            scale_in_size,
+            open_size: Arc::new(RwLock::new(None)),
+            last_sizing: Arc::new(RwLock::new(None)),
+            inventory: Inventory::default(),
+            perp: None,
+            risk_book: None,
+            trade_lock: Arc::new(tokio::sync::Mutex::new(())),
             priority_fee_lamports,
             balance_guard: Arc::new(RwLock::new(BalanceGuard::new(wallet.pubkey()))),
             tx_validator: TransactionValidator::new(wallet.pubkey()),
-            adx_detector: Arc::new(RwLock::new(adx_detector)),
+            regime_detector: Arc::new(RwLock::new(regime_detector)),
+            volatility_detector: Arc::new(RwLock::new(None)),
             candle_builder: Arc::new(RwLock::new(candle_builder)),
+            strategy_candle_builder: Arc::new(RwLock::new(CandleBuilder::new(DEFAULT_TIMEFRAME))),
             regime_multiplier: Arc::new(RwLock::new(WARMUP_MULTIPLIER)), // Start with cautious trading
         })
     }
 
-    /// Create with custom ADX configuration
+    /// Run a custom strategy instead of the one selected by `StrategyConfig::kind`
+    pub fn with_strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
+        self.strategy = Arc::new(RwLock::new(strategy));
+        self
+    }
+
+    /// Create with custom ADX configuration (replaces any configured regime detectors)
     pub fn with_adx_config(mut self, config: AdxConfig) -> Self {
-        self.adx_detector = Arc::new(RwLock::new(AdxRegimeDetector::new(config)));
+        self.regime_detector = Arc::new(RwLock::new(adx_only(config)));
+        self
+    }
+
+    /// Use a custom set of regime detectors (e.g. `RegimeConfig::build`)
+    pub fn with_regime_detector(mut self, detector: CompositeRegimeDetector) -> Self {
+        self.regime_detector = Arc::new(RwLock::new(detector));
+        self
+    }
+
+    /// Scale position size and stop distances by realized volatility (None disables)
+    pub fn with_volatility_detector(mut self, detector: Option<VolatilityRegimeDetector>) -> Self {
+        self.volatility_detector = Arc::new(RwLock::new(detector));
+        self
+    }
+
+    /// Size entries from wallet equity (`SizingConfig::enabled`), else fixed `trade_size_sol`
+    pub fn with_sizing(mut self, config: SizingConfig) -> Self {
+        self.sizer = Arc::new(RwLock::new(PositionSizer::new(config)));
+        self
+    }
+
+    /// Check shorts against a neutral base allocation, or route them to a perp venue
+    pub fn with_inventory(mut self, config: InventoryConfig) -> Self {
+        self.inventory = Inventory::new(config);
+        self
+    }
+
+    /// Perp venue for shorts (used with `short_mode = "perp"`)
+    pub fn with_perp(mut self, perp: Arc<dyn PerpPort>) -> Self {
+        self.perp = Some(perp);
+        self
+    }
+
+    /// Label logs and the shared risk book with this pair name
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = symbol.into();
+        self
+    }
+
+    /// Token decimals for non SOL/USDC pairs
+    /// Rebuilds the default quote price source, so call before `with_price_source`
+    pub fn with_decimals(mut self, base_decimals: u8, quote_decimals: u8) -> Self {
+        self.base_decimals = base_decimals;
+        self.quote_decimals = quote_decimals;
+        self.price_source = Arc::new(
+            JupiterQuotePriceSource::new(
+                self.jupiter.clone(),
+                self.base_mint.clone(),
+                self.quote_mint.clone(),
+                self.slippage_bps,
+            )
+            .with_decimals(base_decimals, quote_decimals),
+        );
+        self
+    }
+
+    /// Check entries against exposure and daily loss shared with other pairs
+    pub fn with_risk_book(mut self, risk_book: Arc<RwLock<PairRiskBook>>) -> Self {
+        self.risk_book = Some(risk_book);
+        self
+    }
+
+    /// Trade from the same wallet as `other`: share its balance guard and
+    /// execute swaps one at a time
+    pub fn with_shared_wallet(mut self, other: &TradingOrchestrator) -> Self {
+        self.balance_guard = Arc::clone(&other.balance_guard);
+        self.trade_lock = Arc::clone(&other.trade_lock);
         self
     }
 
@@ -139,6 +326,36 @@ impl TradingOrchestrator {
         self
     }
 
+    /// Set the candle timeframe the strategy z-score is computed on
+    pub fn with_timeframe(mut self, timeframe: Duration) -> Self {
+        self.strategy_candle_builder = Arc::new(RwLock::new(CandleBuilder::new(timeframe)));
+        self
+    }
+
+    /// Use a custom price source (e.g. a recording-enabled quote source or a replay)
+    pub fn with_price_source(mut self, price_source: Arc<dyn PricePort>) -> Self {
+        self.price_source = price_source;
+        self
+    }
+
+    /// Record quote summaries of executed trades
+    pub fn with_recorder(mut self, recorder: SharedRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Fill paper trades against a virtual wallet at live quotes (required in paper mode)
+    pub fn with_paper_portfolio(mut self, portfolio: PaperPortfolio) -> Self {
+        self.paper_portfolio = Some(Arc::new(RwLock::new(portfolio)));
+        self
+    }
+
+    /// Save the position and its stops to `path` after every tick and resume them on `run`
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
     /// Set custom poll interval
     pub fn with_poll_interval(mut self, interval: Duration) -> Self {
         self.poll_interval = interval;
@@ -150,45 +367,134 @@ impl TradingOrchestrator {
         *self.is_running.write().await = true;
 
         tracing::info!(
-            "Starting trading orchestrator - Paper mode: {}, Poll interval: {:?}",
+            "Starting trading orchestrator - Strategy: {}, Paper mode: {}, Poll interval: {:?}, Timeframe: {:?}",
+            self.strategy.read().await.name(),
             self.paper_mode,
-            self.poll_interval
+            self.poll_interval,
+            self.strategy_candle_builder.read().await.period()
         );
 
+        self.restore_state().await?;
+
         while *self.is_running.read().await {
             if let Err(e) = self.tick().await {
                 tracing::error!("Tick error: {}", e);
                 // Continue running despite errors
             }
+            if let Err(e) = self.save_state().await {
+                tracing::warn!("Failed to save strategy state: {}", e);
+            }
             tokio::time::sleep(self.poll_interval).await;
         }
 
+        if let Some(portfolio) = &self.paper_portfolio {
+            let portfolio = portfolio.read().await;
+            if let Some(price) = portfolio.last_price() {
+                portfolio.log_summary(price);
+            }
+        }
+
         tracing::info!("Trading orchestrator stopped");
         Ok(())
     }
 
+    /// Save the strategy snapshot and open size to the state file (no-op without one)
+    pub async fn save_state(&self) -> Result<(), OrchestratorError> {
+        let Some(path) = &self.state_file else {
+            return Ok(());
+        };
+        let state = SavedState {
+            strategy: self.strategy.read().await.snapshot(),
+            open_size: *self.open_size.read().await,
+        };
+        let json = serde_json::to_string_pretty(&state).map_err(|e| OrchestratorError::StateError(e.to_string()))?;
+
+        // Written off the async runtime and renamed into place, so a crash
+        // mid-write cannot leave a truncated file that blocks the next start
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || {
+            write_atomic(&path, json.as_bytes())
+                .map_err(|e| OrchestratorError::StateError(format!("{}: {}", path.display(), e)))
+        })
+        .await
+        .map_err(|e| OrchestratorError::StateError(e.to_string()))?
+    }
+
+    /// Resume the position saved in the state file, returns whether one was resumed
+    ///
+    /// A state saved by a different strategy kind is ignored with a warning.
+    pub async fn restore_state(&self) -> Result<bool, OrchestratorError> {
+        let Some(state) = self.state_file.as_deref().map(SavedState::load).transpose()?.flatten() else {
+            return Ok(false);
+        };
+
+        let mut strategy = self.strategy.write().await;
+        if state.strategy.name != strategy.name() {
+            tracing::warn!(
+                "{}: saved state is from strategy {}, now running {} - starting flat",
+                self.symbol,
+                state.strategy.name,
+                strategy.name()
+            );
+            return Ok(false);
+        }
+        strategy.restore(&state.strategy);
+        *self.open_size.write().await = state.open_size;
+        if let Some(open) = state.open_size.filter(|open| open.unit_sol > 0.0) {
+            // Put what is still open back into the shared risk book
+            self.book_exposure(open.unit_exposure_pct * open.remaining_sol / open.unit_sol).await;
+        }
+        tracing::info!(
+            "{}: resumed {:?} from saved state ({} trades today)",
+            self.symbol,
+            state.strategy.position,
+            state.strategy.daily_trades
+        );
+        Ok(true)
+    }
+
     /// Execute one trading cycle
     pub async fn tick(&self) -> Result<(), OrchestratorError> {
         // 1. Fetch current price (use Jupiter quote for now)
         let price = self.fetch_price().await?;
 
         // 2. Update candle builder and ADX regime detection
-        let (adx_value, adx_ready, regime) = self.update_regime_detection(price).await;
+        let (adx_value, adx_ready, regime, vol_note) = self.update_regime_detection(price).await;
 
         // 3. Get current regime multiplier (graceful degradation during warmup)
         let multiplier = *self.regime_multiplier.read().await;
 
         // 4. Get action from strategy (does NOT update state yet)
+        // Entries and z-score exits are evaluated on closed candles of the
+        // configured timeframe; SL/TP/time stops are checked on every tick
+        let closed_candle = {
+            let mut builder = self.strategy_candle_builder.write().await;
+            builder.update(price)
+        };
         let action = {
             let mut strategy = self.strategy.write().await;
-            strategy.update(price)
+            strategy.update_tick(price, closed_candle.as_ref())
         };
 
-        // 5. Get z-score for logging
-        let z_score = {
-            let strategy = self.strategy.read().await;
-            strategy.current_zscore().map(|z| z.z_score).unwrap_or(0.0)
+        // 5. Get the strategy signal (z-score) for logging
+        let (z_score, rsi, kalman, timeframes, ensemble) = {
+            let snapshot = self.strategy.read().await.snapshot();
+            (
+                snapshot.signal.unwrap_or(0.0),
+                snapshot.indicators.get("rsi").copied(),
+                snapshot.kalman,
+                snapshot.timeframes,
+                snapshot.ensemble,
+            )
         };
+        let rsi_note = rsi.map(|r| format!(" | RSI: {:.1}", r)).unwrap_or_default();
+        let kalman_note = kalman
+            .map(|k| format!(" | Fair: ${:.2} (K={:.3})", k.fair_value, k.gain))
+            .unwrap_or_default();
+        let timeframe_note: String = timeframes.iter().map(timeframe_note).collect();
+        let ensemble_note = ensemble
+            .map(|tally| format!(" | Votes: {}", tally))
+            .unwrap_or_default();
 
         // 6. Execute if action needed, respecting regime filter
         if let Some(action) = action {
@@ -197,30 +503,64 @@ impl TradingOrchestrator {
                     // For entries, check if regime allows trading
                     if multiplier <= 0.0 {
                         tracing::info!(
-                            "SOL ${:.2} | Z: {:.2} | ADX: {:.1} ({}) | BLOCKED by regime (multiplier=0)",
-                            price, z_score,
+                            "{} ${:.2} | Z: {:.2} | ADX: {:.1} ({}){} | BLOCKED by regime (multiplier=0)",
+                            self.symbol, price, z_score,
                             adx_value.unwrap_or(0.0),
-                            regime
+                            regime,
+                            vol_note
+                        );
+                        return Ok(());
+                    }
+
+                    // Size the entry from equity, volatility, the regime multiplier and
+                    // the strategy's own confidence (OU fit)
+                    let multiplier = multiplier * self.strategy.read().await.size_multiplier();
+                    let (sizing, equity) = self.size_entry(price, multiplier).await;
+                    *self.last_sizing.write().await = Some(sizing.clone());
+                    if sizing.size_sol <= 0.0 {
+                        tracing::info!(
+                            "{} ${:.2} | Z: {:.2} | {:?} SKIPPED by sizing: {}",
+                            self.symbol, price, z_score, action, sizing.reason
                         );
                         return Ok(());
                     }
 
                     // Log the trade attempt with regime info
-                    let warmup_note = if adx_ready { "" } else { " [ADX warming up]" };
+                    let warmup_note = if adx_ready { "" } else { " [regime warming up]" };
                     tracing::info!(
-                        "SOL ${:.2} | Z: {:.2} | ADX: {:.1} ({}) | {:?} (size x{:.0}%){}",
-                        price, z_score,
+                        "{} ${:.2} | Z: {:.2}{} | ADX: {:.1} ({}){} | {:?} {:.4} SOL (size x{:.0}%){}",
+                        self.symbol, price, z_score, ensemble_note,
                         adx_value.unwrap_or(0.0),
                         regime,
+                        vol_note,
                         action,
+                        sizing.size_sol,
                         multiplier * 100.0,
                         warmup_note
                     );
+                    tracing::info!("Sizing: {}", sizing.reason);
+
+                    // Exposure in percent of equity, for limits shared with other pairs
+                    let exposure_pct =
+                        exposure_pct(sizing.size_sol, price, equity, self.max_position_pct * multiplier);
+                    if let Err(e) = self.check_portfolio(exposure_pct).await {
+                        tracing::info!("{} ${:.2} | Z: {:.2} | {:?} SKIPPED: {}", self.symbol, price, z_score, action, e);
+                        return Ok(());
+                    }
+
+                    if action == TradeAction::EnterShort {
+                        if let Err(e) = self.check_short_inventory(sizing.size_sol).await {
+                            tracing::info!("{} ${:.2} | Z: {:.2} | EnterShort SKIPPED: {}", self.symbol, price, z_score, e);
+                            return Ok(());
+                        }
+                    }
 
                     // Execute the trade
-                    match self.execute_trade(&action, price).await {
+                    match self.execute_trade(&action, price, sizing.size_sol).await {
                         Ok(()) => {
                             // Trade succeeded - NOW update strategy state
+                            *self.open_size.write().await = Some(OpenSize::new(sizing.size_sol, exposure_pct));
+                            self.book_exposure(exposure_pct).await;
                             let mut strategy = self.strategy.write().await;
                             strategy.confirm_trade(action, price);
                             tracing::info!("Trade confirmed, strategy state updated");
@@ -234,19 +574,98 @@ impl TradingOrchestrator {
                         }
                     }
                 }
+                TradeAction::ScaleIn => {
+                    // Adding to a position is an entry, so the regime filter applies
+                    if multiplier <= 0.0 {
+                        tracing::info!(
+                            "{} ${:.2} | Z: {:.2} | ADX: {:.1} ({}){} | SCALE-IN BLOCKED by regime (multiplier=0)",
+                            self.symbol, price, z_score,
+                            adx_value.unwrap_or(0.0),
+                            regime,
+                            vol_note
+                        );
+                        return Ok(());
+                    }
+
+                    // Legs are sized from the initial entry, not re-sized from equity
+                    let unit = self.open_size.read().await.unwrap_or_else(|| self.assumed_open_size());
+                    let size_sol = unit.unit_sol * self.scale_in_size;
+                    let equity = match self.risk_book {
+                        Some(_) => self.wallet_equity(price).await.ok(),
+                        None => None,
+                    };
+                    let exposure_pct =
+                        exposure_pct(size_sol, price, equity, unit.unit_exposure_pct * self.scale_in_size);
+                    if let Err(e) = self.check_portfolio(exposure_pct).await {
+                        tracing::info!("{} ${:.2} | Z: {:.2} | ScaleIn SKIPPED: {}", self.symbol, price, z_score, e);
+                        return Ok(());
+                    }
+                    if matches!(self.strategy.read().await.position(), PositionState::Short { .. }) {
+                        if let Err(e) = self.check_short_inventory(size_sol).await {
+                            tracing::info!("{} ${:.2} | Z: {:.2} | ScaleIn SKIPPED: {}", self.symbol, price, z_score, e);
+                            return Ok(());
+                        }
+                    }
+                    tracing::info!(
+                        "{} ${:.2} | Z: {:.2} | ADX: {:.1} ({}){} | SCALE-IN {:.4} SOL",
+                        self.symbol, price, z_score,
+                        adx_value.unwrap_or(0.0),
+                        regime,
+                        vol_note,
+                        size_sol
+                    );
+
+                    match self.execute_trade(&action, price, size_sol).await {
+                        Ok(()) => {
+                            let open_sol = self.confirm_scale_in(price, size_sol, exposure_pct).await;
+                            tracing::info!("Scale-in confirmed, {:.4} SOL open", open_sol);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Scale-in trade failed: {}", e);
+                            return Err(e);
+                        }
+                    }
+                }
+                TradeAction::PartialExit { fraction } => {
+                    // Take profit on part of the position regardless of regime
+                    let remaining_sol = self.open_size.read().await.map_or(self.trade_size_sol, |open| open.remaining_sol);
+                    let size_sol = remaining_sol * fraction;
+                    tracing::info!(
+                        "{} ${:.2} | Z: {:.2} | ADX: {:.1} ({}){} | PARTIAL EXIT {:.0}% ({:.4} SOL)",
+                        self.symbol, price, z_score,
+                        adx_value.unwrap_or(0.0),
+                        regime,
+                        vol_note,
+                        fraction * 100.0,
+                        size_sol
+                    );
+
+                    match self.execute_trade(&action, price, size_sol).await {
+                        Ok(()) => {
+                            self.confirm_partial_exit(fraction, price, size_sol).await;
+                            tracing::info!("Partial exit confirmed");
+                        }
+                        Err(e) => {
+                            tracing::warn!("Partial exit failed - will retry on next tick: {}", e);
+                        }
+                    }
+                }
                 TradeAction::Exit => {
                     // Always allow exits regardless of regime
                     tracing::info!(
-                        "SOL ${:.2} | Z: {:.2} | ADX: {:.1} ({}) | EXIT",
-                        price, z_score,
+                        "{} ${:.2} | Z: {:.2} | ADX: {:.1} ({}){} | EXIT",
+                        self.symbol, price, z_score,
                         adx_value.unwrap_or(0.0),
-                        regime
+                        regime,
+                        vol_note
                     );
 
-                    match self.execute_trade(&action, price).await {
+                    // Unwind what is still open (fixed size when the entry size is unknown,
+                    // e.g. a position resumed from a state file saved without one)
+                    let size_sol = self.open_size.read().await.map_or(self.trade_size_sol, |open| open.remaining_sol);
+                    match self.execute_trade(&action, price, size_sol).await {
                         Ok(()) => {
-                            let mut strategy = self.strategy.write().await;
-                            strategy.confirm_trade(action, price);
+                            self.confirm_exit(price).await;
                             tracing::info!("Exit confirmed, position closed");
                         }
                         Err(e) => {
@@ -256,23 +675,25 @@ impl TradingOrchestrator {
                     }
                 }
                 TradeAction::Hold => {
-                    let warmup_note = if adx_ready { "" } else { " [ADX warming up]" };
+                    let warmup_note = if adx_ready { "" } else { " [regime warming up]" };
                     tracing::info!(
-                        "SOL ${:.2} | Z: {:.2} | ADX: {:.1} ({}) | HOLD{}",
-                        price, z_score,
+                        "{} ${:.2} | Z: {:.2}{}{}{}{} | ADX: {:.1} ({}){} | HOLD{}",
+                        self.symbol, price, z_score, rsi_note, kalman_note, timeframe_note, ensemble_note,
                         adx_value.unwrap_or(0.0),
                         regime,
+                        vol_note,
                         warmup_note
                     );
                 }
             }
         } else {
             // Strategy still warming up (z-score not ready)
-            let warmup_note = if adx_ready { "" } else { " [ADX warming up]" };
+            let warmup_note = if adx_ready { "" } else { " [regime warming up]" };
             tracing::info!(
-                "SOL ${:.2} | Strategy warming up... | ADX: {:.1}{}",
-                price,
+                "{} ${:.2} | Strategy warming up... | ADX: {:.1}{}{}",
+                self.symbol, price,
                 adx_value.unwrap_or(0.0),
+                vol_note,
                 warmup_note
             );
         }
@@ -280,87 +701,462 @@ impl TradingOrchestrator {
         Ok(())
     }
 
-    /// Update regime detection with new price, returns (adx_value, adx_ready, regime_name)
-    async fn update_regime_detection(&self, price: f64) -> (Option<f64>, bool, String) {
+    /// Update regime detection with new price,
+    /// returns (adx_value, adx_ready, regime_name, volatility_note)
+    async fn update_regime_detection(&self, price: f64) -> (Option<f64>, bool, String, String) {
         // Feed price to candle builder
         let maybe_candle = {
             let mut builder = self.candle_builder.write().await;
             builder.update(price)
         };
 
-        // If a candle completed, feed it to ADX
+        // If a candle completed, feed it to the regime detectors
         if let Some(candle) = maybe_candle {
-            let mut adx = self.adx_detector.write().await;
-            adx.update(&candle);
-
-            // Update regime multiplier based on ADX
-            let new_multiplier = if adx.is_ready() {
-                adx.get_position_multiplier()
-            } else {
-                // Graceful degradation: trade cautiously during warmup
-                WARMUP_MULTIPLIER
-            };
-
-            *self.regime_multiplier.write().await = new_multiplier;
-
-            tracing::debug!(
-                "Candle closed: O={:.2} H={:.2} L={:.2} C={:.2} | ADX={:.1} | Multiplier={:.0}%",
-                candle.open, candle.high, candle.low, candle.close,
-                adx.adx(),
-                new_multiplier * 100.0
-            );
+            self.on_regime_candle(&candle).await;
         }
 
-        // Get current ADX state for logging
-        let adx = self.adx_detector.read().await;
-        let adx_ready = adx.is_ready();
-        let adx_value = if adx_ready { Some(adx.adx()) } else { None };
-        let regime = format!("{:?}", adx.get_regime());
+        // Get current regime state for logging
+        let detector = self.regime_detector.read().await;
+        let adx_ready = detector.is_ready();
+        let adx_value = detector.statistic_of("ADX");
+        let vol_note = self
+            .volatility_detector
+            .read()
+            .await
+            .as_ref()
+            .map(|vol| match vol.state() {
+                Some(state) => format!(" | Vol: {:?} ({:.2}x)", state.regime, state.ratio),
+                None => " | Vol: warming up".to_string(),
+            })
+            .unwrap_or_default();
+
+        (adx_value, adx_ready, trend_regime(adx_value), vol_note)
+    }
+
+    /// Feed a closed regime candle to the detectors and refresh the multiplier and stop scale
+    async fn on_regime_candle(&self, candle: &Candle) {
+        let mut detector = self.regime_detector.write().await;
+        detector.update(candle);
+
+        // Update regime multiplier from the ready detectors
+        let regime_multiplier = if detector.is_ready() {
+            detector.get_position_multiplier()
+        } else {
+            // Graceful degradation: trade cautiously during warmup
+            WARMUP_MULTIPLIER
+        };
+
+        // Volatility bands scale size and stop distances (neutral while warming up)
+        let mut volatility = self.volatility_detector.write().await;
+        let new_multiplier = match volatility.as_mut() {
+            Some(vol) => {
+                vol.update(candle);
+                self.strategy.write().await.set_stop_scale(vol.stop_scale());
+                regime_multiplier * vol.get_position_multiplier()
+            }
+            None => regime_multiplier,
+        };
 
-        (adx_value, adx_ready, regime)
+        *self.regime_multiplier.write().await = new_multiplier;
+
+        let stats: Vec<String> = detector
+            .components()
+            .iter()
+            .map(|c| format!("{}={:.2}", c.name, c.statistic.unwrap_or(f64::NAN)))
+            .collect();
+        tracing::debug!(
+            "Candle closed: O={:.2} H={:.2} L={:.2} C={:.2} | {} | Multiplier={:.0}%",
+            candle.open, candle.high, candle.low, candle.close,
+            stats.join(" "),
+            new_multiplier * 100.0
+        );
     }
 
-    /// Fetch current market price using Jupiter quote
-    /// Quotes 1 SOL -> USDC to get the current SOL price
+    /// Fetch current market price from the price source
+    /// The default source quotes 1 SOL -> USDC to get the current SOL price
     async fn fetch_price(&self) -> Result<f64, OrchestratorError> {
-        // Quote 1 SOL (1_000_000_000 lamports) to USDC to get price
-        let amount = 1_000_000_000u64; // 1 SOL
+        let sample = self.price_source.next_price().await
+            .map_err(|e| OrchestratorError::MarketDataError(e.to_string()))?;
 
-        let quote_request = QuoteRequest::new(
-            self.base_mint.clone(),
-            self.quote_mint.clone(),
-            amount,
-            self.slippage_bps,
+        Ok(sample.price)
+    }
+
+    /// Size an entry: equity-based when sizing is enabled, else fixed `trade_size_sol`
+    /// Also returns the wallet equity when sizing or the shared risk book read it
+    async fn size_entry(&self, price: f64, multiplier: f64) -> (SizingDecision, Option<f64>) {
+        let enabled = self.sizer.read().await.config().enabled;
+        if !enabled && self.risk_book.is_none() {
+            return (PositionSizer::fixed(self.trade_size_sol, multiplier, ""), None);
+        }
+        let equity = self.wallet_equity(price).await;
+        let reading = equity.as_ref().ok().copied().filter(|equity| *equity > 0.0);
+        let sizing = if enabled {
+            self.size_from_equity(price, multiplier, equity).await
+        } else {
+            PositionSizer::fixed(self.trade_size_sol, multiplier, "")
+        };
+        (sizing, reading)
+    }
+
+    /// Size an entry against a wallet equity reading, falling back to the fixed size
+    async fn size_from_equity(
+        &self,
+        price: f64,
+        multiplier: f64,
+        equity: Result<f64, OrchestratorError>,
+    ) -> SizingDecision {
+        match equity {
+            Ok(equity) if equity > 0.0 => {
+                let volatility = self.volatility_detector.read().await.as_ref().and_then(|v| v.state());
+                // Volatility targeting takes the place of the volatility band multiplier,
+                // so realized volatility scales the entry once (Extreme stays blocked at 0)
+                let band = volatility.as_ref().map_or(1.0, |v| v.regime.position_multiplier());
+                let multiplier = if band > 0.0 { multiplier / band } else { multiplier };
+                self.sizer.read().await.size(SizingInputs {
+                    equity,
+                    price,
+                    max_position_pct: self.max_position_pct,
+                    regime_multiplier: multiplier,
+                    volatility: volatility.as_ref(),
+                })
+            }
+            Ok(_) => PositionSizer::fixed(self.trade_size_sol, multiplier, "no wallet equity, "),
+            Err(e) => {
+                tracing::warn!("Wallet equity unavailable, using fixed trade size: {}", e);
+                PositionSizer::fixed(self.trade_size_sol, multiplier, "equity unavailable, ")
+            }
+        }
+    }
+
+    /// Wallet equity in quote currency: base balance at `price` plus the quote balance
+    async fn wallet_equity(&self, price: f64) -> Result<f64, OrchestratorError> {
+        if let Some(portfolio) = self.paper_portfolio.as_ref().filter(|_| self.paper_mode) {
+            return Ok(portfolio.read().await.equity(price));
+        }
+        let base_balance = self.token_balance(&self.base_mint)?;
+        let quote_balance = self.token_balance(&self.quote_mint)?;
+        Ok(base_balance * price + quote_balance)
+    }
+
+    /// Wallet balance of `mint` in whole tokens (native lamports for SOL)
+    fn token_balance(&self, mint: &str) -> Result<f64, OrchestratorError> {
+        use solana_sdk::pubkey::Pubkey;
+        use std::str::FromStr;
+
+        const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+        let rpc = self.solana.get_rpc_client();
+        let owner = self.wallet.pubkey();
+        if mint == SOL_MINT {
+            let lamports = rpc.get_balance(&owner)
+                .map_err(|e| OrchestratorError::WalletError(format!("Failed to get balance: {}", e)))?;
+            return Ok(lamports as f64 / 1_000_000_000.0);
+        }
+
+        let mint = Pubkey::from_str(mint)
+            .map_err(|e| OrchestratorError::ConfigError(format!("Invalid mint {}: {}", mint, e)))?;
+        let ata_program = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)
+            .map_err(|e| OrchestratorError::ConfigError(e.to_string()))?;
+        let (token_account, _) = Pubkey::find_program_address(
+            &[owner.as_ref(), spl_token::id().as_ref(), mint.as_ref()],
+            &ata_program,
         );
+        // A missing token account just means no balance yet
+        Ok(rpc
+            .get_token_account_balance(&token_account)
+            .ok()
+            .and_then(|balance| balance.ui_amount)
+            .unwrap_or(0.0))
+    }
+
+    /// Check an entry of `exposure_pct` against the limits shared with other pairs
+    async fn check_portfolio(&self, exposure_pct: f64) -> Result<(), OrchestratorError> {
+        match &self.risk_book {
+            Some(book) => book
+                .read()
+                .await
+                .check_entry(exposure_pct)
+                .map_err(|e| OrchestratorError::RiskLimitExceeded(e.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Record added exposure in the shared risk book
+    async fn book_exposure(&self, exposure_pct: f64) {
+        if let Some(book) = &self.risk_book {
+            book.write().await.add_exposure(&self.symbol, exposure_pct);
+        }
+    }
 
-        let quote = self.jupiter.get_quote(&quote_request).await
-            .map_err(|e| OrchestratorError::MarketDataError(format!("Failed to get quote: {}", e)))?;
+    /// Record closing `fraction` of the position at `return_pct` in the shared risk book
+    async fn book_close(&self, fraction: f64, return_pct: f64) {
+        if let Some(book) = &self.risk_book {
+            book.write().await.reduce(&self.symbol, fraction, return_pct);
+        }
+    }
 
-        // Calculate price from quote
-        // output_amount is in USDC base units (6 decimals)
-        let output_amount = quote.output_amount();
-        let price = output_amount as f64 / 1_000_000.0; // USDC has 6 decimals
+    /// Record closing `fraction` of the open size at `return_pct`
+    ///
+    /// The sizer weights the leg by `weight`, its share of the whole filled
+    /// position, while the risk book reduces the exposure still open.
+    async fn record_close(&self, fraction: f64, weight: f64, return_pct: f64) {
+        self.sizer.write().await.record_partial(return_pct, weight);
+        self.book_close(fraction, return_pct).await;
+    }
 
-        Ok(price)
+    /// Update state after a filled partial exit of `fraction` (`size_sol`) at `price`
+    async fn confirm_partial_exit(&self, fraction: f64, price: f64, size_sol: f64) {
+        let mut weight = fraction;
+        if let Some(open) = self.open_size.write().await.as_mut() {
+            weight = open.closed_share(size_sol);
+            open.remaining_sol = (open.remaining_sol - size_sol).max(0.0);
+        }
+        let mut strategy = self.strategy.write().await;
+        if let Some(return_pct) = position_return_pct(strategy.position(), price) {
+            self.record_close(fraction, weight, return_pct).await;
+        }
+        strategy.confirm_trade(TradeAction::PartialExit { fraction }, price);
     }
 
-    /// Execute a trade action via Jupiter swap
-    async fn execute_trade(&self, action: &TradeAction, price: f64) -> Result<(), OrchestratorError> {
-        if self.paper_mode {
-            tracing::info!(
-                "PAPER TRADE - Action: {:?}, Price: ${:.2}",
-                action,
-                price
-            );
+    /// Update state after a filled scale-in leg of `size_sol` at `price`, returns the size now open
+    ///
+    /// Without a recorded entry size the position holds what an exit would
+    /// unwind (`assumed_open_size`) before the leg; the leg is added once.
+    async fn confirm_scale_in(&self, price: f64, size_sol: f64, exposure_pct: f64) -> f64 {
+        let open_sol = {
+            let mut open_size = self.open_size.write().await;
+            let open = open_size.get_or_insert_with(|| self.assumed_open_size());
+            open.filled_sol += size_sol;
+            open.remaining_sol += size_sol;
+            open.remaining_sol
+        };
+        self.book_exposure(exposure_pct).await;
+        self.strategy.write().await.confirm_trade(TradeAction::ScaleIn, price);
+        open_sol
+    }
+
+    /// Open size assumed for a position whose entry size was not recorded,
+    /// e.g. one resumed from a state file saved without it: the fixed trade size
+    fn assumed_open_size(&self) -> OpenSize {
+        OpenSize::new(self.trade_size_sol, self.max_position_pct)
+    }
+
+    /// Update state after a filled exit of everything still open at `price`
+    async fn confirm_exit(&self, price: f64) {
+        let open = self.open_size.write().await.take();
+        let mut strategy = self.strategy.write().await;
+        if let Some(return_pct) = position_return_pct(strategy.position(), price) {
+            let weight = open.map_or(1.0, |open| open.closed_share(open.remaining_sol));
+            self.record_close(1.0, weight, return_pct).await;
+        }
+        strategy.confirm_trade(TradeAction::Exit, price);
+    }
+
+    /// Hard check that `size_sol` more of spot short is covered by base inventory
+    async fn check_short_inventory(&self, size_sol: f64) -> Result<(), OrchestratorError> {
+        if self.inventory.shorts_on_perp() {
             return Ok(());
         }
 
+        let short_open_sol = match self.strategy.read().await.position() {
+            PositionState::Short { .. } => self.open_size.read().await.map_or(0.0, |open| open.remaining_sol),
+            _ => 0.0,
+        };
+        // Paper mode trades a virtual neutral allocation, less what is already sold
+        let base_balance_sol = if let Some(portfolio) = self.paper_portfolio.as_ref().filter(|_| self.paper_mode) {
+            portfolio.read().await.base_balance()
+        } else if self.paper_mode {
+            self.inventory.config().neutral_base_sol - short_open_sol
+        } else {
+            self.token_balance(&self.base_mint)?
+        };
+
+        self.inventory
+            .check_short(size_sol, short_open_sol, base_balance_sol)
+            .map_err(|e| OrchestratorError::InventoryError(e.to_string()))
+    }
+
+    /// Perp venue for `action` when it trades the short side and shorts go to perps
+    async fn perp_venue(&self, action: &TradeAction) -> Result<Option<Arc<dyn PerpPort>>, OrchestratorError> {
+        if !self.inventory.shorts_on_perp() {
+            return Ok(None);
+        }
+
+        let short_side = match action {
+            TradeAction::EnterShort => true,
+            TradeAction::ScaleIn | TradeAction::Exit | TradeAction::PartialExit { .. } => {
+                matches!(self.strategy.read().await.position(), PositionState::Short { .. })
+            }
+            TradeAction::EnterLong | TradeAction::Hold => false,
+        };
+        if !short_side {
+            return Ok(None);
+        }
+
+        self.perp.clone().map(Some).ok_or_else(|| {
+            OrchestratorError::ConfigError("short_mode = \"perp\" but no perp venue is connected".to_string())
+        })
+    }
+
+    /// Execute a short-side action of `size_sol` on the perp venue
+    async fn execute_perp(
+        &self,
+        perp: &dyn PerpPort,
+        action: &TradeAction,
+        price: f64,
+        size_sol: f64,
+    ) -> Result<(), OrchestratorError> {
+        let venue_error = |e: crate::ports::perp::PerpPortError| OrchestratorError::ExecutionError(e.to_string());
+
+        let fill = match action {
+            TradeAction::EnterShort | TradeAction::ScaleIn => perp.increase(Side::Short, size_sol, price).await,
+            TradeAction::Exit => {
+                // Close whatever the venue holds, even if our size was lost on restart
+                let open = perp.position().await.map_err(venue_error)?;
+                perp.reduce(open.map_or(size_sol, |position| position.size), price).await
+            }
+            TradeAction::PartialExit { .. } => perp.reduce(size_sol, price).await,
+            TradeAction::EnterLong | TradeAction::Hold => return Ok(()),
+        }
+        .map_err(venue_error)?;
+
+        tracing::info!(
+            "PERP {} - Action: {:?}, Size: {:.4} SOL @ ${:.2}, fee ${:.4}, realized ${:.4}",
+            perp.market(),
+            action,
+            fill.size,
+            fill.price,
+            fill.fee,
+            fill.realized_pnl
+        );
+        Ok(())
+    }
+
+    /// Execute a trade action of `size_sol` via Jupiter swap (or the perp venue for perp shorts)
+    async fn execute_trade(&self, action: &TradeAction, price: f64, size_sol: f64) -> Result<(), OrchestratorError> {
+        if let Some(perp) = self.perp_venue(action).await? {
+            return self.execute_perp(perp.as_ref(), action, price, size_sol).await;
+        }
+
+        if self.paper_mode {
+            let portfolio = self.paper_portfolio.as_ref().ok_or_else(|| {
+                OrchestratorError::ExecutionError("Paper mode needs a paper portfolio".to_string())
+            })?;
+            return self.execute_paper(portfolio, action, price, size_sol).await;
+        }
+
         tracing::info!(
-            "EXECUTING TRADE - Action: {:?}, Price: ${:.2}",
+            "EXECUTING TRADE - Action: {:?}, Size: {:.4} SOL, Price: ${:.2}",
             action,
+            size_sol,
             price
         );
 
+        // Determine swap direction and amount based on action
+        let (input_mint, output_mint, amount) = self.get_swap_params(action, price, size_sol).await?;
+
+        if amount == 0 {
+            tracing::warn!("Trade amount is zero, skipping");
+            return Ok(());
+        }
+
+        // Exits jump the rate-limit queue
+        let priority = if matches!(action, TradeAction::Exit | TradeAction::PartialExit { .. }) {
+            RequestPriority::Exit
+        } else {
+            RequestPriority::Entry
+        };
+        self.execute_swap(&input_mint, &output_mint, amount, priority).await?;
+        Ok(())
+    }
+
+    /// Fill a paper trade in the virtual wallet at a live Jupiter quote for the same swap
+    async fn execute_paper(
+        &self,
+        portfolio: &RwLock<PaperPortfolio>,
+        action: &TradeAction,
+        price: f64,
+        size_sol: f64,
+    ) -> Result<(), OrchestratorError> {
+        let (input_mint, output_mint, amount) = self.get_swap_params(action, price, size_sol).await?;
+        if amount == 0 {
+            tracing::warn!("Trade amount is zero, skipping");
+            return Ok(());
+        }
+
+        let priority = if matches!(action, TradeAction::Exit | TradeAction::PartialExit { .. }) {
+            RequestPriority::Exit
+        } else {
+            RequestPriority::Entry
+        };
+        let quote_request = QuoteRequest::new(input_mint, output_mint.clone(), amount, self.slippage_bps);
+        let quote_started = Instant::now();
+        let quote = self.jupiter.get_quote_with_priority(&quote_request, priority).await
+            .map_err(|e| OrchestratorError::ExecutionError(format!("Quote failed: {}", e)))?;
+        let summary = QuoteSummary::from_quote(&quote, quote_started.elapsed());
+        if let Some(ref recorder) = self.recorder {
+            record_shared(recorder, RecordedEvent::Quote(summary.clone()));
+        }
+
+        self.fill_paper(portfolio, action, price, size_sol, output_mint == self.base_mint, summary).await
+    }
+
+    /// Book a paper fill of `size_sol` base against `quote` in the virtual wallet
+    ///
+    /// Short entries, short scale-ins and covers go through `fill_short` so the
+    /// short's round trip is booked against its own entry.
+    async fn fill_paper(
+        &self,
+        portfolio: &RwLock<PaperPortfolio>,
+        action: &TradeAction,
+        price: f64,
+        size_sol: f64,
+        buy_base: bool,
+        quote: QuoteSummary,
+    ) -> Result<(), OrchestratorError> {
+        let side = if buy_base { TradeSide::Buy } else { TradeSide::Sell };
+        // Longs buy to enter and sell to exit; the opposite direction is a short leg
+        let short = if buy_base {
+            matches!(action, TradeAction::Exit | TradeAction::PartialExit { .. })
+        } else {
+            matches!(action, TradeAction::EnterShort | TradeAction::ScaleIn)
+        };
+        let mut portfolio = portfolio.write().await;
+        let trade = if short {
+            portfolio.fill_short(side, size_sol, price, quote)
+        } else {
+            portfolio.fill(side, size_sol, price, quote)
+        }
+        .map_err(|e| OrchestratorError::ExecutionError(e.to_string()))?;
+
+        let pnl_note = trade.pnl_sol.map(|pnl| format!(", realized {:+.4}", pnl)).unwrap_or_default();
+        tracing::info!(
+            "PAPER FILL - Action: {:?}, {} {:.4} @ ${:.4} ({}bps, fee ${:.4}{}) | Equity ${:.2}",
+            action,
+            trade.side,
+            trade.token_amount_decimal(),
+            trade.price,
+            trade.slippage_bps,
+            trade.fee_sol,
+            pnl_note,
+            portfolio.equity(price)
+        );
+        Ok(())
+    }
+
+    /// Swap `amount` raw units of `input_mint` into `output_mint` through Jupiter:
+    /// quote, build, validate, sign, submit and check the balance delta
+    /// Returns the output amount of the executed route in raw units
+    pub async fn execute_swap(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        priority: RequestPriority,
+    ) -> Result<u64, OrchestratorError> {
+        // Pairs sharing the wallet swap one at a time
+        let _trade_guard = self.trade_lock.lock().await;
+
         // 1. Check if trading is halted due to balance anomaly
         if self.balance_guard.read().await.is_halted() {
             return Err(OrchestratorError::ExecutionError(
@@ -373,18 +1169,10 @@ impl TradingOrchestrator {
             .map_err(|e| OrchestratorError::ExecutionError(format!("Failed to get balance: {}", e)))?;
         self.balance_guard.write().await.capture_pre_trade(pre_balance);
 
-        // 3. Determine swap direction and amount based on action
-        let (input_mint, output_mint, amount) = self.get_swap_params(action, price).await?;
-
-        if amount == 0 {
-            tracing::warn!("Trade amount is zero, skipping");
-            return Ok(());
-        }
-
-        // 4. Get quote from Jupiter
+        // 3. Get quote from Jupiter
         let quote_request = QuoteRequest::new(
-            input_mint.clone(),
-            output_mint.clone(),
+            input_mint.to_string(),
+            output_mint.to_string(),
             amount,
             self.slippage_bps,
         );
@@ -394,9 +1182,15 @@ impl TradingOrchestrator {
             amount, input_mint, output_mint
         );
 
-        let quote = self.jupiter.get_quote(&quote_request).await
+        let quote_started = Instant::now();
+        let quote = self.jupiter.get_quote_with_priority(&quote_request, priority).await
             .map_err(|e| OrchestratorError::ExecutionError(format!("Quote failed: {}", e)))?;
 
+        if let Some(ref recorder) = self.recorder {
+            let summary = QuoteSummary::from_quote(&quote, quote_started.elapsed());
+            record_shared(recorder, RecordedEvent::Quote(summary));
+        }
+
         let in_amount = quote.input_amount();
         let out_amount = quote.output_amount();
         let price_impact = quote.price_impact();
@@ -425,7 +1219,7 @@ impl TradingOrchestrator {
 
         tracing::info!("Building swap transaction...");
 
-        let swap_response = self.jupiter.get_swap_transaction(&swap_request).await
+        let swap_response = self.jupiter.get_swap_transaction_with_priority(&swap_request, priority).await
             .map_err(|e| OrchestratorError::ExecutionError(format!("Swap build failed: {}", e)))?;
 
         // 4. Decode base64 transaction
@@ -485,7 +1279,6 @@ impl TradingOrchestrator {
             .map_err(|e| OrchestratorError::ExecutionError(format!("Failed to get post-trade balance: {}", e)))?;
 
         // Calculate expected delta based on swap direction
-        const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
         // Extract DEX fees from route plan for more accurate expected delta
         // Note: fee_amount may not always be returned by Jupiter API
@@ -523,45 +1316,45 @@ impl TradingOrchestrator {
             // Don't return error - trade already executed, just log the warning
         }
 
-        Ok(())
+        Ok(out_amount)
     }
 
-    /// Determine swap parameters based on trade action
-    async fn get_swap_params(&self, action: &TradeAction, price: f64) -> Result<(String, String, u64), OrchestratorError> {
-        match action {
-            TradeAction::EnterLong => {
-                // Buy SOL with USDC: USDC -> SOL
-                let usdc_amount = (self.trade_size_sol * price * 1_000_000.0) as u64;
-                Ok((self.quote_mint.clone(), self.base_mint.clone(), usdc_amount))
-            }
-            TradeAction::EnterShort => {
-                // Sell SOL for USDC: SOL -> USDC
-                let sol_amount = (self.trade_size_sol * 1_000_000_000.0) as u64;
-                Ok((self.base_mint.clone(), self.quote_mint.clone(), sol_amount))
-            }
-            TradeAction::Exit => {
-                // Exit depends on current position
-                let strategy = self.strategy.read().await;
-                match strategy.position() {
-                    PositionState::Long { .. } => {
-                        // Exit long = sell SOL
-                        let sol_amount = (self.trade_size_sol * 1_000_000_000.0) as u64;
-                        Ok((self.base_mint.clone(), self.quote_mint.clone(), sol_amount))
-                    }
-                    PositionState::Short { .. } => {
-                        // Exit short = buy SOL
-                        let usdc_amount = (self.trade_size_sol * price * 1_000_000.0) as u64;
-                        Ok((self.quote_mint.clone(), self.base_mint.clone(), usdc_amount))
-                    }
+    /// Determine swap parameters for `size_sol` based on trade action
+    async fn get_swap_params(
+        &self,
+        action: &TradeAction,
+        price: f64,
+        size_sol: f64,
+    ) -> Result<(String, String, u64), OrchestratorError> {
+        let buy_sol = match action {
+            TradeAction::EnterLong => true,
+            // Spot short: sell SOL down from the neutral allocation (see `check_short_inventory`)
+            TradeAction::EnterShort => false,
+            TradeAction::ScaleIn | TradeAction::Exit | TradeAction::PartialExit { .. } => {
+                // Direction depends on current position: add to it or unwind it
+                let adding = *action == TradeAction::ScaleIn;
+                match self.strategy.read().await.position() {
+                    PositionState::Long { .. } => adding,
+                    PositionState::Short { .. } => !adding,
                     PositionState::Flat => {
-                        tracing::warn!("Exit called but position is flat");
-                        Ok((String::new(), String::new(), 0))
+                        tracing::warn!("{:?} called but position is flat", action);
+                        return Ok((String::new(), String::new(), 0));
                     }
                 }
             }
             TradeAction::Hold => {
-                Ok((String::new(), String::new(), 0))
+                return Ok((String::new(), String::new(), 0));
             }
+        };
+
+        if buy_sol {
+            // Buy base with quote: USDC -> SOL
+            let quote_amount = (size_sol * price * 10f64.powi(self.quote_decimals as i32)) as u64;
+            Ok((self.quote_mint.clone(), self.base_mint.clone(), quote_amount))
+        } else {
+            // Sell base for quote: SOL -> USDC
+            let base_amount = (size_sol * 10f64.powi(self.base_decimals as i32)) as u64;
+            Ok((self.base_mint.clone(), self.quote_mint.clone(), base_amount))
         }
     }
 
@@ -711,6 +1504,11 @@ impl TradingOrchestrator {
         }
     }
 
+    /// Whether trades are only logged
+    pub fn paper_mode(&self) -> bool {
+        self.paper_mode
+    }
+
     /// Stop the trading loop
     pub async fn stop(&self) {
         *self.is_running.write().await = false;
@@ -719,37 +1517,60 @@ impl TradingOrchestrator {
 
     /// Get current status snapshot
     pub async fn status(&self) -> OrchestratorStatus {
-        let strategy = self.strategy.read().await;
+        let snapshot = self.strategy.read().await.snapshot();
         let is_running = *self.is_running.read().await;
 
-        let position = match strategy.position() {
+        let stop_note = snapshot
+            .position
+            .stops()
+            .and_then(|stops| stops.stop_price)
+            .map(|stop| format!(" (stop ${:.2})", stop))
+            .unwrap_or_default();
+        let layer_note = snapshot
+            .layers
+            .as_ref()
+            .filter(|layers| layers.scale_ins() > 0 || !layers.exits.is_empty())
+            .map(|layers| format!(" [{} legs, {:.0}% open]", layers.fills.len(), layers.remaining_size() / layers.filled_size() * 100.0))
+            .unwrap_or_default();
+        let position = match snapshot.position {
             PositionState::Flat => "Flat".to_string(),
-            PositionState::Long { entry_price } => format!("Long @ ${:.2}", entry_price),
-            PositionState::Short { entry_price } => format!("Short @ ${:.2}", entry_price),
+            PositionState::Long { entry_price, .. } => format!("Long @ ${:.2}{}{}", entry_price, stop_note, layer_note),
+            PositionState::Short { entry_price, .. } => format!("Short @ ${:.2}{}{}", entry_price, stop_note, layer_note),
         };
 
-        let current_zscore = strategy.current_zscore().map(|z| z.z_score);
+        let current_zscore = snapshot.signal;
 
-        // Get ADX status
-        let adx = self.adx_detector.read().await;
-        let adx_ready = adx.is_ready();
-        let adx_value = if adx_ready { Some(adx.adx()) } else { None };
-        let trend_regime = format!("{:?}", adx.get_regime());
-        drop(adx); // Release lock before reading multiplier
+        // Get regime status
+        let detector = self.regime_detector.read().await;
+        let adx_ready = detector.is_ready();
+        let adx_value = detector.statistic_of("ADX");
+        let regime_components = detector.components();
+        drop(detector); // Release lock before reading multiplier
+
+        let volatility = self.volatility_detector.read().await.as_ref().and_then(|v| v.state());
 
         let regime_multiplier = *self.regime_multiplier.read().await;
+        let sizing = self.last_sizing.read().await.clone();
 
         OrchestratorStatus {
             is_running,
+            symbol: self.symbol.clone(),
+            strategy: snapshot.name,
             position,
-            daily_trades: strategy.daily_trade_count(),
-            daily_pnl_pct: strategy.daily_pnl_pct(),
+            daily_trades: snapshot.daily_trades,
+            daily_pnl_pct: snapshot.daily_pnl_pct,
             last_price: None, // Could cache this from last tick
             current_zscore,
             adx_value,
-            trend_regime,
+            trend_regime: trend_regime(adx_value),
             regime_multiplier,
             adx_ready,
+            regime_components,
+            volatility,
+            indicators: snapshot.indicators,
+            kalman: snapshot.kalman,
+            timeframes: snapshot.timeframes,
+            sizing,
         }
     }
 
@@ -767,26 +1588,130 @@ impl TradingOrchestrator {
     }
 }
 
+/// Composite regime detector holding a single ADX detector
+fn adx_only(config: AdxConfig) -> CompositeRegimeDetector {
+    CompositeRegimeDetector::new(CombineMode::WeightedAverage)
+        .with_detector(Box::new(AdxRegimeDetector::new(config)), 1.0)
+}
+
+/// Compact log view of one confirmation timeframe, e.g. " | 1h z:+0.52 ADX:18.3"
+fn timeframe_note(state: &TimeframeState) -> String {
+    let z = state.z_score.map(|z| format!(" z:{:+.2}", z)).unwrap_or_else(|| " warming up".to_string());
+    let adx = state.adx.map(|adx| format!(" ADX:{:.1}", adx)).unwrap_or_default();
+    format!(" | {}{}{}", state.timeframe, z, adx)
+}
+
+/// Strategy and open size saved by `save_state`
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedState {
+    strategy: StrategySnapshot,
+    open_size: Option<OpenSize>,
+}
+
+impl SavedState {
+    /// Read `path`, None when there is no saved state yet
+    fn load(path: &Path) -> Result<Option<Self>, OrchestratorError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| OrchestratorError::StateError(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| OrchestratorError::StateError(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// SOL committed to the open position
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct OpenSize {
+    /// Size of the initial entry, the unit scale-in legs are sized in
+    unit_sol: f64,
+    /// Exposure of the initial entry in percent of equity
+    unit_exposure_pct: f64,
+    /// Total size filled by the entry and scale-ins
+    #[serde(default)]
+    filled_sol: f64,
+    /// Size still open after scale-ins and partial exits
+    remaining_sol: f64,
+}
+
+impl OpenSize {
+    fn new(size_sol: f64, exposure_pct: f64) -> Self {
+        Self { unit_sol: size_sol, unit_exposure_pct: exposure_pct, filled_sol: size_sol, remaining_sol: size_sol }
+    }
+
+    /// Share of the whole filled position that closing `size_sol` represents
+    ///
+    /// State saved before `filled_sol` existed counts what is still open as
+    /// the whole position.
+    fn closed_share(&self, size_sol: f64) -> f64 {
+        let filled_sol = if self.filled_sol > 0.0 { self.filled_sol } else { self.remaining_sol };
+        if filled_sol <= 0.0 {
+            return 1.0;
+        }
+        (size_sol / filled_sol).clamp(0.0, 1.0)
+    }
+}
+
+/// Return of an open position closed at `price` in percent, None when flat
+fn position_return_pct(position: PositionState, price: f64) -> Option<f64> {
+    match position {
+        PositionState::Long { entry_price, .. } => Some((price - entry_price) / entry_price * 100.0),
+        PositionState::Short { entry_price, .. } => Some((entry_price - price) / entry_price * 100.0),
+        PositionState::Flat => None,
+    }
+}
+
+/// Share of `equity` that `size` base tokens at `price` commit, in percent
+/// Falls back to `fallback_pct` without an equity reading
+fn exposure_pct(size: f64, price: f64, equity: Option<f64>, fallback_pct: f64) -> f64 {
+    equity.map_or(fallback_pct, |equity| size * price / equity * 100.0)
+}
+
+/// Trend regime name for an ADX value, Unknown while ADX is warming up or absent
+fn trend_regime(adx_value: Option<f64>) -> String {
+    format!("{:?}", adx_value.map(TrendRegime::from_adx).unwrap_or(TrendRegime::Unknown))
+}
+
 // Implement Clone for TradingOrchestrator (needed for sharing across tasks)
 impl Clone for TradingOrchestrator {
     fn clone(&self) -> Self {
         Self {
             strategy: Arc::clone(&self.strategy),
             jupiter: self.jupiter.clone(),
+            price_source: Arc::clone(&self.price_source),
+            recorder: self.recorder.clone(),
             solana: self.solana.clone(),
             wallet: self.wallet.clone(),
+            symbol: self.symbol.clone(),
             base_mint: self.base_mint.clone(),
             quote_mint: self.quote_mint.clone(),
+            base_decimals: self.base_decimals,
+            quote_decimals: self.quote_decimals,
             slippage_bps: self.slippage_bps,
             is_running: Arc::clone(&self.is_running),
             paper_mode: self.paper_mode,
+            paper_portfolio: self.paper_portfolio.clone(),
+            state_file: self.state_file.clone(),
             poll_interval: self.poll_interval,
             trade_size_sol: self.trade_size_sol,
+            max_position_pct: self.max_position_pct,
+            sizer: Arc::clone(&self.sizer),
+            scale_in_size: self.scale_in_size,
+            open_size: Arc::clone(&self.open_size),
+            last_sizing: Arc::clone(&self.last_sizing),
+            inventory: self.inventory.clone(),
+            perp: self.perp.clone(),
+            risk_book: self.risk_book.clone(),
+            trade_lock: Arc::clone(&self.trade_lock),
             priority_fee_lamports: self.priority_fee_lamports,
             balance_guard: Arc::clone(&self.balance_guard),
             tx_validator: self.tx_validator.clone(),
-            adx_detector: Arc::clone(&self.adx_detector),
+            regime_detector: Arc::clone(&self.regime_detector),
+            volatility_detector: Arc::clone(&self.volatility_detector),
             candle_builder: Arc::clone(&self.candle_builder),
+            strategy_candle_builder: Arc::clone(&self.strategy_candle_builder),
             regime_multiplier: Arc::clone(&self.regime_multiplier),
         }
     }
@@ -850,6 +1775,59 @@ mod tests {
         assert_eq!(status.daily_trades, 0);
     }
 
+    #[tokio::test]
+    async fn test_paper_portfolio_fills() {
+        let portfolio = PaperPortfolio::new(SOL_MINT, "SOL", 9, "USDC", 6, 1000.0, 1.0);
+        let orchestrator = create_test_orchestrator()
+            .with_inventory(InventoryConfig::default())
+            .with_paper_portfolio(portfolio);
+        let paper = orchestrator.paper_portfolio.clone().unwrap();
+
+        // Equity and short inventory come from the virtual wallet
+        assert_eq!(orchestrator.wallet_equity(100.0).await.unwrap(), 1100.0);
+
+        let quote = QuoteSummary {
+            timestamp: chrono::Utc::now(),
+            input_mint: orchestrator.quote_mint.clone(),
+            output_mint: SOL_MINT.to_string(),
+            in_amount: 10_000_000,
+            out_amount: 99_000_000,
+            price_impact_pct: 0.0,
+            route_labels: vec![],
+            latency_ms: 10,
+        };
+        orchestrator
+            .fill_paper(&paper, &TradeAction::EnterLong, 100.0, 0.1, true, quote.clone())
+            .await
+            .unwrap();
+
+        {
+            let wallet = paper.read().await;
+            assert!((wallet.base_balance() - 1.099).abs() < 1e-9);
+            assert!((wallet.quote_balance() - (990.0 - 0.0005)).abs() < 1e-9);
+            assert_eq!(wallet.stats().buy_count, 1);
+        }
+
+        // A short sold at $100 and covered at $90 is booked as its own winning round trip
+        let short_quote = |input_mint: &str, output_mint: &str, in_amount, out_amount| QuoteSummary {
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            in_amount,
+            out_amount,
+            ..quote.clone()
+        };
+        let usdc = orchestrator.quote_mint.clone();
+        let sell = short_quote(SOL_MINT, &usdc, 100_000_000, 10_000_000);
+        orchestrator.fill_paper(&paper, &TradeAction::EnterShort, 100.0, 0.1, false, sell).await.unwrap();
+        let cover = short_quote(&usdc, SOL_MINT, 9_000_000, 100_000_000);
+        orchestrator.fill_paper(&paper, &TradeAction::Exit, 90.0, 0.1, true, cover).await.unwrap();
+
+        let wallet = paper.read().await;
+        assert!((wallet.base_balance() - 1.099).abs() < 1e-9);
+        assert_eq!(wallet.stats().winning_trades, 1);
+        assert!((wallet.stats().total_realized_pnl_sol - (1.0 - 0.0005 - 0.00045)).abs() < 1e-6);
+    }
+
     #[tokio::test]
     async fn test_stop_graceful() {
         let orchestrator = create_test_orchestrator();
@@ -869,6 +1847,336 @@ mod tests {
         assert_eq!(orchestrator.poll_interval, Duration::from_secs(5));
     }
 
+    #[tokio::test]
+    async fn test_with_timeframe() {
+        let orchestrator = create_test_orchestrator()
+            .with_timeframe(Duration::from_secs(180));
+
+        let builder = orchestrator.strategy_candle_builder.read().await;
+        assert_eq!(builder.period(), Duration::from_secs(180));
+    }
+
+    #[tokio::test]
+    async fn test_strategy_selected_by_config() {
+        let orchestrator = create_test_orchestrator();
+        assert_eq!(orchestrator.status().await.strategy, "mean_reversion");
+
+        let config = StrategyConfig::default().with_kind(crate::strategy::StrategyKind::OuReversion);
+        let orchestrator = orchestrator.with_strategy(build_strategy(&config));
+        assert_eq!(orchestrator.status().await.strategy, "ou_reversion");
+    }
+
+    #[tokio::test]
+    async fn test_status_exposes_kalman_state() {
+        let orchestrator = create_test_orchestrator();
+        assert!(orchestrator.status().await.kalman.is_none());
+
+        let config = StrategyConfig {
+            lookback_period: 10,
+            ..Default::default()
+        }
+        .with_zscore_estimator(crate::strategy::ZScoreEstimator::Kalman);
+        let mut strategy = build_strategy(&config);
+        for i in 0..20 {
+            let price = 100.0 + (i % 4) as f64 * 0.25;
+            strategy.update_tick(price, Some(&crate::strategy::regime::Candle::new(price, price, price, price, 1.0)));
+        }
+        let orchestrator = orchestrator.with_strategy(strategy);
+
+        let kalman = orchestrator.status().await.kalman.unwrap();
+        assert!(kalman.innovation_variance > 0.0);
+        assert!((kalman.fair_value - 100.4).abs() < 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_regime_components() {
+        use crate::strategy::regime::RegimeDetectorKind;
+
+        let status = create_test_orchestrator().status().await;
+        assert_eq!(status.regime_components.len(), 1);
+        assert_eq!(status.regime_components[0].name, "ADX");
+        assert_eq!(status.trend_regime, "Unknown");
+        assert!(!status.adx_ready);
+
+        let config = crate::strategy::RegimeConfig {
+            detectors: vec![RegimeDetectorKind::Hurst, RegimeDetectorKind::Adf],
+            ..Default::default()
+        };
+        let orchestrator = create_test_orchestrator().with_regime_detector(config.build());
+        let status = orchestrator.status().await;
+        let names: Vec<_> = status.regime_components.iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["Hurst", "ADF"]);
+        assert!(status.adx_value.is_none());
+        assert_eq!(status.regime_multiplier, WARMUP_MULTIPLIER);
+    }
+
+    #[tokio::test]
+    async fn test_volatility_detector_scales_multiplier_and_stops() {
+        use crate::strategy::regime::{VolatilityConfig, VolatilityRegime};
+
+        let orchestrator = create_test_orchestrator();
+        assert!(orchestrator.status().await.volatility.is_none());
+
+        let config = VolatilityConfig {
+            enabled: true,
+            window: 5,
+            baseline_window: 50,
+            ..Default::default()
+        };
+        let orchestrator = orchestrator.with_volatility_detector(Some(VolatilityRegimeDetector::new(config)));
+        let (_, _, _, vol_note) = orchestrator.update_regime_detection(100.0).await;
+        assert_eq!(vol_note, " | Vol: warming up");
+
+        // Quiet baseline, then a burst of wide candles
+        for i in 0..55 {
+            let range = if i < 50 { 0.5 } else { 5.0 };
+            orchestrator
+                .on_regime_candle(&Candle::new(100.0, 100.0 + range, 100.0 - range, 100.0, 1.0))
+                .await;
+        }
+
+        let status = orchestrator.status().await;
+        assert_eq!(status.volatility.unwrap().regime, VolatilityRegime::Extreme);
+        assert_eq!(status.regime_multiplier, 0.0);
+
+        let (_, _, _, vol_note) = orchestrator.update_regime_detection(100.0).await;
+        assert!(vol_note.starts_with(" | Vol: Extreme"), "{}", vol_note);
+    }
+
+    #[tokio::test]
+    async fn test_volatility_targeting_replaces_band_multiplier() {
+        use crate::strategy::regime::{VolatilityConfig, VolatilityRegime};
+
+        let config = VolatilityConfig {
+            enabled: true,
+            window: 5,
+            baseline_window: 50,
+            ..Default::default()
+        };
+        let orchestrator = create_test_orchestrator()
+            .with_sizing(SizingConfig { enabled: true, ..Default::default() })
+            .with_volatility_detector(Some(VolatilityRegimeDetector::new(config)));
+        for i in 0..55 {
+            let range = if i < 50 { 0.5 } else { 1.0 };
+            orchestrator
+                .on_regime_candle(&Candle::new(100.0, 100.0 + range, 100.0 - range, 100.0, 1.0))
+                .await;
+        }
+        let state = orchestrator.status().await.volatility.unwrap();
+        assert_eq!(state.regime, VolatilityRegime::High);
+
+        // Trend multiplier 0.8 times the 50% High band: the band is divided back
+        // out and vol_scale (< 1) applies instead
+        let sized = orchestrator.size_from_equity(100.0, 0.8 * 0.5, Ok(10_000.0)).await;
+        let vol_scale = state.baseline / state.realized;
+        assert!(vol_scale < 1.0);
+        assert!((sized.size_sol - 5.0 * vol_scale * 0.8).abs() < 1e-9, "{}", sized.reason);
+    }
+
+    #[tokio::test]
+    async fn test_spot_short_checks_inventory() {
+        let orchestrator = create_test_orchestrator();
+
+        // No neutral allocation configured: nothing to short from
+        assert!(orchestrator.check_short_inventory(0.1).await.is_err());
+
+        let orchestrator = orchestrator.with_inventory(InventoryConfig {
+            neutral_base_sol: 1.0,
+            min_base_reserve_sol: 0.1,
+            ..Default::default()
+        });
+        assert!(orchestrator.check_short_inventory(0.5).await.is_ok());
+
+        // 0.5 SOL already sold: another 0.5 would dip into the reserve
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterShort, 100.0);
+        *orchestrator.open_size.write().await = Some(OpenSize::new(0.5, 5.0));
+        assert!(orchestrator.check_short_inventory(0.4).await.is_ok());
+        let err = orchestrator.check_short_inventory(0.5).await.unwrap_err();
+        assert!(matches!(err, OrchestratorError::InventoryError(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_shared_risk_book_limits_entries() {
+        use crate::domain::PortfolioLimits;
+
+        let book = Arc::new(RwLock::new(PairRiskBook::new(PortfolioLimits {
+            max_total_exposure_pct: 8.0,
+            ..Default::default()
+        })));
+        let orchestrator = create_test_orchestrator()
+            .with_symbol("SOL/USDC")
+            .with_risk_book(Arc::clone(&book));
+
+        assert!(orchestrator.check_portfolio(5.0).await.is_ok());
+        orchestrator.book_exposure(5.0).await;
+        let err = orchestrator.check_portfolio(5.0).await.unwrap_err();
+        assert!(matches!(err, OrchestratorError::RiskLimitExceeded(_)), "{}", err);
+
+        orchestrator.book_close(1.0, 2.0).await;
+        assert_eq!(book.read().await.total_exposure_pct(), 0.0);
+        assert!((book.read().await.daily_pnl_pct() - 0.1).abs() < 1e-12);
+
+        // Exposure is the entered size against wallet equity, not the configured maximum
+        let portfolio = PaperPortfolio::new(SOL_MINT, "SOL", 9, "USDC", 6, 1000.0, 0.0);
+        let paper = create_test_orchestrator()
+            .with_risk_book(Arc::clone(&book))
+            .with_paper_portfolio(portfolio);
+        let (sizing, equity) = paper.size_entry(100.0, 0.5).await;
+        assert_eq!(equity, Some(1000.0));
+        assert!((exposure_pct(sizing.size_sol, 100.0, equity, 2.5) - 0.5).abs() < 1e-12);
+        assert_eq!(exposure_pct(sizing.size_sol, 100.0, None, 2.5), 2.5);
+
+        // Token decimals drive swap amounts for non SOL/USDC pairs
+        let jup = create_test_orchestrator().with_decimals(6, 6);
+        let (_, _, amount) = jup.get_swap_params(&TradeAction::EnterShort, 0.8, 10.0).await.unwrap();
+        assert_eq!(amount, 10_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_perp_shorts() {
+        use crate::adapters::perp::LocalPerpExchange;
+        use crate::domain::ShortMode;
+
+        let perp = Arc::new(LocalPerpExchange::new("SOL-PERP").with_fee_bps(0.0));
+        let orchestrator = create_test_orchestrator()
+            .with_inventory(InventoryConfig { short_mode: ShortMode::Perp, ..Default::default() })
+            .with_perp(perp.clone());
+
+        // Perp shorts never need base inventory
+        assert!(orchestrator.check_short_inventory(5.0).await.is_ok());
+
+        orchestrator.execute_trade(&TradeAction::EnterShort, 100.0, 0.5).await.unwrap();
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterShort, 100.0);
+        assert_eq!(perp.position().await.unwrap().unwrap().side, Side::Short);
+
+        // Exit closes the venue position
+        orchestrator.execute_trade(&TradeAction::Exit, 98.0, 0.1).await.unwrap();
+        assert!(perp.position().await.unwrap().is_none());
+        assert!((perp.realized_pnl() - 1.0).abs() < 1e-9);
+
+        // Longs stay on spot
+        assert!(orchestrator.perp_venue(&TradeAction::EnterLong).await.unwrap().is_none());
+
+        // Perp mode without a venue refuses short-side trades
+        let unconnected = create_test_orchestrator()
+            .with_inventory(InventoryConfig { short_mode: ShortMode::Perp, ..Default::default() });
+        assert!(unconnected.execute_trade(&TradeAction::EnterShort, 100.0, 0.5).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_entry_sizing() {
+        let orchestrator = create_test_orchestrator();
+
+        // Disabled: fixed trade size times the regime multiplier
+        let (fixed, equity) = orchestrator.size_entry(100.0, 0.5).await;
+        assert!((fixed.size_sol - 0.05).abs() < 1e-12, "{}", fixed.reason);
+        assert_eq!(equity, None);
+
+        let orchestrator = orchestrator.with_sizing(SizingConfig { enabled: true, ..Default::default() });
+
+        // $10,000 equity x 5% max position x 50% regime = $250 = 2.5 SOL
+        let sized = orchestrator.size_from_equity(100.0, 0.5, Ok(10_000.0)).await;
+        assert!((sized.size_sol - 2.5).abs() < 1e-12, "{}", sized.reason);
+
+        let fallback = orchestrator
+            .size_from_equity(100.0, 1.0, Err(OrchestratorError::WalletError("rpc down".to_string())))
+            .await;
+        assert!((fallback.size_sol - 0.1).abs() < 1e-12);
+        assert!(fallback.reason.starts_with("equity unavailable"), "{}", fallback.reason);
+
+        // Exits swap the size passed in (the entered size), not the fixed one
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterLong, 100.0);
+        let (input, output, amount) = orchestrator
+            .get_swap_params(&TradeAction::Exit, 101.0, sized.size_sol)
+            .await
+            .unwrap();
+        assert_eq!((input.as_str(), output.as_str()), (orchestrator.base_mint.as_str(), orchestrator.quote_mint.as_str()));
+        assert_eq!(amount, 2_500_000_000);
+
+        assert_eq!(position_return_pct(PositionState::short(100.0), 98.0), Some(2.0));
+        assert_eq!(position_return_pct(PositionState::Flat, 98.0), None);
+    }
+
+    #[tokio::test]
+    async fn test_scale_in_without_recorded_entry_size() {
+        let orchestrator = create_test_orchestrator();
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterLong, 100.0);
+        assert!(orchestrator.open_size.read().await.is_none());
+
+        // The 0.1 SOL entry is assumed open and each leg is added once
+        assert!((orchestrator.confirm_scale_in(99.0, 0.05, 2.5).await - 0.15).abs() < 1e-12);
+        assert!((orchestrator.confirm_scale_in(98.0, 0.05, 2.5).await - 0.2).abs() < 1e-12);
+        let open = orchestrator.open_size.read().await.unwrap();
+        assert_eq!(open.unit_sol, 0.1);
+        assert!((open.filled_sol - 0.2).abs() < 1e-12);
+        assert_eq!(open.closed_share(open.remaining_sol), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_partial_exit_feeds_kelly() {
+        let orchestrator = create_test_orchestrator().with_sizing(SizingConfig {
+            enabled: true,
+            kelly_fraction: Some(0.5),
+            kelly_min_trades: 2,
+            kelly_window: 10,
+            ..Default::default()
+        });
+        {
+            let mut sizer = orchestrator.sizer.write().await;
+            sizer.record_trade(2.0);
+            sizer.record_trade(-1.0);
+            assert!((sizer.kelly().unwrap().fraction - 0.25).abs() < 1e-12);
+        }
+
+        // Half the position closed at -3%: a half-weight loss
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterLong, 100.0);
+        *orchestrator.open_size.write().await = Some(OpenSize::new(1.0, 5.0));
+        orchestrator.confirm_partial_exit(0.5, 97.0, 0.5).await;
+
+        let kelly = orchestrator.sizer.read().await.kelly().unwrap();
+        assert_eq!(kelly.trades, 3);
+        assert!((kelly.win_rate - 0.4).abs() < 1e-12);
+        assert_eq!(kelly.fraction, 0.0);
+        assert_eq!(orchestrator.open_size.read().await.unwrap().remaining_sol, 0.5);
+
+        // Half of what is left closes a quarter of the position, the exit the last quarter
+        orchestrator.confirm_partial_exit(0.5, 97.0, 0.25).await;
+        orchestrator.confirm_exit(104.0).await;
+
+        // The legs weigh 0.5 + 0.25 + 0.25 = 1.0 on top of the two earlier trades:
+        // wins 1.0 + 0.25 out of a total weight of 3.0
+        let kelly = orchestrator.sizer.read().await.kelly().unwrap();
+        assert_eq!(kelly.trades, 5);
+        assert!((kelly.win_rate - 1.25 / 3.0).abs() < 1e-12);
+        assert!(orchestrator.open_size.read().await.is_none());
+        assert_eq!(orchestrator.strategy.read().await.position(), PositionState::Flat);
+    }
+
+    #[tokio::test]
+    async fn test_state_file_resumes_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("sol-usdc.json");
+
+        let orchestrator = create_test_orchestrator().with_state_file(&path);
+        assert!(!orchestrator.restore_state().await.unwrap());
+        orchestrator.strategy.write().await.confirm_trade(TradeAction::EnterLong, 100.0);
+        orchestrator.strategy.write().await.update_tick(103.0, None);
+        *orchestrator.open_size.write().await = Some(OpenSize::new(0.1, 2.5));
+        orchestrator.save_state().await.unwrap();
+        let before = orchestrator.strategy.read().await.snapshot();
+
+        // A fresh process picks the position up where the last one stopped
+        let restarted = create_test_orchestrator().with_state_file(&path);
+        assert!(restarted.restore_state().await.unwrap());
+        let after = restarted.strategy.read().await.snapshot();
+        assert_eq!(after.position, before.position);
+        assert_eq!(after.layers, before.layers);
+        assert_eq!(after.daily_trades, 1);
+        assert!(after.position_age_secs.is_some());
+        assert_eq!(restarted.open_size.read().await.unwrap().remaining_sol, 0.1);
+        assert!(!restarted.strategy.read().await.is_ready());
+    }
+
     #[tokio::test]
     async fn test_reset_daily() {
         let orchestrator = create_test_orchestrator();
@@ -0,0 +1,401 @@
+//! Paper Portfolio
+//!
+//! Virtual base/quote wallet (SOL/USDC) behind the main bot's paper mode.
+//! Trades run through `PaperTradingEngine` with its balance kept in the quote
+//! token and the base held as the token, so balances, cost basis, fees,
+//! `PaperStats` and the JSON export work exactly as for meme paper trading:
+//! - Fills execute at the live Jupiter quote fetched for each trade
+//!   (`FillModel::Quote`), impact included
+//! - Every fill pays the network, priority and Jito tip costs of a live swap,
+//!   converted to the quote token at the fill price
+//! - The engine's `*_sol` amounts (`sol_amount`, `fee_sol`, `pnl_sol`,
+//!   `total_realized_pnl_sol`, ...) are in the quote token; trades and stats
+//!   carry `quote_currency` so the state file and export say which
+//! - The neutral base allocation is deposited at the first fill price, so
+//!   spot shorts sell from it like they do from a live wallet
+//!
+//! State is saved as JSON after every fill, written to a temporary file and
+//! renamed into place; a restarted paper run picks up its balances and
+//! history. A failed save is logged and the fill still stands, since it
+//! already happened in the virtual wallet.
+//!
+//! Spot shorts (`fill_short`) are booked as their own round trip: the short
+//! sell leaves the allocation's cost basis alone and the cover realizes the
+//! short's proceeds minus its cost, so `PaperStats` counts each short's result.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::adapters::market_data::QuoteSummary;
+use crate::application::state_file::write_atomic;
+use crate::meme::fill_model::{FillCosts, FillModel};
+use crate::meme::paper_trading::{PaperStats, PaperTrade, PaperTradingEngine, TradeSide};
+
+#[derive(Debug, Error)]
+pub enum PaperPortfolioError {
+    #[error("Paper state I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid paper state: {0}")]
+    State(String),
+    #[error("Paper fill rejected: {0}")]
+    Fill(String),
+}
+
+/// Virtual wallet trading one base token against a quote token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperPortfolio {
+    engine: PaperTradingEngine,
+    base_mint: String,
+    base_symbol: String,
+    base_decimals: u8,
+    /// Base allocation not yet deposited, waiting for a first price
+    pending_base: f64,
+    /// Where state is saved after each fill (not persisted itself)
+    #[serde(skip)]
+    state_path: Option<PathBuf>,
+}
+
+impl PaperPortfolio {
+    /// Start with `initial_quote` quote tokens and `initial_base` base tokens
+    pub fn new(
+        base_mint: impl Into<String>,
+        base_symbol: impl Into<String>,
+        base_decimals: u8,
+        quote_symbol: impl Into<String>,
+        quote_decimals: u8,
+        initial_quote: f64,
+        initial_base: f64,
+    ) -> Self {
+        let mut engine = PaperTradingEngine::new(initial_quote)
+            .with_cash(quote_symbol, quote_decimals)
+            .with_fill_model(FillModel::Quote);
+        engine.set_log_trades(false);
+
+        Self {
+            engine,
+            base_mint: base_mint.into(),
+            base_symbol: base_symbol.into(),
+            base_decimals,
+            pending_base: initial_base.max(0.0),
+            state_path: None,
+        }
+    }
+
+    /// Charge `costs` (in SOL) on every fill (default: the network base fee)
+    pub fn with_costs(mut self, costs: FillCosts) -> Self {
+        self.engine = self.engine.with_costs(costs);
+        self
+    }
+
+    /// Resume from the state saved at `path`, or start with `fresh` when there is none;
+    /// either way state is saved back to `path` after each fill
+    pub fn load_or(path: impl Into<PathBuf>, fresh: PaperPortfolio) -> Result<Self, PaperPortfolioError> {
+        let path = path.into();
+        let mut portfolio = if path.exists() {
+            Self::load(&path)?
+        } else {
+            fresh
+        };
+        portfolio.state_path = Some(path);
+        Ok(portfolio)
+    }
+
+    /// Read saved state
+    pub fn load(path: &Path) -> Result<Self, PaperPortfolioError> {
+        let json = std::fs::read_to_string(path)?;
+        let mut portfolio: Self = serde_json::from_str(&json)
+            .map_err(|e| PaperPortfolioError::State(format!("{}: {}", path.display(), e)))?;
+        portfolio.engine.set_log_trades(false);
+        Ok(portfolio)
+    }
+
+    /// Save state to the path given to `load_or` (no-op without one)
+    ///
+    /// The file is replaced atomically, so a crash mid-save keeps the last
+    /// complete state.
+    pub fn save(&self) -> Result<(), PaperPortfolioError> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| PaperPortfolioError::State(e.to_string()))?;
+        write_atomic(path, json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Fill `size` base tokens at the live `quote` for this trade, `price`
+    /// being the reference price in quote per base
+    ///
+    /// `TradeSide::Buy` buys base with quote, `TradeSide::Sell` sells base.
+    pub fn fill(
+        &mut self,
+        side: TradeSide,
+        size: f64,
+        price: f64,
+        quote: QuoteSummary,
+    ) -> Result<PaperTrade, PaperPortfolioError> {
+        self.execute(side, false, size, price, quote)
+    }
+
+    /// Fill a spot short leg: `TradeSide::Sell` opens or adds to the short
+    /// from the base allocation, `TradeSide::Buy` covers it
+    pub fn fill_short(
+        &mut self,
+        side: TradeSide,
+        size: f64,
+        price: f64,
+        quote: QuoteSummary,
+    ) -> Result<PaperTrade, PaperPortfolioError> {
+        self.execute(side, true, size, price, quote)
+    }
+
+    fn execute(
+        &mut self,
+        side: TradeSide,
+        short: bool,
+        size: f64,
+        price: f64,
+        quote: QuoteSummary,
+    ) -> Result<PaperTrade, PaperPortfolioError> {
+        self.seed(price);
+        self.engine.set_sol_value(price);
+        self.engine.record_quote(&self.base_mint, quote);
+
+        let (mint, symbol, decimals) = (self.base_mint.as_str(), self.base_symbol.as_str(), self.base_decimals);
+        let amount = (size * 10_f64.powi(decimals as i32)) as u64;
+        let trade = match (side, short) {
+            (TradeSide::Buy, false) => self.engine.simulate_buy(mint, symbol, decimals, size * price, price),
+            (TradeSide::Buy, true) => self.engine.simulate_cover(mint, symbol, decimals, size * price, price),
+            (TradeSide::Sell, false) => self.engine.simulate_sell(mint, amount, price),
+            (TradeSide::Sell, true) => self.engine.simulate_short(mint, amount, price),
+        }
+        .map_err(PaperPortfolioError::Fill)?;
+
+        if let Err(e) = self.save() {
+            tracing::warn!("Failed to save paper portfolio state: {}", e);
+        }
+        Ok(trade)
+    }
+
+    /// Deposit the base allocation at `price` on first use
+    fn seed(&mut self, price: f64) {
+        if self.pending_base <= 0.0 || price <= 0.0 {
+            return;
+        }
+        let amount = (self.pending_base * 10_f64.powi(self.base_decimals as i32)) as u64;
+        self.engine.deposit(&self.base_mint, &self.base_symbol, self.base_decimals, amount, price);
+        self.pending_base = 0.0;
+    }
+
+    /// Base token symbol
+    pub fn base_symbol(&self) -> &str {
+        &self.base_symbol
+    }
+
+    /// Quote token the balance and all trade amounts are in
+    pub fn quote_currency(&self) -> &str {
+        self.engine.quote_currency()
+    }
+
+    /// Base tokens held, including an allocation not yet deposited
+    pub fn base_balance(&self) -> f64 {
+        self.pending_base
+            + self
+                .engine
+                .get_position(&self.base_mint)
+                .map_or(0.0, |holding| holding.amount_decimal())
+    }
+
+    /// Quote tokens held
+    pub fn quote_balance(&self) -> f64 {
+        self.engine.sol_balance()
+    }
+
+    /// Portfolio value in quote tokens with base at `price`
+    pub fn equity(&self, price: f64) -> f64 {
+        self.quote_balance() + self.base_balance() * price
+    }
+
+    /// Starting value in quote tokens (base allocation at `price` until deposited)
+    pub fn initial_equity(&self, price: f64) -> f64 {
+        self.engine.initial_balance() + self.pending_base * price
+    }
+
+    /// Equity change since the start in quote tokens
+    pub fn pnl(&self, price: f64) -> f64 {
+        self.equity(price) - self.initial_equity(price)
+    }
+
+    /// Price of the latest fill
+    pub fn last_price(&self) -> Option<f64> {
+        self.trades().last().map(|trade| trade.price)
+    }
+
+    pub fn stats(&self) -> &PaperStats {
+        self.engine.get_stats()
+    }
+
+    pub fn trades(&self) -> &[PaperTrade] {
+        self.engine.get_trades()
+    }
+
+    /// Underlying engine, for exports and per-token detail
+    pub fn engine(&self) -> &PaperTradingEngine {
+        &self.engine
+    }
+
+    /// Trade history as JSON (`PaperTrade` list)
+    pub fn export_trades_json(&self) -> Result<String, PaperPortfolioError> {
+        self.engine.export_trades_json().map_err(PaperPortfolioError::State)
+    }
+
+    /// Log balances, PnL at `price` and trade statistics
+    pub fn log_summary(&self, price: f64) {
+        let stats = self.stats();
+        let quote = self.quote_currency();
+        tracing::info!("========== PAPER PORTFOLIO ==========");
+        tracing::info!("{} balance: {:.4}", self.base_symbol, self.base_balance());
+        tracing::info!("{} balance: {:.2}", quote, self.quote_balance());
+        tracing::info!("Equity: {:.2} {} (PnL {:+.2}) @ {:.4}", self.equity(price), quote, self.pnl(price), price);
+        tracing::info!("Trades: {} ({} buys, {} sells)", stats.total_trades, stats.buy_count, stats.sell_count);
+        tracing::info!(
+            "Realized PnL: {:+.2} {}, fees: {:.4} {}",
+            stats.total_realized_pnl_sol, quote, stats.total_fees_sol, quote
+        );
+        tracing::info!("Win Rate: {:.1}%, Profit Factor: {:.2}", stats.win_rate(), stats.profit_factor());
+        tracing::info!("Max Drawdown: {:.2}%", stats.max_drawdown_pct);
+        if let Some(sharpe) = stats.sharpe_ratio() {
+            tracing::info!("Sharpe Ratio: {:.2}", sharpe);
+        }
+        tracing::info!("=====================================");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn quote(input_mint: &str, output_mint: &str, in_amount: u64, out_amount: u64) -> QuoteSummary {
+        QuoteSummary {
+            timestamp: Utc::now(),
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            in_amount,
+            out_amount,
+            price_impact_pct: 0.0,
+            route_labels: vec!["Orca".to_string()],
+            latency_ms: 50,
+        }
+    }
+
+    fn sol_usdc(initial_base: f64) -> PaperPortfolio {
+        PaperPortfolio::new(SOL, "SOL", 9, "USDC", 6, 1000.0, initial_base)
+    }
+
+    #[test]
+    fn test_round_trip_at_quotes() {
+        let mut portfolio = sol_usdc(0.0);
+
+        // 100 USDC buys 0.99 SOL: ~1% worse than the $100 reference
+        let buy = portfolio
+            .fill(TradeSide::Buy, 1.0, 100.0, quote(USDC, SOL, 100_000_000, 990_000_000))
+            .unwrap();
+        assert_eq!(buy.token_amount, 990_000_000);
+        assert_eq!(buy.slippage_bps, 101);
+        // Base fee of 5000 lamports paid in USDC at $100
+        assert!((buy.fee_sol - 0.0005).abs() < 1e-9);
+        assert!((portfolio.quote_balance() - 899.9995).abs() < 1e-6);
+        assert!((portfolio.base_balance() - 0.99).abs() < 1e-9);
+
+        // Sell it all at $110
+        let sell = portfolio
+            .fill(TradeSide::Sell, 0.99, 110.0, quote(SOL, USDC, 990_000_000, 108_900_000))
+            .unwrap();
+        assert!((sell.pnl_sol.unwrap() - (108.9 - 0.00055 - 100.0005)).abs() < 1e-6);
+        assert_eq!(portfolio.base_balance(), 0.0);
+
+        let stats = portfolio.stats();
+        assert_eq!(stats.total_trades, 2);
+        assert_eq!(stats.winning_trades, 1);
+        assert!((stats.total_fees_sol - 0.00105).abs() < 1e-9);
+        assert!((portfolio.pnl(110.0) - sell.pnl_sol.unwrap()).abs() < 1e-6);
+        assert_eq!(portfolio.last_price(), Some(sell.price));
+    }
+
+    #[test]
+    fn test_short_sells_allocation() {
+        let mut portfolio = sol_usdc(1.0);
+        assert_eq!(portfolio.base_balance(), 1.0);
+        assert_eq!(portfolio.initial_equity(100.0), 1100.0);
+
+        // Short 0.5 SOL out of the allocation deposited at $100
+        let short = portfolio
+            .fill_short(TradeSide::Sell, 0.5, 100.0, quote(SOL, USDC, 500_000_000, 50_000_000))
+            .unwrap();
+        assert_eq!(short.pnl_sol, None);
+        assert!((portfolio.base_balance() - 0.5).abs() < 1e-9);
+        assert_eq!(portfolio.initial_equity(90.0), 1100.0);
+
+        // Cover at $90: the short earns $5 of the allocation's $10 loss back, less fees
+        let cover = portfolio
+            .fill_short(TradeSide::Buy, 0.5, 90.0, quote(USDC, SOL, 45_000_000, 500_000_000))
+            .unwrap();
+        let short_pnl = 5.0 - 0.0005 - 0.00045;
+        assert!((cover.pnl_sol.unwrap() - short_pnl).abs() < 1e-6);
+        assert!((portfolio.base_balance() - 1.0).abs() < 1e-9);
+        assert!((portfolio.pnl(90.0) - (-10.0 + short_pnl)).abs() < 1e-6);
+
+        // Stats see one winning round trip, not a loss on the allocation
+        let stats = portfolio.stats();
+        assert_eq!(stats.winning_trades, 1);
+        assert_eq!(stats.losing_trades, 0);
+        assert!((stats.total_realized_pnl_sol - short_pnl).abs() < 1e-6);
+
+        // Shorts beyond the allocation are rejected
+        let err = portfolio
+            .fill_short(TradeSide::Sell, 2.0, 90.0, quote(SOL, USDC, 2_000_000_000, 180_000_000))
+            .unwrap_err();
+        assert!(matches!(err, PaperPortfolioError::Fill(_)));
+    }
+
+    #[test]
+    fn test_state_persists_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("paper").join("portfolio.json");
+
+        let mut portfolio = PaperPortfolio::load_or(&path, sol_usdc(1.0)).unwrap();
+        portfolio
+            .fill(TradeSide::Buy, 1.0, 100.0, quote(USDC, SOL, 100_000_000, 1_000_000_000))
+            .unwrap();
+        assert!(path.exists());
+
+        // A fresh start is ignored once state exists
+        let resumed = PaperPortfolio::load_or(&path, sol_usdc(5.0)).unwrap();
+        assert!((resumed.base_balance() - 2.0).abs() < 1e-9);
+        assert_eq!(resumed.quote_balance(), portfolio.quote_balance());
+        assert_eq!(resumed.trades().len(), 1);
+        assert_eq!(resumed.stats().total_trades, 1);
+
+        let exported: Vec<PaperTrade> = serde_json::from_str(&resumed.export_trades_json().unwrap()).unwrap();
+        assert_eq!(exported[0].side, TradeSide::Buy);
+        assert_eq!(exported[0].quote_currency, "USDC");
+        assert_eq!(resumed.stats().quote_currency, "USDC");
+
+        // A state path that cannot be written does not undo the fill
+        let blocked = dir.path().join("file");
+        std::fs::write(&blocked, "").unwrap();
+        let mut portfolio = PaperPortfolio::load_or(blocked.join("portfolio.json"), sol_usdc(0.0)).unwrap();
+        let trade = portfolio
+            .fill(TradeSide::Buy, 1.0, 100.0, quote(USDC, SOL, 100_000_000, 1_000_000_000))
+            .unwrap();
+        assert_eq!(trade.token_amount, 1_000_000_000);
+        assert!(portfolio.save().is_err());
+        assert_eq!(portfolio.trades().len(), 1);
+    }
+}
@@ -0,0 +1,581 @@
+//! Spread Trader
+//!
+//! Runs a `SpreadStrategy` on two tokens priced in the same quote token and
+//! executes both legs as spot swaps (e.g. mSOL and jitoSOL against SOL).
+//! Selling a leg sells tokens held in the wallet, as with spot shorts.
+//!
+//! Leg risk: the Y leg is executed first and the X leg hedges it.
+//! - Y leg fails: nothing traded, the signal is dropped
+//! - X leg fails after `leg_retries` retries: the Y leg is swapped back
+//! - The unwind fails too: the trader halts with one naked leg, manual review required
+//!
+//! The strategy only records a position once both legs filled. Bought legs
+//! are recorded with the filled output amount, so exits and unwinds sell what
+//! the wallet actually received.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use super::orchestrator::{OrchestratorError, TradingOrchestrator};
+use crate::adapters::jupiter::RequestPriority;
+use crate::ports::price::PricePort;
+use crate::strategy::regime::CandleBuilder;
+use crate::strategy::{SpreadAction, SpreadConfig, SpreadSide, SpreadStrategy};
+
+/// One swap of a spread leg
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegOrder {
+    pub input_mint: String,
+    pub output_mint: String,
+    /// Input amount in raw units
+    pub amount: u64,
+    /// Output expected at the signal price in raw units (paper fills)
+    pub expected_out: u64,
+    pub priority: RequestPriority,
+}
+
+/// Executes the swaps of each leg
+#[async_trait]
+pub trait LegExecutor: Send + Sync {
+    /// Execute `order`, returning the filled output amount in raw units
+    async fn swap(&self, order: &LegOrder) -> Result<u64, OrchestratorError>;
+}
+
+#[async_trait]
+impl LegExecutor for TradingOrchestrator {
+    async fn swap(&self, order: &LegOrder) -> Result<u64, OrchestratorError> {
+        if self.paper_mode() {
+            tracing::info!(
+                "PAPER SWAP - {} {} -> {} {}",
+                order.amount,
+                order.input_mint,
+                order.expected_out,
+                order.output_mint
+            );
+            return Ok(order.expected_out);
+        }
+        self.execute_swap(&order.input_mint, &order.output_mint, order.amount, order.priority)
+            .await
+    }
+}
+
+/// One token of the spread
+#[derive(Clone)]
+pub struct SpreadLeg {
+    /// Label for logs, e.g. "mSOL"
+    pub symbol: String,
+    pub mint: String,
+    pub decimals: u8,
+    /// Price of one whole token in the quote token
+    pub price_source: Arc<dyn PricePort>,
+}
+
+impl SpreadLeg {
+    pub fn new(symbol: impl Into<String>, mint: impl Into<String>, decimals: u8, price_source: Arc<dyn PricePort>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            mint: mint.into(),
+            decimals,
+            price_source,
+        }
+    }
+}
+
+/// Open spread with the token quantities actually held on each leg
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenSpread {
+    pub side: SpreadSide,
+    /// Y tokens bought (long) or sold (short)
+    pub y_qty: f64,
+    /// X tokens sold (long) or bought (short)
+    pub x_qty: f64,
+    /// Hedge ratio at entry
+    pub beta: f64,
+    pub entry_z: f64,
+}
+
+/// Buy or sell `qty` tokens of a leg at `price`
+#[derive(Clone, Copy)]
+struct LegPlan<'a> {
+    leg: &'a SpreadLeg,
+    buy: bool,
+    qty: f64,
+    price: f64,
+}
+
+impl LegPlan<'_> {
+    /// Tokens held (bought) or owed (sold) once the plan filled with `filled` raw output
+    fn filled_qty(&self, filled: u64) -> f64 {
+        if self.buy {
+            filled as f64 / 10f64.powi(self.leg.decimals as i32)
+        } else {
+            self.qty
+        }
+    }
+}
+
+/// Two-leg spread trader
+#[derive(Clone)]
+pub struct SpreadTrader {
+    strategy: Arc<RwLock<SpreadStrategy>>,
+    y: SpreadLeg,
+    x: SpreadLeg,
+    quote_mint: String,
+    quote_decimals: u8,
+    /// Quote tokens on the Y leg per spread; the X leg is beta times that
+    notional: f64,
+    executor: Arc<dyn LegExecutor>,
+    slippage_bps: u16,
+    /// Retries of the hedge leg before unwinding the first leg
+    leg_retries: u32,
+    leg_retry_delay: Duration,
+    timeframe: Duration,
+    poll_interval: Duration,
+    candles: Arc<RwLock<(CandleBuilder, CandleBuilder)>>,
+    open: Arc<RwLock<Option<OpenSpread>>>,
+    /// Reason trading stopped with a naked leg
+    halted: Arc<RwLock<Option<String>>>,
+    is_running: Arc<RwLock<bool>>,
+}
+
+impl SpreadTrader {
+    pub fn new(
+        config: SpreadConfig,
+        y: SpreadLeg,
+        x: SpreadLeg,
+        quote_mint: String,
+        quote_decimals: u8,
+        notional: f64,
+        executor: Arc<dyn LegExecutor>,
+    ) -> Result<Self, OrchestratorError> {
+        config
+            .validate()
+            .map_err(|e| OrchestratorError::ConfigError(e.to_string()))?;
+        if notional <= 0.0 {
+            return Err(OrchestratorError::ConfigError(format!(
+                "spread notional must be > 0, got {}",
+                notional
+            )));
+        }
+
+        let timeframe = Duration::from_secs(300);
+        Ok(Self {
+            strategy: Arc::new(RwLock::new(SpreadStrategy::new(config))),
+            y,
+            x,
+            quote_mint,
+            quote_decimals,
+            notional,
+            executor,
+            slippage_bps: 50,
+            leg_retries: 2,
+            leg_retry_delay: Duration::from_millis(500),
+            timeframe,
+            poll_interval: Duration::from_secs(10),
+            candles: Arc::new(RwLock::new((CandleBuilder::new(timeframe), CandleBuilder::new(timeframe)))),
+            open: Arc::new(RwLock::new(None)),
+            halted: Arc::new(RwLock::new(None)),
+            is_running: Arc::new(RwLock::new(false)),
+        })
+    }
+
+    /// Candle timeframe the strategy runs on
+    pub fn with_timeframe(mut self, timeframe: Duration) -> Self {
+        self.timeframe = timeframe;
+        self.candles = Arc::new(RwLock::new((CandleBuilder::new(timeframe), CandleBuilder::new(timeframe))));
+        self
+    }
+
+    /// Slippage tolerance, also the haircut applied when unwinding a leg
+    pub fn with_slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Retries of the hedge leg and the pause between them
+    pub fn with_leg_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.leg_retries = retries;
+        self.leg_retry_delay = delay;
+        self
+    }
+
+    /// Label for logs, e.g. "mSOL/SOL"
+    pub fn pair_label(&self) -> String {
+        format!("{}/{}", self.y.symbol, self.x.symbol)
+    }
+
+    pub async fn open_spread(&self) -> Option<OpenSpread> {
+        *self.open.read().await
+    }
+
+    /// Reason trading halted, None while trading
+    pub async fn halted(&self) -> Option<String> {
+        self.halted.read().await.clone()
+    }
+
+    /// Poll both legs until stopped
+    pub async fn run(&self) -> Result<(), OrchestratorError> {
+        *self.is_running.write().await = true;
+        tracing::info!(
+            "Starting spread trader {} - timeframe {:?}, notional {} per spread",
+            self.pair_label(),
+            self.timeframe,
+            self.notional
+        );
+
+        while *self.is_running.read().await {
+            if let Err(e) = self.tick().await {
+                tracing::error!("Spread tick error: {}", e);
+                if self.halted.read().await.is_some() {
+                    break;
+                }
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+
+        tracing::info!("Spread trader {} stopped", self.pair_label());
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        *self.is_running.write().await = false;
+    }
+
+    /// Fetch both prices and act on the candles they close
+    pub async fn tick(&self) -> Result<(), OrchestratorError> {
+        let (y_sample, x_sample) = tokio::join!(self.y.price_source.next_price(), self.x.price_source.next_price());
+        let y_price = y_sample.map_err(|e| OrchestratorError::MarketDataError(e.to_string()))?.price;
+        let x_price = x_sample.map_err(|e| OrchestratorError::MarketDataError(e.to_string()))?.price;
+
+        // Both builders share one clock so their candles close together
+        let now = Instant::now();
+        let closed = {
+            let mut candles = self.candles.write().await;
+            (candles.0.update_at(y_price, now), candles.1.update_at(x_price, now))
+        };
+
+        match closed {
+            (Some(y), Some(x)) => self.on_candle(y.close, x.close).await.map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Feed one pair of closes to the strategy and execute its signal
+    pub async fn on_candle(&self, y_price: f64, x_price: f64) -> Result<SpreadAction, OrchestratorError> {
+        if let Some(reason) = self.halted.read().await.clone() {
+            return Err(OrchestratorError::ExecutionError(format!("Spread trading halted: {}", reason)));
+        }
+
+        let Some(action) = self.strategy.write().await.update(y_price, x_price) else {
+            return Ok(SpreadAction::Hold);
+        };
+        let Some(state) = self.strategy.read().await.state() else {
+            return Ok(SpreadAction::Hold);
+        };
+
+        tracing::info!(
+            "{} spread z={:+.2} beta={:.3} cointegrated={} ({:?})",
+            self.pair_label(),
+            state.z_score,
+            state.beta,
+            state.cointegrated,
+            action
+        );
+
+        match action {
+            SpreadAction::Enter(side) => {
+                if state.beta <= 0.0 {
+                    tracing::warn!("Hedge ratio {:.3} is not positive, skipping entry", state.beta);
+                    return Ok(SpreadAction::Hold);
+                }
+                let buy_y = side == SpreadSide::Long;
+                let y = LegPlan { leg: &self.y, buy: buy_y, qty: self.notional / y_price, price: y_price };
+                let x = LegPlan { leg: &self.x, buy: !buy_y, qty: state.beta * self.notional / x_price, price: x_price };
+                let (y_filled, x_filled) = self.execute_legs(y, x, RequestPriority::Entry).await?;
+                *self.open.write().await = Some(OpenSpread {
+                    side,
+                    y_qty: y.filled_qty(y_filled),
+                    x_qty: x.filled_qty(x_filled),
+                    beta: state.beta,
+                    entry_z: state.z_score,
+                });
+            }
+            SpreadAction::Exit => {
+                let Some(open) = *self.open.read().await else {
+                    return Ok(SpreadAction::Hold);
+                };
+                let buy_y = open.side == SpreadSide::Short;
+                self.execute_legs(
+                    LegPlan { leg: &self.y, buy: buy_y, qty: open.y_qty, price: y_price },
+                    LegPlan { leg: &self.x, buy: !buy_y, qty: open.x_qty, price: x_price },
+                    RequestPriority::Exit,
+                )
+                .await?;
+                *self.open.write().await = None;
+            }
+            SpreadAction::Hold => return Ok(action),
+        }
+
+        self.strategy.write().await.confirm(action);
+        Ok(action)
+    }
+
+    /// Swap for `plan`: buying spends quote tokens, selling spends the leg's tokens
+    fn order(&self, plan: &LegPlan<'_>, priority: RequestPriority) -> LegOrder {
+        let tokens = (plan.qty * 10f64.powi(plan.leg.decimals as i32)) as u64;
+        let quote = (plan.qty * plan.price * 10f64.powi(self.quote_decimals as i32)) as u64;
+        if plan.buy {
+            LegOrder {
+                input_mint: self.quote_mint.clone(),
+                output_mint: plan.leg.mint.clone(),
+                amount: quote,
+                expected_out: tokens,
+                priority,
+            }
+        } else {
+            LegOrder {
+                input_mint: plan.leg.mint.clone(),
+                output_mint: self.quote_mint.clone(),
+                amount: tokens,
+                expected_out: quote,
+                priority,
+            }
+        }
+    }
+
+    /// Execute `first`, then hedge it with `second`, unwinding `first` if the hedge never fills
+    /// Returns the filled output of both legs in raw units
+    async fn execute_legs(
+        &self,
+        first: LegPlan<'_>,
+        second: LegPlan<'_>,
+        priority: RequestPriority,
+    ) -> Result<(u64, u64), OrchestratorError> {
+        let first_order = self.order(&first, priority);
+        let first_filled = self.executor.swap(&first_order).await.map_err(|e| {
+            OrchestratorError::ExecutionError(format!("{} leg failed, nothing traded: {}", first.leg.symbol, e))
+        })?;
+
+        let hedge = self.order(&second, priority);
+        let mut last_error = None;
+        for attempt in 0..=self.leg_retries {
+            if attempt > 0 {
+                tokio::time::sleep(self.leg_retry_delay).await;
+            }
+            match self.executor.swap(&hedge).await {
+                Ok(filled) => return Ok((first_filled, filled)),
+                Err(e) => {
+                    tracing::warn!("{} leg attempt {} failed: {}", second.leg.symbol, attempt + 1, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        let hedge_error = last_error.map(|e| e.to_string()).unwrap_or_default();
+
+        tracing::warn!("Unwinding {} leg after {} leg failed", first.leg.symbol, second.leg.symbol);
+        // Swap back what the first leg filled, less a haircut for fees paid from it
+        let haircut = 1.0 - self.slippage_bps as f64 / 10_000.0;
+        let unwind = LegOrder {
+            input_mint: first_order.output_mint.clone(),
+            output_mint: first_order.input_mint.clone(),
+            amount: (first_filled as f64 * haircut) as u64,
+            expected_out: (first_order.amount as f64 * haircut) as u64,
+            priority: RequestPriority::Exit,
+        };
+        match self.executor.swap(&unwind).await {
+            Ok(_) => Err(OrchestratorError::ExecutionError(format!(
+                "{} leg failed ({}), {} leg unwound",
+                second.leg.symbol, hedge_error, first.leg.symbol
+            ))),
+            Err(e) => {
+                let reason = format!(
+                    "naked {} leg: {} leg failed ({}) and the unwind failed ({})",
+                    first.leg.symbol, second.leg.symbol, hedge_error, e
+                );
+                tracing::error!("{} - manual review required", reason);
+                *self.halted.write().await = Some(reason.clone());
+                Err(OrchestratorError::ExecutionError(reason))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::adapters::market_data::ReplayPriceSource;
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const MSOL: &str = "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So";
+    const JITOSOL: &str = "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yyac6Y7kGCPn";
+
+    /// Records every swap, fails swaps from `mint` the next `failures` times,
+    /// fills `fill_ratio` of the expected output (all of it when unset)
+    #[derive(Default)]
+    struct MockExecutor {
+        swaps: Mutex<Vec<LegOrder>>,
+        failing: Mutex<Vec<(String, u32)>>,
+        fill_ratio: Mutex<Option<f64>>,
+    }
+
+    impl MockExecutor {
+        fn fill(&self, ratio: f64) {
+            *self.fill_ratio.lock().unwrap() = Some(ratio);
+        }
+
+        fn fail(&self, input_mint: &str, times: u32) {
+            self.failing.lock().unwrap().push((input_mint.to_string(), times));
+        }
+
+        fn swaps(&self) -> Vec<LegOrder> {
+            self.swaps.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl LegExecutor for MockExecutor {
+        async fn swap(&self, order: &LegOrder) -> Result<u64, OrchestratorError> {
+            let mut failing = self.failing.lock().unwrap();
+            if let Some((_, times)) = failing.iter_mut().find(|(mint, times)| *mint == order.input_mint && *times > 0) {
+                *times -= 1;
+                return Err(OrchestratorError::ExecutionError("route not found".to_string()));
+            }
+            self.swaps.lock().unwrap().push(order.clone());
+            let ratio = self.fill_ratio.lock().unwrap().unwrap_or(1.0);
+            Ok((order.expected_out as f64 * ratio) as u64)
+        }
+    }
+
+    fn spread_trader(executor: Arc<MockExecutor>) -> SpreadTrader {
+        let leg = |symbol: &str, mint: &str| {
+            SpreadLeg::new(symbol, mint, 9, Arc::new(ReplayPriceSource::new(mint, Vec::new())))
+        };
+        SpreadTrader::new(
+            SpreadConfig { window: 20, ..Default::default() },
+            leg("mSOL", MSOL),
+            leg("jitoSOL", JITOSOL),
+            SOL.to_string(),
+            9,
+            1.0,
+            executor,
+        )
+        .unwrap()
+        .with_leg_retries(1, Duration::ZERO)
+    }
+
+    /// mSOL cheapens against jitoSOL by this factor after the warm-up
+    const SHOCK: f64 = 0.9985;
+
+    /// Warm up on a tightly cointegrated pair, then open a long spread
+    async fn warm_up(trader: &SpreadTrader) -> SpreadAction {
+        for i in 0..40 {
+            let wiggle = if i % 2 == 0 { 1.0005 } else { 0.9995 };
+            let x = 1.1 + 0.001 * (i as f64 * 0.7).sin();
+            let action = trader.on_candle(1.2 * (x / 1.1) * wiggle, x).await.unwrap();
+            assert_eq!(action, SpreadAction::Hold);
+        }
+        let x = 1.1 + 0.001 * (40.0 * 0.7_f64).sin();
+        trader.on_candle(1.2 * (x / 1.1) * SHOCK, x).await.unwrap_or(SpreadAction::Hold)
+    }
+
+    #[tokio::test]
+    async fn test_enters_with_both_legs() {
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+
+        assert_eq!(warm_up(&trader).await, SpreadAction::Enter(SpreadSide::Long));
+        let swaps = executor.swaps();
+        assert_eq!(swaps.len(), 2);
+        // Buy mSOL with SOL, then sell jitoSOL for SOL
+        assert_eq!((swaps[0].input_mint.as_str(), swaps[0].output_mint.as_str()), (SOL, MSOL));
+        assert_eq!((swaps[1].input_mint.as_str(), swaps[1].output_mint.as_str()), (JITOSOL, SOL));
+        assert_eq!(swaps[0].amount, 1_000_000_000);
+
+        let open = trader.open_spread().await.unwrap();
+        assert_eq!(open.side, SpreadSide::Long);
+        assert_eq!(trader.strategy.read().await.position(), Some(SpreadSide::Long));
+    }
+
+    #[tokio::test]
+    async fn test_exit_sells_filled_quantity() {
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+        executor.fill(0.99);
+
+        assert_eq!(warm_up(&trader).await, SpreadAction::Enter(SpreadSide::Long));
+        let swaps = executor.swaps();
+        let bought = (swaps[0].expected_out as f64 * 0.99) as u64;
+        let open = trader.open_spread().await.unwrap();
+        assert!((open.y_qty - bought as f64 / 1e9).abs() < 1e-12);
+        // The sold leg owes exactly what was sold
+        assert_eq!((open.x_qty * 1e9) as u64, swaps[1].amount);
+
+        // Reverting spread: sell the mSOL received, not the planned quantity
+        let mut action = SpreadAction::Hold;
+        for _ in 0..20 {
+            action = trader.on_candle(1.2, 1.1).await.unwrap();
+            if action == SpreadAction::Exit {
+                break;
+            }
+        }
+        assert_eq!(action, SpreadAction::Exit);
+        let exit = &executor.swaps()[2];
+        assert_eq!((exit.input_mint.as_str(), exit.amount), (MSOL, bought));
+        assert!(trader.open_spread().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_first_leg_failure_trades_nothing() {
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+        executor.fail(SOL, 1);
+
+        assert_eq!(warm_up(&trader).await, SpreadAction::Hold);
+        assert!(executor.swaps().is_empty());
+        assert!(trader.open_spread().await.is_none());
+        assert_eq!(trader.strategy.read().await.position(), None);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_retried_then_first_leg_unwound() {
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+
+        // One failure is absorbed by the retry
+        executor.fail(JITOSOL, 1);
+        assert_eq!(warm_up(&trader).await, SpreadAction::Enter(SpreadSide::Long));
+        assert_eq!(executor.swaps().len(), 2);
+
+        // Exhausted retries: the mSOL actually bought is sold back, less the haircut
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+        executor.fail(JITOSOL, 2);
+        executor.fill(0.98);
+        assert_eq!(warm_up(&trader).await, SpreadAction::Hold);
+        let swaps = executor.swaps();
+        assert_eq!(swaps.len(), 2);
+        assert_eq!((swaps[1].input_mint.as_str(), swaps[1].output_mint.as_str()), (MSOL, SOL));
+        let filled = (swaps[0].expected_out as f64 * 0.98) as u64;
+        assert_eq!(swaps[1].amount, (filled as f64 * 0.995) as u64);
+        assert_eq!(swaps[1].priority, RequestPriority::Exit);
+        assert!(trader.open_spread().await.is_none());
+        assert!(trader.halted().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_unwind_halts_trading() {
+        let executor = Arc::new(MockExecutor::default());
+        let trader = spread_trader(Arc::clone(&executor));
+        executor.fail(JITOSOL, 2);
+        executor.fail(MSOL, 1);
+
+        assert_eq!(warm_up(&trader).await, SpreadAction::Hold);
+        assert!(trader.halted().await.unwrap().contains("naked mSOL leg"));
+        assert!(trader.on_candle(1.2, 1.1).await.is_err());
+    }
+}
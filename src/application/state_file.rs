@@ -0,0 +1,56 @@
+//! State Files
+//!
+//! Crash-safe writes for the JSON state the bots resume from: the contents
+//! go to a temporary file next to the target, which is then renamed over it.
+//! A crash mid-write leaves the previous state in place instead of a
+//! truncated file that fails to load on the next start.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Replace `path` with `contents` in one step, creating its directory if needed
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = tmp_path(path);
+    let result = std::fs::File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Temporary file in the same directory, so the rename stays on one filesystem
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("sol-usdc.json");
+
+        write_atomic(&path, b"{\"a\":1}").unwrap();
+        write_atomic(&path, b"{\"a\":2}").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"a\":2}");
+        assert!(!tmp_path(&path).exists());
+
+        // A failed write leaves the previous state alone
+        let blocked = dir.path().join("file");
+        std::fs::write(&blocked, "").unwrap();
+        assert!(write_atomic(&blocked.join("state.json"), b"{}").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"a\":2}");
+    }
+}
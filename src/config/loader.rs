@@ -3,7 +3,8 @@
 //! Loads and validates configuration from TOML files matching config.toml structure.
 
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
 // Re-export MemeConfig from meme module for TOML parsing
@@ -22,6 +23,30 @@ pub struct Config {
     pub alerts: AlertsSection,
     #[serde(default)]
     pub jito: JitoSection,
+    /// Market data recording for later replay (optional)
+    #[serde(default)]
+    pub recorder: RecorderSection,
+    /// Virtual wallet for `run --paper`
+    #[serde(default)]
+    pub paper: PaperSection,
+    /// Where the single-pair bot reads prices from (Jupiter quotes by default)
+    #[serde(default)]
+    pub price_source: PriceSourceSection,
+    /// Regime detectors gating entries (ADX only by default)
+    #[serde(default)]
+    pub regime: crate::strategy::RegimeConfig,
+    /// Neutral SOL allocation shorts are taken from, or perp shorts
+    #[serde(default)]
+    pub inventory: crate::domain::InventoryConfig,
+    /// Pairs traded together from one wallet; `[tokens]` alone when empty
+    #[serde(default)]
+    pub pairs: Vec<PairSection>,
+    /// Exposure cap and daily loss limit across `pairs`
+    #[serde(default)]
+    pub portfolio: crate::domain::PortfolioLimits,
+    /// Cointegration spread trading between two tokens (optional)
+    #[serde(default)]
+    pub spread: Option<SpreadSection>,
     /// Meme coin trading configuration (optional)
     #[serde(default)]
     pub meme: Option<MemeConfig>,
@@ -30,20 +55,265 @@ pub struct Config {
 /// Strategy configuration section
 #[derive(Debug, Clone, Deserialize)]
 pub struct StrategySection {
+    /// Strategy to run: "mean_reversion" (default), "ou_reversion" or "ensemble"
+    #[serde(default)]
+    pub kind: crate::strategy::StrategyKind,
     /// Lookback period for rolling mean/std calculation (in candles)
     pub lookback_period: usize,
     /// Z-score threshold for entry (2.0 = moderate, 2.5 = conservative)
     pub z_threshold: f64,
     /// Exit at mean (0.0) or slight overshoot (0.5)
     pub z_exit_threshold: f64,
+    /// Z-score estimator: "simple" (default), "ewma", "robust" (median/MAD), "kalman" or "ou"
+    #[serde(default)]
+    pub zscore_estimator: crate::strategy::ZScoreEstimator,
+    /// EWMA half-life in candles, used when `zscore_estimator = "ewma"`
+    #[serde(default = "default_ewma_half_life")]
+    pub ewma_half_life: f64,
+    /// Kalman fair value settings, used when `zscore_estimator = "kalman"`
+    #[serde(default)]
+    pub kalman: crate::strategy::KalmanConfig,
     /// Minimum volume percentile to trade (filter low-liquidity moments)
     pub min_volume_percentile: f64,
     /// Maximum spread in basis points (0.3% = 30 bps)
     pub max_spread_bps: u32,
     /// Cooldown between trades in seconds
     pub cooldown_seconds: u64,
-    /// Timeframe for candles ("4h" or "1d")
+    /// Timeframe of the candles the z-score is computed on (see SUPPORTED_TIMEFRAMES)
     pub timeframe: String,
+    /// OU settings, used when `kind = "ou_reversion"` or `zscore_estimator = "ou"`
+    /// `dt_minutes` is always derived from `timeframe`
+    #[serde(default)]
+    pub ou: crate::strategy::OuStrategyConfig,
+    /// RSI / MACD / moving average settings; all three with standard periods
+    /// when the section is omitted, only the listed ones when present
+    #[serde(default)]
+    pub indicators: crate::ports::strategy::StrategyParams,
+    /// Conditions on other timeframes that must all agree before entering
+    #[serde(default)]
+    pub timeframes: Vec<TimeframeRuleSection>,
+    /// Scale-in legs and partial take-profit levels (disabled when omitted)
+    #[serde(default)]
+    pub scaling: crate::strategy::ScalingConfig,
+    /// Voting members and thresholds, used when `kind = "ensemble"`
+    #[serde(default)]
+    pub ensemble: crate::strategy::EnsembleConfig,
+    /// Save the open position and its stops here after every tick and resume
+    /// it on restart (nothing is saved when unset)
+    #[serde(default)]
+    pub state_file: Option<String>,
+}
+
+/// One `[[strategy.timeframes]]` entry condition
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimeframeRuleSection {
+    /// Candle timeframe the condition is evaluated on (see SUPPORTED_TIMEFRAMES)
+    pub timeframe: String,
+    /// Candles in the rolling z-score window
+    pub lookback: Option<usize>,
+    /// Block entries while |z| on this timeframe exceeds this
+    pub max_abs_zscore: Option<f64>,
+    /// Require z at least this far from the mean in the entry direction
+    pub min_entry_zscore: Option<f64>,
+    /// Block entries while ADX on this timeframe exceeds this
+    pub max_adx: Option<f64>,
+}
+
+impl TimeframeRuleSection {
+    /// Strategy-layer rule for this section
+    pub fn to_rule(&self) -> Result<crate::strategy::TimeframeRule, ConfigError> {
+        let defaults = crate::strategy::TimeframeRule::new(parse_timeframe(&self.timeframe)?.as_secs());
+        Ok(crate::strategy::TimeframeRule {
+            lookback: self.lookback.unwrap_or(defaults.lookback),
+            max_abs_zscore: self.max_abs_zscore,
+            min_entry_zscore: self.min_entry_zscore,
+            max_adx: self.max_adx,
+            ..defaults
+        })
+    }
+}
+
+/// One `[[pairs]]` entry; unset overrides inherit `[strategy]` and `[risk]`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PairSection {
+    /// Pair name for logs and status, e.g. "JUP/USDC"
+    pub symbol: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    #[serde(default = "default_base_decimals")]
+    pub base_decimals: u8,
+    #[serde(default = "default_quote_decimals")]
+    pub quote_decimals: u8,
+    /// Trade size in base tokens per signal (default `risk.trade_size_sol`)
+    pub trade_size: Option<f64>,
+    pub lookback_period: Option<usize>,
+    pub z_threshold: Option<f64>,
+    pub z_exit_threshold: Option<f64>,
+    /// Strategy candle timeframe (see SUPPORTED_TIMEFRAMES)
+    pub timeframe: Option<String>,
+    pub max_position_pct: Option<f64>,
+    pub stop_loss_pct: Option<f64>,
+    pub take_profit_pct: Option<f64>,
+    /// Base tokens held at zero exposure for spot shorts (see `[inventory]`)
+    pub neutral_base: Option<f64>,
+}
+
+fn default_base_decimals() -> u8 {
+    9 // SOL
+}
+
+fn default_quote_decimals() -> u8 {
+    6 // USDC
+}
+
+impl PairSection {
+    /// Strategy candle timeframe, `[strategy] timeframe` unless overridden
+    pub fn timeframe_duration(&self, strategy: &StrategySection) -> Result<Duration, ConfigError> {
+        parse_timeframe(self.timeframe.as_deref().unwrap_or(&strategy.timeframe))
+    }
+
+    /// Inventory for this pair: `[inventory]` with the pair's neutral allocation
+    /// `neutral_base_sol` only carries over to pairs on the `[tokens]` base mint
+    pub fn inventory_config(&self, config: &Config) -> crate::domain::InventoryConfig {
+        let inherited = if self.base_mint == config.tokens.base_mint {
+            config.inventory.neutral_base_sol
+        } else {
+            0.0
+        };
+        crate::domain::InventoryConfig {
+            neutral_base_sol: self.neutral_base.unwrap_or(inherited),
+            ..config.inventory.clone()
+        }
+    }
+
+    /// Strategy config for this pair: `base` with the overrides applied
+    pub fn strategy_config(
+        &self,
+        base: &crate::strategy::params::StrategyConfig,
+        strategy: &StrategySection,
+    ) -> Result<crate::strategy::params::StrategyConfig, ConfigError> {
+        let mut config = base.clone();
+        config.lookback_period = self.lookback_period.unwrap_or(config.lookback_period);
+        config.z_threshold = self.z_threshold.unwrap_or(config.z_threshold);
+        config.z_exit_threshold = self.z_exit_threshold.unwrap_or(config.z_exit_threshold);
+        config.risk.max_position_pct = self.max_position_pct.unwrap_or(config.risk.max_position_pct);
+        config.risk.stop_loss_pct = self.stop_loss_pct.unwrap_or(config.risk.stop_loss_pct);
+        config.risk.take_profit_pct = self.take_profit_pct.unwrap_or(config.risk.take_profit_pct);
+        config.ou.dt_minutes = self.timeframe_duration(strategy)?.as_secs_f64() / 60.0;
+        Ok(config)
+    }
+}
+
+/// `[spread]`: two tokens priced in one quote token, traded as a cointegrated spread
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpreadSection {
+    /// Y leg, bought when the spread is long (e.g. mSOL)
+    pub y_symbol: String,
+    pub y_mint: String,
+    #[serde(default = "default_base_decimals")]
+    pub y_decimals: u8,
+    /// X leg, the hedge (e.g. jitoSOL)
+    pub x_symbol: String,
+    pub x_mint: String,
+    #[serde(default = "default_base_decimals")]
+    pub x_decimals: u8,
+    /// Token both legs are priced and traded against (SOL by default)
+    #[serde(default = "default_spread_quote_mint")]
+    pub quote_mint: String,
+    #[serde(default = "default_base_decimals")]
+    pub quote_decimals: u8,
+    /// Quote tokens on the Y leg per spread; the X leg is the hedge ratio times that
+    pub notional: f64,
+    /// Candle timeframe (default `[strategy] timeframe`)
+    pub timeframe: Option<String>,
+    /// Retries of the hedge leg before the first leg is unwound
+    #[serde(default = "default_leg_retries")]
+    pub leg_retries: u32,
+    /// Hedge ratio, z-score and cointegration test settings
+    #[serde(flatten)]
+    pub strategy: crate::strategy::SpreadConfig,
+}
+
+fn default_spread_quote_mint() -> String {
+    "So11111111111111111111111111111111111111112".to_string()
+}
+
+fn default_leg_retries() -> u32 {
+    2
+}
+
+impl SpreadSection {
+    /// Spread candle timeframe, `[strategy] timeframe` unless overridden
+    pub fn timeframe_duration(&self, strategy: &StrategySection) -> Result<Duration, ConfigError> {
+        parse_timeframe(self.timeframe.as_deref().unwrap_or(&strategy.timeframe))
+    }
+
+    fn validate(&self, strategy: &StrategySection) -> Result<(), ConfigError> {
+        if self.y_mint == self.x_mint {
+            return Err(ConfigError::ValidationError("spread legs must be different tokens".to_string()));
+        }
+        if self.notional <= 0.0 {
+            return Err(ConfigError::ValidationError(format!(
+                "spread notional must be > 0, got {}",
+                self.notional
+            )));
+        }
+        self.timeframe_duration(strategy)?;
+        self.strategy.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))
+    }
+}
+
+/// Candle timeframes accepted in `[strategy] timeframe`, with their length in seconds
+pub const SUPPORTED_TIMEFRAMES: &[(&str, u64)] = &[
+    ("1m", 60),
+    ("3m", 180),
+    ("5m", 300),
+    ("15m", 900),
+    ("30m", 1_800),
+    ("1h", 3_600),
+    ("2h", 7_200),
+    ("4h", 14_400),
+    ("1d", 86_400),
+];
+
+/// Parse a timeframe string such as "3m" or "4h" into a candle period
+pub fn parse_timeframe(timeframe: &str) -> Result<Duration, ConfigError> {
+    let normalized = timeframe.trim().to_lowercase();
+    SUPPORTED_TIMEFRAMES
+        .iter()
+        .find(|(name, _)| *name == normalized)
+        .map(|(_, secs)| Duration::from_secs(*secs))
+        .ok_or_else(|| {
+            let supported: Vec<&str> = SUPPORTED_TIMEFRAMES.iter().map(|(name, _)| *name).collect();
+            ConfigError::ValidationError(format!(
+                "timeframe must be one of [{}], got \"{}\"",
+                supported.join(", "),
+                timeframe
+            ))
+        })
+}
+
+fn default_ewma_half_life() -> f64 {
+    20.0
+}
+
+impl StrategySection {
+    /// Candle period for the configured timeframe
+    pub fn timeframe_duration(&self) -> Result<Duration, ConfigError> {
+        parse_timeframe(&self.timeframe)
+    }
+
+    /// State file of one `[[pairs]]` entry: `state_file` with the symbol before the extension
+    pub fn pair_state_file(&self, symbol: &str) -> Option<PathBuf> {
+        let path = Path::new(self.state_file.as_ref()?);
+        let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let mut name = format!("{}-{}", stem, symbol.replace('/', "-"));
+        if let Some(ext) = path.extension() {
+            name = format!("{}.{}", name, ext.to_string_lossy());
+        }
+        Some(path.with_file_name(name))
+    }
 }
 
 /// Risk management configuration section
@@ -64,6 +334,12 @@ pub struct RiskSection {
     /// Trade size in SOL per signal (e.g., 0.1 = trade 0.1 SOL)
     #[serde(default = "default_trade_size_sol")]
     pub trade_size_sol: f64,
+    /// Equity-based position sizing (fixed `trade_size_sol` when disabled)
+    #[serde(default)]
+    pub sizing: crate::strategy::SizingConfig,
+    /// Trailing and break-even stops (disabled by default)
+    #[serde(default)]
+    pub trailing: crate::strategy::TrailingStopConfig,
 }
 
 fn default_trade_size_sol() -> f64 {
@@ -99,6 +375,10 @@ pub struct JupiterSection {
     pub max_priority_fee_lamports: u64,
     /// Use dynamic compute unit limits
     pub dynamic_compute_units: bool,
+    /// Shared request budget across all Jupiter endpoints
+    /// Defaults to the API key tier (1 req/s free, 10 req/s with key)
+    #[serde(default)]
+    pub requests_per_second: Option<f64>,
 }
 
 /// Solana RPC configuration section
@@ -181,6 +461,89 @@ impl Default for JitoSection {
     }
 }
 
+/// Market data recorder configuration section
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecorderSection {
+    /// Record every price sample and quote summary to JSON-lines files
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory for recordings
+    #[serde(default = "default_recorder_dir")]
+    pub data_dir: String,
+    /// Rotate files once they reach this size in MB
+    #[serde(default = "default_recorder_max_file_mb")]
+    pub max_file_mb: u64,
+}
+
+fn default_recorder_dir() -> String {
+    "data/recordings".to_string()
+}
+
+fn default_recorder_max_file_mb() -> u64 {
+    64
+}
+
+impl Default for RecorderSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            data_dir: default_recorder_dir(),
+            max_file_mb: default_recorder_max_file_mb(),
+        }
+    }
+}
+
+/// Live price feed for the `[tokens]` pair
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSourceKind {
+    /// Jupiter quote for a small trade
+    #[default]
+    Jupiter,
+    /// Median mid price of AMM pools read over RPC
+    Onchain,
+}
+
+/// Price source section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PriceSourceSection {
+    #[serde(default)]
+    pub kind: PriceSourceKind,
+    /// Pool addresses for `onchain`; the default SOL/USDC pools when empty
+    #[serde(default)]
+    pub pools: Vec<String>,
+}
+
+/// Paper trading wallet section (`run --paper`)
+///
+/// The base allocation is `inventory.neutral_base_sol`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperSection {
+    /// Starting quote balance (USDC)
+    #[serde(default = "default_paper_initial_quote")]
+    pub initial_quote: f64,
+    /// Balances and trade history, resumed when the file exists
+    #[serde(default = "default_paper_state_file")]
+    pub state_file: String,
+}
+
+fn default_paper_initial_quote() -> f64 {
+    1000.0
+}
+
+fn default_paper_state_file() -> String {
+    "data/paper_portfolio.json".to_string()
+}
+
+impl Default for PaperSection {
+    fn default() -> Self {
+        Self {
+            initial_quote: default_paper_initial_quote(),
+            state_file: default_paper_state_file(),
+        }
+    }
+}
+
 /// Configuration errors
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -218,6 +581,50 @@ impl Config {
             )));
         }
 
+        self.strategy.timeframe_duration()?;
+
+        if self.strategy.ewma_half_life <= 0.0 {
+            return Err(ConfigError::ValidationError(format!(
+                "ewma_half_life must be > 0, got {}",
+                self.strategy.ewma_half_life
+            )));
+        }
+
+        self.strategy.indicators.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        for section in &self.strategy.timeframes {
+            section.to_rule()?.validate()
+                .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+        }
+
+        self.strategy.scaling.validate(self.strategy.z_threshold, self.strategy.z_exit_threshold)
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        if self.strategy.zscore_estimator == crate::strategy::ZScoreEstimator::Kalman {
+            self.strategy.kalman.validate()
+                .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+        }
+
+        if self.strategy.kind == crate::strategy::StrategyKind::OuReversion {
+            self.strategy.ou.validate()
+                .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+        }
+
+        if self.strategy.kind == crate::strategy::StrategyKind::Ensemble {
+            let ensemble = &self.strategy.ensemble;
+            ensemble.validate()
+                .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+            if ensemble.has_member(crate::strategy::EnsembleSignal::Kalman) {
+                self.strategy.kalman.validate()
+                    .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+            }
+            if ensemble.has_member(crate::strategy::EnsembleSignal::Ou) {
+                self.strategy.ou.validate()
+                    .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+            }
+        }
+
         if self.strategy.min_volume_percentile < 0.0
             || self.strategy.min_volume_percentile > 100.0
         {
@@ -276,6 +683,12 @@ impl Config {
             ));
         }
 
+        if matches!(self.jupiter.requests_per_second, Some(rps) if rps <= 0.0) {
+            return Err(ConfigError::ValidationError(
+                "jupiter.requests_per_second must be > 0".to_string(),
+            ));
+        }
+
         // Validate Solana
         if self.solana.rpc_url.is_empty() {
             return Err(ConfigError::ValidationError(
@@ -289,6 +702,49 @@ impl Config {
             ));
         }
 
+        // Validate recorder
+        if self.recorder.enabled && self.recorder.max_file_mb == 0 {
+            return Err(ConfigError::ValidationError(
+                "recorder.max_file_mb must be > 0".to_string(),
+            ));
+        }
+
+        // Validate price source
+        if self.price_source.kind == PriceSourceKind::Onchain
+            && self.price_source.pools.is_empty()
+            && self.tokens.pair_symbol != "SOL/USDC"
+        {
+            return Err(ConfigError::ValidationError(
+                "price_source.pools is required for onchain prices of pairs other than SOL/USDC".to_string(),
+            ));
+        }
+
+        // Validate paper wallet
+        if self.paper.initial_quote < 0.0 {
+            return Err(ConfigError::ValidationError(
+                "paper.initial_quote must be >= 0".to_string(),
+            ));
+        }
+
+        self.risk.sizing.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        self.risk.trailing.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        // Validate regime detectors
+        self.regime.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        self.inventory.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        self.validate_pairs()?;
+
+        if let Some(ref spread) = self.spread {
+            spread.validate(&self.strategy)?;
+        }
+
         // Validate meme config if present
         if let Some(ref meme) = self.meme {
             meme.validate()
@@ -298,6 +754,31 @@ impl Config {
         Ok(())
     }
 
+    /// Validate `[[pairs]]` overrides and `[portfolio]` limits
+    fn validate_pairs(&self) -> Result<(), ConfigError> {
+        if self.pairs.is_empty() {
+            return Ok(());
+        }
+
+        self.portfolio.validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
+        let base = crate::strategy::params::StrategyConfig::from(self);
+        let mut symbols = std::collections::HashSet::new();
+        for pair in &self.pairs {
+            if !symbols.insert(pair.symbol.as_str()) {
+                return Err(ConfigError::ValidationError(format!("duplicate pair symbol {}", pair.symbol)));
+            }
+            if pair.trade_size.is_some_and(|size| size <= 0.0) {
+                return Err(ConfigError::ValidationError(format!("pair {}: trade_size must be > 0", pair.symbol)));
+            }
+            pair.strategy_config(&base, &self.strategy)?
+                .validate()
+                .map_err(|e| ConfigError::ValidationError(format!("pair {}: {}", pair.symbol, e)))?;
+        }
+        Ok(())
+    }
+
     /// Get meme config or default
     pub fn meme_config(&self) -> MemeConfig {
         self.meme.clone().unwrap_or_default()
@@ -322,17 +803,37 @@ impl JupiterSection {
         // Fall back to environment variable
         std::env::var("JUPITER_API_KEY").ok()
     }
+
+    /// Gateway settings for the configured API key tier and rate override
+    pub fn gateway_config(&self) -> crate::adapters::jupiter::GatewayConfig {
+        let config = crate::adapters::jupiter::GatewayConfig::for_api_key(self.get_api_key());
+        match self.requests_per_second {
+            Some(rps) => config.with_requests_per_second(rps),
+            None => config,
+        }
+    }
 }
 
 // Conversion from Config to StrategyConfig
 impl From<&Config> for crate::strategy::params::StrategyConfig {
     fn from(config: &Config) -> Self {
-        use crate::strategy::params::{FilterConfig, RiskConfig, StrategyConfig};
+        use crate::strategy::params::{FilterConfig, OuStrategyConfig, RiskConfig, StrategyConfig};
+
+        // Invalid timeframes are rejected by validate(); fall back to the section value
+        let dt_minutes = config
+            .strategy
+            .timeframe_duration()
+            .map(|d| d.as_secs_f64() / 60.0)
+            .unwrap_or(config.strategy.ou.dt_minutes);
 
         StrategyConfig {
+            kind: config.strategy.kind,
             lookback_period: config.strategy.lookback_period,
             z_threshold: config.strategy.z_threshold,
             z_exit_threshold: config.strategy.z_exit_threshold,
+            zscore_estimator: config.strategy.zscore_estimator,
+            ewma_half_life: config.strategy.ewma_half_life,
+            kalman: config.strategy.kalman.clone(),
             cooldown_seconds: config.strategy.cooldown_seconds,
             risk: RiskConfig {
                 max_position_pct: config.risk.max_position_pct,
@@ -341,11 +842,26 @@ impl From<&Config> for crate::strategy::params::StrategyConfig {
                 max_daily_trades: config.risk.max_daily_trades,
                 max_daily_loss_pct: config.risk.max_daily_loss_pct,
                 time_stop_hours: config.risk.time_stop_hours as f64,
+                trailing: config.risk.trailing.clone(),
             },
             filters: FilterConfig {
                 min_volume_percentile: config.strategy.min_volume_percentile,
                 max_spread_bps: config.strategy.max_spread_bps,
             },
+            ou: OuStrategyConfig {
+                dt_minutes,
+                ..config.strategy.ou.clone()
+            },
+            indicators: config.strategy.indicators.clone(),
+            // Invalid rules are rejected by validate()
+            timeframes: config
+                .strategy
+                .timeframes
+                .iter()
+                .filter_map(|section| section.to_rule().ok())
+                .collect(),
+            scaling: config.strategy.scaling.clone(),
+            ensemble: config.strategy.ensemble.clone(),
         }
     }
 }
@@ -783,4 +1299,546 @@ log_file = "logs/butters.log"
         assert_eq!(config.jito.tip_lamports, 10_000);
         assert_eq!(config.jito.api_token, None);
     }
+
+    #[test]
+    fn test_parse_timeframe() {
+        assert_eq!(parse_timeframe("1m").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_timeframe("3m").unwrap(), Duration::from_secs(180));
+        assert_eq!(parse_timeframe("4h").unwrap(), Duration::from_secs(14_400));
+        assert_eq!(parse_timeframe(" 1D ").unwrap(), Duration::from_secs(86_400));
+
+        assert!(matches!(parse_timeframe("7m"), Err(ConfigError::ValidationError(_))));
+        assert!(matches!(parse_timeframe(""), Err(ConfigError::ValidationError(_))));
+        assert!(matches!(parse_timeframe("3 minutes"), Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_invalid_timeframe_rejected() {
+        let invalid_config = create_valid_config().replace("timeframe = \"4h\"", "timeframe = \"90s\"");
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid_config.as_bytes()).unwrap();
+
+        let result = load_config(file.path());
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigError::ValidationError(msg) if msg.contains("timeframe")
+        ));
+    }
+
+    #[test]
+    fn test_timeframe_duration() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(
+            config.strategy.timeframe_duration().unwrap(),
+            Duration::from_secs(4 * 3600)
+        );
+    }
+
+    #[test]
+    fn test_recorder_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        // Disabled by default
+        assert!(!config.recorder.enabled);
+        assert_eq!(config.recorder.data_dir, "data/recordings");
+        assert_eq!(config.recorder.max_file_mb, 64);
+
+        let with_recorder = format!(
+            "{}\n[recorder]\nenabled = true\ndata_dir = \"/tmp/rec\"\nmax_file_mb = 8\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_recorder.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        assert!(config.recorder.enabled);
+        assert_eq!(config.recorder.data_dir, "/tmp/rec");
+        assert_eq!(config.recorder.max_file_mb, 8);
+    }
+
+    #[test]
+    fn test_paper_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        assert_eq!(config.paper.initial_quote, 1000.0);
+        assert_eq!(config.paper.state_file, "data/paper_portfolio.json");
+
+        let with_paper = format!(
+            "{}\n[paper]\ninitial_quote = 250.0\nstate_file = \"/tmp/paper.json\"\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_paper.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        assert_eq!(config.paper.initial_quote, 250.0);
+        assert_eq!(config.paper.state_file, "/tmp/paper.json");
+
+        let negative = format!("{}\n[paper]\ninitial_quote = -1.0\n", create_valid_config());
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(negative.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_price_source_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.price_source.kind, PriceSourceKind::Jupiter);
+        assert!(config.price_source.pools.is_empty());
+
+        let onchain = format!(
+            "{}\n[price_source]\nkind = \"onchain\"\npools = [\"58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2\"]\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(onchain.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.price_source.kind, PriceSourceKind::Onchain);
+        assert_eq!(config.price_source.pools.len(), 1);
+
+        // Default pools only cover SOL/USDC
+        let other_pair = format!("{}\n[price_source]\nkind = \"onchain\"\n", create_valid_config())
+            .replace("pair_symbol = \"SOL/USDC\"", "pair_symbol = \"JUP/USDC\"");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(other_pair.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_jupiter_requests_per_second_override() {
+        let with_rate = create_valid_config().replacen(
+            "dynamic_compute_units = true",
+            "dynamic_compute_units = true\nrequests_per_second = 4.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_rate.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        assert_eq!(config.jupiter.requests_per_second, Some(4.0));
+        assert_eq!(config.jupiter.gateway_config().requests_per_second, 4.0);
+
+        let invalid = create_valid_config().replacen(
+            "dynamic_compute_units = true",
+            "dynamic_compute_units = true\nrequests_per_second = 0.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_strategy_kind_selection() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.strategy.kind, crate::strategy::StrategyKind::MeanReversion);
+
+        let with_ou = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\nkind = \"ou_reversion\"\n\n[strategy.ou]\nz_entry_threshold = -3.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_ou.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        assert_eq!(strategy_config.kind, crate::strategy::StrategyKind::OuReversion);
+        assert_eq!(strategy_config.ou.z_entry_threshold, -3.0);
+        assert_eq!(strategy_config.ou.dt_minutes, 240.0);
+
+        let with_ou_estimator = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\nzscore_estimator = \"ou\"\n\n[strategy.ou]\ntime_stop_half_lives = 2.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_ou_estimator.as_bytes()).unwrap();
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&load_config(file.path()).unwrap());
+        assert_eq!(strategy_config.zscore_estimator, crate::strategy::ZScoreEstimator::Ou);
+        assert_eq!(strategy_config.ou.time_stop_half_lives, 2.0);
+        assert!(strategy_config.ou.confidence_sizing);
+    }
+
+    #[test]
+    fn test_ensemble_section() {
+        use crate::strategy::{EnsembleMember, EnsembleSignal, StrategyKind};
+
+        let with_ensemble = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\nkind = \"ensemble\"\n\n[strategy.ensemble]\nentry_score = 0.6\n\
+             members = [{ signal = \"zscore\", weight = 2.0 }, { signal = \"rsi\" }]",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_ensemble.as_bytes()).unwrap();
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&load_config(file.path()).unwrap());
+        assert_eq!(strategy_config.kind, StrategyKind::Ensemble);
+        assert_eq!(strategy_config.ensemble.entry_score, 0.6);
+        assert_eq!(strategy_config.ensemble.horizon_candles, 5);
+        assert_eq!(
+            strategy_config.ensemble.members,
+            vec![EnsembleMember::new(EnsembleSignal::ZScore, 2.0), EnsembleMember::new(EnsembleSignal::Rsi, 1.0)]
+        );
+        assert!(strategy_config.validate().is_ok());
+
+        let invalid = with_ensemble.replace("entry_score = 0.6", "entry_score = 0.6\nexit_score = 0.8");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_strategy_indicator_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert!(config.strategy.indicators.rsi.is_some());
+        assert!(config.strategy.indicators.macd.is_some());
+
+        let rsi_only = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\n\n[strategy.indicators.rsi]\nperiod = 7\noverbought = 80.0\noversold = 20.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(rsi_only.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        assert_eq!(strategy_config.indicators.rsi.unwrap().period, 7);
+        assert!(strategy_config.indicators.macd.is_none());
+
+        let invalid = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\n\n[strategy.indicators.rsi]\nperiod = 0\noverbought = 80.0\noversold = 20.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_zscore_estimator_selection() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.strategy.zscore_estimator, crate::strategy::ZScoreEstimator::Simple);
+
+        let ewma = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\nzscore_estimator = \"ewma\"\newma_half_life = 12.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(ewma.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        assert_eq!(strategy_config.zscore_estimator, crate::strategy::ZScoreEstimator::Ewma);
+        assert_eq!(strategy_config.ewma_half_life, 12.0);
+
+        let kalman = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\nzscore_estimator = \"kalman\"\n\n[strategy.kalman]\nprocess_noise = 0.05",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(kalman.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        assert_eq!(strategy_config.zscore_estimator, crate::strategy::ZScoreEstimator::Kalman);
+        assert_eq!(strategy_config.kalman.process_noise, 0.05);
+        assert_eq!(strategy_config.kalman.noise_half_life, 50.0);
+
+        let invalid = kalman.replace("process_noise = 0.05", "process_noise = -1.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_regime_section() {
+        use crate::strategy::regime::{CombineMode, RegimeDetectorKind};
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.regime.detectors, vec![RegimeDetectorKind::Adx]);
+
+        let with_regime = format!(
+            "{}\n[regime]\ndetectors = [\"adx\", \"hurst\", \"variance_ratio\"]\ncombine = \"minimum\"\n\n[regime.variance_ratio]\nperiod = 8\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_regime.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.regime.detectors.len(), 3);
+        assert_eq!(config.regime.combine, CombineMode::Minimum);
+        assert_eq!(config.regime.variance_ratio.period, 8);
+        assert_eq!(config.regime.adx, crate::strategy::AdxConfig::crypto_optimized());
+        assert_eq!(config.regime.build().components().len(), 3);
+
+        assert!(config.regime.build_volatility().is_none());
+
+        let with_volatility = format!(
+            "{}\n[regime.volatility]\nenabled = true\nestimator = \"parkinson\"\nwindow = 10\nbaseline_window = 100\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_volatility.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        let volatility = config.regime.build_volatility().unwrap();
+        assert_eq!(volatility.config().estimator, crate::strategy::regime::VolatilityEstimator::Parkinson);
+        assert_eq!(volatility.config().baseline_window, 100);
+
+        let invalid = format!(
+            "{}\n[regime.volatility]\nenabled = true\nwindow = 50\nbaseline_window = 20\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+
+        let invalid = format!("{}\n[regime]\ndetectors = []\n", create_valid_config());
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+
+        let with_adx = format!("{}\n[regime.adx]\nperiod = 14\nexit_threshold = 35.0\n", create_valid_config());
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_adx.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.regime.adx.period, 14);
+        assert_eq!(config.regime.adx.exit_threshold, 35.0);
+        assert_eq!(config.regime.adx.entry_threshold, 20.0);
+
+        let invalid = with_adx.replace("exit_threshold = 35.0", "exit_threshold = 15.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_risk_sizing_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert!(!config.risk.sizing.enabled);
+
+        let with_sizing = create_valid_config().replacen(
+            "trade_size_sol = 0.1",
+            "trade_size_sol = 0.1\n\n[risk.sizing]\nenabled = true\ntarget_volatility = 0.002\nkelly_fraction = 0.25",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_sizing.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert!(config.risk.sizing.enabled);
+        assert_eq!(config.risk.sizing.target_volatility, Some(0.002));
+        assert_eq!(config.risk.sizing.kelly_fraction, Some(0.25));
+        assert_eq!(config.risk.sizing.kelly_min_trades, 20);
+
+        let invalid = with_sizing.replace("kelly_fraction = 0.25", "kelly_fraction = 2.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_risk_trailing_section() {
+        let with_trailing = create_valid_config().replacen(
+            "trade_size_sol = 0.1",
+            "trade_size_sol = 0.1\n\n[risk.trailing]\nactivation_pct = 0.6\natr_multiple = 2.5\nbreak_even_reversion = 0.5",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_trailing.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        let trailing = &strategy_config.risk.trailing;
+        assert_eq!(trailing.activation_pct, Some(0.6));
+        assert_eq!(trailing.atr_multiple, Some(2.5));
+        assert_eq!(trailing.break_even_reversion, Some(0.5));
+        assert_eq!(trailing.atr_period, 14);
+
+        let invalid = with_trailing.replace("break_even_reversion = 0.5", "break_even_reversion = 2.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_inventory_section() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.inventory, crate::domain::InventoryConfig::default());
+
+        let with_inventory = format!(
+            "{}\n[inventory]\nshort_mode = \"perp\"\nneutral_base_sol = 2.0\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_inventory.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.inventory.short_mode, crate::domain::ShortMode::Perp);
+        assert_eq!(config.inventory.neutral_base_sol, 2.0);
+
+        let invalid = with_inventory.replace("neutral_base_sol = 2.0", "neutral_base_sol = -1.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_pairs_section() {
+        let with_pairs = format!(
+            "{}\n[portfolio]\nmax_total_exposure_pct = 12.0\n\n\
+             [[pairs]]\nsymbol = \"SOL/USDC\"\nbase_mint = \"So11111111111111111111111111111111111111112\"\nquote_mint = \"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v\"\n\n\
+             [[pairs]]\nsymbol = \"JUP/USDC\"\nbase_mint = \"JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN\"\nquote_mint = \"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v\"\n\
+             base_decimals = 6\ntrade_size = 60.0\nz_threshold = 2.5\ntimeframe = \"5m\"\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_pairs.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+        assert_eq!(config.pairs.len(), 2);
+        assert_eq!(config.portfolio.max_total_exposure_pct, 12.0);
+        assert_eq!(config.portfolio.max_daily_loss_pct, 3.0);
+
+        let base = crate::strategy::params::StrategyConfig::from(&config);
+        let (sol, jup) = (&config.pairs[0], &config.pairs[1]);
+        assert_eq!((sol.base_decimals, sol.quote_decimals), (9, 6));
+        assert_eq!(sol.strategy_config(&base, &config.strategy).unwrap().z_threshold, 2.0);
+
+        let jup_config = jup.strategy_config(&base, &config.strategy).unwrap();
+        assert_eq!(jup_config.z_threshold, 2.5);
+        assert_eq!(jup_config.lookback_period, base.lookback_period);
+        assert_eq!(jup_config.ou.dt_minutes, 5.0);
+        assert_eq!(jup.timeframe_duration(&config.strategy).unwrap(), Duration::from_secs(300));
+        assert_eq!(jup.base_decimals, 6);
+
+        // Overrides are validated like [strategy]
+        let invalid = with_pairs.replace("z_threshold = 2.5", "z_threshold = 9.0");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+
+        let duplicate = with_pairs.replace("symbol = \"JUP/USDC\"", "symbol = \"SOL/USDC\"");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(duplicate.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_spread_section() {
+        let with_spread = format!(
+            "{}\n[spread]\ny_symbol = \"mSOL\"\ny_mint = \"mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So\"\n\
+             x_symbol = \"jitoSOL\"\nx_mint = \"J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yyac6Y7kGCPn\"\n\
+             notional = 0.5\nhedge_estimator = \"kalman\"\nz_entry = 2.5\ntimeframe = \"15m\"\n",
+            create_valid_config()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_spread.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let spread = config.spread.unwrap();
+        assert_eq!(spread.quote_mint, "So11111111111111111111111111111111111111112");
+        assert_eq!((spread.y_decimals, spread.x_decimals, spread.quote_decimals), (9, 9, 9));
+        assert_eq!(spread.leg_retries, 2);
+        assert_eq!(spread.strategy.hedge_estimator, crate::strategy::HedgeEstimator::Kalman);
+        assert_eq!(spread.strategy.z_entry, 2.5);
+        assert_eq!(spread.strategy.window, 100);
+        assert_eq!(spread.timeframe_duration(&config.strategy).unwrap(), Duration::from_secs(900));
+
+        for (current, invalid) in [("z_entry = 2.5", "z_entry = 5.0"), ("notional = 0.5", "notional = 0.0")] {
+            let mut file = NamedTempFile::new().unwrap();
+            file.write_all(with_spread.replace(current, invalid).as_bytes()).unwrap();
+            assert!(load_config(file.path()).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_strategy_timeframe_rules() {
+        let with_rules = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\n\n[[strategy.timeframes]]\ntimeframe = \"1h\"\nmax_abs_zscore = 2.0\n\n[[strategy.timeframes]]\ntimeframe = \"5m\"\nlookback = 30\nmax_adx = 25.0",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_rules.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let strategy_config = crate::strategy::params::StrategyConfig::from(&config);
+        assert_eq!(strategy_config.timeframes.len(), 2);
+        assert_eq!(strategy_config.timeframes[0].timeframe_secs, 3_600);
+        assert_eq!(strategy_config.timeframes[0].lookback, 20);
+        assert_eq!(strategy_config.timeframes[1].timeframe_secs, 300);
+        assert_eq!(strategy_config.timeframes[1].max_adx, Some(25.0));
+
+        for invalid_rule in ["timeframe = \"7m\"\nmax_adx = 25.0", "timeframe = \"1h\""] {
+            let invalid = create_valid_config().replacen(
+                "timeframe = \"4h\"",
+                &format!("timeframe = \"4h\"\n\n[[strategy.timeframes]]\n{}", invalid_rule),
+                1,
+            );
+            let mut file = NamedTempFile::new().unwrap();
+            file.write_all(invalid.as_bytes()).unwrap();
+            assert!(load_config(file.path()).is_err(), "{}", invalid_rule);
+        }
+    }
+
+    #[test]
+    fn test_strategy_scaling_section() {
+        let with_scaling = create_valid_config().replacen(
+            "timeframe = \"4h\"",
+            "timeframe = \"4h\"\n\n[strategy.scaling]\nscale_in_levels = [3.0, 3.5]\nscale_in_size = 0.5\npartial_exit_levels = [1.0]",
+            1,
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(with_scaling.as_bytes()).unwrap();
+        let config = load_config(file.path()).unwrap();
+
+        let scaling = crate::strategy::params::StrategyConfig::from(&config).scaling;
+        assert_eq!(scaling.scale_in_levels, vec![3.0, 3.5]);
+        assert_eq!(scaling.scale_in_size, 0.5);
+        assert_eq!(scaling.partial_exit_levels, vec![1.0]);
+        assert_eq!(scaling.partial_exit_fraction, 0.5);
+
+        // Scale-in levels inside the entry threshold are rejected
+        let invalid = with_scaling.replace("[3.0, 3.5]", "[1.5, 3.5]");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(invalid.as_bytes()).unwrap();
+        assert!(load_config(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_strategy_state_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(create_valid_config().as_bytes()).unwrap();
+        let mut strategy = load_config(file.path()).unwrap().strategy;
+        assert!(strategy.state_file.is_none());
+        assert!(strategy.pair_state_file("SOL/USDC").is_none());
+
+        strategy.state_file = Some("data/strategy_state.json".to_string());
+        assert_eq!(
+            strategy.pair_state_file("SOL/USDC").unwrap(),
+            PathBuf::from("data/strategy_state-SOL-USDC.json")
+        );
+    }
 }
@@ -5,5 +5,5 @@
 pub mod loader;
 
 pub use loader::{
-    Config, load_config,
+    Config, PriceSourceKind, SpreadSection, load_config, parse_timeframe,
 };
@@ -0,0 +1,186 @@
+//! Inventory Model
+//!
+//! Base (SOL) inventory held against a neutral SOL/USDC allocation.
+//! Exposure is measured relative to `neutral_base_sol`: holding more SOL is
+//! long, holding less is short. A spot short sells SOL down from the neutral
+//! amount and buys it back on exit, so it can never sell more than the
+//! inventory above the fee reserve. With `short_mode = "perp"` shorts go to a
+//! perp venue instead and base inventory is left untouched.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Small tolerance for float sizes
+const EPSILON: f64 = 1e-9;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum InventoryError {
+    #[error("Insufficient base inventory: short needs {required:.4} SOL (incl. reserve), wallet holds {available:.4} SOL")]
+    InsufficientInventory { required: f64, available: f64 },
+
+    #[error("Short of {requested:.4} SOL exceeds the {max:.4} SOL available below the neutral allocation")]
+    ExceedsNeutral { requested: f64, max: f64 },
+
+    #[error("Invalid inventory config: {0}")]
+    InvalidConfig(String),
+}
+
+/// Where short exposure is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortMode {
+    /// Sell base inventory down from the neutral allocation
+    #[default]
+    Inventory,
+    /// Open true shorts on a perp venue (`PerpPort`)
+    Perp,
+}
+
+/// Neutral allocation and reserve for the base token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InventoryConfig {
+    /// Where short exposure is taken
+    pub short_mode: ShortMode,
+    /// SOL held at zero exposure (0 = no spot shorts)
+    pub neutral_base_sol: f64,
+    /// SOL never sold by a short, kept for fees and rent
+    pub min_base_reserve_sol: f64,
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        Self {
+            short_mode: ShortMode::Inventory,
+            neutral_base_sol: 0.0,
+            min_base_reserve_sol: 0.05,
+        }
+    }
+}
+
+impl InventoryConfig {
+    pub fn validate(&self) -> Result<(), InventoryError> {
+        if self.neutral_base_sol < 0.0 || self.min_base_reserve_sol < 0.0 {
+            return Err(InventoryError::InvalidConfig(format!(
+                "neutral_base_sol and min_base_reserve_sol must be >= 0, got {} and {}",
+                self.neutral_base_sol, self.min_base_reserve_sol
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Base inventory checks against the neutral allocation
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    config: InventoryConfig,
+}
+
+impl Inventory {
+    pub fn new(config: InventoryConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &InventoryConfig {
+        &self.config
+    }
+
+    /// Whether shorts are taken on a perp venue instead of from inventory
+    pub fn shorts_on_perp(&self) -> bool {
+        self.config.short_mode == ShortMode::Perp
+    }
+
+    /// Exposure relative to the neutral allocation: positive long, negative short
+    pub fn exposure_sol(&self, base_balance_sol: f64) -> f64 {
+        base_balance_sol - self.config.neutral_base_sol
+    }
+
+    /// Largest spot short: the neutral allocation above the reserve
+    pub fn max_short_sol(&self) -> f64 {
+        (self.config.neutral_base_sol - self.config.min_base_reserve_sol).max(0.0)
+    }
+
+    /// Hard check before selling `size_sol` of base to open or add to a spot short
+    /// `short_open_sol` is the short already open; `base_balance_sol` what the wallet holds
+    pub fn check_short(
+        &self,
+        size_sol: f64,
+        short_open_sol: f64,
+        base_balance_sol: f64,
+    ) -> Result<(), InventoryError> {
+        if self.shorts_on_perp() {
+            return Ok(());
+        }
+
+        let requested = short_open_sol + size_sol;
+        let max = self.max_short_sol();
+        if requested > max + EPSILON {
+            return Err(InventoryError::ExceedsNeutral { requested, max });
+        }
+
+        let required = size_sol + self.config.min_base_reserve_sol;
+        if base_balance_sol + EPSILON < required {
+            return Err(InventoryError::InsufficientInventory {
+                required,
+                available: base_balance_sol,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Inventory {
+        Inventory::new(InventoryConfig {
+            neutral_base_sol: 2.0,
+            min_base_reserve_sol: 0.1,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_short_limited_by_neutral_allocation() {
+        let inventory = inventory();
+        assert!((inventory.max_short_sol() - 1.9).abs() < 1e-12);
+        assert!((inventory.exposure_sol(1.5) + 0.5).abs() < 1e-12);
+
+        assert!(inventory.check_short(1.0, 0.0, 2.0).is_ok());
+        assert!(inventory.check_short(0.9, 1.0, 1.0).is_ok());
+        assert!(matches!(
+            inventory.check_short(1.0, 1.0, 1.0),
+            Err(InventoryError::ExceedsNeutral { .. })
+        ));
+
+        // No neutral allocation, no spot shorts
+        let flat = Inventory::new(InventoryConfig::default());
+        assert!(flat.check_short(0.1, 0.0, 10.0).is_err());
+    }
+
+    #[test]
+    fn test_short_requires_held_inventory() {
+        let inventory = inventory();
+
+        // Within the allocation but the wallet has spent its SOL elsewhere
+        let err = inventory.check_short(1.0, 0.0, 0.5).unwrap_err();
+        assert_eq!(err, InventoryError::InsufficientInventory { required: 1.1, available: 0.5 });
+
+        // Perp shorts never touch base inventory
+        let perp = Inventory::new(InventoryConfig { short_mode: ShortMode::Perp, ..Default::default() });
+        assert!(perp.check_short(5.0, 0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_and_parse() {
+        let config: InventoryConfig = toml::from_str("short_mode = \"perp\"\nneutral_base_sol = 1.5").unwrap();
+        assert_eq!(config.short_mode, ShortMode::Perp);
+        assert_eq!(config.min_base_reserve_sol, 0.05);
+        assert!(config.validate().is_ok());
+
+        let invalid = InventoryConfig { neutral_base_sol: -1.0, ..Default::default() };
+        assert!(invalid.validate().is_err());
+    }
+}
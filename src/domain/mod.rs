@@ -11,6 +11,8 @@ pub mod signal;
 pub mod known_programs;
 pub mod tx_validator;
 pub mod balance_guard;
+pub mod inventory;
+pub mod pair_risk;
 pub mod meme_balance_guard;
 pub mod liquidity_guard;
 pub mod honeypot_detector;
@@ -18,5 +20,7 @@ pub mod rug_detector;
 
 pub use tx_validator::TransactionValidator;
 pub use balance_guard::{BalanceGuard, ExpectedDelta, GuardStatus};
+pub use inventory::{Inventory, InventoryConfig, InventoryError, ShortMode};
+pub use pair_risk::{PairBook, PairRiskBook, PortfolioLimits};
 
 // Meme coin trading safety modules
@@ -0,0 +1,172 @@
+//! Cross-Pair Risk Book
+//!
+//! Risk shared by every pair trading from one wallet:
+//! - Global exposure cap: open exposure across pairs, in percent of equity
+//! - Cross-pair daily loss limit: realized PnL of all pairs, in percent of equity
+//!
+//! A pair's exposure is the share of equity its position commits (entered
+//! size x price / wallet equity, for the entry and each scale-in), so pairs
+//! with different quote tokens add up without price conversion.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::risk::RiskViolation;
+
+/// Limits across all pairs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioLimits {
+    /// Maximum open exposure summed over pairs, percent of equity
+    pub max_total_exposure_pct: f64,
+    /// Block new entries once realized losses across pairs reach this, percent of equity
+    pub max_daily_loss_pct: f64,
+}
+
+impl Default for PortfolioLimits {
+    fn default() -> Self {
+        Self {
+            max_total_exposure_pct: 15.0,
+            max_daily_loss_pct: 3.0,
+        }
+    }
+}
+
+impl PortfolioLimits {
+    pub fn validate(&self) -> Result<(), RiskViolation> {
+        if self.max_total_exposure_pct <= 0.0 || self.max_daily_loss_pct <= 0.0 {
+            return Err(RiskViolation::ValidationFailed(format!(
+                "max_total_exposure_pct and max_daily_loss_pct must be > 0, got {} and {}",
+                self.max_total_exposure_pct, self.max_daily_loss_pct
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Open exposure and realized PnL of one pair
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PairBook {
+    /// Open exposure, percent of equity
+    pub exposure_pct: f64,
+    /// Realized PnL today, percent of equity
+    pub daily_pnl_pct: f64,
+}
+
+/// Exposure and daily PnL of every pair against the portfolio limits
+#[derive(Debug, Clone, Default)]
+pub struct PairRiskBook {
+    limits: PortfolioLimits,
+    pairs: BTreeMap<String, PairBook>,
+}
+
+impl PairRiskBook {
+    pub fn new(limits: PortfolioLimits) -> Self {
+        Self {
+            limits,
+            pairs: BTreeMap::new(),
+        }
+    }
+
+    pub fn limits(&self) -> &PortfolioLimits {
+        &self.limits
+    }
+
+    /// Book of `pair`, zero when it never traded
+    pub fn pair(&self, pair: &str) -> PairBook {
+        self.pairs.get(pair).copied().unwrap_or_default()
+    }
+
+    /// All pairs that traded, by name
+    pub fn pairs(&self) -> &BTreeMap<String, PairBook> {
+        &self.pairs
+    }
+
+    /// Open exposure across pairs, percent of equity
+    pub fn total_exposure_pct(&self) -> f64 {
+        self.pairs.values().map(|book| book.exposure_pct).sum()
+    }
+
+    /// Realized PnL across pairs today, percent of equity
+    pub fn daily_pnl_pct(&self) -> f64 {
+        self.pairs.values().map(|book| book.daily_pnl_pct).sum()
+    }
+
+    /// Check that `exposure_pct` more fits the exposure cap and the daily loss limit
+    pub fn check_entry(&self, exposure_pct: f64) -> Result<(), RiskViolation> {
+        let daily_pnl = self.daily_pnl_pct();
+        if daily_pnl <= -self.limits.max_daily_loss_pct {
+            return Err(RiskViolation::ValidationFailed(format!(
+                "cross-pair daily loss {:.2}% reached the {:.2}% limit",
+                -daily_pnl, self.limits.max_daily_loss_pct
+            )));
+        }
+
+        let total = self.total_exposure_pct() + exposure_pct;
+        if total > self.limits.max_total_exposure_pct + 1e-9 {
+            return Err(RiskViolation::ExposureExceeded(total, self.limits.max_total_exposure_pct));
+        }
+
+        Ok(())
+    }
+
+    /// Record an entry or scale-in of `exposure_pct` on `pair`
+    pub fn add_exposure(&mut self, pair: &str, exposure_pct: f64) {
+        self.pairs.entry(pair.to_string()).or_default().exposure_pct += exposure_pct;
+    }
+
+    /// Record closing `fraction` of `pair`'s exposure at a return of `return_pct`
+    pub fn reduce(&mut self, pair: &str, fraction: f64, return_pct: f64) {
+        let book = self.pairs.entry(pair.to_string()).or_default();
+        let closed = book.exposure_pct * fraction.clamp(0.0, 1.0);
+        book.exposure_pct -= closed;
+        book.daily_pnl_pct += closed * return_pct / 100.0;
+    }
+
+    /// Start a new trading day: realized PnL resets, open exposure stays
+    pub fn reset_daily(&mut self) {
+        for book in self.pairs.values_mut() {
+            book.daily_pnl_pct = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_cap_across_pairs() {
+        let mut book = PairRiskBook::new(PortfolioLimits { max_total_exposure_pct: 10.0, ..Default::default() });
+
+        assert!(book.check_entry(5.0).is_ok());
+        book.add_exposure("SOL/USDC", 5.0);
+        book.add_exposure("JUP/USDC", 4.0);
+        assert!(book.check_entry(1.0).is_ok());
+        assert!(matches!(book.check_entry(2.0), Err(RiskViolation::ExposureExceeded(..))));
+
+        // Closing half of a pair frees its exposure
+        book.reduce("SOL/USDC", 0.5, 0.0);
+        assert!((book.total_exposure_pct() - 6.5).abs() < 1e-12);
+        assert!(book.check_entry(2.0).is_ok());
+    }
+
+    #[test]
+    fn test_daily_loss_across_pairs() {
+        let mut book = PairRiskBook::new(PortfolioLimits { max_daily_loss_pct: 0.2, ..Default::default() });
+
+        // 5% of equity losing 2% = -0.1% of equity, on two pairs
+        book.add_exposure("SOL/USDC", 5.0);
+        book.add_exposure("JUP/USDC", 5.0);
+        book.reduce("SOL/USDC", 1.0, -2.0);
+        assert!(book.check_entry(1.0).is_ok());
+        book.reduce("JUP/USDC", 1.0, -2.0);
+        assert!((book.daily_pnl_pct() + 0.2).abs() < 1e-12);
+        assert!(book.check_entry(1.0).is_err());
+
+        book.reset_daily();
+        assert!(book.check_entry(1.0).is_ok());
+        assert_eq!(book.pair("SOL/USDC"), PairBook::default());
+    }
+}
@@ -15,17 +15,29 @@ use anyhow::{Result, Context, bail};
 use clap::Parser;
 use tracing_subscriber::{fmt, EnvFilter};
 use std::path::Path;
+use std::sync::Arc;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 use crate::adapters::cli::{CliApp, Command, RunCmd, StatusCmd, QuoteCmd, SwapCmd, BacktestCmd, ResumeCmd, MemeCmd};
 use crate::adapters::jito::{JitoBundleClient, JitoConfig, JitoExecutionAdapter};
-use crate::adapters::jupiter::JupiterClient;
+use crate::adapters::jupiter::{JupiterClient, JupiterGateway};
 use crate::adapters::solana::{SolanaClient, WalletManager};
-use crate::application::TradingOrchestrator;
-use crate::config::load_config;
+use crate::adapters::market_data::{
+    JupiterQuotePriceSource, MarketRecorder, OnchainPoolPriceSource, ReplayPriceSource,
+};
+use crate::adapters::pools::OnchainPoolReader;
+use crate::adapters::perp::LocalPerpExchange;
+use crate::domain::ShortMode;
+use crate::meme::FillCosts;
+use crate::application::{
+    Backtester, BacktestConfig, MultiPairOrchestrator, PaperPortfolio, SpreadLeg, SpreadTrader,
+    TradingOrchestrator,
+};
+use crate::config::{load_config, PriceSourceKind};
 use crate::strategy::StrategyConfig;
+use crate::ports::price::PricePort;
 use crate::ports::execution::{ExecutionPort, SwapQuoteRequest, ExecuteSwapRequest};
 
 #[tokio::main]
@@ -42,6 +54,8 @@ async fn main() -> Result<()> {
         Command::Quote(cmd) => quote_command(cmd).await,
         Command::Swap(cmd) => swap_command(cmd).await,
         Command::Backtest(cmd) => backtest_command(cmd).await,
+        Command::Optimize(cmd) => crate::adapters::cli::optimize_command(cmd).await,
+        Command::MonteCarlo(cmd) => crate::adapters::cli::monte_carlo_command(cmd).await,
         Command::Resume(cmd) => resume_command(cmd).await,
         Command::Meme(cmd) => meme_command(cmd).await,
     }
@@ -145,6 +159,22 @@ async fn run_command(cmd: RunCmd) -> Result<()> {
         );
     }
 
+    // The paper wallet holds one base/quote pair; [spread] and [[pairs]] trade several tokens
+    if cmd.paper {
+        if config.spread.is_some() {
+            bail!(
+                "--paper is not supported with [spread]: the paper wallet tracks a single \
+                 base/quote pair.\nRemove [spread] to paper trade the [tokens] pair."
+            );
+        }
+        if !config.pairs.is_empty() {
+            bail!(
+                "--paper is not supported with [[pairs]]: the paper wallet tracks a single \
+                 base/quote pair.\nRemove [[pairs]] to paper trade the [tokens] pair."
+            );
+        }
+    }
+
     // Preflight checks for live trading
     if cmd.live {
         if !cmd.i_accept_losses {
@@ -170,17 +200,17 @@ async fn run_command(cmd: RunCmd) -> Result<()> {
         preflight_checks(&config, &keypair_path).await?;
     }
 
-    // Build components - use API key from config/env for higher rate limits
-    let jupiter = match config.jupiter.get_api_key() {
-        Some(api_key) => {
-            tracing::info!("Using Jupiter API key for higher rate limits");
-            JupiterClient::with_api_key(api_key)
-        }
-        None => {
-            tracing::warn!("No Jupiter API key configured - may hit rate limits");
-            JupiterClient::new()
-        }
-    }.context("Failed to create Jupiter client")?;
+    // Build components - one Jupiter gateway sized to the API key tier
+    // is shared by quotes, swaps and price polling
+    let gateway_config = config.jupiter.gateway_config();
+    if gateway_config.api_key.is_some() {
+        tracing::info!("Using Jupiter API key for higher rate limits");
+    } else {
+        tracing::warn!("No Jupiter API key configured - may hit rate limits");
+    }
+    tracing::info!("Jupiter request budget: {:.1} req/s", gateway_config.requests_per_second);
+    let gateway = JupiterGateway::new(gateway_config).context("Failed to create Jupiter gateway")?;
+    let jupiter = JupiterClient::with_gateway(gateway);
     let solana = SolanaClient::new(config.solana.rpc_url.clone());
 
     // Load wallet with improved error handling
@@ -220,6 +250,70 @@ async fn run_command(cmd: RunCmd) -> Result<()> {
         None
     };
 
+    // Optional market data recording for later replay
+    let recorder = if config.recorder.enabled {
+        tracing::info!("Recording market data to {}", config.recorder.data_dir);
+        Some(
+            MarketRecorder::new(&config.recorder.data_dir)
+                .with_max_file_bytes(config.recorder.max_file_mb * 1024 * 1024)
+                .shared(),
+        )
+    } else {
+        None
+    };
+
+    // Two-leg spread: [spread] replaces the [tokens] pair
+    if let Some(ref spread) = config.spread {
+        if config.price_source.kind == PriceSourceKind::Onchain {
+            tracing::warn!("On-chain pricing covers the [tokens] pair; [spread] legs are priced from Jupiter quotes");
+        }
+        return run_spread(&config, spread, jupiter, solana, wallet).await;
+    }
+
+    // Several pairs from one wallet: [[pairs]] replaces the [tokens] pair
+    if !config.pairs.is_empty() {
+        if recorder.is_some() {
+            tracing::warn!("Market data recording covers a single pair and is disabled with [[pairs]]");
+        }
+        if config.price_source.kind == PriceSourceKind::Onchain {
+            tracing::warn!("On-chain pricing covers the [tokens] pair; [[pairs]] are priced from Jupiter quotes");
+        }
+        return run_pairs(&config, jupiter, solana, wallet).await;
+    }
+
+    // On-chain pool prices, or a recording Jupiter quote source
+    // (the orchestrator quotes without recording by default)
+    let price_source: Option<Arc<dyn PricePort>> = match config.price_source.kind {
+        PriceSourceKind::Onchain => {
+            let pools = if config.price_source.pools.is_empty() {
+                OnchainPoolReader::sol_usdc_pools()
+            } else {
+                config.price_source.pools.clone()
+            };
+            tracing::info!("Pricing {} from {} on-chain pools", config.tokens.pair_symbol, pools.len());
+            let source = OnchainPoolPriceSource::new(
+                OnchainPoolReader::new(solana.clone()),
+                pools,
+                config.tokens.base_mint.clone(),
+                config.tokens.quote_mint.clone(),
+            );
+            Some(match &recorder {
+                Some(recorder) => Arc::new(source.with_recorder(recorder.clone())),
+                None => Arc::new(source),
+            })
+        }
+        PriceSourceKind::Jupiter => recorder.as_ref().map(|recorder| -> Arc<dyn PricePort> {
+            Arc::new(
+                JupiterQuotePriceSource::new(
+                    jupiter.clone(),
+                    config.tokens.base_mint.clone(),
+                    config.tokens.quote_mint.clone(),
+                    config.jupiter.slippage_bps,
+                ).with_recorder(recorder.clone()),
+            )
+        }),
+    };
+
     // Convert config to strategy config
     let strategy_config = StrategyConfig::from(&config);
 
@@ -235,7 +329,42 @@ async fn run_command(cmd: RunCmd) -> Result<()> {
         cmd.paper,
         config.risk.trade_size_sol,
         config.jupiter.max_priority_fee_lamports,
-    ).context("Failed to create orchestrator")?;
+    ).context("Failed to create orchestrator")?
+    .with_timeframe(config.strategy.timeframe_duration()?)
+    .with_regime_detector(config.regime.build())
+    .with_volatility_detector(config.regime.build_volatility())
+    .with_sizing(config.risk.sizing.clone())
+    .with_inventory(config.inventory.clone());
+
+    let orchestrator = match &config.strategy.state_file {
+        Some(path) => orchestrator.with_state_file(path),
+        None => orchestrator,
+    };
+    let orchestrator = with_perp_venue(orchestrator, &config, &config.tokens.pair_symbol, cmd.paper)?;
+
+    // Paper fills go to a virtual wallet, resumed from its state file
+    let orchestrator = if cmd.paper {
+        let portfolio = paper_portfolio(&config)?;
+        tracing::info!(
+            "Paper wallet {}: {:.4} SOL, {:.2} USDC, {} trades",
+            config.paper.state_file,
+            portfolio.base_balance(),
+            portfolio.quote_balance(),
+            portfolio.stats().total_trades
+        );
+        orchestrator.with_paper_portfolio(portfolio)
+    } else {
+        orchestrator
+    };
+
+    let orchestrator = match price_source {
+        Some(price_source) => orchestrator.with_price_source(price_source),
+        None => orchestrator,
+    };
+    let orchestrator = match recorder {
+        Some(recorder) => orchestrator.with_recorder(recorder),
+        None => orchestrator,
+    };
 
     // Setup Ctrl+C handler
     let orch = orchestrator.clone();
@@ -261,8 +390,155 @@ async fn run_command(cmd: RunCmd) -> Result<()> {
     Ok(())
 }
 
+/// Run every `[[pairs]]` entry from one wallet under the `[portfolio]` limits (live only)
+async fn run_pairs(
+    config: &config::Config,
+    jupiter: JupiterClient,
+    solana: SolanaClient,
+    wallet: WalletManager,
+) -> Result<()> {
+    let base = StrategyConfig::from(config);
+    let mut pairs = MultiPairOrchestrator::new(config.portfolio.clone());
+
+    for pair in &config.pairs {
+        let orchestrator = TradingOrchestrator::new(
+            pair.strategy_config(&base, &config.strategy)?,
+            jupiter.clone(),
+            solana.clone(),
+            wallet.clone(),
+            pair.base_mint.clone(),
+            pair.quote_mint.clone(),
+            config.jupiter.slippage_bps,
+            false,
+            pair.trade_size.unwrap_or(config.risk.trade_size_sol),
+            config.jupiter.max_priority_fee_lamports,
+        ).with_context(|| format!("Failed to create orchestrator for {}", pair.symbol))?
+        .with_symbol(pair.symbol.clone())
+        .with_decimals(pair.base_decimals, pair.quote_decimals)
+        .with_timeframe(pair.timeframe_duration(&config.strategy)?)
+        .with_regime_detector(config.regime.build())
+        .with_volatility_detector(config.regime.build_volatility())
+        .with_sizing(config.risk.sizing.clone())
+        .with_inventory(pair.inventory_config(config));
+        let orchestrator = match config.strategy.pair_state_file(&pair.symbol) {
+            Some(path) => orchestrator.with_state_file(path),
+            None => orchestrator,
+        };
+
+        tracing::info!("Pair {}: {} -> {}", pair.symbol, pair.base_mint, pair.quote_mint);
+        pairs = pairs.with_pair(with_perp_venue(orchestrator, config, &pair.symbol, false)?);
+    }
+
+    let pairs = Arc::new(pairs);
+    let shutdown = Arc::clone(&pairs);
+    tokio::spawn(async move {
+        tokio::signal::ctrl_c().await.ok();
+        tracing::info!("Shutdown signal received");
+        shutdown.stop().await;
+    });
+
+    pairs.run().await?;
+    tracing::info!("Butters stopped");
+    Ok(())
+}
+
+/// Trade the `[spread]` legs, swapping through a `TradingOrchestrator` on the quote token (live only)
+async fn run_spread(
+    config: &config::Config,
+    spread: &config::SpreadSection,
+    jupiter: JupiterClient,
+    solana: SolanaClient,
+    wallet: WalletManager,
+) -> Result<()> {
+    let slippage_bps = config.jupiter.slippage_bps;
+    let leg = |symbol: &str, mint: &str, decimals: u8| {
+        let price_source = JupiterQuotePriceSource::new(
+            jupiter.clone(),
+            mint.to_string(),
+            spread.quote_mint.clone(),
+            slippage_bps,
+        ).with_decimals(decimals, spread.quote_decimals);
+        SpreadLeg::new(symbol, mint, decimals, Arc::new(price_source))
+    };
+
+    let executor = TradingOrchestrator::new(
+        StrategyConfig::from(config),
+        jupiter.clone(),
+        solana,
+        wallet,
+        spread.y_mint.clone(),
+        spread.quote_mint.clone(),
+        slippage_bps,
+        false,
+        spread.notional,
+        config.jupiter.max_priority_fee_lamports,
+    ).context("Failed to create swap executor")?;
+
+    let trader = SpreadTrader::new(
+        spread.strategy.clone(),
+        leg(&spread.y_symbol, &spread.y_mint, spread.y_decimals),
+        leg(&spread.x_symbol, &spread.x_mint, spread.x_decimals),
+        spread.quote_mint.clone(),
+        spread.quote_decimals,
+        spread.notional,
+        Arc::new(executor),
+    ).context("Failed to create spread trader")?
+    .with_timeframe(spread.timeframe_duration(&config.strategy)?)
+    .with_slippage_bps(slippage_bps)
+    .with_leg_retries(spread.leg_retries, std::time::Duration::from_millis(500));
+
+    let shutdown = trader.clone();
+    tokio::spawn(async move {
+        tokio::signal::ctrl_c().await.ok();
+        tracing::info!("Shutdown signal received");
+        shutdown.stop().await;
+    });
+
+    trader.run().await?;
+    tracing::info!("Butters stopped");
+    Ok(())
+}
+
+/// Connect the perp venue for `short_mode = "perp"`
+/// No perp DEX adapter yet: perp shorts run against the local stand-in in paper mode only
+fn with_perp_venue(
+    orchestrator: TradingOrchestrator,
+    config: &config::Config,
+    symbol: &str,
+    paper: bool,
+) -> Result<TradingOrchestrator> {
+    if config.inventory.short_mode != ShortMode::Perp {
+        return Ok(orchestrator);
+    }
+    if !paper {
+        bail!("inventory.short_mode = \"perp\" is only supported in paper mode");
+    }
+    let market = format!("{}-PERP", symbol.split('/').next().unwrap_or("SOL"));
+    Ok(orchestrator.with_perp(Arc::new(LocalPerpExchange::new(market))))
+}
+
+/// Virtual SOL/USDC wallet for `run --paper`, resumed from `[paper] state_file`
+fn paper_portfolio(config: &config::Config) -> Result<PaperPortfolio> {
+    let tip_lamports = if config.jito.enabled { config.jito.tip_lamports } else { 0 };
+    let fresh = PaperPortfolio::new(
+        config.tokens.base_mint.clone(),
+        "SOL",
+        9,
+        "USDC",
+        6,
+        config.paper.initial_quote,
+        config.inventory.neutral_base_sol,
+    )
+    .with_costs(FillCosts::new(config.jupiter.max_priority_fee_lamports, tip_lamports));
+    PaperPortfolio::load_or(&config.paper.state_file, fresh).context("Failed to load paper wallet")
+}
+
 async fn status_command(cmd: StatusCmd) -> Result<()> {
     let config = load_config(&cmd.config)?;
+
+    if cmd.paper {
+        return paper_status(&config, cmd.export_trades.as_deref());
+    }
     let solana = SolanaClient::new(config.solana.rpc_url.clone());
 
     // Expand keypair path
@@ -278,6 +554,48 @@ async fn status_command(cmd: StatusCmd) -> Result<()> {
     Ok(())
 }
 
+/// Print the paper wallet saved by `run --paper`, optionally exporting its trades
+fn paper_status(config: &config::Config, export_trades: Option<&Path>) -> Result<()> {
+    let path = Path::new(&config.paper.state_file);
+    if !path.exists() {
+        bail!("No paper wallet at {} - start one with: butters run --paper", path.display());
+    }
+    let portfolio = PaperPortfolio::load(path).context("Failed to load paper wallet")?;
+    let stats = portfolio.stats();
+    let quote = &stats.quote_currency;
+
+    println!("Paper wallet: {}", path.display());
+    println!("{}:  {:.4}", portfolio.base_symbol(), portfolio.base_balance());
+    println!("{}: {:.2}", quote, portfolio.quote_balance());
+    if let Some(price) = portfolio.last_price() {
+        println!(
+            "Equity: {:.2} {} (PnL {:+.2}) at last fill {:.2}",
+            portfolio.equity(price),
+            quote,
+            portfolio.pnl(price),
+            price
+        );
+    }
+    println!();
+    println!("Trades:        {} ({} buys, {} sells)", stats.total_trades, stats.buy_count, stats.sell_count);
+    println!("Realized PnL:  {:+.2} {}", stats.total_realized_pnl_sol, quote);
+    println!("Fees:          {:.4} {}", stats.total_fees_sol, quote);
+    println!("Win rate:      {:.1}%", stats.win_rate());
+    println!("Profit factor: {:.2}", stats.profit_factor());
+    println!("Max drawdown:  {:.2}%", stats.max_drawdown_pct);
+    if let Some(sharpe) = stats.sharpe_ratio() {
+        println!("Sharpe:        {:.2}", sharpe);
+    }
+
+    if let Some(export) = export_trades {
+        std::fs::write(export, portfolio.export_trades_json()?)
+            .with_context(|| format!("Failed to write {}", export.display()))?;
+        println!();
+        println!("Exported {} trades to {}", portfolio.trades().len(), export.display());
+    }
+    Ok(())
+}
+
 /// Load wallet with helpful error messages
 fn load_wallet_with_context(keypair_path: &str, is_paper_mode: bool) -> Result<WalletManager> {
     let path = Path::new(keypair_path);
@@ -485,8 +803,150 @@ async fn swap_command(cmd: SwapCmd) -> Result<()> {
     Ok(())
 }
 
-async fn backtest_command(_cmd: BacktestCmd) -> Result<()> {
-    println!("Backtest command not yet implemented");
+async fn backtest_command(cmd: BacktestCmd) -> Result<()> {
+    let Some(ref replay_path) = cmd.replay else {
+        println!("Backtest from downloaded history not yet implemented");
+        println!("Record a session with [recorder] enabled and pass --replay <PATH>");
+        return Ok(());
+    };
+
+    let config = load_config(&cmd.config)?;
+
+    let mut strategy_config = StrategyConfig::from(&config);
+    if let Some(z) = cmd.z_threshold {
+        strategy_config = strategy_config.with_z_threshold(z);
+    }
+    if let Some(lookback) = cmd.lookback {
+        strategy_config = strategy_config.with_lookback(lookback);
+    }
+    strategy_config.validate().context("Invalid strategy parameters")?;
+
+    let source = ReplayPriceSource::from_path(replay_path, &config.tokens.base_mint)
+        .context(format!("Failed to load recording: {}", replay_path.display()))?;
+
+    println!("Replaying {} price samples for {} from {}", source.len(), cmd.pair, replay_path.display());
+
+    let backtest_config = BacktestConfig {
+        timeframe: config.strategy.timeframe_duration()?,
+        initial_capital: cmd.capital,
+        ..Default::default()
+    };
+
+    if cmd.compare_zscore {
+        let results = Backtester::compare_zscore_estimators(&strategy_config, &backtest_config, source.samples());
+        println!();
+        println!("Z-score estimator comparison ({}):", config.strategy.timeframe);
+        println!("  {:<8} {:>7} {:>9} {:>10} {:>9}", "", "Trades", "Win rate", "Return", "Max DD");
+        for result in &results {
+            let report = &result.report;
+            println!(
+                "  {:<8} {:>7} {:>8.1}% {:>+9.2}% {:>8.2}%",
+                result.label,
+                report.trades.len(),
+                report.win_rate * 100.0,
+                report.total_return_pct,
+                report.max_drawdown_pct
+            );
+        }
+
+        if let Some(ref path) = cmd.export_json {
+            let json = serde_json::to_string_pretty(&results)?;
+            std::fs::write(path, json).context("Failed to write JSON export")?;
+            println!("Exported JSON to {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let mut backtester = Backtester::new(strategy_config, backtest_config);
+    let report = backtester.run(&source).await.context("Backtest failed")?;
+
+    if cmd.verbose {
+        for trade in &report.trades {
+            println!(
+                "  {:?} {} @ ${:.4} -> {} @ ${:.4} | {:+.2}% | ${:+.2}",
+                trade.side,
+                trade.entry_time.format("%Y-%m-%d %H:%M:%S"),
+                trade.entry_price,
+                trade.exit_time.format("%Y-%m-%d %H:%M:%S"),
+                trade.exit_price,
+                trade.pnl_pct,
+                trade.pnl
+            );
+        }
+    }
+
+    println!();
+    println!("Backtest Results ({}, {}):", backtester.strategy().name(), config.strategy.timeframe);
+    println!("  Samples: {} | Candles: {}", report.samples, report.candles);
+    println!("  Trades: {} | Win rate: {:.1}%", report.trades.len(), report.win_rate * 100.0);
+    println!("  Equity: ${:.2} -> ${:.2} ({:+.2}%)", report.initial_capital, report.final_equity, report.total_return_pct);
+    println!("  Max drawdown: {:.2}%", report.max_drawdown_pct);
+    for tf in &report.timeframes {
+        println!(
+            "  {:>4} confirm: {} candles | z {} | ADX {} | blocked {} entries",
+            tf.timeframe,
+            tf.candles,
+            tf.z_score.map(|z| format!("{:+.2}", z)).unwrap_or_else(|| "-".to_string()),
+            tf.adx.map(|adx| format!("{:.1}", adx)).unwrap_or_else(|| "-".to_string()),
+            tf.blocked_entries
+        );
+    }
+    if let Some(last) = report.votes.last() {
+        for vote in &last.tally.votes {
+            println!(
+                "  {:>6} vote: weight {:.2} | hit rate {} over {} votes",
+                vote.signal.name(),
+                vote.weight,
+                vote.hit_rate.map(|h| format!("{:.1}%", h * 100.0)).unwrap_or_else(|| "-".to_string()),
+                vote.scored_votes
+            );
+        }
+    }
+
+    if let Some(ref path) = cmd.export_json {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(path, json).context("Failed to write JSON export")?;
+        println!("Exported JSON to {}", path.display());
+    }
+
+    if let Some(ref path) = cmd.export_csv {
+        let mut csv = String::from("side,entry_time,entry_price,exit_time,exit_price,pnl_pct,pnl\n");
+        for trade in &report.trades {
+            csv.push_str(&format!(
+                "{:?},{},{},{},{},{},{}\n",
+                trade.side,
+                trade.entry_time.to_rfc3339(),
+                trade.entry_price,
+                trade.exit_time.to_rfc3339(),
+                trade.exit_price,
+                trade.pnl_pct,
+                trade.pnl
+            ));
+        }
+        std::fs::write(path, csv).context("Failed to write CSV export")?;
+        println!("Exported CSV to {}", path.display());
+    }
+
+    if let Some(ref path) = cmd.export_votes {
+        let mut csv = String::from("time,action,score");
+        if let Some(first) = report.votes.first() {
+            for vote in &first.tally.votes {
+                let name = vote.signal.name();
+                csv.push_str(&format!(",{name}_vote,{name}_confidence,{name}_weight"));
+            }
+        }
+        csv.push('\n');
+        for record in &report.votes {
+            csv.push_str(&format!("{},{:?},{}", record.time.to_rfc3339(), record.action, record.tally.score));
+            for vote in &record.tally.votes {
+                csv.push_str(&format!(",{},{},{}", vote.vote, vote.confidence, vote.weight));
+            }
+            csv.push('\n');
+        }
+        std::fs::write(path, csv).context("Failed to write votes export")?;
+        println!("Exported {} ensemble votes to {}", report.votes.len(), path.display());
+    }
+
     Ok(())
 }
 
@@ -8,7 +8,7 @@ use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::adapters::jupiter::JupiterClient;
+use crate::adapters::jupiter::{JupiterClient, JupiterGateway};
 use crate::adapters::solana::{SolanaClient, WalletManager};
 use crate::application::{
     MemeOrchestrator, MemeOrchestratorConfig, PersistedState, TokenInfo,
@@ -239,16 +239,15 @@ async fn meme_run_command(cmd: MemeRunCmd) -> Result<()> {
     let keypair_path = shellexpand::tilde(&config.solana.keypair_path).to_string();
 
     // Create Jupiter client
-    let jupiter = match config.jupiter.get_api_key() {
-        Some(api_key) => {
-            tracing::info!("Using Jupiter API key for higher rate limits");
-            JupiterClient::with_api_key(api_key)
-        }
-        None => {
-            tracing::warn!("No Jupiter API key configured - may hit rate limits");
-            JupiterClient::new()
-        }
-    }.context("Failed to create Jupiter client")?;
+    // One gateway shared by swaps, quotes and batched price polling
+    let gateway_config = config.jupiter.gateway_config();
+    if gateway_config.api_key.is_some() {
+        tracing::info!("Using Jupiter API key for higher rate limits");
+    } else {
+        tracing::warn!("No Jupiter API key configured - may hit rate limits");
+    }
+    let gateway = JupiterGateway::new(gateway_config).context("Failed to create Jupiter gateway")?;
+    let jupiter = JupiterClient::with_gateway(gateway);
 
     // Create Solana client
     let solana = SolanaClient::new(config.solana.rpc_url.clone());
@@ -291,6 +290,10 @@ async fn meme_run_command(cmd: MemeRunCmd) -> Result<()> {
         min_ou_confidence: config.meme.as_ref().map(|m| m.min_ou_confidence).unwrap_or(0.3),
         min_half_life_minutes: config.meme.as_ref().map(|m| m.min_half_life_minutes).unwrap_or(5.0),
         max_half_life_minutes: config.meme.as_ref().map(|m| m.max_half_life_minutes).unwrap_or(120.0),
+        price_batch_size: config.meme.as_ref().map(|m| m.price_batch_size).unwrap_or(50),
+        quote_concurrency: config.meme.as_ref().map(|m| m.quote_concurrency).unwrap_or(4),
+        quote_z_margin: config.meme.as_ref().map(|m| m.quote_z_margin).unwrap_or(0.5),
+        max_price_age_secs: config.meme.as_ref().map(|m| m.max_price_age_secs).unwrap_or(180),
     };
 
     // Validate config
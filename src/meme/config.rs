@@ -96,6 +96,27 @@ pub struct MemeConfig {
     #[serde(default = "default_max_price_impact_pct")]
     pub max_price_impact_pct: f64,
 
+    // =========================================================================
+    // Price Polling
+    // =========================================================================
+    /// Maximum mints per Jupiter Price API request
+    #[serde(default = "default_price_batch_size")]
+    pub price_batch_size: usize,
+
+    /// Maximum concurrent Jupiter quotes per tick
+    /// Only the open position and tokens near the entry threshold are quoted
+    #[serde(default = "default_quote_concurrency")]
+    pub quote_concurrency: usize,
+
+    /// Z-score distance above z_entry_threshold within which a token gets a quote price
+    #[serde(default = "default_quote_z_margin")]
+    pub quote_z_margin: f64,
+
+    /// Seconds without a fresh price before a token is stale
+    /// Stale tokens are not entered and frozen prices are not fed to the OU process
+    #[serde(default = "default_max_price_age_secs")]
+    pub max_price_age_secs: u64,
+
     // =========================================================================
     // Safety & Persistence
     // =========================================================================
@@ -161,6 +182,18 @@ fn default_priority_fee_lamports() -> u64 {
 fn default_max_price_impact_pct() -> f64 {
     2.0
 }
+fn default_price_batch_size() -> usize {
+    50
+}
+fn default_quote_concurrency() -> usize {
+    4
+}
+fn default_quote_z_margin() -> f64 {
+    0.5
+}
+fn default_max_price_age_secs() -> u64 {
+    180
+}
 fn default_paper_mode() -> bool {
     true
 }
@@ -187,6 +220,10 @@ impl Default for MemeConfig {
             poll_interval_secs: default_poll_interval_secs(),
             priority_fee_lamports: default_priority_fee_lamports(),
             max_price_impact_pct: default_max_price_impact_pct(),
+            price_batch_size: default_price_batch_size(),
+            quote_concurrency: default_quote_concurrency(),
+            quote_z_margin: default_quote_z_margin(),
+            max_price_age_secs: default_max_price_age_secs(),
             paper_mode: default_paper_mode(),
             data_dir: default_data_dir(),
             tokens: vec![],
@@ -278,6 +315,31 @@ impl MemeConfig {
             ));
         }
 
+        // Price polling
+        if self.price_batch_size == 0 {
+            return Err(MemeConfigError::InvalidValue(
+                "price_batch_size must be > 0".to_string(),
+            ));
+        }
+
+        if self.quote_concurrency == 0 {
+            return Err(MemeConfigError::InvalidValue(
+                "quote_concurrency must be > 0".to_string(),
+            ));
+        }
+
+        if self.quote_z_margin < 0.0 {
+            return Err(MemeConfigError::InvalidValue(
+                "quote_z_margin must be >= 0".to_string(),
+            ));
+        }
+
+        if self.max_price_age_secs == 0 {
+            return Err(MemeConfigError::InvalidValue(
+                "max_price_age_secs must be > 0".to_string(),
+            ));
+        }
+
         // Validate token entries
         for (i, token) in self.tokens.iter().enumerate() {
             if token.mint.is_empty() {
@@ -365,6 +427,21 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_invalid_price_polling() {
+        let config = MemeConfig {
+            quote_concurrency: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = MemeConfig {
+            max_price_age_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_is_half_life_tradeable() {
         let config = MemeConfig::default();
@@ -0,0 +1,382 @@
+//! Paper Fill Models
+//!
+//! How `PaperTradingEngine` turns a reference price into an executed fill:
+//! - `FillModel::FixedSlippage`: flat slippage on the reference price
+//! - `FillModel::BondingCurve`: constant-product swap against the token's
+//!   pump.fun virtual reserves, minus the curve fee; each paper trade moves
+//!   the reserves, so size-dependent impact compounds like on-chain
+//! - `FillModel::Quote`: executes at a recorded Jupiter quote, scaling its
+//!   price impact linearly when the paper size differs from the quoted size
+//!
+//! Amounts on the SOL side are in the engine's balance asset: SOL for meme
+//! trading, or e.g. USDC with `cash_decimals` 6 when quoting SOL itself.
+//!
+//! `FillCosts` adds the per-transaction SOL costs (base fee, priority fee,
+//! Jito tip) that are paid whatever the fill price.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::market_data::QuoteSummary;
+use crate::adapters::pump_fun::BondingCurveState;
+use crate::meme::paper_trading::TradeSide;
+
+/// Lamports per SOL
+pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Base signature fee of a Solana transaction
+pub const BASE_FEE_LAMPORTS: u64 = 5_000;
+
+/// pump.fun bonding curve trading fee
+pub const PUMP_FUN_FEE_BPS: u16 = 100;
+
+/// How simulated orders are priced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum FillModel {
+    /// Reference price worsened by a flat slippage
+    FixedSlippage { slippage_bps: u16 },
+    /// Constant-product swap against the mint's `BondingCurveState`
+    BondingCurve { fee_bps: u16 },
+    /// Recorded Jupiter quote for the mint and direction
+    Quote,
+}
+
+impl Default for FillModel {
+    fn default() -> Self {
+        FillModel::FixedSlippage { slippage_bps: 50 }
+    }
+}
+
+impl FillModel {
+    /// pump.fun curve with its standard fee
+    pub fn pump_fun() -> Self {
+        FillModel::BondingCurve { fee_bps: PUMP_FUN_FEE_BPS }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FillModel::FixedSlippage { .. } => "fixed_slippage",
+            FillModel::BondingCurve { .. } => "bonding_curve",
+            FillModel::Quote => "quote",
+        }
+    }
+
+    /// Fill a buy spending `sol_amount` (excluding `FillCosts`)
+    pub fn fill_buy(
+        &self,
+        market: &mut FillMarket,
+        mint: &str,
+        decimals: u8,
+        cash_decimals: u8,
+        sol_amount: f64,
+        price: f64,
+    ) -> Result<Fill, String> {
+        let scale = 10_f64.powi(decimals as i32);
+        let tokens = match self {
+            FillModel::FixedSlippage { slippage_bps } => {
+                sol_amount / (price * (1.0 + *slippage_bps as f64 / 10_000.0)) * scale
+            }
+            FillModel::BondingCurve { fee_bps } => {
+                let curve = market.curve_mut(mint)?;
+                let sol_in = sol_amount * (1.0 - *fee_bps as f64 / 10_000.0) * LAMPORTS_PER_SOL;
+                let (virtual_sol, virtual_tokens) =
+                    (curve.virtual_sol_reserves as f64, curve.virtual_token_reserves as f64);
+                let tokens_out = virtual_tokens * sol_in / (virtual_sol + sol_in);
+                if curve.real_token_reserves > 0 && tokens_out > curve.real_token_reserves as f64 {
+                    return Err(format!("Buy exceeds bonding curve token reserves for {}", mint));
+                }
+                // Leave the reserves untouched when the buy fills nothing
+                if tokens_out < 1.0 {
+                    return Err(format!("Buy of {} SOL fills zero tokens", sol_amount));
+                }
+
+                curve.virtual_sol_reserves += sol_in as u64;
+                curve.virtual_token_reserves -= tokens_out as u64;
+                curve.real_sol_reserves += sol_in as u64;
+                curve.real_token_reserves = curve.real_token_reserves.saturating_sub(tokens_out as u64);
+                tokens_out
+            }
+            FillModel::Quote => {
+                let quote = market.quote(mint, TradeSide::Buy)?;
+                let quoted_sol = quote.in_amount as f64 / 10_f64.powi(cash_decimals as i32);
+                let quoted_price = quoted_sol / (quote.out_amount as f64 / scale);
+                let impact = scaled_impact(quote, sol_amount / quoted_sol);
+                sol_amount / (quoted_price / (1.0 + quote_impact(quote)) * (1.0 + impact)) * scale
+            }
+        };
+
+        let token_amount = tokens as u64;
+        if token_amount == 0 {
+            return Err(format!("Buy of {} SOL fills zero tokens", sol_amount));
+        }
+        Ok(Fill::new(token_amount, decimals, sol_amount, price, TradeSide::Buy))
+    }
+
+    /// Fill a sell of `token_amount` base units (proceeds before `FillCosts`)
+    pub fn fill_sell(
+        &self,
+        market: &mut FillMarket,
+        mint: &str,
+        decimals: u8,
+        cash_decimals: u8,
+        token_amount: u64,
+        price: f64,
+    ) -> Result<Fill, String> {
+        let scale = 10_f64.powi(decimals as i32);
+        let tokens = token_amount as f64 / scale;
+        let sol_amount = match self {
+            FillModel::FixedSlippage { slippage_bps } => {
+                tokens * price * (1.0 - *slippage_bps as f64 / 10_000.0)
+            }
+            FillModel::BondingCurve { fee_bps } => {
+                let curve = market.curve_mut(mint)?;
+                let tokens_in = token_amount as f64;
+                let (virtual_sol, virtual_tokens) =
+                    (curve.virtual_sol_reserves as f64, curve.virtual_token_reserves as f64);
+                let sol_out = virtual_sol * tokens_in / (virtual_tokens + tokens_in);
+
+                curve.virtual_sol_reserves -= sol_out as u64;
+                curve.virtual_token_reserves += token_amount;
+                curve.real_sol_reserves = curve.real_sol_reserves.saturating_sub(sol_out as u64);
+                curve.real_token_reserves += token_amount;
+                sol_out * (1.0 - *fee_bps as f64 / 10_000.0) / LAMPORTS_PER_SOL
+            }
+            FillModel::Quote => {
+                let quote = market.quote(mint, TradeSide::Sell)?;
+                let quoted_tokens = quote.in_amount as f64 / scale;
+                let quoted_price = (quote.out_amount as f64 / 10_f64.powi(cash_decimals as i32)) / quoted_tokens;
+                let impact = scaled_impact(quote, tokens / quoted_tokens);
+                tokens * quoted_price / (1.0 - quote_impact(quote)) * (1.0 - impact)
+            }
+        };
+
+        Ok(Fill::new(token_amount, decimals, sol_amount.max(0.0), price, TradeSide::Sell))
+    }
+}
+
+/// Quote's own price impact as a fraction
+fn quote_impact(quote: &QuoteSummary) -> f64 {
+    (quote.price_impact_pct / 100.0).clamp(0.0, 0.99)
+}
+
+/// Quote impact scaled to a paper order `size_ratio` times the quoted size
+fn scaled_impact(quote: &QuoteSummary, size_ratio: f64) -> f64 {
+    (quote_impact(quote) * size_ratio).clamp(0.0, 0.99)
+}
+
+/// Executed paper fill
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    /// Tokens received (buy) or sold (sell), in base units
+    pub token_amount: u64,
+    /// SOL spent (buy) or received (sell), excluding fixed costs
+    pub sol_amount: f64,
+    /// Effective SOL per token
+    pub price: f64,
+    /// Fill price versus the reference price, adverse is positive
+    pub slippage_bps: u16,
+}
+
+impl Fill {
+    fn new(token_amount: u64, decimals: u8, sol_amount: f64, reference_price: f64, side: TradeSide) -> Self {
+        let tokens = token_amount as f64 / 10_f64.powi(decimals as i32);
+        let price = if tokens > 0.0 { sol_amount / tokens } else { 0.0 };
+        let adverse = match side {
+            TradeSide::Buy => price / reference_price - 1.0,
+            TradeSide::Sell => 1.0 - price / reference_price,
+        };
+        Self {
+            token_amount,
+            sol_amount,
+            price,
+            slippage_bps: (adverse * 10_000.0).round().clamp(0.0, u16::MAX as f64) as u16,
+        }
+    }
+}
+
+/// Fixed SOL costs of every simulated transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillCosts {
+    /// Signature fee
+    pub network_fee_lamports: u64,
+    /// Compute-unit priority fee
+    pub priority_fee_lamports: u64,
+    /// Jito bundle tip
+    pub jito_tip_lamports: u64,
+}
+
+impl Default for FillCosts {
+    fn default() -> Self {
+        Self {
+            network_fee_lamports: BASE_FEE_LAMPORTS,
+            priority_fee_lamports: 0,
+            jito_tip_lamports: 0,
+        }
+    }
+}
+
+impl FillCosts {
+    pub fn new(priority_fee_lamports: u64, jito_tip_lamports: u64) -> Self {
+        Self {
+            priority_fee_lamports,
+            jito_tip_lamports,
+            ..Default::default()
+        }
+    }
+
+    /// Total per transaction in SOL
+    pub fn per_transaction_sol(&self) -> f64 {
+        (self.network_fee_lamports + self.priority_fee_lamports + self.jito_tip_lamports) as f64 / LAMPORTS_PER_SOL
+    }
+}
+
+/// Latest market state the fill models execute against
+#[derive(Debug, Clone, Default)]
+pub struct FillMarket {
+    curves: HashMap<String, BondingCurveState>,
+    quotes: HashMap<(String, TradeSide), QuoteSummary>,
+}
+
+impl FillMarket {
+    /// Replace the bonding curve snapshot for `state.mint`
+    pub fn update_curve(&mut self, state: BondingCurveState) {
+        self.curves.insert(state.mint.clone(), state);
+    }
+
+    /// Current curve for a mint, including the reserves moved by paper trades
+    pub fn curve(&self, mint: &str) -> Option<&BondingCurveState> {
+        self.curves.get(mint)
+    }
+
+    /// Record a quote: SOL -> mint is a buy, mint -> SOL a sell of `mint`
+    pub fn record_quote(&mut self, mint: &str, quote: QuoteSummary) {
+        let side = if quote.output_mint == mint { TradeSide::Buy } else { TradeSide::Sell };
+        self.quotes.insert((mint.to_string(), side), quote);
+    }
+
+    fn curve_mut(&mut self, mint: &str) -> Result<&mut BondingCurveState, String> {
+        let curve = self
+            .curves
+            .get_mut(mint)
+            .ok_or_else(|| format!("No bonding curve state for {}", mint))?;
+        if curve.complete {
+            return Err(format!("Bonding curve for {} has graduated", mint));
+        }
+        if curve.virtual_sol_reserves == 0 || curve.virtual_token_reserves == 0 {
+            return Err(format!("Bonding curve for {} has no reserves", mint));
+        }
+        Ok(curve)
+    }
+
+    fn quote(&self, mint: &str, side: TradeSide) -> Result<&QuoteSummary, String> {
+        self.quotes
+            .get(&(mint.to_string(), side))
+            .filter(|quote| quote.in_amount > 0 && quote.out_amount > 0)
+            .ok_or_else(|| format!("No recorded {} quote for {}", side, mint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    /// Fresh pump.fun curve: 30 SOL / 1.073B tokens virtual, ~2.8e-8 SOL per token
+    fn curve(mint: &str) -> BondingCurveState {
+        BondingCurveState {
+            mint: mint.to_string(),
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+        }
+    }
+
+    fn quote(input_mint: &str, output_mint: &str, in_amount: u64, out_amount: u64, impact_pct: f64) -> QuoteSummary {
+        QuoteSummary {
+            timestamp: Utc::now(),
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            in_amount,
+            out_amount,
+            price_impact_pct: impact_pct,
+            route_labels: vec!["Raydium".to_string()],
+            latency_ms: 120,
+        }
+    }
+
+    #[test]
+    fn test_bonding_curve_impact_grows_with_size() {
+        let mut market = FillMarket::default();
+        let model = FillModel::pump_fun();
+        let spot = curve("mint").price_per_token();
+
+        market.update_curve(curve("mint"));
+        let small = model.fill_buy(&mut market, "mint", 6, 9, 0.1, spot).unwrap();
+        market.update_curve(curve("mint"));
+        let large = model.fill_buy(&mut market, "mint", 6, 9, 5.0, spot).unwrap();
+
+        // 1% fee plus impact: 0.1 SOL moves a 30 SOL curve ~0.3%, 5 SOL ~17%
+        assert!(small.slippage_bps > 100 && small.slippage_bps < 150);
+        assert!(large.slippage_bps > 1_500);
+        assert!(large.price > small.price);
+
+        // The paper buy moved the reserves
+        assert!(market.curve("mint").unwrap().price_per_token() > spot);
+
+        // Round trip loses fees and impact
+        let sell = model.fill_sell(&mut market, "mint", 6, 9, large.token_amount, spot).unwrap();
+        assert!(sell.sol_amount < 5.0 * 0.99);
+        assert!(sell.sol_amount > 5.0 * 0.97);
+    }
+
+    #[test]
+    fn test_bonding_curve_requires_live_curve() {
+        let mut market = FillMarket::default();
+        let model = FillModel::pump_fun();
+        assert!(model.fill_buy(&mut market, "mint", 6, 9, 0.1, 1e-8).unwrap_err().contains("No bonding curve"));
+
+        market.update_curve(BondingCurveState { complete: true, ..curve("mint") });
+        assert!(model.fill_buy(&mut market, "mint", 6, 9, 0.1, 1e-8).unwrap_err().contains("graduated"));
+    }
+
+    #[test]
+    fn test_quote_fill_scales_impact() {
+        let sol = "So11111111111111111111111111111111111111112";
+        let mut market = FillMarket::default();
+        // 1 SOL -> 1000 tokens (6 decimals) at 0.5% impact
+        market.record_quote("mint", quote(sol, "mint", 1_000_000_000, 1_000_000_000, 0.5));
+        // 1000 tokens -> 0.99 SOL at 0.5% impact
+        market.record_quote("mint", quote("mint", sol, 1_000_000_000, 990_000_000, 0.5));
+
+        let at_size = FillModel::Quote.fill_buy(&mut market, "mint", 6, 9, 1.0, 0.001).unwrap();
+        assert_eq!(at_size.token_amount, 1_000_000_000);
+        assert_eq!(at_size.slippage_bps, 0);
+
+        let double = FillModel::Quote.fill_buy(&mut market, "mint", 6, 9, 2.0, 0.001).unwrap();
+        assert!(double.price > at_size.price);
+
+        let sell = FillModel::Quote.fill_sell(&mut market, "mint", 6, 9, 1_000_000_000, 0.001).unwrap();
+        assert!((sell.sol_amount - 0.99).abs() < 1e-9);
+        assert_eq!(sell.slippage_bps, 100);
+
+        assert!(FillModel::Quote.fill_buy(&mut market, "other", 6, 9, 1.0, 0.001).is_err());
+    }
+
+    #[test]
+    fn test_fixed_costs() {
+        assert_eq!(FillCosts::default().per_transaction_sol(), 0.000005);
+        let costs = FillCosts::new(10_000, 1_000_000);
+        assert!((costs.per_transaction_sol() - 0.001015).abs() < 1e-12);
+
+        let fixed = FillModel::FixedSlippage { slippage_bps: 100 }
+            .fill_buy(&mut FillMarket::default(), "mint", 9, 9, 1.0, 0.001)
+            .unwrap();
+        assert_eq!(fixed.slippage_bps, 100);
+    }
+}
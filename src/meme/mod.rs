@@ -50,6 +50,7 @@
 
 pub mod commands;
 pub mod config;
+pub mod fill_model;
 pub mod paper_trading;
 pub mod traits;
 pub mod types;
@@ -57,3 +58,4 @@ pub mod types;
 // Re-export commonly used items
 pub use commands::execute_meme_command;
 pub use config::{MemeConfig, MemeConfigError};
+pub use fill_model::{Fill, FillCosts, FillMarket, FillModel};
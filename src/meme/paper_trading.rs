@@ -2,12 +2,25 @@
 //!
 //! A simulation harness for testing meme coin trading strategies without real money.
 //! Tracks simulated balances, token holdings, and calculates performance statistics.
+//!
+//! Fills are priced by a pluggable `FillModel` (flat slippage, bonding curve or
+//! recorded quote) and every transaction pays the `FillCosts`, which are
+//! included in the cost basis and realized PnL.
+//!
+//! The balance is SOL by default; the main bot's paper portfolio keeps it in
+//! USDC instead (`with_cash`, `set_sol_value`) and holds SOL as the token.
+//! Amounts named `*_sol` are then in USDC: `quote_currency` on every
+//! `PaperTrade` and on `PaperStats` names the balance asset they are in.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::SystemTime;
 use tracing::{info, warn};
 
+use crate::adapters::market_data::QuoteSummary;
+use crate::adapters::pump_fun::BondingCurveState;
+use crate::meme::fill_model::{FillCosts, FillMarket, FillModel};
+
 /// Token holding in the paper trading engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenHolding {
@@ -83,14 +96,20 @@ pub struct PaperTrade {
     pub sol_amount: f64,
     /// Execution price (SOL per token)
     pub price: f64,
-    /// Simulated slippage in basis points
+    /// Fill price versus the reference price in basis points (adverse)
     pub slippage_bps: u16,
+    /// Network, priority and tip costs paid in SOL
+    #[serde(default)]
+    pub fee_sol: f64,
     /// Timestamp (Unix seconds)
     pub timestamp: u64,
     /// PnL for this trade (sells only)
     pub pnl_sol: Option<f64>,
     /// PnL percentage (sells only)
     pub pnl_pct: Option<f64>,
+    /// Asset `sol_amount`, `price`, `fee_sol` and `pnl_sol` are in
+    #[serde(default = "default_quote_currency")]
+    pub quote_currency: String,
 }
 
 impl PaperTrade {
@@ -101,7 +120,7 @@ impl PaperTrade {
 }
 
 /// Trade direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TradeSide {
     /// Buy tokens with SOL
     Buy,
@@ -119,8 +138,11 @@ impl std::fmt::Display for TradeSide {
 }
 
 /// Paper trading statistics
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaperStats {
+    /// Asset the `*_sol` amounts are in
+    #[serde(default = "default_quote_currency")]
+    pub quote_currency: String,
     /// Total number of trades
     pub total_trades: u32,
     /// Number of buy trades
@@ -135,6 +157,9 @@ pub struct PaperStats {
     pub total_realized_pnl_sol: f64,
     /// Total volume traded in SOL
     pub total_volume_sol: f64,
+    /// Total fixed transaction costs paid in SOL
+    #[serde(default)]
+    pub total_fees_sol: f64,
     /// Largest winning trade in SOL
     pub largest_win_sol: f64,
     /// Largest losing trade in SOL
@@ -151,12 +176,40 @@ pub struct PaperStats {
     trade_returns: Vec<f64>,
 }
 
+impl Default for PaperStats {
+    fn default() -> Self {
+        Self::in_currency(default_quote_currency())
+    }
+}
+
 impl PaperStats {
     /// Create new stats
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create new stats for amounts in `quote_currency`
+    pub fn in_currency(quote_currency: impl Into<String>) -> Self {
+        Self {
+            quote_currency: quote_currency.into(),
+            total_trades: 0,
+            buy_count: 0,
+            sell_count: 0,
+            winning_trades: 0,
+            losing_trades: 0,
+            total_realized_pnl_sol: 0.0,
+            total_volume_sol: 0.0,
+            total_fees_sol: 0.0,
+            largest_win_sol: 0.0,
+            largest_loss_sol: 0.0,
+            sum_wins_sol: 0.0,
+            sum_losses_sol: 0.0,
+            peak_value_sol: 0.0,
+            max_drawdown_pct: 0.0,
+            trade_returns: Vec::new(),
+        }
+    }
+
     /// Win rate as a percentage (0-100)
     pub fn win_rate(&self) -> f64 {
         let total_closed = self.winning_trades + self.losing_trades;
@@ -223,36 +276,35 @@ impl PaperStats {
     fn record_trade(&mut self, trade: &PaperTrade, portfolio_value: f64) {
         self.total_trades += 1;
         self.total_volume_sol += trade.sol_amount;
+        self.total_fees_sol += trade.fee_sol;
 
         match trade.side {
-            TradeSide::Buy => {
-                self.buy_count += 1;
-            }
-            TradeSide::Sell => {
-                self.sell_count += 1;
-                if let Some(pnl) = trade.pnl_sol {
-                    self.total_realized_pnl_sol += pnl;
-
-                    if pnl > 0.0 {
-                        self.winning_trades += 1;
-                        self.sum_wins_sol += pnl;
-                        if pnl > self.largest_win_sol {
-                            self.largest_win_sol = pnl;
-                        }
-                    } else if pnl < 0.0 {
-                        self.losing_trades += 1;
-                        self.sum_losses_sol += pnl.abs();
-                        if pnl.abs() > self.largest_loss_sol {
-                            self.largest_loss_sol = pnl.abs();
-                        }
-                    }
-
-                    // Record return for Sharpe
-                    if let Some(pnl_pct) = trade.pnl_pct {
-                        self.trade_returns.push(pnl_pct / 100.0);
-                    }
+            TradeSide::Buy => self.buy_count += 1,
+            TradeSide::Sell => self.sell_count += 1,
+        }
+
+        // Sells closing longs and buys covering shorts realize PnL
+        if let Some(pnl) = trade.pnl_sol {
+            self.total_realized_pnl_sol += pnl;
+
+            if pnl > 0.0 {
+                self.winning_trades += 1;
+                self.sum_wins_sol += pnl;
+                if pnl > self.largest_win_sol {
+                    self.largest_win_sol = pnl;
+                }
+            } else if pnl < 0.0 {
+                self.losing_trades += 1;
+                self.sum_losses_sol += pnl.abs();
+                if pnl.abs() > self.largest_loss_sol {
+                    self.largest_loss_sol = pnl.abs();
                 }
             }
+
+            // Record return for Sharpe
+            if let Some(pnl_pct) = trade.pnl_pct {
+                self.trade_returns.push(pnl_pct / 100.0);
+            }
         }
 
         // Update peak and drawdown
@@ -267,6 +319,17 @@ impl PaperStats {
     }
 }
 
+/// Held tokens sold short and not yet bought back
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct ShortLot {
+    /// Tokens sold (in base units)
+    token_amount: u64,
+    /// SOL received, net of transaction costs
+    proceeds_sol: f64,
+    /// Cost basis the tokens carried in the holding
+    cost_basis_sol: f64,
+}
+
 /// Paper trading simulation engine
 ///
 /// Simulates trading without real money, tracking balances, positions,
@@ -277,20 +340,54 @@ pub struct PaperTradingEngine {
     sol_balance: f64,
     /// Initial SOL balance (for calculating returns)
     initial_sol_balance: f64,
+    /// Cost-basis value of tokens added by `deposit`, part of the return base
+    #[serde(default)]
+    deposited_value: f64,
     /// Token holdings by mint address
     token_holdings: HashMap<String, TokenHolding>,
+    /// Open shorts by mint address, waiting for their cover
+    #[serde(default)]
+    shorts: HashMap<String, ShortLot>,
     /// Trade history
     trades: Vec<PaperTrade>,
     /// Trading statistics
     stats: PaperStats,
     /// Next trade ID
     next_trade_id: u64,
-    /// Simulated slippage in basis points (default 50 = 0.5%)
-    slippage_bps: u16,
+    /// How fills are priced (default 50 bps flat slippage)
+    #[serde(default)]
+    fill_model: FillModel,
+    /// Fixed SOL costs per transaction
+    #[serde(default)]
+    costs: FillCosts,
+    /// Bonding curves and quotes the fill model executes against
+    #[serde(skip)]
+    market: FillMarket,
+    /// Balance asset, the quote currency of trades and stats
+    #[serde(default = "default_quote_currency")]
+    quote_currency: String,
+    /// Decimals of the balance asset quotes are scaled by (9 for SOL)
+    #[serde(default = "default_cash_decimals")]
+    cash_decimals: u8,
+    /// Balance-asset value of 1 SOL, prices `costs` when the balance is not SOL
+    #[serde(default = "default_sol_value")]
+    sol_value: f64,
     /// Enable trade logging
     log_trades: bool,
 }
 
+fn default_quote_currency() -> String {
+    "SOL".to_string()
+}
+
+fn default_cash_decimals() -> u8 {
+    9
+}
+
+fn default_sol_value() -> f64 {
+    1.0
+}
+
 impl PaperTradingEngine {
     /// Create a new paper trading engine with initial SOL balance
     ///
@@ -305,11 +402,18 @@ impl PaperTradingEngine {
         Self {
             sol_balance: initial_sol,
             initial_sol_balance: initial_sol,
+            deposited_value: 0.0,
             token_holdings: HashMap::new(),
+            shorts: HashMap::new(),
             trades: Vec::new(),
             stats: PaperStats::new(),
             next_trade_id: 1,
-            slippage_bps: 50,
+            fill_model: FillModel::default(),
+            costs: FillCosts::default(),
+            market: FillMarket::default(),
+            quote_currency: default_quote_currency(),
+            cash_decimals: default_cash_decimals(),
+            sol_value: default_sol_value(),
             log_trades: true,
         }
     }
@@ -319,7 +423,59 @@ impl PaperTradingEngine {
     /// # Arguments
     /// * `slippage_bps` - Slippage in basis points (100 = 1%)
     pub fn set_slippage(&mut self, slippage_bps: u16) {
-        self.slippage_bps = slippage_bps;
+        self.fill_model = FillModel::FixedSlippage { slippage_bps };
+    }
+
+    /// Price fills with a different model
+    pub fn with_fill_model(mut self, fill_model: FillModel) -> Self {
+        self.fill_model = fill_model;
+        self
+    }
+
+    /// Charge these fixed costs on every transaction
+    pub fn with_costs(mut self, costs: FillCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Keep the balance in `currency` with these decimals instead of SOL
+    pub fn with_cash(mut self, currency: impl Into<String>, decimals: u8) -> Self {
+        self.quote_currency = currency.into();
+        self.cash_decimals = decimals;
+        self.stats.quote_currency = self.quote_currency.clone();
+        self
+    }
+
+    /// Asset the balance, trades and stats are in
+    pub fn quote_currency(&self) -> &str {
+        &self.quote_currency
+    }
+
+    /// Set the balance-asset value of 1 SOL used to charge `FillCosts`
+    pub fn set_sol_value(&mut self, sol_value: f64) {
+        self.sol_value = sol_value;
+    }
+
+    /// Current fill model
+    pub fn fill_model(&self) -> FillModel {
+        self.fill_model
+    }
+
+    /// Fixed costs per transaction
+    pub fn costs(&self) -> FillCosts {
+        self.costs
+    }
+
+    /// Update the bonding curve used by `FillModel::BondingCurve`
+    ///
+    /// Paper fills move the stored reserves until the next update.
+    pub fn update_bonding_curve(&mut self, state: BondingCurveState) {
+        self.market.update_curve(state);
+    }
+
+    /// Record the latest Jupiter quote for `mint` used by `FillModel::Quote`
+    pub fn record_quote(&mut self, mint: &str, quote: QuoteSummary) {
+        self.market.record_quote(mint, quote);
     }
 
     /// Enable or disable trade logging
@@ -332,6 +488,11 @@ impl PaperTradingEngine {
         self.sol_balance
     }
 
+    /// Starting balance plus deposits, the base for returns
+    pub fn initial_balance(&self) -> f64 {
+        self.initial_sol_balance + self.deposited_value
+    }
+
     /// Simulate buying tokens with SOL
     ///
     /// # Arguments
@@ -350,6 +511,19 @@ impl PaperTradingEngine {
         decimals: u8,
         sol_amount: f64,
         price: f64,
+    ) -> Result<PaperTrade, String> {
+        let trade = self.execute_buy(mint, symbol, decimals, sol_amount, price)?;
+        Ok(self.record(trade))
+    }
+
+    /// Fill a buy and update balances and the holding, without recording it
+    fn execute_buy(
+        &mut self,
+        mint: &str,
+        symbol: &str,
+        decimals: u8,
+        sol_amount: f64,
+        price: f64,
     ) -> Result<PaperTrade, String> {
         // Validate inputs
         if sol_amount <= 0.0 {
@@ -358,36 +532,36 @@ impl PaperTradingEngine {
         if price <= 0.0 {
             return Err("Price must be positive".to_string());
         }
-        if sol_amount > self.sol_balance {
+        let fee_sol = self.costs.per_transaction_sol() * self.sol_value;
+        if sol_amount + fee_sol > self.sol_balance {
             return Err(format!(
                 "Insufficient SOL balance: have {:.4}, need {:.4}",
-                self.sol_balance, sol_amount
+                self.sol_balance,
+                sol_amount + fee_sol
             ));
         }
 
-        // Apply slippage (buy = worse price = higher)
-        let slippage_mult = 1.0 + (self.slippage_bps as f64 / 10000.0);
-        let effective_price = price * slippage_mult;
-
-        // Calculate token amount received
-        let tokens_decimal = sol_amount / effective_price;
-        let token_amount = (tokens_decimal * 10_u64.pow(decimals as u32) as f64) as u64;
+        let fill = self.fill_model.fill_buy(&mut self.market, mint, decimals, self.cash_decimals, sol_amount, price)?;
+        let effective_price = fill.price;
+        let token_amount = fill.token_amount;
+        if token_amount == 0 {
+            return Err(format!("Buy of {} SOL fills zero tokens", sol_amount));
+        }
 
         // Deduct SOL
-        self.sol_balance -= sol_amount;
+        self.sol_balance -= sol_amount + fee_sol;
 
         // Update or create holding
         let holding = self.token_holdings
             .entry(mint.to_string())
             .or_insert_with(|| TokenHolding::new(mint, symbol, decimals));
 
-        // Update average cost basis
+        // Update average cost basis (transaction costs included)
         let prev_total = holding.amount_decimal() * holding.avg_cost_sol;
-        let new_tokens_decimal = token_amount as f64 / 10_u64.pow(decimals as u32) as f64;
-        let new_total = prev_total + sol_amount;
+        let new_total = prev_total + sol_amount + fee_sol;
 
         holding.amount += token_amount;
-        holding.total_cost_sol += sol_amount;
+        holding.total_cost_sol += sol_amount + fee_sol;
 
         if holding.amount > 0 {
             holding.avg_cost_sol = new_total / holding.amount_decimal();
@@ -409,32 +583,15 @@ impl PaperTradingEngine {
             decimals,
             sol_amount,
             price: effective_price,
-            slippage_bps: self.slippage_bps,
+            slippage_bps: fill.slippage_bps,
+            fee_sol,
             timestamp,
             pnl_sol: None,
             pnl_pct: None,
+            quote_currency: self.quote_currency.clone(),
         };
 
         self.next_trade_id += 1;
-
-        // Update stats
-        let portfolio_value = self.calculate_portfolio_value_estimate();
-        self.stats.record_trade(&trade, portfolio_value);
-
-        // Log trade
-        if self.log_trades {
-            info!(
-                "[PAPER] {} {} {} @ {:.8} SOL = {:.4} SOL (slippage: {}bps)",
-                trade.side,
-                new_tokens_decimal,
-                symbol,
-                effective_price,
-                sol_amount,
-                self.slippage_bps
-            );
-        }
-
-        self.trades.push(trade.clone());
         Ok(trade)
     }
 
@@ -452,6 +609,98 @@ impl PaperTradingEngine {
         mint: &str,
         token_amount: u64,
         price: f64,
+    ) -> Result<PaperTrade, String> {
+        let trade = self.execute_sell(mint, token_amount, price)?;
+        Ok(self.record(trade))
+    }
+
+    /// Sell held tokens as a short entry
+    ///
+    /// The holding's cost basis leaves with the tokens but no PnL is
+    /// realized: the proceeds wait for `simulate_cover`, which books the
+    /// short's round trip against them.
+    pub fn simulate_short(
+        &mut self,
+        mint: &str,
+        token_amount: u64,
+        price: f64,
+    ) -> Result<PaperTrade, String> {
+        let mut trade = self.execute_sell(mint, token_amount, price)?;
+        let pnl_sol = trade.pnl_sol.take().unwrap_or(0.0);
+        trade.pnl_pct = None;
+        if let Some(holding) = self.token_holdings.get_mut(mint) {
+            holding.realized_pnl_sol -= pnl_sol;
+        }
+
+        let lot = self.shorts.entry(mint.to_string()).or_default();
+        lot.token_amount += token_amount;
+        lot.proceeds_sol += trade.sol_amount;
+        lot.cost_basis_sol += trade.sol_amount - pnl_sol;
+        Ok(self.record(trade))
+    }
+
+    /// Buy back tokens sold by `simulate_short`, spending `sol_amount`
+    ///
+    /// The covered part of the short realizes its proceeds minus the cover
+    /// cost, and its tokens return to the holding at the cost basis they left
+    /// with. Tokens bought beyond the open short are a plain buy. A cover
+    /// that fills zero tokens fails without touching balances, the holding or
+    /// the short.
+    pub fn simulate_cover(
+        &mut self,
+        mint: &str,
+        symbol: &str,
+        decimals: u8,
+        sol_amount: f64,
+        price: f64,
+    ) -> Result<PaperTrade, String> {
+        let lot = self
+            .shorts
+            .get(mint)
+            .copied()
+            .filter(|lot| lot.token_amount > 0)
+            .ok_or_else(|| format!("No open short for token: {}", mint))?;
+        // Fails on a zero-token fill before any balance changes
+        let mut trade = self.execute_buy(mint, symbol, decimals, sol_amount, price)?;
+
+        let covered = trade.token_amount.min(lot.token_amount);
+        let fraction = covered as f64 / lot.token_amount as f64;
+        let cover_cost = (trade.sol_amount + trade.fee_sol) * covered as f64 / trade.token_amount as f64;
+        let proceeds = lot.proceeds_sol * fraction;
+        let cost_basis = lot.cost_basis_sol * fraction;
+        let pnl_sol = proceeds - cover_cost;
+
+        // The buy booked the covered tokens at the cover cost
+        if let Some(holding) = self.token_holdings.get_mut(mint) {
+            holding.total_cost_sol += cost_basis - cover_cost;
+            holding.avg_cost_sol = holding.total_cost_sol / holding.amount_decimal();
+            holding.realized_pnl_sol += pnl_sol;
+        }
+
+        if covered == lot.token_amount {
+            self.shorts.remove(mint);
+        } else if let Some(open) = self.shorts.get_mut(mint) {
+            open.token_amount -= covered;
+            open.proceeds_sol -= proceeds;
+            open.cost_basis_sol -= cost_basis;
+        }
+
+        trade.pnl_sol = Some(pnl_sol);
+        trade.pnl_pct = Some(if proceeds > 0.0 { pnl_sol / proceeds * 100.0 } else { 0.0 });
+        Ok(self.record(trade))
+    }
+
+    /// Tokens sold short for `mint` and not yet covered (in base units)
+    pub fn short_amount(&self, mint: &str) -> u64 {
+        self.shorts.get(mint).map_or(0, |lot| lot.token_amount)
+    }
+
+    /// Fill a sell and update balances and the holding, without recording it
+    fn execute_sell(
+        &mut self,
+        mint: &str,
+        token_amount: u64,
+        price: f64,
     ) -> Result<PaperTrade, String> {
         // Validate inputs
         if token_amount == 0 {
@@ -473,13 +722,15 @@ impl PaperTradingEngine {
             ));
         }
 
-        // Apply slippage (sell = worse price = lower)
-        let slippage_mult = 1.0 - (self.slippage_bps as f64 / 10000.0);
-        let effective_price = price * slippage_mult;
+        let fill = self.fill_model.fill_sell(
+            &mut self.market, mint, holding.decimals, self.cash_decimals, token_amount, price,
+        )?;
+        let effective_price = fill.price;
+        let fee_sol = self.costs.per_transaction_sol() * self.sol_value;
 
-        // Calculate SOL received
+        // Calculate SOL received, net of transaction costs
         let tokens_decimal = token_amount as f64 / 10_u64.pow(holding.decimals as u32) as f64;
-        let sol_received = tokens_decimal * effective_price;
+        let sol_received = fill.sol_amount - fee_sol;
 
         // Calculate PnL
         let cost_basis = tokens_decimal * holding.avg_cost_sol;
@@ -529,37 +780,78 @@ impl PaperTradingEngine {
             decimals,
             sol_amount: sol_received,
             price: effective_price,
-            slippage_bps: self.slippage_bps,
+            slippage_bps: fill.slippage_bps,
+            fee_sol,
             timestamp,
             pnl_sol: Some(pnl_sol),
             pnl_pct: Some(pnl_pct),
+            quote_currency: self.quote_currency.clone(),
         };
 
         self.next_trade_id += 1;
+        Ok(trade)
+    }
 
+    /// Add an executed trade to the stats and history
+    fn record(&mut self, trade: PaperTrade) -> PaperTrade {
         // Update stats
         let portfolio_value = self.calculate_portfolio_value_estimate();
         self.stats.record_trade(&trade, portfolio_value);
 
         // Log trade
         if self.log_trades {
-            let pnl_emoji = if pnl_sol >= 0.0 { "+" } else { "" };
-            info!(
-                "[PAPER] {} {} {} @ {:.8} SOL = {:.4} SOL | PnL: {}{:.4} SOL ({}{:.2}%)",
-                trade.side,
-                tokens_decimal,
-                symbol,
-                effective_price,
-                sol_received,
-                pnl_emoji,
-                pnl_sol,
-                pnl_emoji,
-                pnl_pct
-            );
+            match (trade.pnl_sol, trade.pnl_pct) {
+                (Some(pnl_sol), Some(pnl_pct)) => {
+                    let pnl_emoji = if pnl_sol >= 0.0 { "+" } else { "" };
+                    info!(
+                        "[PAPER] {} {} {} @ {:.8} SOL = {:.4} SOL | PnL: {}{:.4} SOL ({}{:.2}%)",
+                        trade.side,
+                        trade.token_amount_decimal(),
+                        trade.symbol,
+                        trade.price,
+                        trade.sol_amount,
+                        pnl_emoji,
+                        pnl_sol,
+                        pnl_emoji,
+                        pnl_pct
+                    );
+                }
+                _ => info!(
+                    "[PAPER] {} {} {} @ {:.8} SOL = {:.4} SOL ({} slippage: {}bps, fees: {:.6} SOL)",
+                    trade.side,
+                    trade.token_amount_decimal(),
+                    trade.symbol,
+                    trade.price,
+                    trade.sol_amount,
+                    self.fill_model.name(),
+                    trade.slippage_bps,
+                    trade.fee_sol
+                ),
+            }
         }
 
         self.trades.push(trade.clone());
-        Ok(trade)
+        trade
+    }
+
+    /// Add tokens held before trading started, at `price` cost basis
+    ///
+    /// No trade is recorded; the deposit counts towards the initial balance
+    /// so returns measure trading only. `reset` drops deposits with the
+    /// holdings.
+    pub fn deposit(&mut self, mint: &str, symbol: &str, decimals: u8, token_amount: u64, price: f64) {
+        let holding = self.token_holdings
+            .entry(mint.to_string())
+            .or_insert_with(|| TokenHolding::new(mint, symbol, decimals));
+        let value = token_amount as f64 / 10_u64.pow(decimals as u32) as f64 * price;
+
+        holding.amount += token_amount;
+        holding.total_cost_sol += value;
+        if holding.amount > 0 {
+            holding.avg_cost_sol = holding.total_cost_sol / holding.amount_decimal();
+        }
+        self.deposited_value += value;
+        self.stats.peak_value_sol = self.stats.peak_value_sol.max(self.calculate_portfolio_value_estimate());
     }
 
     /// Get current position for a token
@@ -587,7 +879,7 @@ impl PaperTradingEngine {
             .sum();
 
         // Total PnL = current SOL balance + holdings cost - initial balance
-        self.sol_balance + total_invested - self.initial_sol_balance
+        self.sol_balance + total_invested - self.initial_balance()
     }
 
     /// Calculate total PnL with current market prices
@@ -596,7 +888,7 @@ impl PaperTradingEngine {
     /// * `prices` - Map of mint address to current price in SOL
     pub fn get_pnl_with_prices(&self, prices: &HashMap<String, f64>) -> f64 {
         let portfolio_value = self.calculate_portfolio_value(prices);
-        portfolio_value - self.initial_sol_balance
+        portfolio_value - self.initial_balance()
     }
 
     /// Get trading statistics
@@ -649,20 +941,22 @@ impl PaperTradingEngine {
 
     /// Get return percentage since inception
     pub fn get_return_pct(&self) -> f64 {
-        if self.initial_sol_balance <= 0.0 {
+        let initial = self.initial_balance();
+        if initial <= 0.0 {
             return 0.0;
         }
         let current = self.calculate_portfolio_value_estimate();
-        ((current - self.initial_sol_balance) / self.initial_sol_balance) * 100.0
+        ((current - initial) / initial) * 100.0
     }
 
     /// Get return percentage with market prices
     pub fn get_return_pct_with_prices(&self, prices: &HashMap<String, f64>) -> f64 {
-        if self.initial_sol_balance <= 0.0 {
+        let initial = self.initial_balance();
+        if initial <= 0.0 {
             return 0.0;
         }
         let current = self.calculate_portfolio_value(prices);
-        ((current - self.initial_sol_balance) / self.initial_sol_balance) * 100.0
+        ((current - initial) / initial) * 100.0
     }
 
     /// Reset the paper trading engine
@@ -673,9 +967,11 @@ impl PaperTradingEngine {
         );
 
         self.sol_balance = self.initial_sol_balance;
+        self.deposited_value = 0.0;
         self.token_holdings.clear();
+        self.shorts.clear();
         self.trades.clear();
-        self.stats = PaperStats::new();
+        self.stats = PaperStats::in_currency(self.quote_currency.clone());
         self.next_trade_id = 1;
     }
 
@@ -701,7 +997,7 @@ impl PaperTradingEngine {
     pub fn print_summary(&self) {
         info!("========== PAPER TRADING SUMMARY ==========");
         info!("SOL Balance: {:.4}", self.sol_balance);
-        info!("Initial Balance: {:.4}", self.initial_sol_balance);
+        info!("Initial Balance: {:.4}", self.initial_balance());
         info!("Return: {:.2}%", self.get_return_pct());
         info!("");
         info!("Active Positions: {}", self.token_holdings.len());
@@ -1036,6 +1332,131 @@ mod tests {
         assert!(holding.avg_cost_sol > 0.001 && holding.avg_cost_sol < 0.002);
     }
 
+    #[test]
+    fn test_fixed_costs_reduce_pnl() {
+        let mut engine = PaperTradingEngine::new(10.0).with_costs(FillCosts::new(1_000_000, 1_000_000));
+        engine.set_log_trades(false);
+        engine.set_slippage(0);
+
+        // Round trip at the same price loses both transactions' costs
+        engine.simulate_buy("test", "TEST", 9, 1.0, 0.001).unwrap();
+        let holding = engine.get_position("test").unwrap();
+        assert!((holding.total_cost_sol - 1.002005).abs() < 1e-9);
+
+        let amount = holding.amount;
+        let sell = engine.simulate_sell("test", amount, 0.001).unwrap();
+        assert!((sell.pnl_sol.unwrap() + 0.00401).abs() < 1e-6);
+        assert!((engine.sol_balance() - (10.0 - 0.00401)).abs() < 1e-6);
+        assert!((engine.get_stats().total_fees_sol - 0.00401).abs() < 1e-9);
+        assert_eq!(engine.get_stats().losing_trades, 1);
+
+        // Costs count against the balance check
+        let mut engine = PaperTradingEngine::new(1.0).with_costs(FillCosts::new(1_000_000, 0));
+        engine.set_log_trades(false);
+        assert!(engine.simulate_buy("test", "TEST", 9, 1.0, 0.001).unwrap_err().contains("Insufficient SOL"));
+    }
+
+    #[test]
+    fn test_short_round_trip_books_own_entry() {
+        let mut engine = PaperTradingEngine::new(10.0);
+        engine.set_log_trades(false);
+        engine.set_slippage(0);
+        engine.deposit("test", "TEST", 9, 2_000_000_000, 1.0);
+        let fee = FillCosts::default().per_transaction_sol();
+
+        // The short sell realizes nothing against the deposit's cost basis
+        let short = engine.simulate_short("test", 1_000_000_000, 1.0).unwrap();
+        assert_eq!(short.pnl_sol, None);
+        assert_eq!(engine.short_amount("test"), 1_000_000_000);
+        assert_eq!(engine.get_stats().total_realized_pnl_sol, 0.0);
+
+        // Covering half at 0.9 books half the proceeds against its own cost
+        let half = engine.simulate_cover("test", "TEST", 9, 0.45, 0.9).unwrap();
+        assert_eq!(half.side, TradeSide::Buy);
+        assert!((half.pnl_sol.unwrap() - (0.5 * (1.0 - fee) - (0.45 + fee))).abs() < 1e-9);
+        assert_eq!(engine.short_amount("test"), 500_000_000);
+
+        engine.simulate_cover("test", "TEST", 9, 0.45, 0.9).unwrap();
+        assert_eq!(engine.short_amount("test"), 0);
+        let stats = engine.get_stats();
+        assert_eq!(stats.winning_trades, 2);
+        assert_eq!(stats.sell_count, 1);
+        assert!((stats.total_realized_pnl_sol - (0.1 - 3.0 * fee)).abs() < 1e-9);
+
+        // The deposit is back at its own cost basis
+        let holding = engine.get_position("test").unwrap();
+        assert_eq!(holding.amount, 2_000_000_000);
+        assert!((holding.avg_cost_sol - 1.0).abs() < 1e-9);
+        assert!(engine.simulate_cover("test", "TEST", 9, 0.45, 0.9).unwrap_err().contains("No open short"));
+    }
+
+    #[test]
+    fn test_zero_token_cover_changes_nothing() {
+        let mut engine = PaperTradingEngine::new(10.0);
+        engine.set_log_trades(false);
+        engine.deposit("test", "TEST", 0, 10, 1.0);
+        engine.simulate_short("test", 5, 1.0).unwrap();
+        let balance = engine.sol_balance();
+        let trades = engine.get_trades().len();
+
+        // 0.1 SOL at 1.0 per whole token rounds to zero tokens
+        assert!(engine.simulate_cover("test", "TEST", 0, 0.1, 1.0).is_err());
+        assert_eq!(engine.sol_balance(), balance);
+        assert_eq!(engine.short_amount("test"), 5);
+        assert_eq!(engine.get_position("test").unwrap().amount, 5);
+        assert!(engine.get_position("test").unwrap().avg_cost_sol.is_finite());
+        assert_eq!(engine.get_trades().len(), trades);
+    }
+
+    #[test]
+    fn test_reset_drops_deposits() {
+        let mut engine = PaperTradingEngine::new(10.0);
+        engine.set_log_trades(false);
+        engine.deposit("test", "TEST", 9, 2_000_000_000, 1.0);
+        assert!((engine.initial_balance() - 12.0).abs() < 1e-9);
+        assert!(engine.get_pnl().abs() < 1e-9);
+
+        engine.reset();
+        assert_eq!(engine.sol_balance(), 10.0);
+        assert_eq!(engine.initial_balance(), 10.0);
+        assert!(engine.get_position("test").is_none());
+        assert_eq!(engine.get_pnl(), 0.0);
+    }
+
+    #[test]
+    fn test_bonding_curve_fills() {
+        let curve = BondingCurveState {
+            mint: "pump".to_string(),
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+        };
+        let spot = curve.price_per_token();
+
+        let mut engine = PaperTradingEngine::new(10.0).with_fill_model(FillModel::pump_fun());
+        engine.set_log_trades(false);
+        assert!(engine.simulate_buy("pump", "PUMP", 6, 1.0, spot).is_err());
+
+        engine.update_bonding_curve(curve.clone());
+        let buy = engine.simulate_buy("pump", "PUMP", 6, 1.0, spot).unwrap();
+        // 1% fee plus ~3% impact on a 30 SOL curve
+        assert!(buy.price > spot * 1.03);
+        assert!(buy.slippage_bps > 400);
+
+        // Selling straight back into the curve loses the fee twice
+        let sell = engine.simulate_sell("pump", buy.token_amount, spot).unwrap();
+        let pnl_pct = sell.pnl_pct.unwrap();
+        assert!(pnl_pct < -1.9 && pnl_pct > -2.1);
+
+        // State round trip keeps the model, not the market snapshots
+        let restored = PaperTradingEngine::from_json(&engine.export_state_json().unwrap()).unwrap();
+        assert_eq!(restored.fill_model(), FillModel::pump_fun());
+        assert_eq!(restored.costs(), FillCosts::default());
+    }
+
     #[test]
     fn test_get_trades_for_token() {
         let mut engine = PaperTradingEngine::new(10.0);
@@ -73,84 +73,3 @@ pub trait ExecutionPort {
         self.execute_swap(request).await
     }
 }
-
-pub struct JupiterExecutionPort {
-    api_base_url: String,
-    api_key: Option<String>,
-}
-
-impl JupiterExecutionPort {
-    pub fn new(api_base_url: String, api_key: Option<String>) -> Self {
-        Self {
-            api_base_url,
-            api_key,
-        }
-    }
-}
-
-#[async_trait::async_trait]
-impl ExecutionPort for JupiterExecutionPort {
-    async fn get_swap_quote(
-        &self,
-        request: SwapQuoteRequest,
-    ) -> Result<SwapQuoteResponse, ExecutionError> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/quote", self.api_base_url);
-
-        let response = client
-            .get(&url)
-            .query(&request)
-            .header(
-                "x-api-key",
-                self.api_key.as_ref().ok_or_else(|| {
-                    ExecutionError::InvalidParameters("API key required".to_string())
-                })?,
-            )
-            .send()
-            .await
-            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(ExecutionError::ApiError(
-                response.text().await.unwrap_or_default(),
-            ));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| ExecutionError::ApiError(e.to_string()))
-    }
-
-    async fn execute_swap(
-        &self,
-        request: ExecuteSwapRequest,
-    ) -> Result<ExecuteSwapResponse, ExecutionError> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/swap", self.api_base_url);
-
-        let response = client
-            .post(&url)
-            .json(&request)
-            .header(
-                "x-api-key",
-                self.api_key.as_ref().ok_or_else(|| {
-                    ExecutionError::InvalidParameters("API key required".to_string())
-                })?,
-            )
-            .send()
-            .await
-            .map_err(|e| ExecutionError::ApiError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(ExecutionError::ApiError(
-                response.text().await.unwrap_or_default(),
-            ));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| ExecutionError::ApiError(e.to_string()))
-    }
-}
\ No newline at end of file
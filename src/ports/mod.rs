@@ -3,11 +3,15 @@
 //! This module defines the interfaces (ports) that adapters must implement.
 //! Following hexagonal architecture, these traits abstract:
 //! - Market data feeds (prices, OHLCV)
+//! - Timestamped price sources (live or replayed)
 //! - Trade execution (Jupiter swaps)
+//! - Perp execution (true shorts on a perp DEX)
 //! - Strategy signal generation
 
 pub mod market_data;
+pub mod price;
 pub mod execution;
+pub mod perp;
 pub mod strategy;
 pub mod models;
 
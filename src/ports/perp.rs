@@ -0,0 +1,75 @@
+//! Perp Port
+//!
+//! Execution on a perpetual futures venue, for short exposure that does not
+//! sell base inventory. One position per market, sized in base units (SOL);
+//! orders are market orders at an expected price.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::domain::position::Side;
+
+/// Perp venue error type
+#[derive(Error, Debug)]
+pub enum PerpPortError {
+    #[error("Order rejected: {0}")]
+    Rejected(String),
+
+    #[error("Perp venue error: {0}")]
+    VenueError(String),
+
+    #[error("No open perp position")]
+    NoPosition,
+}
+
+/// Open perp position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerpPosition {
+    pub side: Side,
+    /// Size in base units
+    pub size: f64,
+    /// Size-weighted average entry price
+    pub entry_price: f64,
+}
+
+/// One executed perp order
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerpFill {
+    /// Side of the position the fill applied to
+    pub side: Side,
+    /// Size filled in base units
+    pub size: f64,
+    pub price: f64,
+    /// Fee paid in quote units
+    pub fee: f64,
+    /// PnL realized by a reducing fill in quote units, before fees
+    pub realized_pnl: f64,
+}
+
+/// Perp execution port trait
+#[async_trait]
+pub trait PerpPort: Send + Sync {
+    /// Market traded, e.g. "SOL-PERP"
+    fn market(&self) -> &str;
+
+    /// Open or add to a position on `side` by `size` base units
+    async fn increase(&self, side: Side, size: f64, price: f64) -> Result<PerpFill, PerpPortError>;
+
+    /// Reduce the open position by `size` base units
+    async fn reduce(&self, size: f64, price: f64) -> Result<PerpFill, PerpPortError>;
+
+    /// Current position, None when flat
+    async fn position(&self) -> Result<Option<PerpPosition>, PerpPortError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        assert!(PerpPortError::NoPosition.to_string().contains("No open"));
+        assert!(PerpPortError::Rejected("size".into()).to_string().contains("size"));
+    }
+}
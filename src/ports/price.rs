@@ -0,0 +1,76 @@
+//! Price Port
+//!
+//! Minimal source of timestamped prices for a single token. Live adapters
+//! poll an API for the current price; replay adapters read previously
+//! recorded samples back in order so a session can be re-run offline.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Price source error type
+#[derive(Error, Debug)]
+pub enum PricePortError {
+    #[error("Price fetch failed: {0}")]
+    FetchError(String),
+
+    #[error("Price data parsing error: {0}")]
+    ParseError(String),
+
+    #[error("Price source exhausted")]
+    Exhausted,
+}
+
+/// A single observed price
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceSample {
+    /// Time the price was observed
+    pub timestamp: DateTime<Utc>,
+    /// Mint the price is for
+    pub mint: String,
+    /// Price in quote units (USDC)
+    pub price: f64,
+}
+
+impl PriceSample {
+    /// Create a sample observed now
+    pub fn now(mint: impl Into<String>, price: f64) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            mint: mint.into(),
+            price,
+        }
+    }
+}
+
+/// Price port trait
+#[async_trait]
+pub trait PricePort: Send + Sync {
+    /// Fetch the next price sample
+    /// Live sources return the current price; replay sources return
+    /// `PricePortError::Exhausted` once all samples were consumed
+    async fn next_price(&self) -> Result<PriceSample, PricePortError>;
+
+    /// Mint this source prices
+    fn mint(&self) -> &str;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_sample_json_roundtrip() {
+        let sample = PriceSample::now("So11111111111111111111111111111111111111112", 142.5);
+        let json = serde_json::to_string(&sample).unwrap();
+        let parsed: PriceSample = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, sample);
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert!(PricePortError::Exhausted.to_string().contains("exhausted"));
+        assert!(PricePortError::FetchError("timeout".into()).to_string().contains("timeout"));
+    }
+}
@@ -42,6 +42,47 @@ pub struct StrategyParams {
     pub moving_avg: Option<MovingAvgParams>,
 }
 
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            rsi: Some(RsiParams::default()),
+            macd: Some(MacdParams::default()),
+            moving_avg: Some(MovingAvgParams::default()),
+        }
+    }
+}
+
+impl StrategyParams {
+    /// Validate indicator periods and levels
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        if let Some(ref rsi) = self.rsi {
+            if rsi.period == 0 {
+                return Err(StrategyError::InvalidParameter("rsi.period must be > 0".into()));
+            }
+            if !(0.0 < rsi.oversold && rsi.oversold < rsi.overbought && rsi.overbought < 100.0) {
+                return Err(StrategyError::InvalidParameter(format!(
+                    "rsi levels must satisfy 0 < oversold < overbought < 100, got {} / {}",
+                    rsi.oversold, rsi.overbought
+                )));
+            }
+        }
+        if let Some(ref macd) = self.macd {
+            if macd.fast_period == 0 || macd.signal_period == 0 || macd.fast_period >= macd.slow_period {
+                return Err(StrategyError::InvalidParameter(format!(
+                    "macd periods must satisfy 0 < fast < slow and signal > 0, got {}/{}/{}",
+                    macd.fast_period, macd.slow_period, macd.signal_period
+                )));
+            }
+        }
+        if let Some(ref ma) = self.moving_avg {
+            if ma.period == 0 {
+                return Err(StrategyError::InvalidParameter("moving_avg.period must be > 0".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// RSI-specific parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RsiParams {
@@ -50,6 +91,16 @@ pub struct RsiParams {
     pub oversold: f64,
 }
 
+impl Default for RsiParams {
+    fn default() -> Self {
+        Self {
+            period: 14,
+            overbought: 70.0,
+            oversold: 30.0,
+        }
+    }
+}
+
 /// MACD-specific parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacdParams {
@@ -58,6 +109,16 @@ pub struct MacdParams {
     pub signal_period: usize,
 }
 
+impl Default for MacdParams {
+    fn default() -> Self {
+        Self {
+            fast_period: 12,
+            slow_period: 26,
+            signal_period: 9,
+        }
+    }
+}
+
 /// Moving average parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MovingAvgParams {
@@ -65,8 +126,17 @@ pub struct MovingAvgParams {
     pub ma_type: MovingAverageType,
 }
 
+impl Default for MovingAvgParams {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            ma_type: MovingAverageType::Exponential,
+        }
+    }
+}
+
 /// Moving average calculation types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MovingAverageType {
     Simple,
     Exponential,
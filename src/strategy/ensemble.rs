@@ -0,0 +1,571 @@
+//! Strategy Ensemble
+//!
+//! Runs several signal generators side by side on the same closed candles and
+//! trades their weighted consensus:
+//! - zscore: rolling window z-score, vote = -z / z_threshold
+//! - ou: OU z-score of log closes, vote = z / ou.z_entry_threshold, confidence = fit confidence
+//! - rsi: distance of RSI from 50, scaled to the oversold / overbought levels
+//! - kalman: Kalman fair value residual, vote = -z / z_threshold
+//!
+//! Votes are clamped to -1.0..=1.0 (positive = long). The ensemble score is
+//! sum(weight * confidence * vote) / sum(weight). Every vote is scored
+//! `horizon_candles` later against the price move; with `adapt_weights` a
+//! member's weight is its base weight times 2 x hit rate (floored at
+//! `min_weight_factor`), so a member that keeps calling the wrong direction
+//! fades out of the vote.
+//!
+//! Stop loss, take profit, time stop, trailing and break-even stops, cooldown
+//! and daily limits come from `StrategyConfig::risk` via the shared `RiskGuard`
+//! and are checked on every tick. Break-even treats the score like a negated
+//! z-score, so the stop moves once the score has reverted toward zero.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::position::Side;
+use crate::ports::strategy::RsiParams;
+use crate::strategy::indicators::{IndicatorSet, Rsi};
+use crate::strategy::mean_reversion::{PositionState, TradeAction};
+use crate::strategy::multi_timeframe::MultiTimeframeFilter;
+use crate::strategy::ou_process::OUProcess;
+use crate::strategy::params::{EnsembleConfig, EnsembleSignal, StrategyConfig, ZScoreEstimator};
+use crate::strategy::regime::Candle;
+use crate::strategy::risk_guard::RiskGuard;
+use crate::strategy::traits::{Strategy, StrategySnapshot};
+use crate::strategy::zscore_gate::ZScoreGate;
+
+/// Votes weaker than this are not scored for the hit rate
+const MIN_SCORED_VOTE: f64 = 0.25;
+
+/// One member's vote on the latest closed candle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleVote {
+    pub signal: EnsembleSignal,
+    /// Direction and strength, -1.0 (short) to 1.0 (long)
+    pub vote: f64,
+    /// Signal confidence (0.0-1.0)
+    pub confidence: f64,
+    /// Effective weight after hit-rate adaptation
+    pub weight: f64,
+    /// Share of scored votes that called the move, None until one is scored
+    pub hit_rate: Option<f64>,
+    /// Scored votes in the hit-rate window
+    pub scored_votes: usize,
+}
+
+/// Vote breakdown behind the ensemble score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleTally {
+    /// Weighted consensus, -1.0 to 1.0
+    pub score: f64,
+    pub votes: Vec<EnsembleVote>,
+}
+
+impl fmt::Display for EnsembleTally {
+    /// e.g. "zscore +0.82 ou +0.40 rsi -0.10 kalman +0.55 => +0.47"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for vote in &self.votes {
+            write!(f, "{} {:+.2} ", vote.signal.name(), vote.vote * vote.confidence)?;
+        }
+        write!(f, "=> {:+.2}", self.score)
+    }
+}
+
+/// Signal generator state
+#[derive(Debug)]
+enum SignalState {
+    ZScore(ZScoreGate),
+    Ou(OUProcess),
+    Rsi(Rsi, RsiParams),
+    Kalman(ZScoreGate),
+}
+
+/// A voting member with its hit-rate record
+#[derive(Debug)]
+struct Member {
+    signal: EnsembleSignal,
+    base_weight: f64,
+    state: SignalState,
+    /// Whether each of the last `hit_rate_window` scored votes called the move
+    outcomes: VecDeque<bool>,
+}
+
+impl Member {
+    fn new(signal: EnsembleSignal, weight: f64, config: &StrategyConfig) -> Self {
+        let state = match signal {
+            EnsembleSignal::ZScore => {
+                SignalState::ZScore(ZScoreGate::new(config.clone().with_zscore_estimator(ZScoreEstimator::Simple)))
+            }
+            EnsembleSignal::Ou => SignalState::Ou(OUProcess::new(config.lookback_period, config.ou.dt_minutes)),
+            EnsembleSignal::Rsi => {
+                let params = config.indicators.rsi.clone().unwrap_or_default();
+                SignalState::Rsi(Rsi::new(params.period), params)
+            }
+            EnsembleSignal::Kalman => {
+                SignalState::Kalman(ZScoreGate::new(config.clone().with_zscore_estimator(ZScoreEstimator::Kalman)))
+            }
+        };
+        Self {
+            signal,
+            base_weight: weight,
+            state,
+            outcomes: VecDeque::new(),
+        }
+    }
+
+    /// Feed a close and return (vote, confidence), None while warming up
+    fn update(&mut self, close: f64, config: &StrategyConfig) -> Option<(f64, f64)> {
+        match self.state {
+            SignalState::ZScore(ref mut gate) | SignalState::Kalman(ref mut gate) => {
+                let result = gate.update(close)?;
+                Some(((-result.z_score / config.z_threshold).clamp(-1.0, 1.0), 1.0))
+            }
+            SignalState::Ou(ref mut ou) => {
+                ou.update(close);
+                if !ou.is_ready() {
+                    return None;
+                }
+                // A ready process without a usable fit abstains rather than blocking the vote
+                let ou_config = &config.ou;
+                let usable = ou.params().filter(|p| p.is_valid() && p.confidence >= ou_config.min_confidence);
+                let in_range = ou.half_life_minutes().is_some_and(|h| {
+                    h >= ou_config.min_half_life_minutes && h <= ou_config.max_half_life_minutes
+                });
+                match (usable, ou.current_z_score()) {
+                    (Some(params), Some(z)) if in_range => {
+                        Some(((z / ou_config.z_entry_threshold).clamp(-1.0, 1.0), params.confidence))
+                    }
+                    _ => Some((0.0, 0.0)),
+                }
+            }
+            SignalState::Rsi(ref mut rsi, ref params) => {
+                let value = rsi.update(close)?;
+                let vote = if value < 50.0 {
+                    (50.0 - value) / (50.0 - params.oversold)
+                } else {
+                    -(value - 50.0) / (params.overbought - 50.0)
+                };
+                Some((vote.clamp(-1.0, 1.0), 1.0))
+            }
+        }
+    }
+
+    /// Record whether a scored vote called the move
+    fn record(&mut self, hit: bool, window: usize) {
+        self.outcomes.push_back(hit);
+        while self.outcomes.len() > window {
+            self.outcomes.pop_front();
+        }
+    }
+
+    fn hit_rate(&self) -> Option<f64> {
+        if self.outcomes.is_empty() {
+            return None;
+        }
+        let hits = self.outcomes.iter().filter(|&&hit| hit).count();
+        Some(hits as f64 / self.outcomes.len() as f64)
+    }
+
+    /// Base weight scaled by the hit rate once enough votes are scored
+    fn weight(&self, config: &EnsembleConfig) -> f64 {
+        match self.hit_rate() {
+            Some(hit_rate) if config.adapt_weights && self.outcomes.len() >= config.min_scored_votes => {
+                self.base_weight * (2.0 * hit_rate).max(config.min_weight_factor)
+            }
+            _ => self.base_weight,
+        }
+    }
+
+    fn reset(&mut self) {
+        match self.state {
+            SignalState::ZScore(ref mut gate) | SignalState::Kalman(ref mut gate) => gate.reset(),
+            SignalState::Ou(ref mut ou) => ou.reset(),
+            SignalState::Rsi(ref mut rsi, _) => rsi.reset(),
+        }
+        self.outcomes.clear();
+    }
+}
+
+/// Votes waiting `horizon_candles` to be scored
+#[derive(Debug, Clone)]
+struct PendingVotes {
+    candle: usize,
+    price: f64,
+    votes: Vec<f64>,
+}
+
+/// Weighted-vote ensemble of signal generators
+#[derive(Debug)]
+pub struct EnsembleStrategy {
+    config: StrategyConfig,
+    members: Vec<Member>,
+    pending: VecDeque<PendingVotes>,
+    tally: Option<EnsembleTally>,
+    indicators: IndicatorSet,
+    timeframes: MultiTimeframeFilter,
+    candles: usize,
+    /// Position, stops, cooldown and daily limits
+    guard: RiskGuard,
+}
+
+impl EnsembleStrategy {
+    /// Create an ensemble of the members in `config.ensemble`
+    pub fn new(config: StrategyConfig) -> Self {
+        let members = config
+            .ensemble
+            .members
+            .iter()
+            .map(|m| Member::new(m.signal, m.weight, &config))
+            .collect();
+        let indicators = IndicatorSet::new(&config.indicators);
+        let timeframes = MultiTimeframeFilter::new(&config.timeframes);
+        let guard = RiskGuard::new(&config);
+        Self {
+            config,
+            members,
+            pending: VecDeque::new(),
+            tally: None,
+            indicators,
+            timeframes,
+            candles: 0,
+            guard,
+        }
+    }
+
+    fn ensemble_config(&self) -> &EnsembleConfig {
+        &self.config.ensemble
+    }
+
+    /// Vote breakdown of the latest closed candle, None while any member is warming up
+    pub fn tally(&self) -> Option<&EnsembleTally> {
+        self.tally.as_ref()
+    }
+
+    /// Check stop loss, take profit, time stop and trailing stops for the open position
+    pub fn check_stops(&self, price: f64) -> bool {
+        self.guard.stop_hit(price)
+    }
+
+    /// Score votes that have reached the horizon against the move since they were cast
+    fn score_pending(&mut self, close: f64) {
+        let horizon = self.ensemble_config().horizon_candles;
+        let window = self.ensemble_config().hit_rate_window;
+        while self.pending.front().is_some_and(|p| p.candle + horizon <= self.candles) {
+            let Some(pending) = self.pending.pop_front() else {
+                break;
+            };
+            let price_move = close - pending.price;
+            if price_move == 0.0 {
+                continue;
+            }
+            for (member, vote) in self.members.iter_mut().zip(&pending.votes) {
+                if vote.abs() >= MIN_SCORED_VOTE {
+                    member.record((*vote > 0.0) == (price_move > 0.0), window);
+                }
+            }
+        }
+    }
+
+    /// Feed a closed candle to every member and evaluate entries and consensus exits
+    fn update_candle(&mut self, candle: &Candle) -> Option<TradeAction> {
+        self.candles += 1;
+        self.indicators.update(candle.close);
+        self.guard.update_candle(candle);
+        self.score_pending(candle.close);
+
+        let config = &self.config;
+        let votes: Vec<Option<(f64, f64)>> =
+            self.members.iter_mut().map(|m| m.update(candle.close, config)).collect();
+        let Some(votes) = votes.into_iter().collect::<Option<Vec<_>>>() else {
+            self.tally = None;
+            return None;
+        };
+
+        self.pending.push_back(PendingVotes {
+            candle: self.candles,
+            price: candle.close,
+            votes: votes.iter().map(|(vote, _)| *vote).collect(),
+        });
+
+        let ensemble = &self.config.ensemble;
+        let votes: Vec<EnsembleVote> = self
+            .members
+            .iter()
+            .zip(votes)
+            .map(|(member, (vote, confidence))| EnsembleVote {
+                signal: member.signal,
+                vote,
+                confidence,
+                weight: member.weight(ensemble),
+                hit_rate: member.hit_rate(),
+                scored_votes: member.outcomes.len(),
+            })
+            .collect();
+        let total_weight: f64 = votes.iter().map(|v| v.weight).sum();
+        let score = if total_weight > 0.0 {
+            votes.iter().map(|v| v.weight * v.confidence * v.vote).sum::<f64>() / total_weight
+        } else {
+            0.0
+        };
+        self.tally = Some(EnsembleTally { score, votes });
+        self.guard.update_break_even(-score);
+
+        let action = match self.guard.position() {
+            PositionState::Flat => {
+                if self.guard.is_in_cooldown() || !self.guard.check_risk_limits() {
+                    TradeAction::Hold
+                } else if score >= ensemble.entry_score {
+                    TradeAction::EnterLong
+                } else if ensemble.allow_short && score <= -ensemble.entry_score {
+                    TradeAction::EnterShort
+                } else {
+                    TradeAction::Hold
+                }
+            }
+            PositionState::Long { .. } if score <= ensemble.exit_score => TradeAction::Exit,
+            PositionState::Short { .. } if score >= -ensemble.exit_score => TradeAction::Exit,
+            _ => TradeAction::Hold,
+        };
+
+        Some(action)
+    }
+}
+
+impl Strategy for EnsembleStrategy {
+    fn name(&self) -> &'static str {
+        "ensemble"
+    }
+
+    fn update_tick(&mut self, price: f64, closed_candle: Option<&Candle>) -> Option<TradeAction> {
+        self.timeframes.update(price, self.guard.now());
+        self.guard.update_trailing(price);
+        let stop_hit = self.check_stops(price);
+        let candle_action = closed_candle.and_then(|candle| self.update_candle(candle)).map(|action| {
+            match action {
+                TradeAction::EnterLong | TradeAction::EnterShort if !self.timeframes.confirm(action) => {
+                    TradeAction::Hold
+                }
+                _ => action,
+            }
+        });
+
+        if stop_hit {
+            return Some(TradeAction::Exit);
+        }
+        match self.guard.position() {
+            PositionState::Flat => candle_action,
+            _ => candle_action.or(Some(TradeAction::Hold)),
+        }
+    }
+
+    fn confirm_trade(&mut self, action: TradeAction, price: f64) {
+        let time_stop_hours = self.config.risk.time_stop_hours;
+        let signal = self.tally.as_ref().map(|tally| -tally.score);
+        match action {
+            TradeAction::EnterLong => self.guard.open(Side::Long, price, signal, time_stop_hours),
+            TradeAction::EnterShort => self.guard.open(Side::Short, price, signal, time_stop_hours),
+            TradeAction::Exit => self.guard.close(price, 1.0),
+            // Positions are not layered
+            TradeAction::ScaleIn | TradeAction::PartialExit { .. } | TradeAction::Hold => {}
+        }
+    }
+
+    fn position(&self) -> PositionState {
+        self.guard.position()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.tally.is_some()
+    }
+
+    fn set_clock(&mut self, now: Instant) {
+        self.guard.set_clock(now);
+    }
+
+    fn set_stop_scale(&mut self, scale: f64) {
+        self.guard.set_stop_scale(scale);
+    }
+
+    fn reset(&mut self) {
+        for member in &mut self.members {
+            member.reset();
+        }
+        self.pending.clear();
+        self.tally = None;
+        self.indicators.reset();
+        self.timeframes.reset();
+        self.candles = 0;
+        self.guard.reset();
+    }
+
+    fn reset_daily(&mut self) {
+        self.guard.reset_daily();
+    }
+
+    fn restore(&mut self, snapshot: &StrategySnapshot) {
+        self.guard.restore(snapshot);
+    }
+
+    fn snapshot(&self) -> StrategySnapshot {
+        let mut indicators = self.indicators.values().to_map();
+        if let Some(ref tally) = self.tally {
+            for vote in &tally.votes {
+                indicators.insert(format!("vote_{}", vote.signal.name()), vote.vote);
+            }
+        }
+
+        StrategySnapshot {
+            name: self.name().to_string(),
+            ready: self.is_ready(),
+            position: self.guard.position(),
+            position_age_secs: self.guard.position_age().map(|age| age.as_secs_f64()),
+            time_stop_hours: self.guard.position_time_stop_hours(),
+            daily_trades: self.guard.daily_trades(),
+            daily_pnl_pct: self.guard.daily_pnl(),
+            signal: self.tally.as_ref().map(|t| t.score),
+            indicators,
+            kalman: None,
+            timeframes: self.timeframes.states(),
+            layers: None,
+            ensemble: self.tally.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::params::{EnsembleMember, OuStrategyConfig, RiskConfig, StrategyKind, TrailingStopConfig};
+
+    fn config() -> StrategyConfig {
+        StrategyConfig {
+            kind: StrategyKind::Ensemble,
+            lookback_period: 40,
+            cooldown_seconds: 0,
+            ou: OuStrategyConfig {
+                min_confidence: 0.0,
+                min_half_life_minutes: 0.5,
+                max_half_life_minutes: 600.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn candle(close: f64) -> Candle {
+        Candle::new(close, close, close, close, 1.0)
+    }
+
+    fn feed(strategy: &mut EnsembleStrategy, close: f64) -> Option<TradeAction> {
+        strategy.update_tick(close, Some(&candle(close)))
+    }
+
+    fn warm_up(strategy: &mut EnsembleStrategy) {
+        for i in 0..120 {
+            feed(strategy, 100.0 * (1.0 + 0.01 * (0.7 * i as f64).sin()));
+        }
+    }
+
+    #[test]
+    fn test_consensus_enters_and_exits() {
+        let mut strategy = EnsembleStrategy::new(config());
+        assert_eq!(feed(&mut strategy, 100.0), None);
+        warm_up(&mut strategy);
+        assert!(strategy.is_ready());
+
+        let action = feed(&mut strategy, 96.0);
+        let tally = strategy.tally().unwrap().clone();
+        assert_eq!(tally.votes.len(), 4);
+        assert!(tally.votes.iter().all(|v| v.vote > 0.0), "{}", tally);
+        assert!(tally.score >= 0.5);
+        assert_eq!(action, Some(TradeAction::EnterLong));
+        assert_eq!(strategy.position(), PositionState::Flat); // not until confirmed
+
+        strategy.confirm_trade(TradeAction::EnterLong, 96.0);
+        let exit = (0..20).map(|i| feed(&mut strategy, 100.0 + (i % 2) as f64 * 0.5)).position(|a| a == Some(TradeAction::Exit));
+        assert!(exit.is_some());
+        assert!(strategy.tally().unwrap().score <= 0.0);
+
+        let snapshot = strategy.snapshot();
+        assert_eq!(snapshot.name, "ensemble");
+        assert_eq!(snapshot.signal, Some(snapshot.ensemble.as_ref().unwrap().score));
+        assert!(snapshot.indicators.contains_key("vote_kalman"));
+    }
+
+    #[test]
+    fn test_wrong_member_loses_weight() {
+        let mut config = config();
+        config.ensemble.members = vec![EnsembleMember::new(EnsembleSignal::ZScore, 2.0)];
+
+        // A steady uptrend keeps the z-score member calling for a reversal that never comes
+        let mut strategy = EnsembleStrategy::new(config.clone());
+        for i in 0..200 {
+            feed(&mut strategy, 100.0 + 0.1 * i as f64);
+        }
+        let vote = &strategy.tally().unwrap().votes[0];
+        assert!(vote.vote < 0.0);
+        assert_eq!(vote.hit_rate, Some(0.0));
+        assert_eq!(vote.scored_votes, config.ensemble.hit_rate_window);
+        assert!((vote.weight - 2.0 * config.ensemble.min_weight_factor).abs() < 1e-12);
+
+        config.ensemble.adapt_weights = false;
+        let mut strategy = EnsembleStrategy::new(config);
+        for i in 0..200 {
+            feed(&mut strategy, 100.0 + 0.1 * i as f64);
+        }
+        assert_eq!(strategy.tally().unwrap().votes[0].weight, 2.0);
+    }
+
+    #[test]
+    fn test_weight_follows_hit_rate() {
+        let config = EnsembleConfig::default();
+        let mut member = Member::new(EnsembleSignal::Rsi, 1.0, &StrategyConfig::default());
+        for i in 0..config.min_scored_votes - 1 {
+            member.record(i % 4 != 0, config.hit_rate_window);
+        }
+        // Too few scored votes to adapt
+        assert_eq!(member.weight(&config), 1.0);
+
+        member.record(true, config.hit_rate_window);
+        assert!((member.hit_rate().unwrap() - 0.7).abs() < 1e-12);
+        assert!((member.weight(&config) - 1.4).abs() < 1e-12);
+
+        for _ in 0..config.hit_rate_window {
+            member.record(false, config.hit_rate_window);
+        }
+        assert_eq!(member.outcomes.len(), config.hit_rate_window);
+        assert_eq!(member.weight(&config), config.min_weight_factor);
+    }
+
+    #[test]
+    fn test_stops_run_without_candle() {
+        let mut strategy = EnsembleStrategy::new(config());
+        strategy.confirm_trade(TradeAction::EnterShort, 100.0);
+
+        assert_eq!(strategy.update_tick(99.5, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(103.0, None), Some(TradeAction::Exit));
+
+        strategy.confirm_trade(TradeAction::Exit, 103.0);
+        assert_eq!(strategy.position(), PositionState::Flat);
+        assert!((strategy.snapshot().daily_pnl_pct + 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trailing_stop_applies() {
+        let mut config = config();
+        config.risk = RiskConfig {
+            trailing: TrailingStopConfig { activation_pct: Some(1.0), trail_pct: 0.5, ..Default::default() },
+            ..Default::default()
+        };
+        let mut strategy = EnsembleStrategy::new(config);
+        strategy.confirm_trade(TradeAction::EnterShort, 100.0);
+
+        // Down 1.2%: trail 0.5% above the low, well inside the plain stop loss
+        assert_eq!(strategy.update_tick(98.8, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(99.2, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(99.4, None), Some(TradeAction::Exit));
+    }
+}
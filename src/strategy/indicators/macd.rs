@@ -0,0 +1,104 @@
+//! MACD (Moving Average Convergence Divergence)
+//!
+//! MACD line = EMA(fast) - EMA(slow), signal = EMA(signal) of the MACD line,
+//! histogram = MACD - signal.
+
+use super::moving_average::Ema;
+
+/// MACD output for one update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdResult {
+    pub macd: f64,
+    /// None until the signal EMA has warmed up
+    pub signal: Option<f64>,
+    pub histogram: Option<f64>,
+}
+
+/// Streaming MACD
+#[derive(Debug, Clone)]
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+    last: Option<MacdResult>,
+}
+
+impl Macd {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            fast: Ema::new(fast_period),
+            slow: Ema::new(slow_period),
+            signal: Ema::new(signal_period),
+            last: None,
+        }
+    }
+
+    /// Add a price, returns the MACD once the slow EMA is ready
+    pub fn update(&mut self, price: f64) -> Option<MacdResult> {
+        let fast = self.fast.update(price);
+        let slow = self.slow.update(price);
+
+        if let (Some(fast), Some(slow)) = (fast, slow) {
+            let macd = fast - slow;
+            let signal = self.signal.update(macd);
+            self.last = Some(MacdResult {
+                macd,
+                signal,
+                histogram: signal.map(|s| macd - s),
+            });
+        }
+        self.last
+    }
+
+    pub fn value(&self) -> Option<MacdResult> {
+        self.last
+    }
+
+    /// True once the signal line is available
+    pub fn is_ready(&self) -> bool {
+        self.signal.is_ready()
+    }
+
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macd_warmup() {
+        let mut macd = Macd::new(2, 4, 3);
+        for i in 0..3 {
+            assert!(macd.update(100.0 + i as f64).is_none());
+        }
+
+        // Slow EMA ready: MACD line available, signal not yet
+        let first = macd.update(103.0).unwrap();
+        assert!(first.signal.is_none());
+
+        macd.update(104.0);
+        let ready = macd.update(105.0).unwrap();
+        assert!(macd.is_ready());
+        assert!(ready.histogram.is_some());
+    }
+
+    #[test]
+    fn test_macd_sign_follows_trend() {
+        let mut macd = Macd::new(3, 6, 3);
+        for i in 0..20 {
+            macd.update(100.0 + i as f64);
+        }
+        assert!(macd.value().unwrap().macd > 0.0);
+
+        for i in 0..20 {
+            macd.update(120.0 - 2.0 * i as f64);
+        }
+        assert!(macd.value().unwrap().macd < 0.0);
+    }
+}
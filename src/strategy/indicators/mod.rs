@@ -0,0 +1,171 @@
+//! Technical Indicators
+//!
+//! Streaming indicator library with O(1) updates, in the same style as
+//! `AdxRegimeDetector`: feed one value at a time, read the latest result.
+//! - Moving averages: SMA, EMA and Wilder's smoothed MA
+//! - RSI (Wilder)
+//! - MACD with signal line and histogram
+//!
+//! `IndicatorSet` bundles the indicators configured by `StrategyParams`
+//! and reports them as `IndicatorValues`.
+
+mod macd;
+mod moving_average;
+mod rsi;
+
+pub use macd::{Macd, MacdResult};
+pub use moving_average::{Ema, MovingAverage, Sma, Smma};
+pub use rsi::Rsi;
+
+use std::collections::BTreeMap;
+
+use crate::ports::strategy::{IndicatorValues, MovingAverageType, RsiParams, StrategyParams};
+
+/// Indicators configured by `StrategyParams`, updated together
+#[derive(Debug, Clone)]
+pub struct IndicatorSet {
+    rsi: Option<(Rsi, RsiParams)>,
+    macd: Option<Macd>,
+    sma: Option<Sma>,
+    /// Exponential-family average: EMA, or SMMA when `ma_type` is Smoothed
+    ema: Option<MovingAverage>,
+}
+
+impl IndicatorSet {
+    pub fn new(params: &StrategyParams) -> Self {
+        let (sma, ema) = match params.moving_avg {
+            Some(ref ma) => {
+                let ema_type = match ma.ma_type {
+                    MovingAverageType::Smoothed => MovingAverageType::Smoothed,
+                    _ => MovingAverageType::Exponential,
+                };
+                (Some(Sma::new(ma.period)), Some(MovingAverage::new(ema_type, ma.period)))
+            }
+            None => (None, None),
+        };
+
+        Self {
+            rsi: params.rsi.clone().map(|p| (Rsi::new(p.period), p)),
+            macd: params
+                .macd
+                .as_ref()
+                .map(|p| Macd::new(p.fast_period, p.slow_period, p.signal_period)),
+            sma,
+            ema,
+        }
+    }
+
+    /// Feed one price to every indicator
+    pub fn update(&mut self, price: f64) -> IndicatorValues {
+        if let Some((ref mut rsi, _)) = self.rsi {
+            rsi.update(price);
+        }
+        if let Some(ref mut macd) = self.macd {
+            macd.update(price);
+        }
+        if let Some(ref mut sma) = self.sma {
+            sma.update(price);
+        }
+        if let Some(ref mut ema) = self.ema {
+            ema.update(price);
+        }
+        self.values()
+    }
+
+    /// Latest values (None for indicators still warming up or not configured)
+    pub fn values(&self) -> IndicatorValues {
+        let macd = self.macd.as_ref().and_then(|m| m.value());
+        IndicatorValues {
+            rsi: self.rsi.as_ref().and_then(|(rsi, _)| rsi.value()),
+            macd: macd.map(|m| m.macd),
+            macd_signal: macd.and_then(|m| m.signal),
+            macd_histogram: macd.and_then(|m| m.histogram),
+            sma: self.sma.as_ref().and_then(|s| s.value()),
+            ema: self.ema.as_ref().and_then(|e| e.value()),
+        }
+    }
+
+    /// RSI is at or below the configured oversold level
+    pub fn is_rsi_oversold(&self) -> Option<bool> {
+        let (rsi, params) = self.rsi.as_ref()?;
+        rsi.value().map(|v| v <= params.oversold)
+    }
+
+    /// RSI is at or above the configured overbought level
+    pub fn is_rsi_overbought(&self) -> Option<bool> {
+        let (rsi, params) = self.rsi.as_ref()?;
+        rsi.value().map(|v| v >= params.overbought)
+    }
+
+    pub fn reset(&mut self) {
+        if let Some((ref mut rsi, _)) = self.rsi {
+            rsi.reset();
+        }
+        if let Some(ref mut macd) = self.macd {
+            macd.reset();
+        }
+        if let Some(ref mut sma) = self.sma {
+            sma.reset();
+        }
+        if let Some(ref mut ema) = self.ema {
+            ema.reset();
+        }
+    }
+}
+
+impl IndicatorValues {
+    /// Available values keyed by name, for snapshots and status output
+    pub fn to_map(&self) -> BTreeMap<String, f64> {
+        [
+            ("rsi", self.rsi),
+            ("macd", self.macd),
+            ("macd_signal", self.macd_signal),
+            ("macd_histogram", self.macd_histogram),
+            ("sma", self.sma),
+            ("ema", self.ema),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| (name.to_string(), v)))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator_set_defaults() {
+        let mut set = IndicatorSet::new(&StrategyParams::default());
+        let mut values = set.values();
+        assert!(values.rsi.is_none());
+
+        for i in 0..60 {
+            values = set.update(100.0 + (i as f64 / 5.0).sin());
+        }
+
+        assert!(values.rsi.is_some());
+        assert!(values.macd_histogram.is_some());
+        assert!(values.sma.is_some() && values.ema.is_some());
+        assert_eq!(values.to_map().len(), 6);
+    }
+
+    #[test]
+    fn test_unconfigured_indicators_stay_empty() {
+        let params = StrategyParams {
+            rsi: Some(RsiParams { period: 3, overbought: 70.0, oversold: 30.0 }),
+            macd: None,
+            moving_avg: None,
+        };
+        let mut set = IndicatorSet::new(&params);
+        for price in [10.0, 9.0, 8.0, 7.0, 6.0] {
+            set.update(price);
+        }
+
+        let values = set.values();
+        assert_eq!(values.rsi, Some(0.0));
+        assert!(values.macd.is_none() && values.sma.is_none());
+        assert_eq!(set.is_rsi_oversold(), Some(true));
+        assert_eq!(set.is_rsi_overbought(), Some(false));
+    }
+}
@@ -0,0 +1,238 @@
+//! Moving Averages
+//!
+//! Streaming SMA, EMA and Wilder's smoothed MA (SMMA). Every update is O(1):
+//! the SMA is the running mean of a `RollingStats` window, and the EMA/SMMA
+//! are seeded with the SMA of their first `period` values, then updated
+//! recursively.
+
+use crate::ports::strategy::MovingAverageType;
+use crate::strategy::stats::RollingStats;
+
+/// Simple moving average over a fixed window
+#[derive(Debug, Clone)]
+pub struct Sma {
+    stats: RollingStats,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            stats: RollingStats::new(period),
+        }
+    }
+
+    /// Add a value, returns the average once the window is full
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.stats.push(value);
+        self.value()
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.stats.mean().filter(|_| self.is_ready())
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.stats.is_full()
+    }
+
+    pub fn period(&self) -> usize {
+        self.stats.capacity()
+    }
+
+    pub fn reset(&mut self) {
+        self.stats.reset();
+    }
+}
+
+/// Recursive average `avg += alpha * (value - avg)`, seeded with an SMA
+#[derive(Debug, Clone)]
+struct Recursive {
+    period: usize,
+    alpha: f64,
+    seed_sum: f64,
+    count: usize,
+    value: Option<f64>,
+}
+
+impl Recursive {
+    fn new(period: usize, alpha: f64) -> Self {
+        Self {
+            period: period.max(1),
+            alpha,
+            seed_sum: 0.0,
+            count: 0,
+            value: None,
+        }
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.count += 1;
+        self.value = match self.value {
+            Some(avg) => Some(avg + self.alpha * (value - avg)),
+            None => {
+                self.seed_sum += value;
+                (self.count >= self.period).then(|| self.seed_sum / self.period as f64)
+            }
+        };
+        self.value
+    }
+
+    fn reset(&mut self) {
+        self.seed_sum = 0.0;
+        self.count = 0;
+        self.value = None;
+    }
+}
+
+/// Exponential moving average (alpha = 2 / (period + 1))
+#[derive(Debug, Clone)]
+pub struct Ema(Recursive);
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self(Recursive::new(period, 2.0 / (period.max(1) as f64 + 1.0)))
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.0.update(value)
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.0.value
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.0.value.is_some()
+    }
+
+    pub fn period(&self) -> usize {
+        self.0.period
+    }
+
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Wilder's smoothed moving average (alpha = 1 / period), as used by RSI and ADX
+#[derive(Debug, Clone)]
+pub struct Smma(Recursive);
+
+impl Smma {
+    pub fn new(period: usize) -> Self {
+        Self(Recursive::new(period, 1.0 / period.max(1) as f64))
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.0.update(value)
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.0.value
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.0.value.is_some()
+    }
+
+    pub fn period(&self) -> usize {
+        self.0.period
+    }
+
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Moving average of a configurable type
+#[derive(Debug, Clone)]
+pub enum MovingAverage {
+    Simple(Sma),
+    Exponential(Ema),
+    Smoothed(Smma),
+}
+
+impl MovingAverage {
+    pub fn new(ma_type: MovingAverageType, period: usize) -> Self {
+        match ma_type {
+            MovingAverageType::Simple => Self::Simple(Sma::new(period)),
+            MovingAverageType::Exponential => Self::Exponential(Ema::new(period)),
+            MovingAverageType::Smoothed => Self::Smoothed(Smma::new(period)),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        match self {
+            Self::Simple(ma) => ma.update(value),
+            Self::Exponential(ma) => ma.update(value),
+            Self::Smoothed(ma) => ma.update(value),
+        }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Self::Simple(ma) => ma.value(),
+            Self::Exponential(ma) => ma.value(),
+            Self::Smoothed(ma) => ma.value(),
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.value().is_some()
+    }
+
+    pub fn reset(&mut self) {
+        match self {
+            Self::Simple(ma) => ma.reset(),
+            Self::Exponential(ma) => ma.reset(),
+            Self::Smoothed(ma) => ma.reset(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sma_rolls_window() {
+        let mut sma = Sma::new(3);
+        assert_eq!(sma.update(1.0), None);
+        assert_eq!(sma.update(2.0), None);
+        assert_eq!(sma.update(3.0), Some(2.0));
+        assert!((sma.update(6.0).unwrap() - 11.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ema_seeded_with_sma() {
+        let mut ema = Ema::new(3); // alpha = 0.5
+        ema.update(1.0);
+        ema.update(2.0);
+        assert_eq!(ema.update(3.0), Some(2.0));
+        assert_eq!(ema.update(4.0), Some(3.0));
+    }
+
+    #[test]
+    fn test_smma_uses_wilder_alpha() {
+        let mut smma = Smma::new(4); // alpha = 0.25
+        for v in [2.0, 2.0, 2.0, 2.0] {
+            smma.update(v);
+        }
+        assert_eq!(smma.update(6.0), Some(3.0));
+    }
+
+    #[test]
+    fn test_moving_average_type_dispatch() {
+        let mut simple = MovingAverage::new(MovingAverageType::Simple, 2);
+        let mut smoothed = MovingAverage::new(MovingAverageType::Smoothed, 2);
+        for v in [1.0, 3.0, 5.0] {
+            simple.update(v);
+            smoothed.update(v);
+        }
+        assert_eq!(simple.value(), Some(4.0));
+        assert_eq!(smoothed.value(), Some(3.5));
+
+        simple.reset();
+        assert!(!simple.is_ready());
+    }
+}
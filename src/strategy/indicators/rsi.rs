@@ -0,0 +1,87 @@
+//! Relative Strength Index
+//!
+//! Wilder's RSI: average gains and losses are smoothed with alpha = 1/period
+//! after an SMA seed over the first `period` changes.
+//! RSI = 100 - 100 / (1 + avg_gain / avg_loss), on a 0-100 scale.
+
+use super::moving_average::Smma;
+
+/// Streaming RSI
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    prev: Option<f64>,
+    avg_gain: Smma,
+    avg_loss: Smma,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            prev: None,
+            avg_gain: Smma::new(period),
+            avg_loss: Smma::new(period),
+        }
+    }
+
+    /// Add a price, returns the RSI once `period + 1` prices were seen
+    pub fn update(&mut self, price: f64) -> Option<f64> {
+        if let Some(prev) = self.prev.replace(price) {
+            let change = price - prev;
+            self.avg_gain.update(change.max(0.0));
+            self.avg_loss.update((-change).max(0.0));
+        }
+        self.value()
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        let gain = self.avg_gain.value()?;
+        let loss = self.avg_loss.value()?;
+
+        Some(if loss == 0.0 {
+            if gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        })
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.avg_gain.is_ready()
+    }
+
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.avg_gain.reset();
+        self.avg_loss.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_extremes() {
+        let mut rising = Rsi::new(5);
+        let mut flat = Rsi::new(5);
+        for i in 0..6 {
+            rising.update(100.0 + i as f64);
+            flat.update(100.0);
+        }
+        assert_eq!(rising.value(), Some(100.0));
+        assert_eq!(flat.value(), Some(50.0));
+    }
+
+    #[test]
+    fn test_rsi_warmup_and_wilder_smoothing() {
+        let mut rsi = Rsi::new(2);
+        assert_eq!(rsi.update(10.0), None);
+        assert_eq!(rsi.update(12.0), None);
+        // Changes +2, -1: avg gain 1.0, avg loss 0.5 => RS 2 => RSI 66.67
+        let value = rsi.update(11.0).unwrap();
+        assert!((value - 200.0 / 3.0).abs() < 1e-9);
+
+        // Change -1: gain 0.5, loss 0.75 => RS 2/3 => RSI 40
+        let value = rsi.update(10.0).unwrap();
+        assert!((value - 40.0).abs() < 1e-9);
+    }
+}
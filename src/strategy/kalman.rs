@@ -0,0 +1,209 @@
+//! Kalman Fair Value Estimator
+//!
+//! Local-level Kalman filter that tracks a latent fair value behind noisy
+//! prices, as an alternative to the rolling mean:
+//!   price(t)      = fair(t) + v,   v ~ N(0, R)
+//!   fair(t)       = fair(t-1) + w, w ~ N(0, Q)
+//!
+//! R is estimated adaptively from the innovations (EWMA of innovation^2 minus
+//! the prior variance), and Q = process_noise * R, so the filter is scale-free:
+//! the higher `process_noise`, the faster the fair value follows the price.
+//! After a regime shift the innovations grow, R and Q grow with them and the
+//! gain recovers, instead of waiting for a full window to roll over.
+//!
+//! The z-score is the standardized innovation (price - prior fair value) / sqrt(S),
+//! where S = P_prior + R is the innovation variance.
+
+use serde::{Deserialize, Serialize};
+
+use crate::strategy::params::KalmanConfig;
+use crate::strategy::stats::EwmaStats;
+
+/// Floor for R relative to the innovation variance estimate
+const MIN_NOISE_FRACTION: f64 = 1e-3;
+
+/// Filter state after the latest update
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KalmanState {
+    /// Posterior fair value (after seeing the latest price)
+    pub fair_value: f64,
+    /// Prior fair value the latest price was compared against
+    pub prior_fair_value: f64,
+    /// Posterior variance of the fair value (P)
+    pub variance: f64,
+    /// Latest innovation (price - prior fair value)
+    pub innovation: f64,
+    /// Innovation variance (S = P_prior + R)
+    pub innovation_variance: f64,
+    /// Kalman gain applied to the latest innovation
+    pub gain: f64,
+    /// Estimated measurement noise (R)
+    pub measurement_noise: f64,
+    /// Process noise used for the next prediction (Q)
+    pub process_noise: f64,
+}
+
+impl KalmanState {
+    /// Standardized innovation, None while the innovation variance is zero
+    pub fn z_score(&self) -> Option<f64> {
+        (self.innovation_variance > 0.0).then(|| self.innovation / self.innovation_variance.sqrt())
+    }
+
+    /// Volatility estimate (sqrt of the innovation variance)
+    pub fn volatility(&self) -> f64 {
+        self.innovation_variance.sqrt()
+    }
+}
+
+/// Streaming local-level Kalman filter
+#[derive(Debug, Clone)]
+pub struct KalmanFilter {
+    config: KalmanConfig,
+    /// EWMA of squared innovations (estimates S)
+    innovation_sq: EwmaStats,
+    fair_value: Option<f64>,
+    variance: f64,
+    measurement_noise: f64,
+    process_noise: f64,
+    state: Option<KalmanState>,
+}
+
+impl KalmanFilter {
+    pub fn new(config: KalmanConfig) -> Self {
+        let innovation_sq = EwmaStats::new(config.noise_half_life);
+        Self {
+            config,
+            innovation_sq,
+            fair_value: None,
+            variance: 0.0,
+            measurement_noise: 0.0,
+            process_noise: 0.0,
+            state: None,
+        }
+    }
+
+    /// Feed one price, returns the state once an innovation has been observed
+    pub fn update(&mut self, price: f64) -> Option<KalmanState> {
+        let Some(fair_value) = self.fair_value else {
+            self.fair_value = Some(price);
+            return None;
+        };
+
+        // Predict
+        let prior_variance = self.variance + self.process_noise;
+        let innovation = price - fair_value;
+
+        // Adapt R so that P_prior + R matches the observed innovation variance
+        self.innovation_sq.push(innovation * innovation);
+        let observed = self.innovation_sq.mean().unwrap_or(0.0);
+        self.measurement_noise = (observed - prior_variance).max(observed * MIN_NOISE_FRACTION);
+
+        // Update
+        let innovation_variance = prior_variance + self.measurement_noise;
+        let gain = if innovation_variance > 0.0 {
+            prior_variance / innovation_variance
+        } else {
+            0.0
+        };
+        let posterior = fair_value + gain * innovation;
+        self.fair_value = Some(posterior);
+        self.variance = (1.0 - gain) * prior_variance;
+        self.process_noise = self.config.process_noise * self.measurement_noise;
+
+        self.state = Some(KalmanState {
+            fair_value: posterior,
+            prior_fair_value: fair_value,
+            variance: self.variance,
+            innovation,
+            innovation_variance,
+            gain,
+            measurement_noise: self.measurement_noise,
+            process_noise: self.process_noise,
+        });
+        self.state
+    }
+
+    /// State after the latest update
+    pub fn state(&self) -> Option<KalmanState> {
+        self.state
+    }
+
+    /// Current fair value estimate
+    pub fn fair_value(&self) -> Option<f64> {
+        self.fair_value
+    }
+
+    pub fn config(&self) -> &KalmanConfig {
+        &self.config
+    }
+
+    pub fn reset(&mut self) {
+        self.innovation_sq.reset();
+        self.fair_value = None;
+        self.variance = 0.0;
+        self.measurement_noise = 0.0;
+        self.process_noise = 0.0;
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::stats::RollingStats;
+
+    fn noisy(i: usize) -> f64 {
+        ((i * 7919) % 17) as f64 / 17.0 - 0.5
+    }
+
+    #[test]
+    fn test_tracks_level_and_noise() {
+        let mut filter = KalmanFilter::new(KalmanConfig::default());
+        assert!(filter.update(100.0).is_none());
+
+        for i in 0..500 {
+            filter.update(100.0 + noisy(i));
+        }
+
+        let state = filter.state().unwrap();
+        assert!((state.fair_value - 100.0).abs() < 0.2);
+        assert!(state.gain > 0.0 && state.gain < 0.5);
+        assert!(state.innovation_variance > 0.0);
+        assert!((state.innovation_variance - (state.variance / (1.0 - state.gain) + state.measurement_noise)).abs() < 1e-9);
+        assert!(state.z_score().unwrap().abs() < 3.0);
+    }
+
+    #[test]
+    fn test_recovers_from_level_shift_faster_than_window_mean() {
+        let mut filter = KalmanFilter::new(KalmanConfig::default());
+        let mut window = RollingStats::new(50);
+
+        for i in 0..300 {
+            let price = 100.0 + noisy(i);
+            filter.update(price);
+            window.push(price);
+        }
+        for i in 300..320 {
+            let price = 110.0 + noisy(i);
+            filter.update(price);
+            window.push(price);
+        }
+
+        let kalman_lag = 110.0 - filter.fair_value().unwrap();
+        let window_lag = 110.0 - window.mean().unwrap();
+        assert!(kalman_lag < window_lag, "kalman lag {} vs window lag {}", kalman_lag, window_lag);
+    }
+
+    #[test]
+    fn test_flat_prices_have_no_z_score() {
+        let mut filter = KalmanFilter::new(KalmanConfig::default());
+        for _ in 0..10 {
+            filter.update(50.0);
+        }
+        assert_eq!(filter.state().unwrap().z_score(), None);
+
+        filter.reset();
+        assert!(filter.state().is_none());
+        assert!(filter.fair_value().is_none());
+    }
+}
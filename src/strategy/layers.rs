@@ -0,0 +1,151 @@
+//! Layered Positions
+//!
+//! Position model for scaling in and taking partial profit:
+//! - Fills: the initial entry plus legs added at deeper z-score levels
+//! - Exit legs: partial take-profits closing a fraction of the remaining size
+//! - Average entry price over all fills, remaining size, realized PnL per leg
+//!
+//! Sizes are in units of the initial entry (the first fill is 1.0), so the
+//! strategy stays independent of how the orchestrator sizes swaps.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::position::Side;
+
+/// One entry fill
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    pub price: f64,
+    /// Size in units of the initial entry
+    pub size: f64,
+    /// Z-score the fill was signalled at
+    pub z_score: Option<f64>,
+}
+
+/// One realized exit leg
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExitLeg {
+    pub price: f64,
+    /// Size closed, in units of the initial entry
+    pub size: f64,
+    /// Return of the leg against the average entry price in percent
+    pub pnl_pct: f64,
+}
+
+impl ExitLeg {
+    /// Realized PnL in percent of one initial-entry unit
+    pub fn realized_pnl(&self) -> f64 {
+        self.size * self.pnl_pct
+    }
+}
+
+/// Open position made of several fills and partial exits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionLayers {
+    pub side: Side,
+    pub fills: Vec<Fill>,
+    pub exits: Vec<ExitLeg>,
+}
+
+impl PositionLayers {
+    /// Open with the initial entry fill (size 1.0)
+    pub fn open(side: Side, price: f64, z_score: Option<f64>) -> Self {
+        Self {
+            side,
+            fills: vec![Fill { price, size: 1.0, z_score }],
+            exits: Vec::new(),
+        }
+    }
+
+    /// Add a scale-in fill
+    pub fn add_fill(&mut self, price: f64, size: f64, z_score: Option<f64>) {
+        self.fills.push(Fill { price, size, z_score });
+    }
+
+    /// Scale-in legs added after the initial entry
+    pub fn scale_ins(&self) -> usize {
+        self.fills.len().saturating_sub(1)
+    }
+
+    /// Size-weighted average entry price over all fills
+    pub fn average_entry(&self) -> f64 {
+        let size: f64 = self.fills.iter().map(|f| f.size).sum();
+        if size <= 0.0 {
+            return 0.0;
+        }
+        self.fills.iter().map(|f| f.price * f.size).sum::<f64>() / size
+    }
+
+    /// Total size filled
+    pub fn filled_size(&self) -> f64 {
+        self.fills.iter().map(|f| f.size).sum()
+    }
+
+    /// Size still open
+    pub fn remaining_size(&self) -> f64 {
+        (self.filled_size() - self.exits.iter().map(|e| e.size).sum::<f64>()).max(0.0)
+    }
+
+    /// Return of the open size at `price` against the average entry, in percent
+    pub fn pnl_pct(&self, price: f64) -> f64 {
+        let entry = self.average_entry();
+        if entry <= 0.0 {
+            return 0.0;
+        }
+        match self.side {
+            Side::Long => (price - entry) / entry * 100.0,
+            Side::Short => (entry - price) / entry * 100.0,
+        }
+    }
+
+    /// Close `fraction` of the remaining size at `price`
+    pub fn close_fraction(&mut self, price: f64, fraction: f64) -> ExitLeg {
+        let leg = ExitLeg {
+            price,
+            size: self.remaining_size() * fraction.clamp(0.0, 1.0),
+            pnl_pct: self.pnl_pct(price),
+        };
+        self.exits.push(leg);
+        leg
+    }
+
+    /// Realized PnL of all exit legs, in percent of one initial-entry unit
+    pub fn realized_pnl(&self) -> f64 {
+        self.exits.iter().map(ExitLeg::realized_pnl).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_entry_and_partial_exits() {
+        let mut layers = PositionLayers::open(Side::Long, 100.0, Some(-2.5));
+        layers.add_fill(94.0, 0.5, Some(-3.0));
+        assert_eq!(layers.scale_ins(), 1);
+        assert!((layers.average_entry() - 98.0).abs() < 1e-12);
+        assert!((layers.filled_size() - 1.5).abs() < 1e-12);
+
+        // Half of 1.5 at +2% against the 98.0 average
+        let leg = layers.close_fraction(99.96, 0.5);
+        assert!((leg.size - 0.75).abs() < 1e-12);
+        assert!((leg.pnl_pct - 2.0).abs() < 1e-9);
+        assert!((layers.remaining_size() - 0.75).abs() < 1e-12);
+        assert!((layers.realized_pnl() - 1.5).abs() < 1e-9);
+
+        // Average entry is the cost basis, not moved by exits
+        assert!((layers.average_entry() - 98.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_short_pnl() {
+        let mut layers = PositionLayers::open(Side::Short, 100.0, None);
+        layers.add_fill(110.0, 1.0, None);
+        assert!((layers.pnl_pct(94.5) - 10.0).abs() < 1e-9);
+
+        let leg = layers.close_fraction(94.5, 2.0); // clamped to everything
+        assert!((leg.size - 2.0).abs() < 1e-12);
+        assert_eq!(layers.remaining_size(), 0.0);
+    }
+}
@@ -10,35 +10,109 @@
 //! Exit Logic:
 //! - Exit LONG when z_score > +z_exit_threshold OR take_profit OR stop_loss OR time_stop
 //! - Exit SHORT when z_score < -z_exit_threshold OR take_profit OR stop_loss OR time_stop
+//! - Exit either side on a trailing or break-even stop (`RiskConfig::trailing`)
+//!
+//! Layered positions (`StrategyConfig::scaling`):
+//! - SCALE IN at each deeper |z| level beyond the entry threshold
+//! - PARTIAL EXIT of the remaining size at intermediate |z| levels toward the mean
+//!
+//! OU signals (`zscore_estimator = "ou"`):
+//! - Entries need a confident OU fit with a half-life in range (`StrategyConfig::ou`)
+//! - The time stop is a multiple of the half-life at entry instead of `time_stop_hours`
+//! - `size_multiplier()` scales entries by the estimation confidence
+//!
+//! When driven by candles, z-scores are computed on closed candles via
+//! `update_candle()` while `check_stops()` enforces SL/TP/time stops intrabar.
+
+use std::time::Instant;
 
-use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 
+use crate::domain::position::Side;
 use crate::ports::strategy::{StrategyPort, StrategyError, Signal, IndicatorValues};
-use crate::strategy::params::StrategyConfig;
+use crate::strategy::indicators::IndicatorSet;
+use crate::strategy::params::{StrategyConfig, ZScoreEstimator};
+use crate::strategy::regime::Candle;
+use crate::strategy::risk_guard::RiskGuard;
+use crate::strategy::traits::{Strategy, StrategySnapshot};
+use crate::strategy::kalman::KalmanState;
+use crate::strategy::layers::PositionLayers;
+use crate::strategy::multi_timeframe::MultiTimeframeFilter;
 use crate::strategy::zscore_gate::{ZScoreGate, ZScoreResult};
 
 /// Trading action generated by the strategy
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TradeAction {
     /// Enter a long position
     EnterLong,
     /// Enter a short position
     EnterShort,
+    /// Add a leg to the current position at a deeper z-score level
+    ScaleIn,
+    /// Close `fraction` of the remaining position (partial take-profit)
+    PartialExit { fraction: f64 },
     /// Exit current position
     Exit,
     /// Hold current position / no action
     Hold,
 }
 
+/// Protective stop state carried by an open position
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StopState {
+    /// Best price since entry (highest for longs, lowest for shorts)
+    pub high_water: Option<f64>,
+    /// Active trailing or break-even stop price
+    pub stop_price: Option<f64>,
+    /// Z-score at entry, for the break-even move after partial reversion
+    pub entry_z: Option<f64>,
+    /// Whether the stop has been moved to break-even
+    pub break_even: bool,
+}
+
 /// Position state tracked by the strategy
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PositionState {
     /// No open position
     Flat,
     /// Long position open
-    Long { entry_price: f64 },
+    Long {
+        entry_price: f64,
+        #[serde(default)]
+        stops: StopState,
+    },
     /// Short position open
-    Short { entry_price: f64 },
+    Short {
+        entry_price: f64,
+        #[serde(default)]
+        stops: StopState,
+    },
+}
+
+impl PositionState {
+    /// Long position with no stop moved yet
+    pub fn long(entry_price: f64) -> Self {
+        PositionState::Long {
+            entry_price,
+            stops: StopState { high_water: Some(entry_price), ..Default::default() },
+        }
+    }
+
+    /// Short position with no stop moved yet
+    pub fn short(entry_price: f64) -> Self {
+        PositionState::Short {
+            entry_price,
+            stops: StopState { high_water: Some(entry_price), ..Default::default() },
+        }
+    }
+
+    /// Stop state of the open position, None when flat
+    pub fn stops(&self) -> Option<&StopState> {
+        match self {
+            PositionState::Flat => None,
+            PositionState::Long { stops, .. } | PositionState::Short { stops, .. } => Some(stops),
+        }
+    }
 }
 
 /// Mean reversion strategy implementation
@@ -48,46 +122,60 @@ pub struct MeanReversionStrategy {
     config: StrategyConfig,
     /// Z-score calculation gate
     zscore_gate: ZScoreGate,
-    /// Current position state
-    position: PositionState,
-    /// Last trade timestamp for cooldown
-    last_trade_time: Option<Instant>,
-    /// Position entry timestamp for time-based exit
-    entry_time: Option<Instant>,
-    /// Daily trade counter
-    daily_trades: u32,
-    /// Daily P&L tracking
-    daily_pnl: f64,
+    /// RSI / MACD / moving averages on the same closes as the z-score
+    indicators: IndicatorSet,
+    /// Entry confirmation on additional candle timeframes
+    timeframes: MultiTimeframeFilter,
+    /// Position, stops, cooldown and daily limits
+    guard: RiskGuard,
+    /// Fills and partial exits of the open position
+    layers: Option<PositionLayers>,
 }
 
 impl MeanReversionStrategy {
     /// Create a new mean reversion strategy
     pub fn new(config: StrategyConfig) -> Self {
         let zscore_gate = ZScoreGate::new(config.clone());
+        let indicators = IndicatorSet::new(&config.indicators);
+        let timeframes = MultiTimeframeFilter::new(&config.timeframes);
+        let guard = RiskGuard::new(&config);
         Self {
             config,
             zscore_gate,
-            position: PositionState::Flat,
-            last_trade_time: None,
-            entry_time: None,
-            daily_trades: 0,
-            daily_pnl: 0.0,
+            indicators,
+            timeframes,
+            guard,
+            layers: None,
         }
     }
 
+    /// Drive cooldown and time stops from a simulated clock instead of the wall clock
+    /// Replays call this before each recorded tick so timing matches the live session
+    pub fn set_clock(&mut self, now: Instant) {
+        self.guard.set_clock(now);
+    }
+
+    /// Scale stop-loss and take-profit distances, e.g. wider in high volatility
+    pub fn set_stop_scale(&mut self, scale: f64) {
+        self.guard.set_stop_scale(scale);
+    }
+
     /// Update strategy with new price and get trade action
     /// NOTE: This only signals the action - call confirm_trade() after successful execution
     pub fn update(&mut self, price: f64) -> Option<TradeAction> {
+        self.indicators.update(price);
+
         // Update z-score gate
         let zscore_result = self.zscore_gate.update(price)?;
+        self.guard.update_break_even(zscore_result.z_score);
 
         // Check if we're in cooldown (but NOT for Exit - always allow exit attempts)
-        if self.is_in_cooldown() && !matches!(self.position, PositionState::Long { .. } | PositionState::Short { .. }) {
+        if self.guard.is_in_cooldown() && matches!(self.guard.position(), PositionState::Flat) {
             return Some(TradeAction::Hold);
         }
 
         // Check risk limits (but NOT for Exit - always allow exit attempts)
-        if !self.check_risk_limits() && matches!(self.position, PositionState::Flat) {
+        if !self.guard.check_risk_limits() && matches!(self.guard.position(), PositionState::Flat) {
             return Some(TradeAction::Hold);
         }
 
@@ -98,6 +186,52 @@ impl MeanReversionStrategy {
         Some(action)
     }
 
+    /// Update strategy with a closed candle and get trade action
+    /// The candle close feeds the z-score; the lookback is therefore measured in candles
+    pub fn update_candle(&mut self, candle: &Candle) -> Option<TradeAction> {
+        self.guard.update_candle(candle);
+        self.update(candle.close)
+    }
+
+    /// Check stop-loss, take-profit and time stop against a live tick
+    /// Does not touch the z-score buffer, so it is safe to call on every tick between candles.
+    /// Returns None while warming up, Exit if a stop is hit, Hold otherwise
+    pub fn check_stops(&self, price: f64) -> Option<TradeAction> {
+        if !self.is_ready() {
+            return None;
+        }
+
+        let hit = self.guard.stop_hit(price);
+        Some(if hit { TradeAction::Exit } else { TradeAction::Hold })
+    }
+
+    /// Process one live tick: SL/TP/time stops are checked on every tick, while
+    /// entries and z-score exits are only evaluated when a candle has closed
+    pub fn update_tick(&mut self, price: f64, closed_candle: Option<&Candle>) -> Option<TradeAction> {
+        self.timeframes.update(price, self.guard.now());
+        self.guard.update_trailing(price);
+        let stop_action = self.check_stops(price);
+        let candle_action = closed_candle
+            .and_then(|candle| self.update_candle(candle))
+            .map(|action| self.confirm_timeframes(action));
+
+        match stop_action {
+            Some(TradeAction::Exit) => stop_action,
+            _ => candle_action.or(stop_action),
+        }
+    }
+
+    /// Hold instead of entering or scaling in unless every configured timeframe agrees
+    fn confirm_timeframes(&mut self, action: TradeAction) -> TradeAction {
+        let confirmed = match (action, self.guard.position()) {
+            (TradeAction::EnterLong | TradeAction::EnterShort, _) => self.timeframes.confirm(action),
+            (TradeAction::ScaleIn, PositionState::Long { .. }) => self.timeframes.confirm_scale_in(Side::Long),
+            (TradeAction::ScaleIn, PositionState::Short { .. }) => self.timeframes.confirm_scale_in(Side::Short),
+            _ => true,
+        };
+        if confirmed { action } else { TradeAction::Hold }
+    }
+
     /// Confirm a trade was successfully executed - updates internal state
     /// Call this ONLY after the on-chain transaction confirms
     pub fn confirm_trade(&mut self, action: TradeAction, price: f64) {
@@ -106,10 +240,12 @@ impl MeanReversionStrategy {
 
     /// Evaluate what action to take based on current state
     fn evaluate_action(&self, zscore: &ZScoreResult, current_price: f64) -> TradeAction {
-        match self.position {
+        match self.guard.position() {
             PositionState::Flat => {
                 // Look for entry signals
-                if zscore.is_oversold(self.config.z_threshold) {
+                if !self.is_ou_tradeable() {
+                    TradeAction::Hold
+                } else if zscore.is_oversold(self.config.z_threshold) {
                     TradeAction::EnterLong
                 } else if zscore.is_overbought(self.config.z_threshold) {
                     TradeAction::EnterShort
@@ -117,116 +253,141 @@ impl MeanReversionStrategy {
                     TradeAction::Hold
                 }
             }
-            PositionState::Long { entry_price } => {
+            PositionState::Long { entry_price, .. } => {
                 // Check exit conditions for long
                 let pnl_pct = (current_price - entry_price) / entry_price * 100.0;
 
-                // Check time-based exit first
-                if let Some(entry_time) = self.entry_time {
-                    let hours_elapsed = entry_time.elapsed().as_secs_f64() / 3600.0;
-                    if hours_elapsed >= self.config.risk.time_stop_hours {
-                        return TradeAction::Exit; // Time stop
-                    }
-                }
-
-                if pnl_pct >= self.config.risk.take_profit_pct {
-                    TradeAction::Exit // Take profit
-                } else if pnl_pct <= -self.config.risk.stop_loss_pct {
-                    TradeAction::Exit // Stop loss
-                } else if zscore.is_overbought(self.config.z_exit_threshold) {
-                    TradeAction::Exit // Mean reversion exit (z-score crossed above exit threshold)
+                // Time stop, take profit, stop loss, trailing stop, or mean reversion exit
+                // (z-score crossed above exit threshold)
+                if self.guard.is_stop_hit(pnl_pct)
+                    || self.guard.is_trailing_stop_hit(current_price)
+                    || zscore.is_overbought(self.config.z_exit_threshold)
+                {
+                    TradeAction::Exit
                 } else {
-                    TradeAction::Hold
+                    self.layer_action(-zscore.z_score)
                 }
             }
-            PositionState::Short { entry_price } => {
+            PositionState::Short { entry_price, .. } => {
                 // Check exit conditions for short
                 let pnl_pct = (entry_price - current_price) / entry_price * 100.0;
 
-                // Check time-based exit first
-                if let Some(entry_time) = self.entry_time {
-                    let hours_elapsed = entry_time.elapsed().as_secs_f64() / 3600.0;
-                    if hours_elapsed >= self.config.risk.time_stop_hours {
-                        return TradeAction::Exit; // Time stop
-                    }
-                }
-
-                if pnl_pct >= self.config.risk.take_profit_pct {
-                    TradeAction::Exit // Take profit
-                } else if pnl_pct <= -self.config.risk.stop_loss_pct {
-                    TradeAction::Exit // Stop loss
-                } else if zscore.is_oversold(self.config.z_exit_threshold) {
-                    TradeAction::Exit // Mean reversion exit (z-score crossed below exit threshold)
+                // Time stop, take profit, stop loss, trailing stop, or mean reversion exit
+                // (z-score crossed below exit threshold)
+                if self.guard.is_stop_hit(pnl_pct)
+                    || self.guard.is_trailing_stop_hit(current_price)
+                    || zscore.is_oversold(self.config.z_exit_threshold)
+                {
+                    TradeAction::Exit
                 } else {
-                    TradeAction::Hold
+                    self.layer_action(zscore.z_score)
                 }
             }
         }
     }
 
+    /// Scale-in or partial exit for the open position
+    /// `stretch` is the z-score in the entry direction (positive = further from the mean)
+    fn layer_action(&self, stretch: f64) -> TradeAction {
+        let scaling = &self.config.scaling;
+        let (scale_ins, partial_exits) = self
+            .layers
+            .as_ref()
+            .map_or((0, 0), |layers| (layers.scale_ins(), layers.exits.len()));
+
+        if scaling
+            .scale_in_levels
+            .get(scale_ins)
+            .is_some_and(|&level| stretch >= level && self.guard.check_risk_limits())
+        {
+            TradeAction::ScaleIn
+        } else if scaling.partial_exit_levels.get(partial_exits).is_some_and(|&level| stretch <= level) {
+            TradeAction::PartialExit { fraction: scaling.partial_exit_fraction }
+        } else {
+            TradeAction::Hold
+        }
+    }
+
+    /// Whether the OU fit is good enough to enter on (always true for other estimators)
+    fn is_ou_tradeable(&self) -> bool {
+        if self.config.zscore_estimator != ZScoreEstimator::Ou {
+            return true;
+        }
+        let ou = &self.config.ou;
+        self.zscore_gate.ou_params().is_some_and(|params| {
+            let half_life_minutes = params.half_life * 60.0;
+            params.is_valid()
+                && params.confidence >= ou.min_confidence
+                && half_life_minutes >= ou.min_half_life_minutes
+                && half_life_minutes <= ou.max_half_life_minutes
+        })
+    }
+
+    /// Time stop for a new position: `time_stop_half_lives` OU half-lives, else `time_stop_hours`
+    fn entry_time_stop_hours(&self) -> f64 {
+        let half_lives = self.config.ou.time_stop_half_lives;
+        self.zscore_gate
+            .ou_params()
+            .filter(|_| half_lives > 0.0)
+            .map_or(self.config.risk.time_stop_hours, |params| params.half_life * half_lives)
+    }
+
+    /// Entry size scale: the OU estimation confidence with `confidence_sizing`, else 1.0
+    pub fn size_multiplier(&self) -> f64 {
+        if !self.config.ou.confidence_sizing {
+            return 1.0;
+        }
+        self.zscore_gate
+            .ou_params()
+            .map_or(1.0, |params| params.confidence.clamp(0.0, 1.0))
+    }
+
+    /// Time stop of the open position in hours
+    pub fn time_stop_hours(&self) -> f64 {
+        self.guard.time_stop_hours()
+    }
+
     /// Handle trade execution updates
     fn on_trade_executed(&mut self, action: TradeAction, price: f64) {
+        let entry_z = self.current_zscore().map(|z| z.z_score);
         match action {
             TradeAction::EnterLong => {
-                self.position = PositionState::Long { entry_price: price };
-                self.last_trade_time = Some(Instant::now());
-                self.entry_time = Some(Instant::now());
-                self.daily_trades += 1;
+                self.guard.open(Side::Long, price, entry_z, self.entry_time_stop_hours());
+                self.layers = Some(PositionLayers::open(Side::Long, price, entry_z));
             }
             TradeAction::EnterShort => {
-                self.position = PositionState::Short { entry_price: price };
-                self.last_trade_time = Some(Instant::now());
-                self.entry_time = Some(Instant::now());
-                self.daily_trades += 1;
+                self.guard.open(Side::Short, price, entry_z, self.entry_time_stop_hours());
+                self.layers = Some(PositionLayers::open(Side::Short, price, entry_z));
             }
-            TradeAction::Exit => {
-                // Calculate and track P&L
-                let pnl = match self.position {
-                    PositionState::Long { entry_price } => {
-                        (price - entry_price) / entry_price * 100.0
-                    }
-                    PositionState::Short { entry_price } => {
-                        (entry_price - price) / entry_price * 100.0
-                    }
-                    PositionState::Flat => 0.0,
+            TradeAction::ScaleIn => {
+                let Some(layers) = self.layers.as_mut() else {
+                    return;
                 };
-                self.daily_pnl += pnl;
-                self.position = PositionState::Flat;
-                self.last_trade_time = Some(Instant::now());
-                self.entry_time = None;
+                layers.add_fill(price, self.config.scaling.scale_in_size, entry_z);
+                self.guard.add_to_position(layers.average_entry());
+            }
+            TradeAction::PartialExit { fraction } => {
+                if let Some(layers) = self.layers.as_mut() {
+                    self.guard.realize_partial(layers.close_fraction(price, fraction).realized_pnl());
+                }
+            }
+            TradeAction::Exit => {
+                // Track P&L of the remaining size
+                let remaining = self.layers.take().map_or(1.0, |layers| layers.remaining_size());
+                self.guard.close(price, remaining);
             }
             TradeAction::Hold => {}
         }
     }
 
-    /// Check if strategy is in cooldown period
-    fn is_in_cooldown(&self) -> bool {
-        if let Some(last_trade) = self.last_trade_time {
-            let cooldown = Duration::from_secs(self.config.cooldown_seconds);
-            last_trade.elapsed() < cooldown
-        } else {
-            false
-        }
-    }
-
-    /// Check if risk limits allow trading
-    fn check_risk_limits(&self) -> bool {
-        // Check daily trade limit
-        if self.daily_trades >= self.config.risk.max_daily_trades {
-            return false;
-        }
-
-        // Check daily loss limit
-        if self.daily_pnl <= -self.config.risk.max_daily_loss_pct {
-            return false;
-        }
-
-        true
-    }
-
     /// Get current position state
     pub fn position(&self) -> PositionState {
-        self.position
+        self.guard.position()
+    }
+
+    /// Fills and partial exits of the open position
+    pub fn layers(&self) -> Option<&PositionLayers> {
+        self.layers.as_ref()
     }
 
     /// Get current z-score if available
@@ -234,6 +395,16 @@ impl MeanReversionStrategy {
         self.zscore_gate.calculate()
     }
 
+    /// Kalman fair value filter state (Kalman z-score estimator only)
+    pub fn kalman_state(&self) -> Option<KalmanState> {
+        self.zscore_gate.kalman_state()
+    }
+
+    /// Get current RSI / MACD / moving average values
+    pub fn indicator_values(&self) -> IndicatorValues {
+        self.indicators.values()
+    }
+
     /// Check if strategy is ready (has enough data)
     pub fn is_ready(&self) -> bool {
         self.zscore_gate.is_ready()
@@ -242,27 +413,118 @@ impl MeanReversionStrategy {
     /// Reset strategy state (for new trading session)
     pub fn reset(&mut self) {
         self.zscore_gate.reset();
-        self.position = PositionState::Flat;
-        self.last_trade_time = None;
-        self.entry_time = None;
-        self.daily_trades = 0;
-        self.daily_pnl = 0.0;
+        self.indicators.reset();
+        self.timeframes.reset();
+        self.guard.reset();
+        self.layers = None;
     }
 
     /// Reset daily counters (call at start of each trading day)
     pub fn reset_daily(&mut self) {
-        self.daily_trades = 0;
-        self.daily_pnl = 0.0;
+        self.guard.reset_daily();
+    }
+
+    /// Resume the position, stops, layers and daily counters of a saved snapshot
+    pub fn restore(&mut self, snapshot: &StrategySnapshot) {
+        self.guard.restore(snapshot);
+        self.layers = match snapshot.position {
+            PositionState::Flat => None,
+            // Snapshots without layers resume as a single fill at the entry
+            PositionState::Long { entry_price, stops } => snapshot
+                .layers
+                .clone()
+                .or_else(|| Some(PositionLayers::open(Side::Long, entry_price, stops.entry_z))),
+            PositionState::Short { entry_price, stops } => snapshot
+                .layers
+                .clone()
+                .or_else(|| Some(PositionLayers::open(Side::Short, entry_price, stops.entry_z))),
+        };
     }
 
     /// Get daily trade count
     pub fn daily_trade_count(&self) -> u32 {
-        self.daily_trades
+        self.guard.daily_trades()
     }
 
     /// Get daily P&L percentage
     pub fn daily_pnl_pct(&self) -> f64 {
-        self.daily_pnl
+        self.guard.daily_pnl()
+    }
+}
+
+impl Strategy for MeanReversionStrategy {
+    fn name(&self) -> &'static str {
+        "mean_reversion"
+    }
+
+    fn update_tick(&mut self, price: f64, closed_candle: Option<&Candle>) -> Option<TradeAction> {
+        MeanReversionStrategy::update_tick(self, price, closed_candle)
+    }
+
+    fn confirm_trade(&mut self, action: TradeAction, price: f64) {
+        MeanReversionStrategy::confirm_trade(self, action, price)
+    }
+
+    fn position(&self) -> PositionState {
+        self.guard.position()
+    }
+
+    fn is_ready(&self) -> bool {
+        MeanReversionStrategy::is_ready(self)
+    }
+
+    fn set_clock(&mut self, now: Instant) {
+        MeanReversionStrategy::set_clock(self, now)
+    }
+
+    fn set_stop_scale(&mut self, scale: f64) {
+        MeanReversionStrategy::set_stop_scale(self, scale)
+    }
+
+    fn reset(&mut self) {
+        MeanReversionStrategy::reset(self)
+    }
+
+    fn reset_daily(&mut self) {
+        MeanReversionStrategy::reset_daily(self)
+    }
+
+    fn size_multiplier(&self) -> f64 {
+        MeanReversionStrategy::size_multiplier(self)
+    }
+
+    fn restore(&mut self, snapshot: &StrategySnapshot) {
+        MeanReversionStrategy::restore(self, snapshot)
+    }
+
+    fn snapshot(&self) -> StrategySnapshot {
+        let zscore = self.current_zscore();
+        let mut indicators = self.indicators.values().to_map();
+        if let Some(ref z) = zscore {
+            indicators.insert("z_score".to_string(), z.z_score);
+            indicators.insert("mean".to_string(), z.mean);
+            indicators.insert("std_dev".to_string(), z.std_dev);
+        }
+        if let Some(params) = self.zscore_gate.ou_params() {
+            indicators.insert("ou_half_life_hours".to_string(), params.half_life);
+            indicators.insert("ou_confidence".to_string(), params.confidence);
+        }
+
+        StrategySnapshot {
+            name: Strategy::name(self).to_string(),
+            ready: self.is_ready(),
+            position: self.guard.position(),
+            position_age_secs: self.guard.position_age().map(|age| age.as_secs_f64()),
+            time_stop_hours: self.guard.position_time_stop_hours(),
+            daily_trades: self.guard.daily_trades(),
+            daily_pnl_pct: self.guard.daily_pnl(),
+            signal: zscore.map(|z| z.z_score),
+            indicators,
+            kalman: self.kalman_state(),
+            timeframes: self.timeframes.states(),
+            layers: self.layers.clone(),
+            ensemble: None,
+        }
     }
 }
 
@@ -279,13 +541,20 @@ impl StrategyPort for MeanReversionStrategy {
                 let signal = match action {
                     TradeAction::EnterLong => Signal::StrongBuy,
                     TradeAction::EnterShort => Signal::StrongSell,
-                    TradeAction::Exit => {
-                        match self.position {
+                    TradeAction::Exit | TradeAction::PartialExit { .. } => {
+                        match self.guard.position() {
                             PositionState::Long { .. } => Signal::Sell,
                             PositionState::Short { .. } => Signal::Buy,
                             PositionState::Flat => Signal::Hold,
                         }
                     }
+                    TradeAction::ScaleIn => {
+                        match self.guard.position() {
+                            PositionState::Long { .. } => Signal::Buy,
+                            PositionState::Short { .. } => Signal::Sell,
+                            PositionState::Flat => Signal::Hold,
+                        }
+                    }
                     TradeAction::Hold => Signal::Hold,
                 };
                 signals.push(signal);
@@ -314,17 +583,19 @@ impl StrategyPort for MeanReversionStrategy {
         let zscore = self.zscore_gate.calculate()
             .ok_or_else(|| StrategyError::CalculationError("Failed to calculate z-score".into()))?;
 
+        // Batch values from a fresh set so the live indicators are untouched
+        let mut batch = IndicatorSet::new(&self.config.indicators);
+        let values = data.iter().fold(batch.values(), |_, &price| batch.update(price));
+
+        // sma reports the z-score center (mean, EWMA mean or median), the baseline the strategy trades against
         Ok(IndicatorValues {
-            rsi: None,
-            macd: None,
-            macd_signal: None,
-            macd_histogram: None,
             sma: Some(zscore.mean),
-            ema: None,
+            ..values
         })
     }
 
     fn validate_params(&self) -> Result<(), StrategyError> {
+        self.config.indicators.validate()?;
         self.config.validate()
             .map_err(|e| StrategyError::ConfigurationError(e.to_string()))
     }
@@ -333,6 +604,8 @@ impl StrategyPort for MeanReversionStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+    use crate::strategy::params::{ScalingConfig, TrailingStopConfig};
 
     fn create_test_strategy() -> MeanReversionStrategy {
         let config = StrategyConfig {
@@ -392,7 +665,7 @@ mod tests {
         }
 
         // Manually set position for test
-        strategy.position = PositionState::Long { entry_price: 100.0 };
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
 
         // Price rises 2% (above 1.5% take profit)
         let action = strategy.update(102.0);
@@ -409,17 +682,166 @@ mod tests {
         }
 
         // Manually set position for test
-        strategy.position = PositionState::Long { entry_price: 100.0 };
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
 
         // Price drops 3% (below -2% stop loss)
         let action = strategy.update(97.0);
         assert_eq!(action, Some(TradeAction::Exit));
     }
 
+    #[test]
+    fn test_stop_scale_widens_and_tightens_stops() {
+        let mut strategy = create_test_strategy();
+        for _ in 0..10 {
+            strategy.update(100.0);
+        }
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
+
+        // 3% drop is inside a doubled stop loss
+        strategy.set_stop_scale(2.0);
+        assert_eq!(strategy.update(97.0), Some(TradeAction::Hold));
+
+        // 1.5% drop hits a halved stop loss
+        strategy.set_stop_scale(0.5);
+        assert!(!strategy.guard.is_stop_hit(-0.9));
+        assert!(strategy.guard.is_stop_hit(-1.5));
+    }
+
+    #[test]
+    fn test_update_candle_uses_close() {
+        let mut strategy = create_test_strategy();
+
+        for i in 0..10 {
+            let candle = Candle::new(100.0, 120.0, 80.0, 100.0 + (i % 2) as f64, 5.0);
+            strategy.update_candle(&candle);
+        }
+
+        assert!(strategy.is_ready());
+        let z = strategy.current_zscore().unwrap();
+        assert!((z.mean - 100.5).abs() < 1e-9);
+        assert_eq!(z.current_price, 101.0);
+    }
+
+    #[test]
+    fn test_check_stops_intrabar() {
+        let mut strategy = create_test_strategy();
+
+        // Not ready yet
+        assert_eq!(strategy.check_stops(100.0), None);
+
+        for _ in 0..10 {
+            strategy.update(100.0);
+        }
+        assert_eq!(strategy.check_stops(100.0), Some(TradeAction::Hold));
+
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
+        let buffer_len = strategy.zscore_gate.buffer_len();
+
+        assert_eq!(strategy.check_stops(100.5), Some(TradeAction::Hold));
+        assert_eq!(strategy.check_stops(97.0), Some(TradeAction::Exit)); // Stop loss
+        assert_eq!(strategy.check_stops(102.0), Some(TradeAction::Exit)); // Take profit
+
+        // Ticks between candles must not enter the z-score window
+        assert_eq!(strategy.zscore_gate.buffer_len(), buffer_len);
+
+        strategy.guard.open(Side::Short, 100.0, None, 24.0);
+        assert_eq!(strategy.check_stops(103.0), Some(TradeAction::Exit));
+        assert_eq!(strategy.check_stops(99.5), Some(TradeAction::Hold));
+    }
+
+    #[test]
+    fn test_update_tick_prefers_stop_exit() {
+        let mut strategy = create_test_strategy();
+        for i in 0..10 {
+            strategy.update(100.0 + (i % 2) as f64);
+        }
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
+
+        // No candle closed: only stops are evaluated
+        assert_eq!(strategy.update_tick(100.2, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(97.0, None), Some(TradeAction::Exit));
+
+        // Candle closed with a stop hit: stop exit wins
+        let candle = Candle::new(100.0, 100.0, 97.0, 97.0, 3.0);
+        assert_eq!(strategy.update_tick(97.0, Some(&candle)), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_simulated_clock_time_stop() {
+        let mut strategy = create_test_strategy();
+        for i in 0..10 {
+            strategy.update(100.0 + (i % 2) as f64);
+        }
+
+        let start = Instant::now();
+        strategy.set_clock(start);
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        assert_eq!(strategy.check_stops(100.1), Some(TradeAction::Hold));
+
+        // Default time stop is 24h
+        strategy.set_clock(start + Duration::from_secs(24 * 3600));
+        assert_eq!(strategy.check_stops(100.1), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_timeframe_rules_veto_entries() {
+        use crate::strategy::params::TimeframeRule;
+
+        let entry_after = |strategy: &mut MeanReversionStrategy| {
+            let start = Instant::now();
+            let mut action = None;
+            for i in 0..11u64 {
+                strategy.set_clock(start + Duration::from_secs(i * 60));
+                let price = if i == 10 { 90.0 } else { 100.0 + (i % 2) as f64 };
+                action = strategy.update_tick(price, Some(&Candle::new(price, price, price, price, 1.0)));
+            }
+            action
+        };
+
+        assert_eq!(entry_after(&mut create_test_strategy()), Some(TradeAction::EnterLong));
+
+        // The 1h timeframe has not closed a candle yet, so it cannot agree
+        let config = StrategyConfig {
+            timeframes: vec![TimeframeRule::new(3600).with_max_abs_zscore(2.0)],
+            ..create_test_strategy().config
+        };
+        let mut strategy = MeanReversionStrategy::new(config);
+        assert_eq!(entry_after(&mut strategy), Some(TradeAction::Hold));
+
+        let timeframes = Strategy::snapshot(&strategy).timeframes;
+        assert_eq!(timeframes.len(), 1);
+        assert_eq!(timeframes[0].timeframe, "1h");
+        assert!(!timeframes[0].ready);
+        assert_eq!(timeframes[0].blocked_entries, 1);
+    }
+
+    #[test]
+    fn test_timeframe_rules_veto_scale_ins() {
+        use crate::strategy::params::TimeframeRule;
+
+        let mut strategy = create_test_strategy();
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        assert_eq!(strategy.confirm_timeframes(TradeAction::ScaleIn), TradeAction::ScaleIn);
+
+        // Judged like an entry in the position's direction, so an unready timeframe vetoes it
+        let config = StrategyConfig {
+            timeframes: vec![TimeframeRule::new(3600).with_max_abs_zscore(2.0)],
+            ..create_test_strategy().config
+        };
+        let mut strategy = MeanReversionStrategy::new(config);
+        strategy.confirm_trade(TradeAction::EnterShort, 100.0);
+        assert_eq!(strategy.confirm_timeframes(TradeAction::ScaleIn), TradeAction::Hold);
+        assert_eq!(strategy.confirm_timeframes(TradeAction::Exit), TradeAction::Exit);
+        assert_eq!(strategy.timeframes.states()[0].blocked_entries, 1);
+    }
+
     #[test]
     fn test_daily_trade_limit() {
         let mut strategy = create_test_strategy();
-        strategy.daily_trades = 10; // At limit
+        for _ in 0..10 {
+            strategy.guard.open(Side::Long, 100.0, None, 24.0);
+            strategy.guard.close(100.0, 1.0);
+        } // At limit
 
         // Fill buffer
         for _ in 0..10 {
@@ -434,7 +856,8 @@ mod tests {
     #[test]
     fn test_daily_loss_limit() {
         let mut strategy = create_test_strategy();
-        strategy.daily_pnl = -4.0; // Beyond -3% limit
+        strategy.guard.open(Side::Long, 100.0, None, 24.0);
+        strategy.guard.close(96.0, 1.0); // Beyond -3% limit
 
         // Fill buffer
         for _ in 0..10 {
@@ -454,8 +877,8 @@ mod tests {
         for _ in 0..10 {
             strategy.update(100.0);
         }
-        strategy.daily_trades = 5;
-        strategy.daily_pnl = -1.0;
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        strategy.confirm_trade(TradeAction::Exit, 99.0);
 
         strategy.reset();
 
@@ -494,6 +917,12 @@ mod tests {
         assert!(indicators.is_ok());
         let ind = indicators.unwrap();
         assert!(ind.sma.is_some());
+        assert!(ind.rsi.is_some()); // 15 prices > RSI(14) warmup
+        assert_eq!(ind.rsi, Some(100.0)); // strictly rising
+        assert!(ind.macd.is_none()); // MACD(12, 26) needs 26 prices
+
+        // Live indicators are not affected by the batch calculation
+        assert!(strategy.indicator_values().rsi.is_none());
     }
 
     #[test]
@@ -501,4 +930,238 @@ mod tests {
         let strategy = create_test_strategy();
         assert!(strategy.validate_params().is_ok());
     }
+    fn trailing_strategy(trailing: TrailingStopConfig) -> MeanReversionStrategy {
+        let mut config = StrategyConfig {
+            lookback_period: 10,
+            z_threshold: 2.0,
+            cooldown_seconds: 0,
+            ..Default::default()
+        };
+        config.risk.take_profit_pct = 10.0;
+        config.risk.stop_loss_pct = 5.0;
+        config.risk.trailing = trailing;
+        let mut strategy = MeanReversionStrategy::new(config);
+        for i in 0..10 {
+            let close = 100.0 + (i % 2) as f64 * 0.2;
+            strategy.update_candle(&Candle::new(close, close + 0.5, close - 0.5, close, 1.0));
+        }
+        assert!(strategy.is_ready());
+        strategy
+    }
+
+    #[test]
+    fn test_trailing_stop_follows_high_water() {
+        let mut strategy = trailing_strategy(TrailingStopConfig {
+            activation_pct: Some(1.0),
+            trail_pct: 0.5,
+            ..Default::default()
+        });
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+
+        // Not activated below +1%
+        assert_eq!(strategy.update_tick(100.5, None), Some(TradeAction::Hold));
+        assert!(strategy.position().stops().unwrap().stop_price.is_none());
+
+        // Activated at +2%: stop trails 0.5% below the high
+        assert_eq!(strategy.update_tick(102.0, None), Some(TradeAction::Hold));
+        let stop = strategy.position().stops().unwrap().stop_price.unwrap();
+        assert!((stop - 101.49).abs() < 1e-9);
+
+        // A pullback keeps the high-water mark and the stop
+        assert_eq!(strategy.update_tick(101.8, None), Some(TradeAction::Hold));
+        let stops = *strategy.position().stops().unwrap();
+        assert_eq!(stops.high_water, Some(102.0));
+        assert_eq!(stops.stop_price, Some(stop));
+
+        assert_eq!(strategy.update_tick(101.4, None), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_short_trailing_stop_by_atr() {
+        let mut strategy = trailing_strategy(TrailingStopConfig {
+            activation_pct: Some(1.0),
+            atr_multiple: Some(2.0),
+            atr_period: 5,
+            ..Default::default()
+        });
+        // Unit-range candles: ATR = 1.0
+        assert!((strategy.guard.atr().unwrap() - 1.0).abs() < 1e-9);
+
+        strategy.confirm_trade(TradeAction::EnterShort, 100.0);
+        assert_eq!(strategy.update_tick(97.0, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.position().stops().unwrap().stop_price, Some(99.0));
+
+        // Lower low drags the stop down, a bounce does not lift it
+        strategy.update_tick(96.5, None);
+        strategy.update_tick(97.5, None);
+        assert_eq!(strategy.position().stops().unwrap().stop_price, Some(98.5));
+        assert_eq!(strategy.update_tick(98.6, None), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_break_even_after_partial_reversion() {
+        let mut strategy = trailing_strategy(TrailingStopConfig {
+            break_even_reversion: Some(0.5),
+            break_even_offset_pct: 0.1,
+            ..Default::default()
+        });
+        strategy.guard.open(Side::Long, 100.0, Some(-2.0), 24.0);
+
+        // Reverted a quarter of the way: stop unchanged
+        strategy.guard.update_break_even(-1.5);
+        assert!(strategy.position().stops().unwrap().stop_price.is_none());
+
+        // Halfway to the mean: stop at entry plus the fee offset
+        strategy.guard.update_break_even(-0.9);
+        let stops = *strategy.position().stops().unwrap();
+        assert!(stops.break_even);
+        assert!((stops.stop_price.unwrap() - 100.1).abs() < 1e-9);
+
+        assert_eq!(strategy.update_tick(100.3, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(100.05, None), Some(TradeAction::Exit));
+    }
+
+    #[test]
+    fn test_stop_state_survives_persistence() {
+        let mut strategy = trailing_strategy(TrailingStopConfig {
+            activation_pct: Some(0.5),
+            ..Default::default()
+        });
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        strategy.update_tick(101.0, None);
+        assert!(strategy.position().stops().unwrap().entry_z.is_some());
+
+        // A restarted strategy resumes the saved position with its trailing stop
+        let json = serde_json::to_string(&strategy.snapshot()).unwrap();
+        let mut restarted = trailing_strategy(TrailingStopConfig {
+            activation_pct: Some(0.5),
+            ..Default::default()
+        });
+        restarted.restore(&serde_json::from_str(&json).unwrap());
+        assert_eq!(restarted.position(), strategy.position());
+        assert_eq!(restarted.position().stops().unwrap().high_water, Some(101.0));
+        assert_eq!(restarted.layers().map(|l| l.fills.len()), Some(1));
+        assert_eq!(restarted.daily_trade_count(), 1);
+        assert_eq!(restarted.update_tick(100.4, None), Some(TradeAction::Exit));
+
+        // Positions persisted before stop tracking still load
+        let legacy: PositionState = serde_json::from_str(r#"{"Long":{"entry_price":100.0}}"#).unwrap();
+        assert_eq!(legacy.stops(), Some(&StopState::default()));
+    }
+
+    #[test]
+    fn test_scale_in_and_partial_exit() {
+        let config = StrategyConfig {
+            lookback_period: 10,
+            z_threshold: 2.0,
+            cooldown_seconds: 0,
+            scaling: ScalingConfig {
+                scale_in_levels: vec![3.0],
+                partial_exit_levels: vec![1.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut strategy = MeanReversionStrategy::new(config);
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+
+        // Stretched past the scale-in level: add an equal leg, entry becomes the average
+        assert_eq!(strategy.layer_action(2.5), TradeAction::Hold);
+        assert_eq!(strategy.layer_action(3.2), TradeAction::ScaleIn);
+        strategy.confirm_trade(TradeAction::ScaleIn, 94.0);
+        assert!(matches!(strategy.position(), PositionState::Long { entry_price, .. } if entry_price == 97.0));
+        assert_eq!(strategy.layers().unwrap().scale_ins(), 1);
+
+        // Each level fires once
+        assert_eq!(strategy.layer_action(3.5), TradeAction::Hold);
+
+        // Reverted through the partial level: close half of the 2.0 units
+        assert_eq!(strategy.layer_action(0.8), TradeAction::PartialExit { fraction: 0.5 });
+        strategy.confirm_trade(TradeAction::PartialExit { fraction: 0.5 }, 99.0);
+        assert_eq!(strategy.layer_action(0.5), TradeAction::Hold);
+        assert!((strategy.layers().unwrap().remaining_size() - 1.0).abs() < 1e-12);
+
+        // Final exit realizes the remaining unit
+        strategy.confirm_trade(TradeAction::Exit, 100.0);
+        assert!(strategy.layers().is_none());
+        let expected = (2.0 + 3.0) / 97.0 * 100.0;
+        assert!((strategy.daily_pnl_pct() - expected).abs() < 1e-9);
+    }
+
+    fn ou_strategy(min_confidence: f64) -> MeanReversionStrategy {
+        let mut config = StrategyConfig {
+            lookback_period: 60,
+            z_threshold: 2.0,
+            cooldown_seconds: 0,
+            zscore_estimator: ZScoreEstimator::Ou,
+            ..Default::default()
+        };
+        // Hourly candles, half-life of a few hours
+        config.ou.dt_minutes = 60.0;
+        config.ou.max_half_life_minutes = 600.0;
+        config.ou.min_confidence = min_confidence;
+
+        let mut strategy = MeanReversionStrategy::new(config);
+        for i in 0..60 {
+            strategy.update(100.0 + (i as f64 * 0.5).sin());
+        }
+        strategy
+    }
+
+    #[test]
+    fn test_ou_signals_set_time_stop_and_size() {
+        let mut strategy = ou_strategy(0.3);
+        assert!(strategy.is_ou_tradeable());
+        let params = strategy.zscore_gate.ou_params().unwrap().clone();
+        assert!((strategy.size_multiplier() - params.confidence).abs() < 1e-12);
+        assert!(params.confidence > 0.3 && params.confidence < 1.0);
+
+        // Time stop = 3 half-lives of the fit at entry, not the fixed 24h
+        let start = Instant::now();
+        strategy.set_clock(start);
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        let expected_hours = params.half_life * 3.0;
+        assert!((strategy.time_stop_hours() - expected_hours).abs() < 1e-9);
+        assert!(expected_hours < 24.0);
+
+        strategy.set_clock(start + Duration::from_secs_f64((expected_hours - 0.1) * 3600.0));
+        assert_eq!(strategy.check_stops(100.1), Some(TradeAction::Hold));
+        strategy.set_clock(start + Duration::from_secs_f64(expected_hours * 3600.0));
+        assert_eq!(strategy.check_stops(100.1), Some(TradeAction::Exit));
+
+        let snapshot = Strategy::snapshot(&strategy);
+        assert_eq!(snapshot.indicators["ou_half_life_hours"], params.half_life);
+    }
+
+    #[test]
+    fn test_ou_time_stop_survives_restart() {
+        let mut strategy = ou_strategy(0.3);
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+        let expected_hours = strategy.time_stop_hours();
+        assert!(expected_hours < 24.0);
+
+        // A restarted strategy has no OU fit yet but keeps the half-life time stop
+        let json = serde_json::to_string(&Strategy::snapshot(&strategy)).unwrap();
+        let mut restarted = MeanReversionStrategy::new(strategy.config.clone());
+        restarted.restore(&serde_json::from_str(&json).unwrap());
+        assert_eq!(restarted.time_stop_hours(), expected_hours);
+
+        // Snapshots saved without a time stop fall back to the fixed one
+        let mut legacy = Strategy::snapshot(&strategy);
+        legacy.time_stop_hours = None;
+        restarted.restore(&legacy);
+        assert_eq!(restarted.time_stop_hours(), 24.0);
+    }
+
+    #[test]
+    fn test_ou_entries_need_confident_fit() {
+        let mut strategy = ou_strategy(0.99);
+        assert!(!strategy.is_ou_tradeable());
+        assert_eq!(strategy.update(90.0), Some(TradeAction::Hold));
+
+        // Other estimators are unaffected
+        let simple = create_test_strategy();
+        assert!(simple.is_ou_tradeable());
+        assert_eq!(simple.size_multiplier(), 1.0);
+    }
 }
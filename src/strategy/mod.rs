@@ -1,11 +1,23 @@
 //! Strategy Layer - Mean Reversion with Z-Score Gating
 //!
-//! Implements a conservative mean reversion strategy:
+//! Strategies implement the `Strategy` trait and are selected by
+//! `StrategyConfig::kind`. The default is a conservative mean reversion strategy:
 //! - Z-score threshold of 2.5 for entry signals
 //! - Rolling statistics over configurable lookback period
 //! - Volume and spread filters for noise reduction
 //! - ADX-based regime detection to filter trending markets
+//! - Hurst / variance ratio / ADF regime detectors, combined by a composite
+//! - Realized volatility bands scaling position size and stop distances
+//! - Multi-timeframe confirmation of entries
+//! - Equity-based position sizing with volatility targeting and fractional Kelly
+//! - Layered positions: scale-in legs and partial take-profits
+//! - Shared stop, trailing stop, cooldown and daily limit guard for every strategy
 //! - OU process parameter estimation for meme coin mean reversion
+//! - Streaming RSI / MACD / moving average indicators
+//! - O(1) rolling statistics shared by the z-score gate and OU estimator
+//! - Kalman filter fair value as an alternative to the rolling mean
+//! - Cointegration spread trading between two correlated assets
+//! - Weighted-vote ensemble of z-score, OU, RSI and Kalman signals
 
 pub mod params;
 pub mod zscore_gate;
@@ -13,11 +25,37 @@ pub mod mean_reversion;
 pub mod regime;
 pub mod ou_process;
 pub mod launch_sniper;
+pub mod ou_reversion;
+pub mod traits;
+pub mod indicators;
+pub mod stats;
+pub mod kalman;
+pub mod multi_timeframe;
+pub mod sizing;
+pub mod layers;
+pub mod spread;
+pub mod ensemble;
+pub mod risk_guard;
 
-pub use params::StrategyConfig;
+pub use params::{
+    StrategyConfig, StrategyKind, OuStrategyConfig, ZScoreEstimator, KalmanConfig, TimeframeRule,
+    TrailingStopConfig, ScalingConfig, EnsembleConfig, EnsembleMember, EnsembleSignal,
+};
+pub use kalman::{KalmanFilter, KalmanState};
+pub use multi_timeframe::{MultiTimeframeFilter, TimeframeState};
+pub use sizing::{PositionSizer, SizingConfig, SizingDecision, SizingInputs};
+pub use layers::{ExitLeg, Fill, PositionLayers};
+pub use ensemble::{EnsembleStrategy, EnsembleTally, EnsembleVote};
+pub use spread::{HedgeEstimator, SpreadAction, SpreadConfig, SpreadSide, SpreadState, SpreadStrategy};
 pub use mean_reversion::{MeanReversionStrategy, TradeAction, PositionState};
+pub use ou_reversion::OuReversionStrategy;
+pub use risk_guard::RiskGuard;
+pub use traits::{Strategy, StrategySnapshot, build_strategy};
+pub use indicators::IndicatorSet;
 pub use regime::{
     RegimeDetector,
     AdxRegimeDetector, AdxConfig,
     CandleBuilder,
+    CompositeRegimeDetector, RegimeComponent, RegimeConfig,
+    VolatilityRegime, VolatilityRegimeDetector, VolatilityState,
 };
@@ -0,0 +1,272 @@
+//! Multi-Timeframe Entry Confirmation
+//!
+//! Builds candles on several timeframes from the same price ticks and only
+//! lets an entry through when every configured `TimeframeRule` agrees, e.g.:
+//! - 1h: |z| below 2 (not trending away from the mean)
+//! - 5m: ADX below 25 (ranging)
+//! - 1m: z beyond 2 in the entry direction (local extreme)
+//!
+//! Each timeframe keeps a rolling z-score of its closes and, when the rule
+//! uses it, an ADX detector. Scale-ins must pass the same rules as an entry
+//! in the open position's direction. Exits are never blocked.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::position::Side;
+use crate::strategy::mean_reversion::TradeAction;
+use crate::strategy::params::TimeframeRule;
+use crate::strategy::regime::{AdxConfig, AdxRegimeDetector, CandleBuilder};
+use crate::strategy::stats::RollingStats;
+
+/// Per-timeframe state for status output and reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeframeState {
+    /// Timeframe label ("5m", "1h", ...)
+    pub timeframe: String,
+    /// Closed candles seen
+    pub candles: usize,
+    /// Whether every indicator the rule uses is warmed up
+    pub ready: bool,
+    /// Z-score of the latest close against the rolling window
+    pub z_score: Option<f64>,
+    /// ADX, when the rule uses it and it is warmed up
+    pub adx: Option<f64>,
+    /// Entries this timeframe has blocked
+    pub blocked_entries: u64,
+}
+
+/// Short label for a timeframe in seconds ("90s", "5m", "4h", "1d")
+pub fn timeframe_label(secs: u64) -> String {
+    match secs {
+        s if s > 0 && s.is_multiple_of(86_400) => format!("{}d", s / 86_400),
+        s if s > 0 && s.is_multiple_of(3_600) => format!("{}h", s / 3_600),
+        s if s > 0 && s.is_multiple_of(60) => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+#[derive(Debug)]
+struct TimeframeTrack {
+    rule: TimeframeRule,
+    builder: CandleBuilder,
+    closes: RollingStats,
+    adx: Option<AdxRegimeDetector>,
+    candles: usize,
+    z_score: Option<f64>,
+    blocked_entries: u64,
+}
+
+impl TimeframeTrack {
+    fn new(rule: &TimeframeRule) -> Self {
+        Self {
+            rule: rule.clone(),
+            builder: CandleBuilder::new(Duration::from_secs(rule.timeframe_secs)),
+            closes: RollingStats::new(rule.lookback),
+            adx: rule.max_adx.map(|_| AdxRegimeDetector::new(AdxConfig::crypto_optimized())),
+            candles: 0,
+            z_score: None,
+            blocked_entries: 0,
+        }
+    }
+
+    fn update(&mut self, price: f64, now: Instant) {
+        let Some(candle) = self.builder.update_at(price, now) else {
+            return;
+        };
+        self.candles += 1;
+        self.closes.push(candle.close);
+        if let Some(adx) = self.adx.as_mut() {
+            adx.update_candle(&candle);
+        }
+
+        self.z_score = match (self.closes.is_full(), self.closes.mean(), self.closes.std_dev()) {
+            (true, Some(mean), Some(std_dev)) if std_dev > 0.0 => Some((candle.close - mean) / std_dev),
+            (true, Some(_), Some(_)) => Some(0.0),
+            _ => None,
+        };
+    }
+
+    fn adx_value(&self) -> Option<f64> {
+        self.adx.as_ref().filter(|adx| adx.is_valid()).map(|adx| adx.adx())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.z_score.is_some() && (self.adx.is_none() || self.adx_value().is_some())
+    }
+
+    /// Whether this timeframe agrees with the entry
+    fn agrees(&self, action: TradeAction) -> bool {
+        // Direction of the entry: z is expected below the mean for longs
+        // (scale-ins carry no direction, see `confirm_scale_in`)
+        let direction = match action {
+            TradeAction::EnterLong => -1.0,
+            TradeAction::EnterShort => 1.0,
+            TradeAction::ScaleIn | TradeAction::PartialExit { .. } | TradeAction::Exit | TradeAction::Hold => {
+                return true
+            }
+        };
+        let (Some(z), true) = (self.z_score, self.is_ready()) else {
+            return false;
+        };
+        let entry_z = direction * z;
+
+        self.rule.max_abs_zscore.is_none_or(|max| z.abs() <= max)
+            && self.rule.min_entry_zscore.is_none_or(|min| entry_z >= min)
+            && self
+                .rule
+                .max_adx
+                .is_none_or(|max| self.adx_value().is_some_and(|adx| adx <= max))
+    }
+
+    fn state(&self) -> TimeframeState {
+        TimeframeState {
+            timeframe: timeframe_label(self.rule.timeframe_secs),
+            candles: self.candles,
+            ready: self.is_ready(),
+            z_score: self.z_score,
+            adx: self.adx_value(),
+            blocked_entries: self.blocked_entries,
+        }
+    }
+}
+
+/// Entry filter requiring agreement across timeframes
+#[derive(Debug)]
+pub struct MultiTimeframeFilter {
+    tracks: Vec<TimeframeTrack>,
+}
+
+impl MultiTimeframeFilter {
+    pub fn new(rules: &[TimeframeRule]) -> Self {
+        Self {
+            tracks: rules.iter().map(TimeframeTrack::new).collect(),
+        }
+    }
+
+    /// No rules configured - every entry passes
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Feed a price tick observed at `now` to every timeframe
+    pub fn update(&mut self, price: f64, now: Instant) {
+        for track in &mut self.tracks {
+            track.update(price, now);
+        }
+    }
+
+    /// Whether every timeframe agrees with `action`; blocked entries are counted
+    /// against each disagreeing timeframe
+    pub fn confirm(&mut self, action: TradeAction) -> bool {
+        let mut confirmed = true;
+        for track in &mut self.tracks {
+            if !track.agrees(action) {
+                track.blocked_entries += 1;
+                confirmed = false;
+            }
+        }
+        confirmed
+    }
+
+    /// Whether every timeframe agrees with adding to an open `side` position,
+    /// judged like a new entry in that direction
+    pub fn confirm_scale_in(&mut self, side: Side) -> bool {
+        self.confirm(match side {
+            Side::Long => TradeAction::EnterLong,
+            Side::Short => TradeAction::EnterShort,
+        })
+    }
+
+    /// State of each timeframe, in configuration order
+    pub fn states(&self) -> Vec<TimeframeState> {
+        self.tracks.iter().map(TimeframeTrack::state).collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.tracks = self.tracks.iter().map(|track| TimeframeTrack::new(&track.rule)).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed one tick per second for `secs` seconds starting at `start`
+    fn feed(filter: &mut MultiTimeframeFilter, start: Instant, secs: std::ops::Range<u64>, price: impl Fn(u64) -> f64) {
+        for s in secs {
+            filter.update(price(s), start + Duration::from_secs(s));
+        }
+    }
+
+    fn oscillating(s: u64) -> f64 {
+        100.0 + ((s / 60) % 4) as f64 * 0.1
+    }
+
+    #[test]
+    fn test_timeframe_labels() {
+        assert_eq!(timeframe_label(60), "1m");
+        assert_eq!(timeframe_label(300), "5m");
+        assert_eq!(timeframe_label(3_600), "1h");
+        assert_eq!(timeframe_label(86_400), "1d");
+        assert_eq!(timeframe_label(90), "90s");
+    }
+
+    #[test]
+    fn test_blocks_until_warmed_up() {
+        let mut filter = MultiTimeframeFilter::new(&[TimeframeRule::new(60).with_max_abs_zscore(3.0)]);
+        let start = Instant::now();
+        feed(&mut filter, start, 0..60 * 10, oscillating);
+        assert!(!filter.confirm(TradeAction::EnterLong));
+        assert!(filter.confirm(TradeAction::Exit));
+
+        feed(&mut filter, start, 60 * 10..60 * 25, oscillating);
+        let state = &filter.states()[0];
+        assert!(state.ready);
+        assert_eq!(state.timeframe, "1m");
+        assert!(filter.confirm(TradeAction::EnterLong));
+        assert_eq!(filter.states()[0].blocked_entries, 1);
+    }
+
+    #[test]
+    fn test_requires_agreement_across_timeframes() {
+        let rules = [
+            // Higher timeframe must not be stretched
+            TimeframeRule { lookback: 5, ..TimeframeRule::new(300).with_max_abs_zscore(1.5) },
+            // Lower timeframe must be at an extreme in the entry direction
+            TimeframeRule { lookback: 10, ..TimeframeRule::new(60).with_min_entry_zscore(1.5) },
+        ];
+        let mut filter = MultiTimeframeFilter::new(&rules);
+        let start = Instant::now();
+
+        // Flat-ish market, then one sharp 1m drop
+        feed(&mut filter, start, 0..3_600, oscillating);
+        assert!(!filter.confirm(TradeAction::EnterLong), "no 1m extreme yet");
+
+        feed(&mut filter, start, 3_600..3_661, |_| 99.0);
+        let states = filter.states();
+        assert!(states[1].z_score.unwrap() < -1.5, "{:?}", states);
+        assert!(states[0].z_score.unwrap().abs() <= 1.5, "{:?}", states);
+        assert!(filter.confirm(TradeAction::EnterLong));
+        assert!(!filter.confirm(TradeAction::EnterShort));
+        assert!(filter.confirm_scale_in(Side::Long));
+        assert!(!filter.confirm_scale_in(Side::Short));
+
+        filter.reset();
+        assert_eq!(filter.states()[0].candles, 0);
+        assert_eq!(filter.states()[1].blocked_entries, 0);
+    }
+
+    #[test]
+    fn test_adx_condition() {
+        let mut filter = MultiTimeframeFilter::new(&[TimeframeRule::new(60).with_max_adx(25.0)]);
+        let start = Instant::now();
+
+        // Steady trend: ADX climbs well above 25
+        feed(&mut filter, start, 0..60 * 60, |s| 100.0 + s as f64 * 0.01);
+        let state = &filter.states()[0];
+        assert!(state.adx.unwrap() > 25.0, "{:?}", state);
+        assert!(!filter.confirm(TradeAction::EnterLong));
+    }
+}
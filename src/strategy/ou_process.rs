@@ -11,10 +11,14 @@
 //! measured by z_ou = (log_price - mu) / (sigma / sqrt(2*theta))
 //!
 //! Ported from butters-sniper for kyzlo-dex meme coin orchestrator.
+//!
+//! Sample moments and the lag-1 autocorrelation are maintained incrementally
+//! by `RollingAutocorrelation`, so each update is O(1).
 
-use std::collections::VecDeque;
 use serde::{Deserialize, Serialize};
 
+use crate::strategy::stats::RollingAutocorrelation;
+
 /// Minimum theta value to prevent division issues
 const MIN_THETA: f64 = 0.0001;
 /// Maximum theta value for reasonable mean reversion
@@ -83,10 +87,10 @@ pub enum OUSignal {
 }
 
 /// Ornstein-Uhlenbeck process estimator
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OUProcess {
-    /// Rolling buffer of log prices
-    log_prices: VecDeque<f64>,
+    /// Rolling log prices with running moments and lag-1 co-moments
+    log_prices: RollingAutocorrelation,
     /// Maximum number of samples to keep
     lookback: usize,
     /// Time step between samples (in minutes)
@@ -105,7 +109,7 @@ impl OUProcess {
     /// * `dt_minutes` - Time step between samples in minutes
     pub fn new(lookback: usize, dt_minutes: f64) -> Self {
         Self {
-            log_prices: VecDeque::with_capacity(lookback + 1),
+            log_prices: RollingAutocorrelation::new(lookback),
             lookback,
             dt_minutes,
             params: None,
@@ -122,12 +126,7 @@ impl OUProcess {
         }
 
         let log_price = price.ln();
-        self.log_prices.push_back(log_price);
-
-        // Maintain rolling window
-        while self.log_prices.len() > self.lookback {
-            self.log_prices.pop_front();
-        }
+        self.log_prices.push(log_price);
 
         // Re-estimate parameters
         self.params = self.estimate_params();
@@ -139,37 +138,26 @@ impl OUProcess {
     /// Estimate OU parameters using Maximum Likelihood Estimation
     fn estimate_params(&self) -> Option<OUParams> {
         let n = self.log_prices.len();
-        if n < self.min_samples {
+        if n < self.min_samples || n < 3 {
             return None;
         }
 
-        let prices: Vec<f64> = self.log_prices.iter().copied().collect();
         let dt = self.dt_minutes / 60.0; // Convert to hours for half-life calculation
 
         // Calculate sample statistics
-        let mean = prices.iter().sum::<f64>() / n as f64;
-        let variance = prices.iter()
-            .map(|x| (x - mean).powi(2))
-            .sum::<f64>() / (n - 1) as f64;
+        let values = self.log_prices.values();
+        let mean = values.mean()?;
+        let variance = values.sample_variance()?;
 
         if variance < MIN_VARIANCE {
             return None; // No variability, can't estimate
         }
 
-        // Calculate lag-1 autocorrelation (rho)
-        let mut cov_sum = 0.0;
-        let mut var_sum_x = 0.0;
-        let mut var_sum_y = 0.0;
-        let mean_x: f64 = prices[..n-1].iter().sum::<f64>() / (n - 1) as f64;
-        let mean_y: f64 = prices[1..].iter().sum::<f64>() / (n - 1) as f64;
-
-        for i in 0..(n - 1) {
-            let x = prices[i] - mean_x;
-            let y = prices[i + 1] - mean_y;
-            cov_sum += x * y;
-            var_sum_x += x * x;
-            var_sum_y += y * y;
-        }
+        // Calculate lag-1 autocorrelation (rho) over the n - 1 consecutive pairs
+        let lagged = self.log_prices.lagged();
+        let cov_sum = lagged.co_moment();
+        let var_sum_x = lagged.m2_x();
+        let var_sum_y = lagged.m2_y();
 
         let var_x = var_sum_x / (n - 2) as f64;
         let var_y = var_sum_y / (n - 2) as f64;
@@ -279,9 +267,9 @@ impl OUProcess {
 
     /// Get current z-score if parameters are available
     pub fn current_z_score(&self) -> Option<f64> {
-        let current_log_price = self.log_prices.back()?;
+        let current_log_price = self.log_prices.last()?;
         let params = self.params.as_ref()?;
-        Some(params.z_score(*current_log_price))
+        Some(params.z_score(current_log_price))
     }
 
     /// Get the number of samples currently stored
@@ -291,7 +279,7 @@ impl OUProcess {
 
     /// Reset the estimator
     pub fn reset(&mut self) {
-        self.log_prices.clear();
+        self.log_prices.reset();
         self.params = None;
     }
 
@@ -0,0 +1,315 @@
+//! OU Reversion Strategy
+//!
+//! Single-pair version of the meme coin OU logic behind the `Strategy` trait:
+//! - Fit OU parameters on closed candle closes (log prices)
+//! - Trade only while the fit is valid, confident and the half-life is in range
+//! - LONG when z_ou < z_entry_threshold, exit when z_ou >= z_exit_threshold
+//! - Shorts mirror the rules when `allow_short` is set
+//!
+//! Stop loss, take profit, time stop, trailing and break-even stops, cooldown
+//! and daily limits come from `StrategyConfig::risk` via the shared `RiskGuard`
+//! and are checked on every tick.
+
+use std::time::Instant;
+
+use crate::domain::position::Side;
+
+use crate::strategy::indicators::IndicatorSet;
+use crate::strategy::mean_reversion::{PositionState, TradeAction};
+use crate::strategy::multi_timeframe::MultiTimeframeFilter;
+use crate::strategy::ou_process::{OUParams, OUProcess};
+use crate::strategy::params::{OuStrategyConfig, StrategyConfig};
+use crate::strategy::regime::Candle;
+use crate::strategy::risk_guard::RiskGuard;
+use crate::strategy::traits::{Strategy, StrategySnapshot};
+
+/// OU z-score reversion strategy
+#[derive(Debug)]
+pub struct OuReversionStrategy {
+    config: StrategyConfig,
+    ou: OUProcess,
+    indicators: IndicatorSet,
+    timeframes: MultiTimeframeFilter,
+    /// Position, stops, cooldown and daily limits
+    guard: RiskGuard,
+}
+
+impl OuReversionStrategy {
+    /// Create a new OU reversion strategy
+    pub fn new(config: StrategyConfig) -> Self {
+        let ou = OUProcess::new(config.lookback_period, config.ou.dt_minutes);
+        let indicators = IndicatorSet::new(&config.indicators);
+        let timeframes = MultiTimeframeFilter::new(&config.timeframes);
+        let guard = RiskGuard::new(&config);
+        Self {
+            config,
+            ou,
+            indicators,
+            timeframes,
+            guard,
+        }
+    }
+
+    fn ou_config(&self) -> &OuStrategyConfig {
+        &self.config.ou
+    }
+
+    /// Current OU z-score if parameters are available
+    pub fn current_z_score(&self) -> Option<f64> {
+        self.ou.current_z_score()
+    }
+
+    /// OU parameters of the current fit (half-life in hours)
+    pub fn ou_params(&self) -> Option<&OUParams> {
+        self.ou.params()
+    }
+
+    /// Whether the current OU fit is good enough to enter on
+    pub fn is_tradeable(&self) -> bool {
+        let Some(params) = self.ou.params() else {
+            return false;
+        };
+        if !params.is_valid() || params.confidence < self.ou_config().min_confidence {
+            return false;
+        }
+        match self.ou.half_life_minutes() {
+            Some(half_life) => {
+                half_life >= self.ou_config().min_half_life_minutes
+                    && half_life <= self.ou_config().max_half_life_minutes
+            }
+            None => false,
+        }
+    }
+
+    /// Check stop loss, take profit, time stop and trailing stops for the open position
+    pub fn check_stops(&self, price: f64) -> bool {
+        self.guard.stop_hit(price)
+    }
+
+    /// Feed a closed candle and evaluate entries and z-score exits
+    fn update_candle(&mut self, candle: &Candle) -> Option<TradeAction> {
+        self.ou.update(candle.close);
+        self.indicators.update(candle.close);
+        self.guard.update_candle(candle);
+        let z = self.ou.current_z_score()?;
+        self.guard.update_break_even(z);
+        let ou = self.ou_config();
+
+        let action = match self.guard.position() {
+            PositionState::Flat => {
+                if self.guard.is_in_cooldown() || !self.guard.check_risk_limits() || !self.is_tradeable() {
+                    TradeAction::Hold
+                } else if z < ou.z_entry_threshold {
+                    TradeAction::EnterLong
+                } else if ou.allow_short && z > -ou.z_entry_threshold {
+                    TradeAction::EnterShort
+                } else {
+                    TradeAction::Hold
+                }
+            }
+            PositionState::Long { .. } if z >= ou.z_exit_threshold => TradeAction::Exit,
+            PositionState::Short { .. } if z <= -ou.z_exit_threshold => TradeAction::Exit,
+            _ => TradeAction::Hold,
+        };
+
+        Some(action)
+    }
+}
+
+impl Strategy for OuReversionStrategy {
+    fn name(&self) -> &'static str {
+        "ou_reversion"
+    }
+
+    fn update_tick(&mut self, price: f64, closed_candle: Option<&Candle>) -> Option<TradeAction> {
+        // Stops do not depend on the OU fit, so an open position is protected
+        // even if the parameters become invalid
+        self.timeframes.update(price, self.guard.now());
+        self.guard.update_trailing(price);
+        let stop_hit = self.check_stops(price);
+        let candle_action = closed_candle.and_then(|candle| self.update_candle(candle)).map(|action| {
+            match action {
+                TradeAction::EnterLong | TradeAction::EnterShort if !self.timeframes.confirm(action) => {
+                    TradeAction::Hold
+                }
+                _ => action,
+            }
+        });
+
+        if stop_hit {
+            return Some(TradeAction::Exit);
+        }
+        match self.guard.position() {
+            PositionState::Flat => candle_action,
+            _ => candle_action.or(Some(TradeAction::Hold)),
+        }
+    }
+
+    fn confirm_trade(&mut self, action: TradeAction, price: f64) {
+        let time_stop_hours = self.config.risk.time_stop_hours;
+        let z = self.current_z_score();
+        match action {
+            TradeAction::EnterLong => self.guard.open(Side::Long, price, z, time_stop_hours),
+            TradeAction::EnterShort => self.guard.open(Side::Short, price, z, time_stop_hours),
+            TradeAction::Exit => self.guard.close(price, 1.0),
+            // Positions are not layered
+            TradeAction::ScaleIn | TradeAction::PartialExit { .. } | TradeAction::Hold => {}
+        }
+    }
+
+    fn position(&self) -> PositionState {
+        self.guard.position()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ou.is_ready()
+    }
+
+    fn set_clock(&mut self, now: Instant) {
+        self.guard.set_clock(now);
+    }
+
+    fn set_stop_scale(&mut self, scale: f64) {
+        self.guard.set_stop_scale(scale);
+    }
+
+    fn reset(&mut self) {
+        self.ou.reset();
+        self.indicators.reset();
+        self.timeframes.reset();
+        self.guard.reset();
+    }
+
+    fn reset_daily(&mut self) {
+        self.guard.reset_daily();
+    }
+
+    fn restore(&mut self, snapshot: &StrategySnapshot) {
+        self.guard.restore(snapshot);
+    }
+
+    fn snapshot(&self) -> StrategySnapshot {
+        let mut indicators = self.indicators.values().to_map();
+        if let Some(params) = self.ou.params() {
+            indicators.insert("theta".to_string(), params.theta);
+            indicators.insert("mu".to_string(), params.mu);
+            indicators.insert("sigma".to_string(), params.sigma);
+            indicators.insert("confidence".to_string(), params.confidence);
+            indicators.insert("rho".to_string(), params.rho);
+        }
+        if let Some(half_life) = self.ou.half_life_minutes() {
+            indicators.insert("half_life_minutes".to_string(), half_life);
+        }
+        let z = self.current_z_score();
+        if let Some(z) = z {
+            indicators.insert("z_score".to_string(), z);
+        }
+
+        StrategySnapshot {
+            name: self.name().to_string(),
+            ready: self.is_ready(),
+            position: self.guard.position(),
+            position_age_secs: self.guard.position_age().map(|age| age.as_secs_f64()),
+            time_stop_hours: self.guard.position_time_stop_hours(),
+            daily_trades: self.guard.daily_trades(),
+            daily_pnl_pct: self.guard.daily_pnl(),
+            signal: z,
+            indicators,
+            kalman: None,
+            timeframes: self.timeframes.states(),
+            layers: None,
+            ensemble: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::params::{RiskConfig, StrategyKind, TrailingStopConfig};
+
+    fn config() -> StrategyConfig {
+        StrategyConfig {
+            kind: StrategyKind::OuReversion,
+            lookback_period: 60,
+            cooldown_seconds: 0,
+            ou: OuStrategyConfig {
+                z_entry_threshold: -2.0,
+                min_confidence: 0.0,
+                min_half_life_minutes: 0.5,
+                max_half_life_minutes: 600.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn candle(close: f64) -> Candle {
+        Candle::new(close, close, close, close, 1.0)
+    }
+
+    /// Deterministic AR(1) around 100 (rho ~0.8)
+    fn warm_up(strategy: &mut OuReversionStrategy) {
+        let mut x: f64 = 0.0;
+        for i in 0..120 {
+            let shock = ((i * 7919) % 13) as f64 / 13.0 - 0.5;
+            x = 0.8 * x + shock * 0.01;
+            strategy.update_tick(100.0 * x.exp(), Some(&candle(100.0 * x.exp())));
+        }
+    }
+
+    #[test]
+    fn test_enters_long_on_deep_oversold() {
+        let mut strategy = OuReversionStrategy::new(config());
+        warm_up(&mut strategy);
+        assert!(strategy.is_ready());
+        assert!(strategy.is_tradeable());
+
+        let action = strategy.update_tick(97.0, Some(&candle(97.0)));
+        assert_eq!(action, Some(TradeAction::EnterLong));
+        assert_eq!(strategy.position(), PositionState::Flat); // not until confirmed
+    }
+
+    #[test]
+    fn test_stops_run_without_candle() {
+        let mut strategy = OuReversionStrategy::new(config());
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+
+        // Not warmed up, but the open position is still protected
+        assert_eq!(strategy.update_tick(100.5, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(97.0, None), Some(TradeAction::Exit));
+
+        strategy.confirm_trade(TradeAction::Exit, 97.0);
+        assert_eq!(strategy.position(), PositionState::Flat);
+        assert!((strategy.snapshot().daily_pnl_pct + 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_short_unless_enabled() {
+        let mut strategy = OuReversionStrategy::new(config());
+        warm_up(&mut strategy);
+        assert_eq!(strategy.update_tick(101.0, Some(&candle(101.0))), Some(TradeAction::Hold));
+
+        let mut config = config();
+        config.ou.allow_short = true;
+        let mut strategy = OuReversionStrategy::new(config);
+        warm_up(&mut strategy);
+        assert_eq!(strategy.update_tick(101.0, Some(&candle(101.0))), Some(TradeAction::EnterShort));
+    }
+
+    #[test]
+    fn test_trailing_stop_applies() {
+        let mut config = config();
+        config.risk = RiskConfig {
+            trailing: TrailingStopConfig { activation_pct: Some(1.0), trail_pct: 0.5, ..Default::default() },
+            ..Default::default()
+        };
+        let mut strategy = OuReversionStrategy::new(config);
+        strategy.confirm_trade(TradeAction::EnterLong, 100.0);
+
+        // Up 1.2%: trail 0.5% behind the high, well inside the plain stop loss
+        assert_eq!(strategy.update_tick(101.2, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(100.8, None), Some(TradeAction::Hold));
+        assert_eq!(strategy.update_tick(100.6, None), Some(TradeAction::Exit));
+    }
+}
@@ -1,13 +1,78 @@
 //! Strategy Parameters
 //!
-//! Configuration structs for the mean reversion strategy.
+//! Configuration structs for the trading strategies.
 //! Default values target ~1.5% trigger frequency.
 
 use serde::{Deserialize, Serialize};
 
+use crate::ports::strategy::StrategyParams;
+
+/// Strategy implementation selected in config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    /// Rolling z-score mean reversion (long and short)
+    #[default]
+    MeanReversion,
+    /// Ornstein-Uhlenbeck z-score reversion (the meme coin logic)
+    OuReversion,
+    /// Weighted vote of several signal generators (`ensemble`)
+    Ensemble,
+}
+
+impl StrategyKind {
+    /// Name used in config and logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrategyKind::MeanReversion => "mean_reversion",
+            StrategyKind::OuReversion => "ou_reversion",
+            StrategyKind::Ensemble => "ensemble",
+        }
+    }
+}
+
+/// How `ZScoreGate` estimates the center and scale of the price window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZScoreEstimator {
+    /// Window mean and population standard deviation
+    #[default]
+    Simple,
+    /// Exponentially weighted mean and variance (`ewma_half_life`)
+    Ewma,
+    /// Window median and MAD scaled to a normal std dev (robust to spikes)
+    Robust,
+    /// Kalman filter fair value and innovation std dev (`kalman`)
+    Kalman,
+    /// Ornstein-Uhlenbeck fit of log closes: equilibrium and stationary std dev (`ou`)
+    Ou,
+}
+
+impl ZScoreEstimator {
+    pub const ALL: [ZScoreEstimator; 5] = [Self::Simple, Self::Ewma, Self::Robust, Self::Kalman, Self::Ou];
+
+    /// Name used in config and logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            ZScoreEstimator::Simple => "simple",
+            ZScoreEstimator::Ewma => "ewma",
+            ZScoreEstimator::Robust => "robust",
+            ZScoreEstimator::Kalman => "kalman",
+            ZScoreEstimator::Ou => "ou",
+        }
+    }
+}
+
+fn default_ewma_half_life() -> f64 {
+    20.0
+}
+
 /// Main strategy configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrategyConfig {
+    /// Which strategy to run
+    #[serde(default)]
+    pub kind: StrategyKind,
     /// Number of candles for rolling statistics
     pub lookback_period: usize,
     /// Z-score threshold for entry signals (e.g., 2.5 = 2.5 std devs)
@@ -15,23 +80,56 @@ pub struct StrategyConfig {
     /// Z-score threshold for exit signals (0.37 = academically optimized)
     /// When z-score crosses this threshold toward mean, exit position
     pub z_exit_threshold: f64,
+    /// Z-score estimator (simple window, EWMA or median/MAD)
+    #[serde(default)]
+    pub zscore_estimator: ZScoreEstimator,
+    /// EWMA half-life in candles (used when `zscore_estimator` is `Ewma`)
+    #[serde(default = "default_ewma_half_life")]
+    pub ewma_half_life: f64,
+    /// Kalman fair value settings (used when `zscore_estimator` is `Kalman`)
+    #[serde(default)]
+    pub kalman: KalmanConfig,
     /// Minimum seconds between trades
     pub cooldown_seconds: u64,
     /// Risk management settings
     pub risk: RiskConfig,
     /// Market filters
     pub filters: FilterConfig,
+    /// OU reversion settings (used when `kind` is `OuReversion`)
+    #[serde(default)]
+    pub ou: OuStrategyConfig,
+    /// RSI / MACD / moving average settings, computed on closed candles
+    #[serde(default)]
+    pub indicators: StrategyParams,
+    /// Higher/lower timeframe conditions that must all agree before entering
+    #[serde(default)]
+    pub timeframes: Vec<TimeframeRule>,
+    /// Scale-in and partial take-profit levels (`MeanReversionStrategy`)
+    #[serde(default)]
+    pub scaling: ScalingConfig,
+    /// Members and voting rules (used when `kind` is `Ensemble`)
+    #[serde(default)]
+    pub ensemble: EnsembleConfig,
 }
 
 impl Default for StrategyConfig {
     fn default() -> Self {
         Self {
+            kind: StrategyKind::MeanReversion,
             lookback_period: 50,
             z_threshold: 2.5,
             z_exit_threshold: 0.37, // academically optimized threshold
+            zscore_estimator: ZScoreEstimator::Simple,
+            ewma_half_life: default_ewma_half_life(),
+            kalman: KalmanConfig::default(),
             cooldown_seconds: 300, // 5 minutes
             risk: RiskConfig::default(),
             filters: FilterConfig::default(),
+            ou: OuStrategyConfig::default(),
+            indicators: StrategyParams::default(),
+            timeframes: Vec::new(),
+            scaling: ScalingConfig::default(),
+            ensemble: EnsembleConfig::default(),
         }
     }
 }
@@ -49,6 +147,18 @@ impl StrategyConfig {
         self
     }
 
+    /// Create a new config running the given strategy
+    pub fn with_kind(mut self, kind: StrategyKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Create a new config using the given z-score estimator
+    pub fn with_zscore_estimator(mut self, estimator: ZScoreEstimator) -> Self {
+        self.zscore_estimator = estimator;
+        self
+    }
+
     /// Create a new config with custom z-exit threshold
     pub fn with_z_exit_threshold(mut self, threshold: f64) -> Self {
         self.z_exit_threshold = threshold;
@@ -66,12 +176,118 @@ impl StrategyConfig {
         if self.z_exit_threshold < 0.0 || self.z_exit_threshold >= self.z_threshold {
             return Err(ConfigError::InvalidZExitThreshold(self.z_exit_threshold));
         }
+        if self.ewma_half_life <= 0.0 {
+            return Err(ConfigError::InvalidEwmaHalfLife(self.ewma_half_life));
+        }
+        if self.zscore_estimator == ZScoreEstimator::Kalman {
+            self.kalman.validate()?;
+        }
         self.risk.validate()?;
         self.filters.validate()?;
+        if self.kind == StrategyKind::OuReversion {
+            self.ou.validate()?;
+        }
+        if self.kind == StrategyKind::Ensemble {
+            self.ensemble.validate()?;
+            if self.ensemble.has_member(EnsembleSignal::Kalman) {
+                self.kalman.validate()?;
+            }
+            if self.ensemble.has_member(EnsembleSignal::Ou) {
+                self.ou.validate()?;
+            }
+        }
+        self.indicators
+            .validate()
+            .map_err(|e| ConfigError::InvalidIndicatorParams(e.to_string()))?;
+        for rule in &self.timeframes {
+            rule.validate()?;
+        }
+        // Only the z-score strategy layers positions; other kinds would ignore the levels
+        if self.scaling.is_enabled() && self.kind != StrategyKind::MeanReversion {
+            return Err(ConfigError::InvalidScalingParams(format!(
+                "scaling is only supported by mean_reversion, not {}",
+                self.kind.name()
+            )));
+        }
+        self.scaling.validate(self.z_threshold, self.z_exit_threshold)
+    }
+}
+
+/// Layered position configuration
+///
+/// Levels are absolute z-scores: a long entered at z < -2.5 adds a leg at
+/// each of z <= -3.0, -3.5, -4.0 and takes partial profit once z has come
+/// back above -1.0. The final exit stays at `z_exit_threshold`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalingConfig {
+    /// |z| levels beyond the entry threshold to add a leg at, ascending
+    pub scale_in_levels: Vec<f64>,
+    /// Size of each added leg relative to the initial entry
+    pub scale_in_size: f64,
+    /// |z| levels between the exit and entry thresholds to take partial profit at, descending
+    pub partial_exit_levels: Vec<f64>,
+    /// Fraction of the remaining position closed at each partial level
+    pub partial_exit_fraction: f64,
+}
+
+impl Default for ScalingConfig {
+    fn default() -> Self {
+        Self {
+            scale_in_levels: Vec::new(),
+            scale_in_size: 1.0,
+            partial_exit_levels: Vec::new(),
+            partial_exit_fraction: 0.5,
+        }
+    }
+}
+
+impl ScalingConfig {
+    /// Whether any scale-in or partial take-profit level is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.scale_in_levels.is_empty() || !self.partial_exit_levels.is_empty()
+    }
+
+    pub fn validate(&self, z_threshold: f64, z_exit_threshold: f64) -> Result<(), ConfigError> {
+        let invalid = |msg: String| Err(ConfigError::InvalidScalingParams(msg));
+        let ascending = |levels: &[f64]| levels.windows(2).all(|w| w[0] < w[1]);
+
+        if !self.scale_in_levels.is_empty() {
+            if !ascending(&self.scale_in_levels) || self.scale_in_levels[0] <= z_threshold {
+                return invalid(format!(
+                    "scale_in_levels must be ascending and beyond z_threshold {}, got {:?}",
+                    z_threshold, self.scale_in_levels
+                ));
+            }
+            if self.scale_in_size <= 0.0 {
+                return invalid(format!("scale_in_size must be > 0, got {}", self.scale_in_size));
+            }
+        }
+
+        if !self.partial_exit_levels.is_empty() {
+            let mut levels = self.partial_exit_levels.clone();
+            levels.reverse();
+            if !ascending(&levels)
+                || levels[0] <= z_exit_threshold
+                || levels[levels.len() - 1] >= z_threshold
+            {
+                return invalid(format!(
+                    "partial_exit_levels must be descending between z_exit_threshold {} and z_threshold {}, got {:?}",
+                    z_exit_threshold, z_threshold, self.partial_exit_levels
+                ));
+            }
+            if self.partial_exit_fraction <= 0.0 || self.partial_exit_fraction >= 1.0 {
+                return invalid(format!(
+                    "partial_exit_fraction must be between 0 and 1, got {}",
+                    self.partial_exit_fraction
+                ));
+            }
+        }
         Ok(())
     }
 }
 
+
 /// Risk management configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskConfig {
@@ -87,6 +303,9 @@ pub struct RiskConfig {
     pub max_daily_loss_pct: f64,
     /// Time-based stop in hours (exit after N hours if no movement)
     pub time_stop_hours: f64,
+    /// Trailing and break-even stops for `MeanReversionStrategy` (disabled by default)
+    #[serde(default)]
+    pub trailing: TrailingStopConfig,
 }
 
 impl Default for RiskConfig {
@@ -98,6 +317,7 @@ impl Default for RiskConfig {
             max_daily_trades: 10,
             max_daily_loss_pct: 3.0,
             time_stop_hours: 24.0,
+            trailing: TrailingStopConfig::default(),
         }
     }
 }
@@ -116,6 +336,79 @@ impl RiskConfig {
         if self.time_stop_hours <= 0.0 {
             return Err(ConfigError::InvalidTimeStop(self.time_stop_hours));
         }
+        self.trailing.validate()
+    }
+}
+
+/// Trailing stop and break-even stop configuration
+///
+/// Once the position is `activation_pct` in profit, the stop trails the best
+/// price since entry by `trail_pct` (or `atr_multiple` x ATR when set). After the
+/// z-score has reverted `break_even_reversion` of the way from its entry value
+/// to the mean, the stop is raised to break-even.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrailingStopConfig {
+    /// Profit in percent that activates the trailing stop (None disables trailing)
+    pub activation_pct: Option<f64>,
+    /// Trail distance in percent of the best price
+    pub trail_pct: f64,
+    /// Trail by this multiple of the ATR instead of `trail_pct`
+    pub atr_multiple: Option<f64>,
+    /// ATR period in strategy candles
+    pub atr_period: usize,
+    /// Fraction of the entry z-score reverted before moving the stop to
+    /// break-even (e.g. 0.5 = halfway to the mean, None disables)
+    pub break_even_reversion: Option<f64>,
+    /// Break-even stop offset in percent beyond entry (covers fees)
+    pub break_even_offset_pct: f64,
+}
+
+impl Default for TrailingStopConfig {
+    fn default() -> Self {
+        Self {
+            activation_pct: None,
+            trail_pct: 0.5,
+            atr_multiple: None,
+            atr_period: 14,
+            break_even_reversion: None,
+            break_even_offset_pct: 0.0,
+        }
+    }
+}
+
+impl TrailingStopConfig {
+    /// Whether any trailing or break-even stop is configured
+    pub fn is_enabled(&self) -> bool {
+        self.activation_pct.is_some() || self.break_even_reversion.is_some()
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |msg: String| Err(ConfigError::InvalidTrailingStop(msg));
+        if self.activation_pct.is_some_and(|pct| pct < 0.0) {
+            return invalid(format!("activation_pct must be >= 0, got {:?}", self.activation_pct));
+        }
+        if self.trail_pct <= 0.0 || self.trail_pct > 50.0 {
+            return invalid(format!("trail_pct must be 0-50, got {}", self.trail_pct));
+        }
+        if self.atr_multiple.is_some_and(|m| m <= 0.0) {
+            return invalid(format!("atr_multiple must be > 0, got {:?}", self.atr_multiple));
+        }
+        if self.atr_period < 2 {
+            return invalid(format!("atr_period must be >= 2, got {}", self.atr_period));
+        }
+        if self.break_even_reversion.is_some_and(|r| r <= 0.0 || r > 1.0) {
+            return invalid(format!(
+                "break_even_reversion must be 0-1, got {:?}",
+                self.break_even_reversion
+            ));
+        }
+        if self.break_even_offset_pct < 0.0 {
+            return invalid(format!(
+                "break_even_offset_pct must be >= 0, got {}",
+                self.break_even_offset_pct
+            ));
+        }
         Ok(())
     }
 }
@@ -150,6 +443,346 @@ impl FilterConfig {
     }
 }
 
+/// Ornstein-Uhlenbeck reversion configuration
+///
+/// The OU lookback is `lookback_period`; stops and daily limits come from `risk`.
+/// The mean reversion strategy with `zscore_estimator = "ou"` enters on its own
+/// `z_threshold` but uses the confidence, half-life and time stop settings here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OuStrategyConfig {
+    /// Minutes between OU samples (the candle timeframe)
+    pub dt_minutes: f64,
+    /// OU z-score below which to enter long (e.g., -3.5)
+    pub z_entry_threshold: f64,
+    /// OU z-score at or above which to exit a long (e.g., 0.0 = equilibrium)
+    pub z_exit_threshold: f64,
+    /// Minimum estimation confidence (0.0-1.0)
+    pub min_confidence: f64,
+    /// Minimum half-life in minutes
+    pub min_half_life_minutes: f64,
+    /// Maximum half-life in minutes
+    pub max_half_life_minutes: f64,
+    /// Mirror the entry/exit rules for shorts
+    pub allow_short: bool,
+    /// Time stop in half-lives of the fit at entry, replacing `risk.time_stop_hours`
+    /// (mean reversion with the OU estimator; 0 keeps the fixed time stop)
+    pub time_stop_half_lives: f64,
+    /// Scale entries by the estimation confidence (mean reversion with the OU estimator)
+    pub confidence_sizing: bool,
+}
+
+impl Default for OuStrategyConfig {
+    fn default() -> Self {
+        Self {
+            dt_minutes: 1.0,
+            z_entry_threshold: -3.5,
+            z_exit_threshold: 0.0,
+            min_confidence: 0.3,
+            min_half_life_minutes: 5.0,
+            max_half_life_minutes: 120.0,
+            allow_short: false,
+            time_stop_half_lives: 3.0,
+            confidence_sizing: true,
+        }
+    }
+}
+
+impl OuStrategyConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.dt_minutes <= 0.0 {
+            return Err(ConfigError::InvalidOuParams(format!(
+                "dt_minutes must be > 0, got {}",
+                self.dt_minutes
+            )));
+        }
+        if self.z_entry_threshold >= 0.0 || self.z_exit_threshold <= self.z_entry_threshold {
+            return Err(ConfigError::InvalidOuParams(format!(
+                "need z_entry_threshold < 0 and z_exit_threshold > z_entry_threshold, got {} / {}",
+                self.z_entry_threshold, self.z_exit_threshold
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            return Err(ConfigError::InvalidOuParams(format!(
+                "min_confidence must be 0-1, got {}",
+                self.min_confidence
+            )));
+        }
+        if self.min_half_life_minutes >= self.max_half_life_minutes {
+            return Err(ConfigError::InvalidOuParams(
+                "min_half_life_minutes must be < max_half_life_minutes".to_string(),
+            ));
+        }
+        if self.time_stop_half_lives.is_nan() || self.time_stop_half_lives < 0.0 {
+            return Err(ConfigError::InvalidOuParams(format!(
+                "time_stop_half_lives must be >= 0, got {}",
+                self.time_stop_half_lives
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Signal generator that votes in the ensemble
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnsembleSignal {
+    /// Rolling window z-score (`lookback_period`, `z_threshold`)
+    #[serde(rename = "zscore")]
+    ZScore,
+    /// OU z-score of log closes (`ou`), weighted by the fit confidence
+    Ou,
+    /// RSI distance from 50 (`indicators.rsi` levels)
+    Rsi,
+    /// Kalman fair value residual (`kalman`, `z_threshold`)
+    Kalman,
+}
+
+impl EnsembleSignal {
+    pub const ALL: [EnsembleSignal; 4] = [Self::ZScore, Self::Ou, Self::Rsi, Self::Kalman];
+
+    /// Name used in config and logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnsembleSignal::ZScore => "zscore",
+            EnsembleSignal::Ou => "ou",
+            EnsembleSignal::Rsi => "rsi",
+            EnsembleSignal::Kalman => "kalman",
+        }
+    }
+}
+
+fn default_member_weight() -> f64 {
+    1.0
+}
+
+/// One voting member of the ensemble
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleMember {
+    pub signal: EnsembleSignal,
+    /// Base weight before hit-rate adaptation
+    #[serde(default = "default_member_weight")]
+    pub weight: f64,
+}
+
+impl EnsembleMember {
+    pub fn new(signal: EnsembleSignal, weight: f64) -> Self {
+        Self { signal, weight }
+    }
+}
+
+/// Ensemble voting configuration
+///
+/// Each member votes in -1.0..=1.0 (positive = long) on every closed candle.
+/// The ensemble score is the weight- and confidence-weighted mean of the votes;
+/// entries need |score| >= `entry_score`, exits fire once the score falls
+/// back to `exit_score` (mirrored for shorts). Stops come from `risk`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnsembleConfig {
+    /// Voting signals with their base weights
+    pub members: Vec<EnsembleMember>,
+    /// Score at or beyond which to enter (0-1)
+    pub entry_score: f64,
+    /// Score at or below which a long exits (shorts mirror)
+    pub exit_score: f64,
+    /// Enter shorts on a negative consensus
+    pub allow_short: bool,
+    /// Scale member weights by their recent hit rates
+    pub adapt_weights: bool,
+    /// Candles after a vote at which it is scored against the price move
+    pub horizon_candles: usize,
+    /// Scored votes kept per member for the hit rate
+    pub hit_rate_window: usize,
+    /// Scored votes a member needs before its weight adapts
+    pub min_scored_votes: usize,
+    /// Floor of the weight factor (2 x hit rate, so 1.0 at a 50% hit rate)
+    pub min_weight_factor: f64,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            members: EnsembleSignal::ALL
+                .iter()
+                .map(|&signal| EnsembleMember::new(signal, 1.0))
+                .collect(),
+            entry_score: 0.5,
+            exit_score: 0.0,
+            allow_short: true,
+            adapt_weights: true,
+            horizon_candles: 5,
+            hit_rate_window: 50,
+            min_scored_votes: 10,
+            min_weight_factor: 0.1,
+        }
+    }
+}
+
+impl EnsembleConfig {
+    /// Whether `signal` votes in the ensemble
+    pub fn has_member(&self, signal: EnsembleSignal) -> bool {
+        self.members.iter().any(|m| m.signal == signal)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |msg: String| Err(ConfigError::InvalidEnsembleParams(msg));
+
+        if self.members.is_empty() {
+            return invalid("at least one member is required".to_string());
+        }
+        for (i, member) in self.members.iter().enumerate() {
+            if self.members[..i].iter().any(|m| m.signal == member.signal) {
+                return invalid(format!("{} is listed twice", member.signal.name()));
+            }
+            if !member.weight.is_finite() || member.weight <= 0.0 {
+                return invalid(format!("{} weight must be > 0, got {}", member.signal.name(), member.weight));
+            }
+        }
+        if self.entry_score <= 0.0 || self.entry_score > 1.0 {
+            return invalid(format!("entry_score must be 0 < score <= 1, got {}", self.entry_score));
+        }
+        if self.exit_score < -1.0 || self.exit_score >= self.entry_score {
+            return invalid(format!(
+                "exit_score must be -1 <= score < entry_score {}, got {}",
+                self.entry_score, self.exit_score
+            ));
+        }
+        if self.horizon_candles == 0 || self.hit_rate_window == 0 {
+            return invalid("horizon_candles and hit_rate_window must be > 0".to_string());
+        }
+        if self.min_scored_votes > self.hit_rate_window {
+            return invalid(format!(
+                "min_scored_votes {} exceeds hit_rate_window {}",
+                self.min_scored_votes, self.hit_rate_window
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_weight_factor) {
+            return invalid(format!("min_weight_factor must be 0-1, got {}", self.min_weight_factor));
+        }
+        Ok(())
+    }
+}
+
+/// Kalman fair value filter configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KalmanConfig {
+    /// Process noise as a fraction of measurement noise (Q / R)
+    /// Higher = fair value follows price faster (0.01 ~ 20-candle EMA)
+    pub process_noise: f64,
+    /// Half-life in candles of the adaptive measurement noise estimate
+    pub noise_half_life: f64,
+}
+
+impl Default for KalmanConfig {
+    fn default() -> Self {
+        Self {
+            process_noise: 0.01,
+            noise_half_life: 50.0,
+        }
+    }
+}
+
+impl KalmanConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.process_noise <= 0.0 || self.process_noise > 10.0 {
+            return Err(ConfigError::InvalidKalmanParams(format!(
+                "process_noise must be 0 < q <= 10, got {}",
+                self.process_noise
+            )));
+        }
+        if self.noise_half_life <= 0.0 {
+            return Err(ConfigError::InvalidKalmanParams(format!(
+                "noise_half_life must be > 0, got {}",
+                self.noise_half_life
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Entry condition evaluated on its own candle timeframe
+///
+/// Every set condition must hold on the latest closed candle of the
+/// timeframe; an entry is blocked while the timeframe is warming up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeframeRule {
+    /// Candle length in seconds (60 = 1m, 3600 = 1h)
+    pub timeframe_secs: u64,
+    /// Candles in the rolling z-score window
+    #[serde(default = "default_timeframe_lookback")]
+    pub lookback: usize,
+    /// Block entries while |z| exceeds this (price trending away from the mean)
+    #[serde(default)]
+    pub max_abs_zscore: Option<f64>,
+    /// Require z at least this far from the mean in the entry direction
+    #[serde(default)]
+    pub min_entry_zscore: Option<f64>,
+    /// Block entries while ADX exceeds this
+    #[serde(default)]
+    pub max_adx: Option<f64>,
+}
+
+fn default_timeframe_lookback() -> usize {
+    20
+}
+
+impl TimeframeRule {
+    /// Rule on `timeframe_secs` candles with no conditions set
+    pub fn new(timeframe_secs: u64) -> Self {
+        Self {
+            timeframe_secs,
+            lookback: default_timeframe_lookback(),
+            max_abs_zscore: None,
+            min_entry_zscore: None,
+            max_adx: None,
+        }
+    }
+
+    pub fn with_max_abs_zscore(mut self, z: f64) -> Self {
+        self.max_abs_zscore = Some(z);
+        self
+    }
+
+    pub fn with_min_entry_zscore(mut self, z: f64) -> Self {
+        self.min_entry_zscore = Some(z);
+        self
+    }
+
+    pub fn with_max_adx(mut self, adx: f64) -> Self {
+        self.max_adx = Some(adx);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.timeframe_secs == 0 {
+            return Err(ConfigError::InvalidTimeframeRule("timeframe must be > 0".to_string()));
+        }
+        if self.lookback < 2 {
+            return Err(ConfigError::InvalidTimeframeRule(format!(
+                "lookback must be >= 2, got {}",
+                self.lookback
+            )));
+        }
+        if self.max_abs_zscore.is_none() && self.min_entry_zscore.is_none() && self.max_adx.is_none() {
+            return Err(ConfigError::InvalidTimeframeRule(format!(
+                "{}s rule sets no condition",
+                self.timeframe_secs
+            )));
+        }
+        if self.max_abs_zscore.is_some_and(|z| z <= 0.0) || self.min_entry_zscore.is_some_and(|z| z < 0.0) {
+            return Err(ConfigError::InvalidTimeframeRule(
+                "z-score conditions must be positive".to_string(),
+            ));
+        }
+        if self.max_adx.is_some_and(|adx| adx <= 0.0 || adx > 100.0) {
+            return Err(ConfigError::InvalidTimeframeRule("max_adx must be 0 < adx <= 100".to_string()));
+        }
+        Ok(())
+    }
+}
+
 /// Configuration validation errors
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ConfigError {
@@ -159,6 +792,8 @@ pub enum ConfigError {
     InvalidZThreshold(f64),
     #[error("Invalid z-exit threshold: {0} (must be >= 0 and < z_threshold)")]
     InvalidZExitThreshold(f64),
+    #[error("Invalid EWMA half-life: {0} candles (must be > 0)")]
+    InvalidEwmaHalfLife(f64),
     #[error("Invalid position size: {0}% (must be 0 < size <= 100)")]
     InvalidPositionSize(f64),
     #[error("Invalid stop loss: {0}% (must be 0 < loss <= 50)")]
@@ -171,6 +806,26 @@ pub enum ConfigError {
     InvalidVolumeFilter(f64),
     #[error("Invalid spread filter: {0} bps (max 500)")]
     InvalidSpreadFilter(u32),
+    #[error("Invalid Kalman parameters: {0}")]
+    InvalidKalmanParams(String),
+    #[error("Invalid OU parameters: {0}")]
+    InvalidOuParams(String),
+    #[error("Invalid indicator parameters: {0}")]
+    InvalidIndicatorParams(String),
+    #[error("Invalid regime detector parameters: {0}")]
+    InvalidRegimeParams(String),
+    #[error("Invalid timeframe rule: {0}")]
+    InvalidTimeframeRule(String),
+    #[error("Invalid position sizing parameters: {0}")]
+    InvalidSizingParams(String),
+    #[error("Invalid trailing stop: {0}")]
+    InvalidTrailingStop(String),
+    #[error("Invalid scaling parameters: {0}")]
+    InvalidScalingParams(String),
+    #[error("Invalid spread parameters: {0}")]
+    InvalidSpreadParams(String),
+    #[error("Invalid ensemble parameters: {0}")]
+    InvalidEnsembleParams(String),
 }
 
 #[cfg(test)]
@@ -244,6 +899,29 @@ mod tests {
         assert!(risk.validate().is_err());
     }
 
+    #[test]
+    fn test_ou_config_validated_only_when_selected() {
+        let ou = OuStrategyConfig {
+            z_entry_threshold: 1.0,
+            ..Default::default()
+        };
+        let config = StrategyConfig {
+            ou,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = config.with_kind(StrategyKind::OuReversion);
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidOuParams(_))));
+    }
+
+    #[test]
+    fn test_strategy_kind_serde() {
+        let kind: StrategyKind = serde_json::from_str("\"ou_reversion\"").unwrap();
+        assert_eq!(kind, StrategyKind::OuReversion);
+        assert_eq!(StrategyKind::default().name(), "mean_reversion");
+    }
+
     #[test]
     fn test_filter_config_validation() {
         let mut filters = FilterConfig::default();
@@ -252,4 +930,152 @@ mod tests {
         filters.min_volume_percentile = 150.0;
         assert!(filters.validate().is_err());
     }
+
+    #[test]
+    fn test_zscore_estimator_config() {
+        let config = StrategyConfig::default();
+        assert_eq!(config.zscore_estimator, ZScoreEstimator::Simple);
+
+        let config: StrategyConfig = serde_json::from_value(serde_json::json!({
+            "lookback_period": 30,
+            "z_threshold": 2.0,
+            "z_exit_threshold": 0.5,
+            "zscore_estimator": "robust",
+            "cooldown_seconds": 0,
+            "risk": RiskConfig::default(),
+            "filters": FilterConfig::default(),
+        }))
+        .unwrap();
+        assert_eq!(config.zscore_estimator, ZScoreEstimator::Robust);
+        assert_eq!(config.ewma_half_life, 20.0);
+
+        let config = StrategyConfig {
+            zscore_estimator: ZScoreEstimator::Ewma,
+            ewma_half_life: 0.0,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidEwmaHalfLife(_))));
+    }
+
+    #[test]
+    fn test_kalman_config_validated_when_selected() {
+        let config = StrategyConfig {
+            kalman: KalmanConfig { process_noise: 0.0, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = config.with_zscore_estimator(ZScoreEstimator::Kalman);
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidKalmanParams(_))));
+    }
+
+    #[test]
+    fn test_timeframe_rules_validated() {
+        let config = StrategyConfig {
+            timeframes: vec![TimeframeRule::new(3600).with_max_abs_zscore(2.0)],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        for rule in [
+            TimeframeRule::new(300),
+            TimeframeRule::new(0).with_max_adx(25.0),
+            TimeframeRule::new(300).with_max_adx(150.0),
+            TimeframeRule { lookback: 1, ..TimeframeRule::new(60).with_min_entry_zscore(2.0) },
+        ] {
+            let config = StrategyConfig { timeframes: vec![rule], ..Default::default() };
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidTimeframeRule(_))));
+        }
+    }
+
+    #[test]
+    fn test_trailing_stop_validation() {
+        let mut config = StrategyConfig::default();
+        assert!(!config.risk.trailing.is_enabled());
+
+        config.risk.trailing = TrailingStopConfig {
+            activation_pct: Some(0.5),
+            atr_multiple: Some(2.0),
+            break_even_reversion: Some(0.5),
+            ..Default::default()
+        };
+        assert!(config.risk.trailing.is_enabled());
+        assert!(config.validate().is_ok());
+
+        for trailing in [
+            TrailingStopConfig { trail_pct: 0.0, ..Default::default() },
+            TrailingStopConfig { atr_multiple: Some(-1.0), ..Default::default() },
+            TrailingStopConfig { break_even_reversion: Some(1.5), ..Default::default() },
+            TrailingStopConfig { atr_period: 1, ..Default::default() },
+        ] {
+            config.risk.trailing = trailing;
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidTrailingStop(_))));
+        }
+    }
+    #[test]
+    fn test_scaling_validation() {
+        let scaling = ScalingConfig {
+            scale_in_levels: vec![3.0, 3.5, 4.0],
+            partial_exit_levels: vec![1.5, 1.0],
+            ..Default::default()
+        };
+        let config = StrategyConfig { scaling, ..Default::default() };
+        assert!(config.scaling.is_enabled());
+        assert!(config.validate().is_ok());
+
+        for scaling in [
+            // Not beyond the 2.5 entry threshold
+            ScalingConfig { scale_in_levels: vec![2.0, 3.0], ..Default::default() },
+            ScalingConfig { scale_in_levels: vec![3.5, 3.0], ..Default::default() },
+            ScalingConfig { scale_in_levels: vec![3.0], scale_in_size: 0.0, ..Default::default() },
+            // Ascending, or outside (z_exit_threshold, z_threshold)
+            ScalingConfig { partial_exit_levels: vec![1.0, 1.5], ..Default::default() },
+            ScalingConfig { partial_exit_levels: vec![3.0], ..Default::default() },
+            ScalingConfig { partial_exit_levels: vec![0.2], ..Default::default() },
+            ScalingConfig { partial_exit_levels: vec![1.0], partial_exit_fraction: 1.0, ..Default::default() },
+        ] {
+            let config = StrategyConfig { scaling, ..Default::default() };
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidScalingParams(_))));
+        }
+
+        // Valid levels, but the kind has no layered positions
+        let scaling = ScalingConfig { scale_in_levels: vec![3.0], ..Default::default() };
+        for kind in [StrategyKind::OuReversion, StrategyKind::Ensemble] {
+            let config = StrategyConfig { scaling: scaling.clone(), ..Default::default() }.with_kind(kind);
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidScalingParams(_))));
+        }
+    }
+    #[test]
+    fn test_ensemble_validation() {
+        let config = StrategyConfig::default().with_kind(StrategyKind::Ensemble);
+        assert_eq!(config.ensemble.members.len(), EnsembleSignal::ALL.len());
+        assert!(config.validate().is_ok());
+
+        let members: Vec<EnsembleMember> =
+            serde_json::from_str(r#"[{"signal": "zscore"}, {"signal": "rsi", "weight": 0.5}]"#).unwrap();
+        assert_eq!(members[0], EnsembleMember::new(EnsembleSignal::ZScore, 1.0));
+        assert_eq!(members[1].weight, 0.5);
+
+        for ensemble in [
+            EnsembleConfig { members: Vec::new(), ..Default::default() },
+            EnsembleConfig { members: vec![EnsembleMember::new(EnsembleSignal::Rsi, 0.0)], ..Default::default() },
+            EnsembleConfig {
+                members: vec![EnsembleMember::new(EnsembleSignal::Ou, 1.0); 2],
+                ..Default::default()
+            },
+            EnsembleConfig { entry_score: 1.5, ..Default::default() },
+            EnsembleConfig { exit_score: 0.5, ..Default::default() },
+            EnsembleConfig { min_scored_votes: 100, ..Default::default() },
+        ] {
+            let config = StrategyConfig { ensemble, ..config.clone() };
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidEnsembleParams(_))));
+        }
+
+        // Member settings are validated only for members that vote
+        let mut config = config;
+        config.kalman.process_noise = 0.0;
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidKalmanParams(_))));
+        config.ensemble.members.retain(|m| m.signal != EnsembleSignal::Kalman);
+        assert!(config.validate().is_ok());
+    }
 }
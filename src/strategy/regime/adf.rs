@@ -0,0 +1,234 @@
+//! ADF-Style Stationarity Regime Detection
+//!
+//! Dickey-Fuller regression of log closes over a rolling window:
+//!   dx[t] = alpha + gamma * x[t-1] + e[t]
+//! The t-statistic of gamma is compared with the Dickey-Fuller critical value
+//! (about -2.86 at 5% with a constant). No lagged difference terms are
+//! included, so this is the plain DF test rather than a full augmented one.
+//!
+//! For mean reversion strategies:
+//! - t below the critical value: Unit root rejected, series is stationary (favorable)
+//! - t near zero: Cannot reject a random walk (neutral)
+//! - t > 0: Explosive / trending (unfavorable)
+
+use serde::{Deserialize, Serialize};
+
+use super::{mean_reversion_confidence, Candle, RegimeDetector, RegimeSignal};
+use crate::strategy::params::ConfigError;
+use crate::strategy::stats::RollingCovariance;
+
+/// Configuration for the ADF-style detector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdfConfig {
+    /// Observations in the rolling regression (candles)
+    pub window: usize,
+    /// t-statistic treated as stationary (Favorable boundary)
+    pub critical_value: f64,
+}
+
+impl Default for AdfConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            critical_value: -2.86,
+        }
+    }
+}
+
+impl AdfConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.window < 20 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "adf window must be >= 20, got {}",
+                self.window
+            )));
+        }
+        if self.critical_value >= 0.0 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "adf critical_value must be < 0, got {}",
+                self.critical_value
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Dickey-Fuller regression result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdfResult {
+    /// Estimated gamma (negative = mean-reverting)
+    pub gamma: f64,
+    /// t-statistic of gamma
+    pub t_stat: f64,
+}
+
+impl AdfResult {
+    /// Half-life of mean reversion in candles, None unless gamma < 0
+    pub fn half_life(&self) -> Option<f64> {
+        (self.gamma < 0.0 && self.gamma > -1.0).then(|| -(2.0_f64.ln()) / (1.0 + self.gamma).ln())
+    }
+}
+
+/// Rolling Dickey-Fuller regime detector
+#[derive(Debug)]
+pub struct AdfRegimeDetector {
+    config: AdfConfig,
+    /// Pairs of (x[t-1], dx[t])
+    regression: RollingCovariance,
+    prev: Option<f64>,
+    result: Option<AdfResult>,
+    signal: Option<RegimeSignal>,
+}
+
+impl AdfRegimeDetector {
+    pub fn new(config: AdfConfig) -> Self {
+        let regression = RollingCovariance::new(config.window);
+        Self {
+            config,
+            regression,
+            prev: None,
+            result: None,
+            signal: None,
+        }
+    }
+
+    /// Latest regression result
+    pub fn result(&self) -> Option<AdfResult> {
+        self.result
+    }
+
+    /// Latest regime signal
+    pub fn signal(&self) -> Option<RegimeSignal> {
+        self.signal
+    }
+
+}
+
+/// Dickey-Fuller regression over the (x[t-1], dx[t]) pairs in `regression`
+fn estimate(regression: &RollingCovariance) -> Option<AdfResult> {
+    let n = regression.count();
+    let m2_x = regression.m2_x();
+    if n < 3 || m2_x <= 0.0 {
+        return None;
+    }
+
+    let gamma = regression.co_moment() / m2_x;
+    let residual_ss = (regression.m2_y() - gamma * regression.co_moment()).max(0.0);
+    let standard_error = (residual_ss / (n - 2) as f64 / m2_x).sqrt();
+    if standard_error <= 0.0 {
+        return None;
+    }
+
+    Some(AdfResult {
+        gamma,
+        t_stat: gamma / standard_error,
+    })
+}
+
+/// Dickey-Fuller test of a whole series, e.g. the residuals of a cointegrating
+/// regression. None with fewer than 4 values or a constant series.
+pub fn dickey_fuller(series: &[f64]) -> Option<AdfResult> {
+    let mut regression = RollingCovariance::new(series.len().saturating_sub(1));
+    for pair in series.windows(2) {
+        regression.push(pair[0], pair[1] - pair[0]);
+    }
+    estimate(&regression)
+}
+
+impl RegimeDetector for AdfRegimeDetector {
+    fn update(&mut self, candle: &Candle) -> Option<RegimeSignal> {
+        if !(candle.close > 0.0 && candle.close.is_finite()) {
+            return None;
+        }
+        let x = candle.close.ln();
+        if let Some(prev) = self.prev.replace(x) {
+            self.regression.push(prev, x - prev);
+        }
+
+        if !self.regression.is_full() {
+            return None;
+        }
+
+        self.result = estimate(&self.regression);
+        let score = self.result?.t_stat / self.config.critical_value;
+        self.signal = Some(RegimeSignal::from_confidence(mean_reversion_confidence(score)));
+        self.signal
+    }
+
+    fn get_position_multiplier(&self) -> f64 {
+        self.signal.map(|s| s.position_multiplier()).unwrap_or(0.0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.signal.is_some()
+    }
+
+    fn name(&self) -> &'static str {
+        "ADF"
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.result.map(|r| r.t_stat)
+    }
+
+    fn reset(&mut self) {
+        self.regression.reset();
+        self.prev = None;
+        self.result = None;
+        self.signal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::test_support::{candles, SeriesKind};
+
+    fn run(kind: SeriesKind) -> AdfRegimeDetector {
+        let mut detector = AdfRegimeDetector::new(AdfConfig::default());
+        for candle in candles(kind, 300) {
+            detector.update(&candle);
+        }
+        detector
+    }
+
+    #[test]
+    fn test_adf_separates_regimes() {
+        let reverting = run(SeriesKind::MeanReverting);
+        let random = run(SeriesKind::RandomWalk);
+
+        let stationary = reverting.result().unwrap();
+        assert!(stationary.t_stat < -2.86, "{:?}", stationary);
+        assert!(stationary.half_life().unwrap() < 5.0);
+        assert!(matches!(reverting.signal(), Some(RegimeSignal::Favorable(_))));
+
+        let unit_root = random.result().unwrap();
+        assert!(unit_root.t_stat > -2.86, "{:?}", unit_root);
+        assert!(!matches!(random.signal(), Some(RegimeSignal::Favorable(_))));
+    }
+
+    #[test]
+    fn test_dickey_fuller_on_series() {
+        let closes = |kind| -> Vec<f64> { candles(kind, 300).iter().map(|c| c.close.ln()).collect() };
+
+        let stationary = dickey_fuller(&closes(SeriesKind::MeanReverting)).unwrap();
+        assert!(stationary.t_stat < -2.86, "{:?}", stationary);
+        let unit_root = dickey_fuller(&closes(SeriesKind::RandomWalk)).unwrap();
+        assert!(unit_root.t_stat > -2.86, "{:?}", unit_root);
+
+        assert!(dickey_fuller(&[1.0, 2.0]).is_none());
+        assert!(dickey_fuller(&[1.0; 10]).is_none());
+    }
+
+    #[test]
+    fn test_warmup_needs_full_window() {
+        let mut detector = AdfRegimeDetector::new(AdfConfig::default());
+        let data = candles(SeriesKind::MeanReverting, 101);
+        for candle in &data[..100] {
+            assert!(detector.update(candle).is_none());
+        }
+        assert!(detector.update(&data[100]).is_some());
+        assert!(detector.statistic().is_some());
+    }
+}
@@ -13,10 +13,16 @@
 //! - ADX 20-25: Transition zone (neutral)
 //! - ADX > 25: Trending market (unfavorable for mean reversion)
 
+use serde::{Deserialize, Serialize};
+
 use super::{Candle, RegimeDetector, RegimeSignal};
+use crate::strategy::params::ConfigError;
 
 /// Configuration for ADX calculation
-#[derive(Debug, Clone)]
+///
+/// Omitted fields in a `[regime.adx]` section take the crypto-optimized values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default = "AdxConfig::crypto_optimized")]
 pub struct AdxConfig {
     /// Lookback period (default: 14)
     pub period: usize,
@@ -58,6 +64,28 @@ impl AdxConfig {
     pub fn warmup_periods(&self) -> usize {
         2 * self.period - 1
     }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.period < 2 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "adx period must be >= 2, got {}",
+                self.period
+            )));
+        }
+        if self.ranging_threshold > self.trending_threshold {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "adx ranging_threshold {} must be <= trending_threshold {}",
+                self.ranging_threshold, self.trending_threshold
+            )));
+        }
+        if self.entry_threshold <= 0.0 || self.entry_threshold > self.exit_threshold || self.exit_threshold > 100.0 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "adx thresholds must satisfy 0 < entry <= exit <= 100, got {} / {}",
+                self.entry_threshold, self.exit_threshold
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// ADX calculation result
@@ -401,6 +429,12 @@ impl AdxRegimeDetector {
         self.minus_di
     }
 
+    /// Average True Range (Wilder-smoothed true range), None until `period` bars are seen
+    pub fn atr(&self) -> Option<f64> {
+        (self.bars_processed >= self.config.period)
+            .then(|| self.smoothed_tr / self.config.period as f64)
+    }
+
     /// Check if trading is enabled (based on hysteresis)
     pub fn is_trading_enabled(&self) -> bool {
         self.is_trading_enabled
@@ -461,6 +495,10 @@ impl RegimeDetector for AdxRegimeDetector {
         "ADX"
     }
 
+    fn statistic(&self) -> Option<f64> {
+        self.is_valid().then_some(self.adx_value)
+    }
+
     fn reset(&mut self) {
         self.prev_high = None;
         self.prev_low = None;
@@ -629,6 +667,19 @@ mod tests {
         assert!(detector.is_trading_enabled());
     }
 
+    #[test]
+    fn test_config_validation_and_serde() {
+        assert!(AdxConfig::default().validate().is_ok());
+        assert!(AdxConfig::crypto_optimized().validate().is_ok());
+        assert!(AdxConfig { period: 1, ..Default::default() }.validate().is_err());
+        assert!(AdxConfig { entry_threshold: 30.0, exit_threshold: 25.0, ..Default::default() }.validate().is_err());
+        assert!(AdxConfig { ranging_threshold: 30.0, ..Default::default() }.validate().is_err());
+
+        // Omitted fields fall back to the crypto-optimized settings
+        let config: AdxConfig = serde_json::from_str(r#"{"exit_threshold": 35.0}"#).unwrap();
+        assert_eq!(config, AdxConfig { exit_threshold: 35.0, ..AdxConfig::crypto_optimized() });
+    }
+
     #[test]
     fn test_position_multiplier() {
         let mut detector = create_test_detector();
@@ -759,4 +810,20 @@ mod tests {
         // This is tested implicitly through ADX calculation
         assert!(detector.bars_processed == 2);
     }
+    #[test]
+    fn test_atr() {
+        let mut detector = create_test_detector();
+        assert!(detector.atr().is_none());
+
+        // Constant 10-wide bars without gaps: ATR is the bar range
+        for _ in 0..5 {
+            detector.update_candle(&create_candle(105.0, 95.0, 100.0));
+        }
+        assert!((detector.atr().unwrap() - 10.0).abs() < 1e-9);
+
+        for _ in 0..50 {
+            detector.update_candle(&create_candle(102.0, 98.0, 100.0));
+        }
+        assert!((detector.atr().unwrap() - 4.0).abs() < 1e-3);
+    }
 }
@@ -49,8 +49,11 @@ impl CandleBuilder {
     /// Update with a new price tick
     /// Returns Some(Candle) if a candle completed, None otherwise
     pub fn update(&mut self, price: f64) -> Option<Candle> {
-        let now = Instant::now();
+        self.update_at(price, Instant::now())
+    }
 
+    /// Update with a price tick observed at `now` (used when replaying recorded ticks)
+    pub fn update_at(&mut self, price: f64, now: Instant) -> Option<Candle> {
         // Initialize first candle
         if self.candle_start.is_none() {
             self.start_new_candle(price, now);
@@ -177,6 +180,20 @@ mod tests {
         assert_eq!(c.close, 100.0); // Close is from previous candle
     }
 
+    #[test]
+    fn test_update_at_uses_supplied_time() {
+        let mut builder = CandleBuilder::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(builder.update_at(100.0, start).is_none());
+        assert!(builder.update_at(101.0, start + Duration::from_secs(30)).is_none());
+
+        let candle = builder.update_at(102.0, start + Duration::from_secs(60)).unwrap();
+        assert_eq!(candle.open, 100.0);
+        assert_eq!(candle.close, 101.0);
+        assert_eq!(candle.volume, 2.0);
+    }
+
     #[test]
     fn test_high_low_tracking() {
         let mut builder = CandleBuilder::new(Duration::from_millis(100));
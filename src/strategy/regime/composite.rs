@@ -0,0 +1,371 @@
+//! Composite Regime Detection
+//!
+//! Combines several `RegimeDetector`s into the single signal and position
+//! multiplier the orchestrator uses. Only detectors that are ready take part,
+//! so a slow detector does not hold back the others during warmup.
+//!
+//! Combine modes:
+//! - `WeightedAverage`: Weighted mean of confidences and multipliers
+//! - `Minimum`: Most pessimistic detector wins (any veto blocks entries)
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AdfConfig, AdfRegimeDetector, AdxConfig, AdxRegimeDetector, Candle, HurstConfig,
+    HurstRegimeDetector, RegimeDetector, RegimeSignal, VarianceRatioConfig, VarianceRatioDetector,
+    VolatilityConfig, VolatilityRegimeDetector,
+};
+use crate::strategy::params::ConfigError;
+
+/// How component signals are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombineMode {
+    #[default]
+    WeightedAverage,
+    Minimum,
+}
+
+/// Selectable regime detectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegimeDetectorKind {
+    Adx,
+    Hurst,
+    VarianceRatio,
+    Adf,
+}
+
+/// Regime detection configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegimeConfig {
+    /// Detectors to combine (equal weights)
+    pub detectors: Vec<RegimeDetectorKind>,
+    pub combine: CombineMode,
+    pub adx: AdxConfig,
+    pub hurst: HurstConfig,
+    pub variance_ratio: VarianceRatioConfig,
+    pub adf: AdfConfig,
+    /// Realized volatility detector, run alongside the combined detectors
+    pub volatility: VolatilityConfig,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            detectors: vec![RegimeDetectorKind::Adx],
+            combine: CombineMode::default(),
+            adx: AdxConfig::crypto_optimized(),
+            hurst: HurstConfig::default(),
+            variance_ratio: VarianceRatioConfig::default(),
+            adf: AdfConfig::default(),
+            volatility: VolatilityConfig::default(),
+        }
+    }
+}
+
+impl RegimeConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.detectors.is_empty() {
+            return Err(ConfigError::InvalidRegimeParams(
+                "at least one regime detector is required".to_string(),
+            ));
+        }
+        for kind in &self.detectors {
+            match kind {
+                RegimeDetectorKind::Adx => self.adx.validate()?,
+                RegimeDetectorKind::Hurst => self.hurst.validate()?,
+                RegimeDetectorKind::VarianceRatio => self.variance_ratio.validate()?,
+                RegimeDetectorKind::Adf => self.adf.validate()?,
+            }
+        }
+        if self.volatility.enabled {
+            self.volatility.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Build the volatility detector, None unless enabled
+    pub fn build_volatility(&self) -> Option<VolatilityRegimeDetector> {
+        self.volatility
+            .enabled
+            .then(|| VolatilityRegimeDetector::new(self.volatility.clone()))
+    }
+
+    /// Build the composite detector
+    pub fn build(&self) -> CompositeRegimeDetector {
+        self.detectors.iter().fold(
+            CompositeRegimeDetector::new(self.combine),
+            |composite, kind| {
+                let detector: Box<dyn RegimeDetector> = match kind {
+                    RegimeDetectorKind::Adx => Box::new(AdxRegimeDetector::new(self.adx.clone())),
+                    RegimeDetectorKind::Hurst => Box::new(HurstRegimeDetector::new(self.hurst.clone())),
+                    RegimeDetectorKind::VarianceRatio => {
+                        Box::new(VarianceRatioDetector::new(self.variance_ratio.clone()))
+                    }
+                    RegimeDetectorKind::Adf => Box::new(AdfRegimeDetector::new(self.adf.clone())),
+                };
+                composite.with_detector(detector, 1.0)
+            },
+        )
+    }
+}
+
+/// Per-detector view for status display
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegimeComponent {
+    pub name: &'static str,
+    pub weight: f64,
+    pub ready: bool,
+    /// Underlying statistic (ADX, H, VR, t), None while warming up
+    pub statistic: Option<f64>,
+    /// Latest signal, None while warming up
+    #[serde(skip)]
+    pub signal: Option<RegimeSignal>,
+    pub multiplier: f64,
+}
+
+struct Weighted {
+    detector: Box<dyn RegimeDetector>,
+    weight: f64,
+    signal: Option<RegimeSignal>,
+}
+
+/// Regime detector combining several detectors
+pub struct CompositeRegimeDetector {
+    mode: CombineMode,
+    detectors: Vec<Weighted>,
+    signal: Option<RegimeSignal>,
+}
+
+impl std::fmt::Debug for CompositeRegimeDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeRegimeDetector")
+            .field("mode", &self.mode)
+            .field("detectors", &self.components())
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+impl Default for CompositeRegimeDetector {
+    fn default() -> Self {
+        Self::new(CombineMode::default())
+    }
+}
+
+impl CompositeRegimeDetector {
+    pub fn new(mode: CombineMode) -> Self {
+        Self {
+            mode,
+            detectors: Vec::new(),
+            signal: None,
+        }
+    }
+
+    /// Add a detector; non-positive weights are ignored in the weighted average
+    pub fn with_detector(mut self, detector: Box<dyn RegimeDetector>, weight: f64) -> Self {
+        self.detectors.push(Weighted {
+            detector,
+            weight: weight.max(0.0),
+            signal: None,
+        });
+        self
+    }
+
+    pub fn mode(&self) -> CombineMode {
+        self.mode
+    }
+
+    /// Latest combined signal
+    pub fn signal(&self) -> Option<RegimeSignal> {
+        self.signal
+    }
+
+    /// State of each component detector
+    pub fn components(&self) -> Vec<RegimeComponent> {
+        self.detectors
+            .iter()
+            .map(|w| RegimeComponent {
+                name: w.detector.name(),
+                weight: w.weight,
+                ready: w.detector.is_ready(),
+                statistic: w.detector.statistic(),
+                signal: w.signal,
+                multiplier: w.detector.get_position_multiplier(),
+            })
+            .collect()
+    }
+
+    /// Statistic of the first component with the given name
+    pub fn statistic_of(&self, name: &str) -> Option<f64> {
+        self.detectors
+            .iter()
+            .find(|w| w.detector.name() == name)
+            .and_then(|w| w.detector.statistic())
+    }
+
+    /// Combine a value over ready components according to the mode
+    fn combine(&self, value: impl Fn(&Weighted) -> Option<f64>) -> Option<f64> {
+        let ready = self
+            .detectors
+            .iter()
+            .filter(|w| w.detector.is_ready())
+            .filter_map(|w| value(w).map(|v| (w.weight, v)));
+
+        match self.mode {
+            CombineMode::Minimum => ready.map(|(_, v)| v).reduce(f64::min),
+            CombineMode::WeightedAverage => {
+                let (total, weight) = ready.fold((0.0, 0.0), |(total, weight), (w, v)| (total + w * v, weight + w));
+                (weight > 0.0).then(|| total / weight)
+            }
+        }
+    }
+}
+
+impl RegimeDetector for CompositeRegimeDetector {
+    fn update(&mut self, candle: &Candle) -> Option<RegimeSignal> {
+        for w in &mut self.detectors {
+            if let Some(signal) = w.detector.update(candle) {
+                w.signal = Some(signal);
+            }
+        }
+
+        self.signal = self
+            .combine(|w| w.signal.map(|s| s.confidence()))
+            .map(RegimeSignal::from_confidence);
+        self.signal
+    }
+
+    fn get_position_multiplier(&self) -> f64 {
+        self.combine(|w| Some(w.detector.get_position_multiplier())).unwrap_or(0.0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.detectors.iter().any(|w| w.detector.is_ready())
+    }
+
+    fn name(&self) -> &'static str {
+        "Composite"
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.signal.map(|s| s.confidence())
+    }
+
+    fn reset(&mut self) {
+        for w in &mut self.detectors {
+            w.detector.reset();
+            w.signal = None;
+        }
+        self.signal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::test_support::{candles, SeriesKind};
+
+    /// Detector stuck at a fixed signal
+    struct Fixed(RegimeSignal);
+
+    impl RegimeDetector for Fixed {
+        fn update(&mut self, _candle: &Candle) -> Option<RegimeSignal> {
+            Some(self.0)
+        }
+        fn get_position_multiplier(&self) -> f64 {
+            self.0.position_multiplier()
+        }
+        fn is_ready(&self) -> bool {
+            true
+        }
+        fn name(&self) -> &'static str {
+            "Fixed"
+        }
+        fn reset(&mut self) {}
+    }
+
+    fn candle() -> Candle {
+        Candle::new(100.0, 101.0, 99.0, 100.0, 1000.0)
+    }
+
+    #[test]
+    fn test_weighted_average_and_minimum() {
+        let build = |mode| {
+            CompositeRegimeDetector::new(mode)
+                .with_detector(Box::new(Fixed(RegimeSignal::Favorable(1.0))), 3.0)
+                .with_detector(Box::new(Fixed(RegimeSignal::Unfavorable(0.2))), 1.0)
+        };
+
+        let mut average = build(CombineMode::WeightedAverage);
+        let signal = average.update(&candle()).unwrap();
+        assert!((signal.confidence() - 0.8).abs() < 1e-12);
+        assert!((average.get_position_multiplier() - 0.75).abs() < 1e-12);
+
+        let mut minimum = build(CombineMode::Minimum);
+        assert!(matches!(minimum.update(&candle()), Some(RegimeSignal::Unfavorable(_))));
+        assert_eq!(minimum.get_position_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn test_adx_only_matches_adx_detector() {
+        let mut composite = RegimeConfig::default().build();
+        let mut adx = AdxRegimeDetector::new(AdxConfig::crypto_optimized());
+
+        for c in candles(SeriesKind::RandomWalk, 60) {
+            let expected = adx.update(&c);
+            assert_eq!(composite.update(&c), expected);
+            assert_eq!(composite.is_ready(), adx.is_ready());
+            if adx.is_ready() {
+                assert_eq!(composite.get_position_multiplier(), adx.get_position_multiplier());
+            }
+        }
+        assert_eq!(composite.statistic_of("ADX"), Some(adx.adx()));
+    }
+
+    #[test]
+    fn test_unready_components_are_skipped() {
+        let config = RegimeConfig {
+            detectors: vec![RegimeDetectorKind::Hurst, RegimeDetectorKind::VarianceRatio],
+            hurst: HurstConfig { window: 200, ..Default::default() },
+            ..Default::default()
+        };
+        config.validate().unwrap();
+        let mut composite = config.build();
+
+        for c in candles(SeriesKind::MeanReverting, 150) {
+            composite.update(&c);
+        }
+        let components = composite.components();
+        assert_eq!(components.len(), 2);
+        assert!(!components[0].ready && components[1].ready);
+        assert!(composite.statistic_of("Hurst").is_none());
+        assert!(matches!(composite.signal(), Some(RegimeSignal::Favorable(_))));
+        assert_eq!(composite.get_position_multiplier(), components[1].multiplier);
+
+        composite.reset();
+        assert!(!composite.is_ready());
+        assert!(composite.signal().is_none());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(RegimeConfig::default().validate().is_ok());
+        assert!(RegimeConfig { detectors: vec![], ..Default::default() }.validate().is_err());
+
+        let config: RegimeConfig = toml::from_str(
+            r#"
+            detectors = ["hurst", "variance_ratio", "adf"]
+            combine = "minimum"
+
+            [hurst]
+            max_lag = 1
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.combine, CombineMode::Minimum);
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidRegimeParams(_))));
+    }
+}
@@ -0,0 +1,218 @@
+//! Hurst Exponent Regime Detection
+//!
+//! Estimates the Hurst exponent H of log closes from how the variance of
+//! lagged differences scales with the lag: Var(x[t] - x[t-tau]) ~ tau^(2H).
+//! H is half the OLS slope of ln(variance) on ln(tau) over lags 1, 2, 4, ...
+//!
+//! For mean reversion strategies:
+//! - H < 0.5: Anti-persistent, moves tend to reverse (favorable)
+//! - H ~ 0.5: Random walk (neutral)
+//! - H > 0.5: Persistent, moves tend to continue (unfavorable)
+
+use serde::{Deserialize, Serialize};
+
+use super::{mean_reversion_confidence, Candle, RegimeDetector, RegimeSignal};
+use crate::strategy::params::ConfigError;
+use crate::strategy::stats::RollingDifferences;
+
+/// Configuration for the Hurst exponent detector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HurstConfig {
+    /// Differences per lag in the rolling window (candles)
+    pub window: usize,
+    /// Largest lag; lags are the powers of two up to this
+    pub max_lag: usize,
+    /// Hurst exponent treated as clearly mean-reverting (Favorable boundary)
+    pub favorable_hurst: f64,
+}
+
+impl Default for HurstConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            max_lag: 16,
+            favorable_hurst: 0.4,
+        }
+    }
+}
+
+impl HurstConfig {
+    /// Lags used in the regression: 1, 2, 4, ... up to `max_lag`
+    pub fn lags(&self) -> Vec<usize> {
+        std::iter::successors(Some(1usize), |lag| lag.checked_mul(2))
+            .take_while(|&lag| lag <= self.max_lag)
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.window < 20 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "hurst window must be >= 20, got {}",
+                self.window
+            )));
+        }
+        if self.max_lag < 2 || self.max_lag >= self.window {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "hurst max_lag must be 2..window, got {}",
+                self.max_lag
+            )));
+        }
+        if self.favorable_hurst <= 0.0 || self.favorable_hurst >= 0.5 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "favorable_hurst must be 0 < H < 0.5, got {}",
+                self.favorable_hurst
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rolling Hurst exponent regime detector
+#[derive(Debug)]
+pub struct HurstRegimeDetector {
+    config: HurstConfig,
+    differences: RollingDifferences,
+    hurst: Option<f64>,
+    signal: Option<RegimeSignal>,
+}
+
+impl HurstRegimeDetector {
+    pub fn new(config: HurstConfig) -> Self {
+        let differences = RollingDifferences::new(&config.lags(), config.window);
+        Self {
+            config,
+            differences,
+            hurst: None,
+            signal: None,
+        }
+    }
+
+    /// Latest Hurst exponent estimate
+    pub fn hurst(&self) -> Option<f64> {
+        self.hurst
+    }
+
+    /// Latest regime signal
+    pub fn signal(&self) -> Option<RegimeSignal> {
+        self.signal
+    }
+
+    fn estimate(&self) -> Option<f64> {
+        let points: Vec<(f64, f64)> = self
+            .differences
+            .iter()
+            .filter_map(|(lag, stats)| {
+                let variance = stats.sample_variance()?;
+                (variance > 0.0).then(|| ((lag as f64).ln(), variance.ln()))
+            })
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+
+        Some(sxy / sxx / 2.0)
+    }
+}
+
+impl RegimeDetector for HurstRegimeDetector {
+    fn update(&mut self, candle: &Candle) -> Option<RegimeSignal> {
+        if !(candle.close > 0.0 && candle.close.is_finite()) {
+            return None;
+        }
+        self.differences.push(candle.close.ln());
+
+        if !self.differences.is_full() {
+            return None;
+        }
+
+        self.hurst = self.estimate();
+        let score = (0.5 - self.hurst?) / (0.5 - self.config.favorable_hurst);
+        self.signal = Some(RegimeSignal::from_confidence(mean_reversion_confidence(score)));
+        self.signal
+    }
+
+    fn get_position_multiplier(&self) -> f64 {
+        self.signal.map(|s| s.position_multiplier()).unwrap_or(0.0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.signal.is_some()
+    }
+
+    fn name(&self) -> &'static str {
+        "Hurst"
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.hurst
+    }
+
+    fn reset(&mut self) {
+        self.differences.reset();
+        self.hurst = None;
+        self.signal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::test_support::{candles, SeriesKind};
+
+    fn run(kind: SeriesKind) -> HurstRegimeDetector {
+        let mut detector = HurstRegimeDetector::new(HurstConfig::default());
+        for candle in candles(kind, 400) {
+            detector.update(&candle);
+        }
+        detector
+    }
+
+    #[test]
+    fn test_lags_are_powers_of_two() {
+        assert_eq!(HurstConfig::default().lags(), vec![1, 2, 4, 8, 16]);
+        assert!(HurstConfig { max_lag: 1, ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_hurst_separates_regimes() {
+        let reverting = run(SeriesKind::MeanReverting);
+        let random = run(SeriesKind::RandomWalk);
+        let trending = run(SeriesKind::Trending);
+
+        let h_reverting = reverting.hurst().unwrap();
+        let h_random = random.hurst().unwrap();
+        let h_trending = trending.hurst().unwrap();
+        assert!(h_reverting < 0.3, "mean-reverting H = {}", h_reverting);
+        assert!((h_random - 0.5).abs() < 0.15, "random walk H = {}", h_random);
+        assert!(h_trending > 0.6, "trending H = {}", h_trending);
+
+        assert!(matches!(reverting.signal(), Some(RegimeSignal::Favorable(_))));
+        assert!(matches!(trending.signal(), Some(RegimeSignal::Unfavorable(_))));
+        assert_eq!(trending.get_position_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn test_warmup_and_reset() {
+        let mut detector = HurstRegimeDetector::new(HurstConfig::default());
+        for candle in candles(SeriesKind::MeanReverting, 100) {
+            assert!(detector.update(&candle).is_none());
+        }
+        assert!(!detector.is_ready());
+
+        for candle in candles(SeriesKind::MeanReverting, 50) {
+            detector.update(&candle);
+        }
+        assert!(detector.is_ready());
+
+        detector.reset();
+        assert!(!detector.is_ready());
+        assert!(detector.statistic().is_none());
+    }
+}
@@ -3,12 +3,32 @@
 //! Provides trait-based regime detection to identify when market conditions
 //! are favorable for mean reversion strategies. Uses ADX and other indicators
 //! to filter out trending markets where mean reversion typically fails.
+//!
+//! Detectors:
+//! - `AdxRegimeDetector`: Trend strength (low ADX = ranging market)
+//! - `HurstRegimeDetector`: Hurst exponent (H < 0.5 = anti-persistent)
+//! - `VarianceRatioDetector`: Lo-MacKinlay variance ratio (VR < 1 = reverting)
+//! - `AdfRegimeDetector`: Dickey-Fuller t-statistic (stationarity)
+//! - `CompositeRegimeDetector`: Combines several detectors into one multiplier
+//! - `VolatilityRegimeDetector`: Realized volatility bands (sizing and stop scaling)
 
+pub mod adf;
 pub mod adx;
 mod candle_builder;
+pub mod composite;
+pub mod hurst;
+pub mod variance_ratio;
+pub mod volatility;
 
+pub use adf::{dickey_fuller, AdfConfig, AdfRegimeDetector, AdfResult};
 pub use adx::{AdxRegimeDetector, AdxConfig, AdxResult, TrendRegime, TrendDirection};
 pub use candle_builder::CandleBuilder;
+pub use composite::{CombineMode, CompositeRegimeDetector, RegimeComponent, RegimeConfig, RegimeDetectorKind};
+pub use hurst::{HurstConfig, HurstRegimeDetector};
+pub use variance_ratio::{VarianceRatio, VarianceRatioConfig, VarianceRatioDetector};
+pub use volatility::{
+    VolatilityConfig, VolatilityEstimator, VolatilityRegime, VolatilityRegimeDetector, VolatilityState,
+};
 
 /// OHLC candle data for regime detection
 #[derive(Debug, Clone, Copy)]
@@ -102,10 +122,79 @@ pub trait RegimeDetector: Send + Sync {
     /// Get detector name for logging/display
     fn name(&self) -> &'static str;
 
+    /// Latest underlying statistic (ADX value, Hurst exponent, ...) for display
+    fn statistic(&self) -> Option<f64> {
+        None
+    }
+
     /// Reset detector state
     fn reset(&mut self);
 }
 
+/// Map a mean-reversion score to a confidence
+///
+/// Score 0 is a random walk and lands on the Neutral floor (0.4); score 1 is
+/// the detector's significance boundary and lands on the Favorable floor (0.7).
+/// Negative scores (trending) fall into Unfavorable.
+fn mean_reversion_confidence(score: f64) -> f64 {
+    (0.4 + 0.3 * score).clamp(0.0, 1.0)
+}
+
+/// Synthetic series shared by the detector and strategy tests
+#[cfg(test)]
+pub mod test_support {
+    use super::Candle;
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum SeriesKind {
+        /// AR(1) log price with coefficient 0.5
+        MeanReverting,
+        /// Gaussian random walk in log price
+        RandomWalk,
+        /// Returns follow an AR(1) with coefficient 0.5 (momentum)
+        Trending,
+    }
+
+    /// Deterministic LCG + Box-Muller normal generator, seeded with the field
+    pub struct Normal(pub u64);
+
+    impl Normal {
+        fn uniform(&mut self) -> f64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        }
+
+        pub fn sample(&mut self) -> f64 {
+            let (u1, u2) = (self.uniform(), self.uniform());
+            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+        }
+    }
+
+    /// `n` candles whose closes follow the given process around 100
+    pub fn candles(kind: SeriesKind, n: usize) -> Vec<Candle> {
+        let mut rng = Normal(42);
+        let base = 100.0_f64.ln();
+        let mut x = 0.0;
+        let mut ret = 0.0;
+
+        (0..n)
+            .map(|_| {
+                let shock = 0.01 * rng.sample();
+                match kind {
+                    SeriesKind::MeanReverting => x = 0.5 * x + shock,
+                    SeriesKind::RandomWalk => x += shock,
+                    SeriesKind::Trending => {
+                        ret = 0.5 * ret + shock;
+                        x += ret;
+                    }
+                }
+                let close = (base + x).exp();
+                Candle::new(close, close * 1.001, close * 0.999, close, 1000.0)
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +239,14 @@ mod tests {
         assert_eq!(unfavorable.position_multiplier(), 0.0);
     }
 
+    #[test]
+    fn test_mean_reversion_confidence_boundaries() {
+        assert!(matches!(RegimeSignal::from_confidence(mean_reversion_confidence(1.0)), RegimeSignal::Favorable(_)));
+        assert!(matches!(RegimeSignal::from_confidence(mean_reversion_confidence(0.0)), RegimeSignal::Neutral(_)));
+        assert!(matches!(RegimeSignal::from_confidence(mean_reversion_confidence(-0.5)), RegimeSignal::Unfavorable(_)));
+        assert_eq!(mean_reversion_confidence(5.0), 1.0);
+    }
+
     #[test]
     fn test_confidence_clamping() {
         let over = RegimeSignal::from_confidence(1.5);
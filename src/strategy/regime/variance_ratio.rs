@@ -0,0 +1,198 @@
+//! Variance Ratio Regime Detection
+//!
+//! Lo-MacKinlay variance ratio of log closes over a rolling window:
+//!   VR(q) = Var(q-period returns) / (q * Var(1-period returns))
+//! with overlapping q-period returns. Under a random walk VR = 1 and
+//!   z = (VR - 1) / sqrt(2(2q - 1)(q - 1) / (3qT))
+//! is asymptotically standard normal (homoskedastic form, T = 1-period returns).
+//!
+//! For mean reversion strategies:
+//! - VR < 1 (z < 0): Returns are negatively autocorrelated (favorable)
+//! - VR ~ 1: Random walk (neutral)
+//! - VR > 1 (z > 0): Returns are positively autocorrelated, trending (unfavorable)
+
+use serde::{Deserialize, Serialize};
+
+use super::{mean_reversion_confidence, Candle, RegimeDetector, RegimeSignal};
+use crate::strategy::params::ConfigError;
+use crate::strategy::stats::RollingDifferences;
+
+/// Configuration for the variance ratio detector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VarianceRatioConfig {
+    /// Returns in the rolling window (candles)
+    pub window: usize,
+    /// Aggregation period q in candles
+    pub period: usize,
+    /// |z| treated as significant (Favorable boundary when z is negative)
+    pub significance_z: f64,
+}
+
+impl Default for VarianceRatioConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            period: 4,
+            significance_z: 1.96,
+        }
+    }
+}
+
+impl VarianceRatioConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.period < 2 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "variance ratio period must be >= 2, got {}",
+                self.period
+            )));
+        }
+        if self.window < 4 * self.period {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "variance ratio window must be >= 4 * period, got {}",
+                self.window
+            )));
+        }
+        if self.significance_z <= 0.0 {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "significance_z must be > 0, got {}",
+                self.significance_z
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Variance ratio test result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarianceRatio {
+    /// VR(q)
+    pub ratio: f64,
+    /// Test statistic under the random walk null
+    pub z_score: f64,
+}
+
+/// Rolling Lo-MacKinlay variance ratio regime detector
+#[derive(Debug)]
+pub struct VarianceRatioDetector {
+    config: VarianceRatioConfig,
+    differences: RollingDifferences,
+    result: Option<VarianceRatio>,
+    signal: Option<RegimeSignal>,
+}
+
+impl VarianceRatioDetector {
+    pub fn new(config: VarianceRatioConfig) -> Self {
+        let differences = RollingDifferences::new(&[1, config.period], config.window);
+        Self {
+            config,
+            differences,
+            result: None,
+            signal: None,
+        }
+    }
+
+    /// Latest variance ratio and z-statistic
+    pub fn variance_ratio(&self) -> Option<VarianceRatio> {
+        self.result
+    }
+
+    /// Latest regime signal
+    pub fn signal(&self) -> Option<RegimeSignal> {
+        self.signal
+    }
+
+    fn estimate(&self) -> Option<VarianceRatio> {
+        let q = self.config.period;
+        let var_1 = self.differences.stats(1)?.sample_variance()?;
+        let var_q = self.differences.stats(q)?.sample_variance()?;
+        if var_1 <= 0.0 {
+            return None;
+        }
+
+        let q = q as f64;
+        let t = self.config.window as f64;
+        let ratio = var_q / (q * var_1);
+        let null_variance = 2.0 * (2.0 * q - 1.0) * (q - 1.0) / (3.0 * q * t);
+
+        Some(VarianceRatio {
+            ratio,
+            z_score: (ratio - 1.0) / null_variance.sqrt(),
+        })
+    }
+}
+
+impl RegimeDetector for VarianceRatioDetector {
+    fn update(&mut self, candle: &Candle) -> Option<RegimeSignal> {
+        if !(candle.close > 0.0 && candle.close.is_finite()) {
+            return None;
+        }
+        self.differences.push(candle.close.ln());
+
+        if !self.differences.is_full() {
+            return None;
+        }
+
+        self.result = self.estimate();
+        let score = -self.result?.z_score / self.config.significance_z;
+        self.signal = Some(RegimeSignal::from_confidence(mean_reversion_confidence(score)));
+        self.signal
+    }
+
+    fn get_position_multiplier(&self) -> f64 {
+        self.signal.map(|s| s.position_multiplier()).unwrap_or(0.0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.signal.is_some()
+    }
+
+    fn name(&self) -> &'static str {
+        "VarianceRatio"
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.result.map(|r| r.ratio)
+    }
+
+    fn reset(&mut self) {
+        self.differences.reset();
+        self.result = None;
+        self.signal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::test_support::{candles, SeriesKind};
+
+    fn run(kind: SeriesKind) -> VarianceRatioDetector {
+        let mut detector = VarianceRatioDetector::new(VarianceRatioConfig::default());
+        for candle in candles(kind, 300) {
+            detector.update(&candle);
+        }
+        detector
+    }
+
+    #[test]
+    fn test_variance_ratio_separates_regimes() {
+        let reverting = run(SeriesKind::MeanReverting).variance_ratio().unwrap();
+        let random = run(SeriesKind::RandomWalk).variance_ratio().unwrap();
+        let trending = run(SeriesKind::Trending).variance_ratio().unwrap();
+
+        assert!(reverting.ratio < 0.7 && reverting.z_score < -1.96, "{:?}", reverting);
+        assert!(random.z_score.abs() < 1.96, "{:?}", random);
+        assert!(trending.ratio > 1.3 && trending.z_score > 1.96, "{:?}", trending);
+
+        assert!(matches!(run(SeriesKind::MeanReverting).signal(), Some(RegimeSignal::Favorable(_))));
+        assert!(matches!(run(SeriesKind::Trending).signal(), Some(RegimeSignal::Unfavorable(_))));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(VarianceRatioConfig::default().validate().is_ok());
+        assert!(VarianceRatioConfig { period: 1, ..Default::default() }.validate().is_err());
+        assert!(VarianceRatioConfig { window: 10, ..Default::default() }.validate().is_err());
+    }
+}
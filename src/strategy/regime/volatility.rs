@@ -0,0 +1,348 @@
+//! Realized Volatility Regime Detection
+//!
+//! Estimates per-candle variance of log prices from OHLC ranges and compares
+//! short-window realized volatility with a longer baseline:
+//!   ratio = sqrt(mean variance over `window` / mean variance over `baseline_window`)
+//!
+//! Estimators:
+//! - Parkinson: ln(H/L)^2 / (4 ln 2)
+//! - Garman-Klass: 0.5 ln(H/L)^2 - (2 ln 2 - 1) ln(C/O)^2
+//!
+//! The volatility regime scales position size (smaller in high volatility,
+//! no entries in extreme volatility) and stop-loss / take-profit distances
+//! (wider when volatility is above baseline, tighter when below).
+
+use serde::{Deserialize, Serialize};
+
+use super::{Candle, RegimeDetector, RegimeSignal};
+use crate::strategy::params::ConfigError;
+use crate::strategy::stats::RollingStats;
+
+/// OHLC variance estimator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatilityEstimator {
+    Parkinson,
+    #[default]
+    GarmanKlass,
+}
+
+impl VolatilityEstimator {
+    /// Variance of log price over one candle, None for invalid candles
+    pub fn candle_variance(&self, candle: &Candle) -> Option<f64> {
+        if !candle.is_valid() || candle.low <= 0.0 || candle.open <= 0.0 {
+            return None;
+        }
+        let range = (candle.high / candle.low).ln();
+        let variance = match self {
+            Self::Parkinson => range * range / (4.0 * std::f64::consts::LN_2),
+            Self::GarmanKlass => {
+                let body = (candle.close / candle.open).ln();
+                0.5 * range * range - (2.0 * std::f64::consts::LN_2 - 1.0) * body * body
+            }
+        };
+        Some(variance.max(0.0))
+    }
+}
+
+/// Volatility level relative to the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VolatilityRegime {
+    /// Well below baseline - moves may not cover fees
+    Low,
+    /// Around baseline
+    Normal,
+    /// Above baseline - size down, widen stops
+    High,
+    /// Far above baseline (liquidation cascades) - no entries
+    Extreme,
+    /// Not enough data
+    Unknown,
+}
+
+impl VolatilityRegime {
+    /// Position size multiplier for this regime
+    pub fn position_multiplier(&self) -> f64 {
+        match self {
+            Self::Low => 0.75,
+            Self::Normal => 1.0,
+            Self::High => 0.5,
+            Self::Extreme => 0.0,
+            Self::Unknown => 1.0,
+        }
+    }
+
+    /// Regime signal for combining with other detectors
+    pub fn signal(&self) -> RegimeSignal {
+        match self {
+            Self::Normal | Self::Unknown => RegimeSignal::Favorable(1.0),
+            Self::Low => RegimeSignal::Neutral(0.6),
+            Self::High => RegimeSignal::Neutral(0.5),
+            Self::Extreme => RegimeSignal::Unfavorable(0.0),
+        }
+    }
+}
+
+/// Configuration for the volatility regime detector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolatilityConfig {
+    /// Run the volatility detector alongside the regime detectors
+    pub enabled: bool,
+    pub estimator: VolatilityEstimator,
+    /// Candles in the realized volatility window
+    pub window: usize,
+    /// Candles in the baseline volatility window
+    pub baseline_window: usize,
+    /// Ratio below which volatility is Low
+    pub low_ratio: f64,
+    /// Ratio above which volatility is High
+    pub high_ratio: f64,
+    /// Ratio above which volatility is Extreme
+    pub extreme_ratio: f64,
+    /// Bounds for the stop-loss / take-profit scale (the ratio, clamped)
+    pub min_stop_scale: f64,
+    pub max_stop_scale: f64,
+}
+
+impl Default for VolatilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            estimator: VolatilityEstimator::default(),
+            window: 20,
+            baseline_window: 240,
+            low_ratio: 0.6,
+            high_ratio: 1.5,
+            extreme_ratio: 2.5,
+            min_stop_scale: 0.5,
+            max_stop_scale: 2.0,
+        }
+    }
+}
+
+impl VolatilityConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.window < 2 || self.baseline_window <= self.window {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "volatility windows must satisfy 2 <= window < baseline_window, got {} / {}",
+                self.window, self.baseline_window
+            )));
+        }
+        if !(0.0 < self.low_ratio && self.low_ratio < 1.0 && 1.0 < self.high_ratio && self.high_ratio < self.extreme_ratio) {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "volatility ratios must satisfy 0 < low < 1 < high < extreme, got {} / {} / {}",
+                self.low_ratio, self.high_ratio, self.extreme_ratio
+            )));
+        }
+        if !(0.0 < self.min_stop_scale && self.min_stop_scale <= 1.0 && self.max_stop_scale >= 1.0) {
+            return Err(ConfigError::InvalidRegimeParams(format!(
+                "stop scale bounds must satisfy 0 < min <= 1 <= max, got {} / {}",
+                self.min_stop_scale, self.max_stop_scale
+            )));
+        }
+        Ok(())
+    }
+
+    /// Classify a realized / baseline volatility ratio
+    pub fn classify(&self, ratio: f64) -> VolatilityRegime {
+        if ratio >= self.extreme_ratio {
+            VolatilityRegime::Extreme
+        } else if ratio >= self.high_ratio {
+            VolatilityRegime::High
+        } else if ratio < self.low_ratio {
+            VolatilityRegime::Low
+        } else {
+            VolatilityRegime::Normal
+        }
+    }
+}
+
+/// Volatility state after the latest candle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VolatilityState {
+    pub regime: VolatilityRegime,
+    /// Realized volatility per candle (log-price standard deviation)
+    pub realized: f64,
+    /// Baseline volatility per candle
+    pub baseline: f64,
+    /// realized / baseline
+    pub ratio: f64,
+    /// Multiplier for stop-loss and take-profit distances
+    pub stop_scale: f64,
+}
+
+/// Realized volatility regime detector
+#[derive(Debug)]
+pub struct VolatilityRegimeDetector {
+    config: VolatilityConfig,
+    recent: RollingStats,
+    baseline: RollingStats,
+    state: Option<VolatilityState>,
+}
+
+impl VolatilityRegimeDetector {
+    pub fn new(config: VolatilityConfig) -> Self {
+        let recent = RollingStats::new(config.window);
+        let baseline = RollingStats::new(config.baseline_window);
+        Self {
+            config,
+            recent,
+            baseline,
+            state: None,
+        }
+    }
+
+    /// Latest volatility state
+    pub fn state(&self) -> Option<VolatilityState> {
+        self.state
+    }
+
+    /// Current regime, Unknown while warming up
+    pub fn regime(&self) -> VolatilityRegime {
+        self.state.map(|s| s.regime).unwrap_or(VolatilityRegime::Unknown)
+    }
+
+    /// Stop-loss / take-profit scale, 1.0 while warming up
+    pub fn stop_scale(&self) -> f64 {
+        self.state.map(|s| s.stop_scale).unwrap_or(1.0)
+    }
+
+    pub fn config(&self) -> &VolatilityConfig {
+        &self.config
+    }
+
+    fn estimate(&self) -> Option<VolatilityState> {
+        let recent = self.recent.mean()?;
+        let baseline = self.baseline.mean()?;
+        if baseline <= 0.0 {
+            return None;
+        }
+
+        let ratio = (recent / baseline).sqrt();
+        Some(VolatilityState {
+            regime: self.config.classify(ratio),
+            realized: recent.sqrt(),
+            baseline: baseline.sqrt(),
+            ratio,
+            stop_scale: ratio.clamp(self.config.min_stop_scale, self.config.max_stop_scale),
+        })
+    }
+}
+
+impl RegimeDetector for VolatilityRegimeDetector {
+    fn update(&mut self, candle: &Candle) -> Option<RegimeSignal> {
+        let variance = self.config.estimator.candle_variance(candle)?;
+        self.recent.push(variance);
+        self.baseline.push(variance);
+
+        if !self.baseline.is_full() {
+            return None;
+        }
+
+        self.state = self.estimate();
+        self.state.map(|s| s.regime.signal())
+    }
+
+    fn get_position_multiplier(&self) -> f64 {
+        self.regime().position_multiplier()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.state.is_some()
+    }
+
+    fn name(&self) -> &'static str {
+        "Volatility"
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.state.map(|s| s.ratio)
+    }
+
+    fn reset(&mut self) {
+        self.recent.reset();
+        self.baseline.reset();
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Candle with a high-low range of `range` (fraction of price) and a flat body
+    fn candle(range: f64) -> Candle {
+        Candle::new(100.0, 100.0 * (1.0 + range / 2.0), 100.0 * (1.0 - range / 2.0), 100.0, 1.0)
+    }
+
+    fn config() -> VolatilityConfig {
+        VolatilityConfig {
+            enabled: true,
+            window: 10,
+            baseline_window: 100,
+            ..Default::default()
+        }
+    }
+
+    fn feed(detector: &mut VolatilityRegimeDetector, range: f64, n: usize) {
+        for _ in 0..n {
+            detector.update(&candle(range));
+        }
+    }
+
+    #[test]
+    fn test_estimators() {
+        let c = Candle::new(100.0, 102.0, 98.0, 101.0, 1.0);
+        let range = (102.0_f64 / 98.0).ln();
+        let parkinson = VolatilityEstimator::Parkinson.candle_variance(&c).unwrap();
+        assert!((parkinson - range * range / (4.0 * 2.0_f64.ln())).abs() < 1e-15);
+
+        let gk = VolatilityEstimator::GarmanKlass.candle_variance(&c).unwrap();
+        assert!(gk > 0.0 && gk < 0.5 * range * range);
+
+        let invalid = Candle::new(100.0, 98.0, 102.0, 100.0, 1.0);
+        assert!(VolatilityEstimator::Parkinson.candle_variance(&invalid).is_none());
+    }
+
+    #[test]
+    fn test_classifies_volatility_bands() {
+        let mut detector = VolatilityRegimeDetector::new(config());
+        feed(&mut detector, 0.01, 99);
+        assert!(!detector.is_ready());
+        assert_eq!(detector.stop_scale(), 1.0);
+
+        feed(&mut detector, 0.01, 1);
+        assert_eq!(detector.regime(), VolatilityRegime::Normal);
+        assert!((detector.state().unwrap().ratio - 1.0).abs() < 1e-9);
+        assert_eq!(detector.get_position_multiplier(), 1.0);
+
+        // Doubling the range doubles realized volatility over the short window
+        feed(&mut detector, 0.02, 10);
+        let state = detector.state().unwrap();
+        assert_eq!(state.regime, VolatilityRegime::High);
+        assert!(state.stop_scale > 1.5);
+        assert_eq!(detector.get_position_multiplier(), 0.5);
+
+        feed(&mut detector, 0.06, 10);
+        assert_eq!(detector.regime(), VolatilityRegime::Extreme);
+        assert_eq!(detector.stop_scale(), 2.0);
+        assert!(matches!(detector.regime().signal(), RegimeSignal::Unfavorable(_)));
+
+        feed(&mut detector, 0.001, 100);
+        feed(&mut detector, 0.0001, 10);
+        assert_eq!(detector.regime(), VolatilityRegime::Low);
+        assert_eq!(detector.stop_scale(), 0.5);
+
+        detector.reset();
+        assert_eq!(detector.regime(), VolatilityRegime::Unknown);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(VolatilityConfig::default().validate().is_ok());
+        assert!(VolatilityConfig { baseline_window: 10, ..Default::default() }.validate().is_err());
+        assert!(VolatilityConfig { high_ratio: 3.0, ..Default::default() }.validate().is_err());
+        assert!(VolatilityConfig { min_stop_scale: 0.0, ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! Risk Guard
+//!
+//! Position and risk bookkeeping shared by every `Strategy`:
+//! - Stop loss, take profit and time stop on the open position, scaled by
+//!   the volatility stop scale
+//! - Trailing and break-even stops (`RiskConfig::trailing`)
+//! - Cooldown after a trade and the daily trade / loss limits
+//!
+//! Strategies decide entries and signal exits; the guard owns the
+//! `PositionState` those decisions are confirmed into.
+
+use std::time::{Duration, Instant};
+
+use crate::domain::position::Side;
+use crate::strategy::mean_reversion::PositionState;
+use crate::strategy::params::{RiskConfig, StrategyConfig};
+use crate::strategy::regime::{AdxConfig, AdxRegimeDetector, Candle};
+use crate::strategy::traits::StrategySnapshot;
+
+/// Stops, cooldown and daily limits around one position
+#[derive(Debug)]
+pub struct RiskGuard {
+    risk: RiskConfig,
+    cooldown: Duration,
+    /// Current position state
+    position: PositionState,
+    /// Last trade timestamp for cooldown
+    last_trade_time: Option<Instant>,
+    /// Position entry timestamp for time-based exit
+    entry_time: Option<Instant>,
+    /// Time stop of the open position in hours
+    time_stop_hours: f64,
+    /// Daily trade counter
+    daily_trades: u32,
+    /// Daily P&L tracking
+    daily_pnl: f64,
+    /// Simulated clock for replays (None = wall clock)
+    clock: Option<Instant>,
+    /// Stop-loss / take-profit scale from volatility regime detection
+    stop_scale: f64,
+    /// True range tracker for ATR trailing stops (only with `atr_multiple`)
+    atr: Option<AdxRegimeDetector>,
+}
+
+impl RiskGuard {
+    pub fn new(config: &StrategyConfig) -> Self {
+        Self {
+            risk: config.risk.clone(),
+            cooldown: Duration::from_secs(config.cooldown_seconds),
+            position: PositionState::Flat,
+            last_trade_time: None,
+            entry_time: None,
+            time_stop_hours: config.risk.time_stop_hours,
+            daily_trades: 0,
+            daily_pnl: 0.0,
+            clock: None,
+            stop_scale: 1.0,
+            atr: Self::atr_tracker(&config.risk),
+        }
+    }
+
+    fn atr_tracker(risk: &RiskConfig) -> Option<AdxRegimeDetector> {
+        let trailing = &risk.trailing;
+        trailing.atr_multiple.map(|_| {
+            AdxRegimeDetector::new(AdxConfig {
+                period: trailing.atr_period,
+                ..AdxConfig::default()
+            })
+        })
+    }
+
+    /// Drive cooldown and time stops from a simulated clock instead of the wall clock
+    pub fn set_clock(&mut self, now: Instant) {
+        self.clock = Some(now);
+    }
+
+    /// Current time according to the strategy clock
+    pub fn now(&self) -> Instant {
+        self.clock.unwrap_or_else(Instant::now)
+    }
+
+    /// Scale stop-loss, take-profit and trail distances, e.g. wider in high volatility
+    pub fn set_stop_scale(&mut self, scale: f64) {
+        self.stop_scale = scale;
+    }
+
+    pub fn position(&self) -> PositionState {
+        self.position
+    }
+
+    /// Time stop of the open position in hours
+    pub fn time_stop_hours(&self) -> f64 {
+        self.time_stop_hours
+    }
+
+    pub fn daily_trades(&self) -> u32 {
+        self.daily_trades
+    }
+
+    /// Realized P&L today in percent
+    pub fn daily_pnl(&self) -> f64 {
+        self.daily_pnl
+    }
+
+    /// Time stop of the open position in hours, None when flat
+    pub fn position_time_stop_hours(&self) -> Option<f64> {
+        self.entry_time.map(|_| self.time_stop_hours)
+    }
+
+    /// Time since the open position was entered, None when flat
+    pub fn position_age(&self) -> Option<Duration> {
+        self.entry_time.map(|entry| self.now().saturating_duration_since(entry))
+    }
+
+    /// Resume the position, stops and daily counters of a saved snapshot
+    ///
+    /// The time stop and cooldown count from the original entry when the
+    /// snapshot carries its age; the position keeps the time stop it was
+    /// opened with, or `time_stop_hours` when the snapshot has none.
+    pub fn restore(&mut self, snapshot: &StrategySnapshot) {
+        let now = self.now();
+        self.position = snapshot.position;
+        self.daily_trades = snapshot.daily_trades;
+        self.daily_pnl = snapshot.daily_pnl_pct;
+        self.time_stop_hours = snapshot.time_stop_hours.unwrap_or(self.risk.time_stop_hours);
+        self.entry_time = match snapshot.position {
+            PositionState::Flat => None,
+            _ => Some(
+                snapshot
+                    .position_age_secs
+                    .and_then(|age| now.checked_sub(Duration::from_secs_f64(age.max(0.0))))
+                    .unwrap_or(now),
+            ),
+        };
+        if self.entry_time.is_some() {
+            self.last_trade_time = self.entry_time;
+        }
+    }
+
+    /// ATR of the trailing stop tracker, None unless `atr_multiple` is set and warmed up
+    pub fn atr(&self) -> Option<f64> {
+        self.atr.as_ref().and_then(|atr| atr.atr())
+    }
+
+    /// Return of the open position at `price` in percent, None when flat
+    pub fn pnl_pct(&self, price: f64) -> Option<f64> {
+        match self.position {
+            PositionState::Flat => None,
+            PositionState::Long { entry_price, .. } => Some((price - entry_price) / entry_price * 100.0),
+            PositionState::Short { entry_price, .. } => Some((entry_price - price) / entry_price * 100.0),
+        }
+    }
+
+    /// Feed a closed strategy candle to the ATR tracker
+    pub fn update_candle(&mut self, candle: &Candle) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.update_candle(candle);
+        }
+    }
+
+    /// Whether any stop of the open position is hit at `price`
+    pub fn stop_hit(&self, price: f64) -> bool {
+        self.pnl_pct(price).is_some_and(|pnl_pct| self.is_stop_hit(pnl_pct)) || self.is_trailing_stop_hit(price)
+    }
+
+    /// Check time stop, take profit and stop loss for a position P&L (in percent)
+    pub fn is_stop_hit(&self, pnl_pct: f64) -> bool {
+        // Check time-based exit first
+        if let Some(entry_time) = self.entry_time {
+            let hours_elapsed = self.now().saturating_duration_since(entry_time).as_secs_f64() / 3600.0;
+            if hours_elapsed >= self.time_stop_hours {
+                return true; // Time stop
+            }
+        }
+
+        pnl_pct >= self.risk.take_profit_pct * self.stop_scale
+            || pnl_pct <= -self.risk.stop_loss_pct * self.stop_scale
+    }
+
+    /// Whether the price crossed the trailing or break-even stop of the open position
+    pub fn is_trailing_stop_hit(&self, price: f64) -> bool {
+        match self.position {
+            PositionState::Flat => false,
+            PositionState::Long { stops, .. } => stops.stop_price.is_some_and(|stop| price <= stop),
+            PositionState::Short { stops, .. } => stops.stop_price.is_some_and(|stop| price >= stop),
+        }
+    }
+
+    /// Track the high-water mark and, once in enough profit, trail the stop behind it
+    /// The stop only ever moves in the position's favor
+    pub fn update_trailing(&mut self, price: f64) {
+        let trailing = &self.risk.trailing;
+        let activation_pct = trailing.activation_pct;
+        let trail_pct = trailing.trail_pct * self.stop_scale;
+        // ATR distance when configured and warmed up, else a percentage of the best price
+        let atr_distance = trailing
+            .atr_multiple
+            .zip(self.atr.as_ref().and_then(|atr| atr.atr()))
+            .map(|(multiple, atr)| multiple * atr);
+
+        match &mut self.position {
+            PositionState::Flat => {}
+            PositionState::Long { entry_price, stops } => {
+                let best = stops.high_water.map_or(price, |hw| hw.max(price));
+                stops.high_water = Some(best);
+                if activation_pct.is_some_and(|pct| (best - *entry_price) / *entry_price * 100.0 >= pct) {
+                    let stop = best - atr_distance.unwrap_or(best * trail_pct / 100.0);
+                    stops.stop_price = Some(stops.stop_price.map_or(stop, |s| s.max(stop)));
+                }
+            }
+            PositionState::Short { entry_price, stops } => {
+                let best = stops.high_water.map_or(price, |hw| hw.min(price));
+                stops.high_water = Some(best);
+                if activation_pct.is_some_and(|pct| (*entry_price - best) / *entry_price * 100.0 >= pct) {
+                    let stop = best + atr_distance.unwrap_or(best * trail_pct / 100.0);
+                    stops.stop_price = Some(stops.stop_price.map_or(stop, |s| s.min(stop)));
+                }
+            }
+        }
+    }
+
+    /// Move the stop to break-even once the signal has partially reverted toward the mean
+    ///
+    /// `signal` follows the z-score convention: negative when stretched long
+    /// (oversold), positive when stretched short.
+    pub fn update_break_even(&mut self, signal: f64) {
+        let trailing = &self.risk.trailing;
+        let Some(reversion) = trailing.break_even_reversion else {
+            return;
+        };
+        let offset = trailing.break_even_offset_pct / 100.0;
+
+        match &mut self.position {
+            PositionState::Flat => {}
+            PositionState::Long { entry_price, stops } => {
+                // Entry z is negative for longs; reversion pulls it toward zero
+                if let Some(entry_z) = stops.entry_z.filter(|_| !stops.break_even) {
+                    if signal >= entry_z * (1.0 - reversion) {
+                        let stop = *entry_price * (1.0 + offset);
+                        stops.stop_price = Some(stops.stop_price.map_or(stop, |s| s.max(stop)));
+                        stops.break_even = true;
+                    }
+                }
+            }
+            PositionState::Short { entry_price, stops } => {
+                if let Some(entry_z) = stops.entry_z.filter(|_| !stops.break_even) {
+                    if signal <= entry_z * (1.0 - reversion) {
+                        let stop = *entry_price * (1.0 - offset);
+                        stops.stop_price = Some(stops.stop_price.map_or(stop, |s| s.min(stop)));
+                        stops.break_even = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check if strategy is in cooldown period
+    pub fn is_in_cooldown(&self) -> bool {
+        self.last_trade_time
+            .is_some_and(|last_trade| self.now().saturating_duration_since(last_trade) < self.cooldown)
+    }
+
+    /// Check if risk limits allow trading
+    pub fn check_risk_limits(&self) -> bool {
+        self.daily_trades < self.risk.max_daily_trades && self.daily_pnl > -self.risk.max_daily_loss_pct
+    }
+
+    /// Open a position at `price`; `entry_signal` arms the break-even stop
+    pub fn open(&mut self, side: Side, price: f64, entry_signal: Option<f64>, time_stop_hours: f64) {
+        let mut position = match side {
+            Side::Long => PositionState::long(price),
+            Side::Short => PositionState::short(price),
+        };
+        if let PositionState::Long { stops, .. } | PositionState::Short { stops, .. } = &mut position {
+            stops.entry_z = entry_signal;
+        }
+        self.position = position;
+        self.last_trade_time = Some(self.now());
+        self.entry_time = Some(self.now());
+        self.time_stop_hours = time_stop_hours;
+        self.daily_trades += 1;
+    }
+
+    /// Move the open position's entry to `average` after adding to it
+    pub fn add_to_position(&mut self, average: f64) {
+        if let PositionState::Long { entry_price, .. } | PositionState::Short { entry_price, .. } =
+            &mut self.position
+        {
+            *entry_price = average;
+        }
+        self.last_trade_time = Some(self.now());
+    }
+
+    /// Book a realized partial exit of `pnl` percent
+    pub fn realize_partial(&mut self, pnl: f64) {
+        self.daily_pnl += pnl;
+        self.last_trade_time = Some(self.now());
+    }
+
+    /// Close the `remaining` size of the position at `price`
+    pub fn close(&mut self, price: f64, remaining: f64) {
+        self.daily_pnl += self.pnl_pct(price).unwrap_or(0.0) * remaining;
+        self.position = PositionState::Flat;
+        self.last_trade_time = Some(self.now());
+        self.entry_time = None;
+    }
+
+    /// Flat, no history, fresh daily counters (the clock and stop scale are kept)
+    pub fn reset(&mut self) {
+        self.atr = Self::atr_tracker(&self.risk);
+        self.position = PositionState::Flat;
+        self.last_trade_time = None;
+        self.entry_time = None;
+        self.time_stop_hours = self.risk.time_stop_hours;
+        self.reset_daily();
+    }
+
+    /// Reset daily counters (call at start of each trading day)
+    pub fn reset_daily(&mut self) {
+        self.daily_trades = 0;
+        self.daily_pnl = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::params::TrailingStopConfig;
+
+    fn guard(risk: RiskConfig, cooldown_seconds: u64) -> RiskGuard {
+        RiskGuard::new(&StrategyConfig { risk, cooldown_seconds, ..Default::default() })
+    }
+
+    #[test]
+    fn test_stops_and_limits() {
+        let mut guard = guard(RiskConfig { max_daily_trades: 1, ..Default::default() }, 60);
+        let start = Instant::now();
+        guard.set_clock(start);
+        assert!(!guard.stop_hit(90.0));
+        assert!(guard.check_risk_limits());
+
+        guard.open(Side::Short, 100.0, None, 1.0);
+        assert!(guard.is_in_cooldown());
+        assert!(!guard.check_risk_limits());
+        assert!(!guard.stop_hit(101.0));
+        assert!(guard.stop_hit(102.5));
+        assert!(guard.stop_hit(98.0));
+
+        // Time stop, then the loss counts against the day
+        guard.set_clock(start + Duration::from_secs(3600));
+        assert!(!guard.is_in_cooldown());
+        assert!(guard.stop_hit(100.0));
+        guard.close(101.0, 1.0);
+        assert_eq!(guard.position(), PositionState::Flat);
+        assert!((guard.daily_pnl() + 1.0).abs() < 1e-9);
+
+        guard.reset_daily();
+        assert!(guard.check_risk_limits());
+    }
+
+    #[test]
+    fn test_trailing_and_break_even() {
+        let risk = RiskConfig {
+            trailing: TrailingStopConfig {
+                activation_pct: Some(1.0),
+                trail_pct: 0.5,
+                break_even_reversion: Some(0.5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut guard = guard(risk, 0);
+        guard.open(Side::Long, 100.0, Some(-2.0), 24.0);
+
+        guard.update_break_even(-1.2);
+        assert!(guard.position().stops().unwrap().stop_price.is_none());
+        guard.update_break_even(-1.0);
+        assert_eq!(guard.position().stops().unwrap().stop_price, Some(100.0));
+
+        guard.update_trailing(101.2);
+        assert!((guard.position().stops().unwrap().stop_price.unwrap() - 100.694).abs() < 1e-9);
+        assert!(!guard.stop_hit(100.8));
+        assert!(guard.stop_hit(100.6));
+    }
+}
@@ -0,0 +1,385 @@
+//! Position Sizing
+//!
+//! Sizes entries from wallet equity instead of a fixed SOL amount:
+//!   size = min(equity * max_position_pct * vol_scale * regime_multiplier,
+//!              equity * max_position_pct)
+//!
+//! - Volatility targeting: vol_scale = target / realized volatility per regime
+//!   candle (the long-run baseline when no target is set), capped at `max_vol_scale`.
+//!   Upsizing can win back a regime cut but never exceeds `max_position_pct`,
+//!   and it replaces the volatility band multiplier rather than stacking on it
+//! - Fractional Kelly: once enough trades have closed, size is also capped at
+//!   `kelly_fraction * f* * equity`, with f* = p - (1 - p) / (avg_win / avg_loss)
+//!   estimated from the most recent `kelly_window` trade returns, partial exits
+//!   weighted by the share of the position they closed
+//!
+//! Every decision carries a human-readable reason for the trade log.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::strategy::params::ConfigError;
+use crate::strategy::regime::VolatilityState;
+
+/// Position sizing configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SizingConfig {
+    /// Size from equity (false = fixed `trade_size_sol` times the regime multiplier)
+    pub enabled: bool,
+    /// Target realized volatility per regime candle (log-price std dev, e.g. 0.002);
+    /// None targets the baseline volatility
+    pub target_volatility: Option<f64>,
+    /// Largest upsizing from volatility targeting
+    pub max_vol_scale: f64,
+    /// Fraction of the Kelly optimum to cap size at (None disables Kelly)
+    pub kelly_fraction: Option<f64>,
+    /// Closed trades required before the Kelly cap applies
+    pub kelly_min_trades: usize,
+    /// Most recent closed trades in the Kelly estimate
+    pub kelly_window: usize,
+    /// Entries smaller than this (SOL) are skipped
+    pub min_size_sol: f64,
+}
+
+impl Default for SizingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_volatility: None,
+            max_vol_scale: 2.0,
+            kelly_fraction: None,
+            kelly_min_trades: 20,
+            kelly_window: 100,
+            min_size_sol: 0.01,
+        }
+    }
+}
+
+impl SizingConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |msg: String| Err(ConfigError::InvalidSizingParams(msg));
+        if self.target_volatility.is_some_and(|v| v <= 0.0) {
+            return invalid(format!("target_volatility must be > 0, got {:?}", self.target_volatility));
+        }
+        if self.max_vol_scale < 1.0 {
+            return invalid(format!("max_vol_scale must be >= 1, got {}", self.max_vol_scale));
+        }
+        if self.kelly_fraction.is_some_and(|f| f <= 0.0 || f > 1.0) {
+            return invalid(format!("kelly_fraction must be 0-1, got {:?}", self.kelly_fraction));
+        }
+        if self.kelly_min_trades == 0 || self.kelly_window < self.kelly_min_trades {
+            return invalid(format!(
+                "kelly windows must satisfy 1 <= kelly_min_trades <= kelly_window, got {} / {}",
+                self.kelly_min_trades, self.kelly_window
+            ));
+        }
+        if self.min_size_sol < 0.0 {
+            return invalid(format!("min_size_sol must be >= 0, got {}", self.min_size_sol));
+        }
+        Ok(())
+    }
+}
+
+/// Market and account state an entry is sized against
+#[derive(Debug, Clone, Copy)]
+pub struct SizingInputs<'a> {
+    /// Wallet equity in quote currency (USD)
+    pub equity: f64,
+    pub price: f64,
+    /// `RiskConfig::max_position_pct`
+    pub max_position_pct: f64,
+    /// Regime multiplier (0.0-1.0), excluding the volatility band multiplier
+    /// when `volatility` is set (vol_scale takes its place)
+    pub regime_multiplier: f64,
+    /// Latest realized volatility, None when the detector is disabled or warming up
+    pub volatility: Option<&'a VolatilityState>,
+}
+
+/// Kelly estimate from closed trades
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KellyEstimate {
+    pub trades: usize,
+    pub win_rate: f64,
+    /// Average win / average loss
+    pub payoff_ratio: f64,
+    /// Full Kelly fraction, clamped to 0-1
+    pub fraction: f64,
+}
+
+/// Size of an entry and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizingDecision {
+    /// Entry size in SOL, 0 to skip the trade
+    pub size_sol: f64,
+    pub reason: String,
+}
+
+/// Position sizer tracking closed trade returns for the Kelly estimate
+#[derive(Debug, Clone)]
+pub struct PositionSizer {
+    config: SizingConfig,
+    /// Closed returns in percent with the share of the position each closed
+    returns: VecDeque<(f64, f64)>,
+}
+
+impl PositionSizer {
+    pub fn new(config: SizingConfig) -> Self {
+        Self {
+            config,
+            returns: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &SizingConfig {
+        &self.config
+    }
+
+    /// Record the return of a closed trade in percent
+    pub fn record_trade(&mut self, return_pct: f64) {
+        self.record_partial(return_pct, 1.0);
+    }
+
+    /// Record a partial exit that closed `weight` (0-1) of the position at `return_pct`
+    ///
+    /// Partial legs count as trades for the window, weighted in the win rate
+    /// and average win / loss by the share they closed.
+    pub fn record_partial(&mut self, return_pct: f64, weight: f64) {
+        if weight <= 0.0 {
+            return;
+        }
+        if self.returns.len() == self.config.kelly_window {
+            self.returns.pop_front();
+        }
+        self.returns.push_back((return_pct, weight.min(1.0)));
+    }
+
+    /// Kelly estimate, None until `kelly_min_trades` trades have closed
+    pub fn kelly(&self) -> Option<KellyEstimate> {
+        let trades = self.returns.len();
+        if trades < self.config.kelly_min_trades {
+            return None;
+        }
+
+        // Weighted by the share of the position each return closed
+        let (mut win_weight, mut loss_weight, mut win_sum, mut loss_sum) = (0.0, 0.0, 0.0, 0.0);
+        for &(return_pct, weight) in &self.returns {
+            if return_pct > 0.0 {
+                win_weight += weight;
+                win_sum += return_pct * weight;
+            } else {
+                loss_weight += weight;
+                loss_sum -= return_pct * weight;
+            }
+        }
+        let win_rate = win_weight / (win_weight + loss_weight);
+        let avg_win = if win_weight > 0.0 { win_sum / win_weight } else { 0.0 };
+        let avg_loss = if loss_weight > 0.0 { loss_sum / loss_weight } else { 0.0 };
+
+        let (payoff_ratio, fraction) = if avg_loss <= 0.0 {
+            // No losing trades yet: the estimate is unbounded, rely on the fraction cap
+            (f64::INFINITY, 1.0)
+        } else {
+            let b = avg_win / avg_loss;
+            let f = if b > 0.0 { win_rate - (1.0 - win_rate) / b } else { 0.0 };
+            (b, f.clamp(0.0, 1.0))
+        };
+
+        Some(KellyEstimate {
+            trades,
+            win_rate,
+            payoff_ratio,
+            fraction,
+        })
+    }
+
+    /// Volatility targeting scale, 1.0 without a volatility estimate
+    fn vol_scale(&self, volatility: Option<&VolatilityState>) -> Option<f64> {
+        let state = volatility?;
+        let target = self.config.target_volatility.unwrap_or(state.baseline);
+        (state.realized > 0.0).then(|| (target / state.realized).min(self.config.max_vol_scale))
+    }
+
+    /// Size an entry
+    pub fn size(&self, inputs: SizingInputs) -> SizingDecision {
+        let max_usd = inputs.equity.max(0.0) * inputs.max_position_pct / 100.0;
+        let mut reason = format!(
+            "equity ${:.2} x {:.1}% = ${:.2}",
+            inputs.equity, inputs.max_position_pct, max_usd
+        );
+
+        let mut size_usd = max_usd;
+        if let Some(scale) = self.vol_scale(inputs.volatility) {
+            size_usd *= scale;
+            reason.push_str(&format!(" | vol x{:.2}", scale));
+        }
+        size_usd *= inputs.regime_multiplier;
+        reason.push_str(&format!(" | regime x{:.0}%", inputs.regime_multiplier * 100.0));
+        if size_usd > max_usd {
+            size_usd = max_usd;
+            reason.push_str(" | capped at max position");
+        }
+
+        if let (Some(fraction), Some(kelly)) = (self.config.kelly_fraction, self.kelly()) {
+            let cap_usd = inputs.equity.max(0.0) * fraction * kelly.fraction;
+            reason.push_str(&format!(
+                " | kelly f*={:.2} (p={:.2}, {} trades) cap ${:.2}",
+                kelly.fraction, kelly.win_rate, kelly.trades, cap_usd
+            ));
+            size_usd = size_usd.min(cap_usd);
+        }
+
+        let size_sol = if inputs.price > 0.0 { size_usd / inputs.price } else { 0.0 };
+        if size_sol < self.config.min_size_sol {
+            reason.push_str(&format!(
+                " -> {:.4} SOL below minimum {:.4}, skipped",
+                size_sol, self.config.min_size_sol
+            ));
+            return SizingDecision { size_sol: 0.0, reason };
+        }
+
+        reason.push_str(&format!(" -> {:.4} SOL", size_sol));
+        SizingDecision { size_sol, reason }
+    }
+
+    /// Fixed size used when sizing is disabled or equity is unavailable
+    pub fn fixed(trade_size_sol: f64, regime_multiplier: f64, note: &str) -> SizingDecision {
+        let size_sol = trade_size_sol * regime_multiplier;
+        SizingDecision {
+            size_sol,
+            reason: format!(
+                "{}fixed {:.4} SOL x {:.0}% regime -> {:.4} SOL",
+                note,
+                trade_size_sol,
+                regime_multiplier * 100.0,
+                size_sol
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::VolatilityRegime;
+
+    fn inputs(volatility: Option<&VolatilityState>) -> SizingInputs<'_> {
+        SizingInputs {
+            equity: 10_000.0,
+            price: 100.0,
+            max_position_pct: 5.0,
+            regime_multiplier: 1.0,
+            volatility,
+        }
+    }
+
+    fn volatility(realized: f64, baseline: f64) -> VolatilityState {
+        VolatilityState {
+            regime: VolatilityRegime::Normal,
+            realized,
+            baseline,
+            ratio: realized / baseline,
+            stop_scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_sizes_from_equity_and_regime() {
+        let sizer = PositionSizer::new(SizingConfig { enabled: true, ..Default::default() });
+        let decision = sizer.size(inputs(None));
+        assert!((decision.size_sol - 5.0).abs() < 1e-12, "{}", decision.reason);
+        assert!(decision.reason.contains("equity $10000.00 x 5.0%"), "{}", decision.reason);
+
+        let half = sizer.size(SizingInputs { regime_multiplier: 0.5, ..inputs(None) });
+        assert!((half.size_sol - 2.5).abs() < 1e-12);
+
+        let tiny = sizer.size(SizingInputs { equity: 10.0, ..inputs(None) });
+        assert_eq!(tiny.size_sol, 0.0);
+        assert!(tiny.reason.ends_with("skipped"), "{}", tiny.reason);
+    }
+
+    #[test]
+    fn test_volatility_targeting() {
+        let sizer = PositionSizer::new(SizingConfig::default());
+
+        // Realized twice the baseline: half size
+        let high = volatility(0.004, 0.002);
+        assert!((sizer.size(inputs(Some(&high))).size_sol - 2.5).abs() < 1e-12);
+
+        // Calm market: never above the max position
+        let calm = volatility(0.0005, 0.002);
+        let capped = sizer.size(inputs(Some(&calm)));
+        assert!((capped.size_sol - 5.0).abs() < 1e-12);
+        assert!(capped.reason.contains("capped at max position"), "{}", capped.reason);
+
+        // Upsizing (at most max_vol_scale) wins back part of a regime cut
+        let calm = volatility(0.0016, 0.002);
+        let cut = sizer.size(SizingInputs { regime_multiplier: 0.5, ..inputs(Some(&calm)) });
+        assert!((cut.size_sol - 3.125).abs() < 1e-12, "{}", cut.reason);
+
+        // Explicit target
+        let sizer = PositionSizer::new(SizingConfig { target_volatility: Some(0.001), ..Default::default() });
+        assert!((sizer.size(inputs(Some(&high))).size_sol - 1.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fractional_kelly_cap() {
+        let config = SizingConfig {
+            kelly_fraction: Some(0.5),
+            kelly_min_trades: 10,
+            kelly_window: 10,
+            ..Default::default()
+        };
+        let mut sizer = PositionSizer::new(config);
+
+        // 60% winners at +2%, 40% losers at -1%: f* = 0.6 - 0.4 / 2 = 0.4
+        for i in 0..10 {
+            assert!(sizer.kelly().is_none());
+            sizer.record_trade(if i % 5 < 3 { 2.0 } else { -1.0 });
+        }
+        let kelly = sizer.kelly().unwrap();
+        assert!((kelly.fraction - 0.4).abs() < 1e-12);
+        assert!((kelly.payoff_ratio - 2.0).abs() < 1e-12);
+
+        // Cap = 10000 * 0.5 * 0.4 = $2000, well above the $500 position limit
+        assert!((sizer.size(inputs(None)).size_sol - 5.0).abs() < 1e-12);
+
+        // A losing streak pushes the edge negative: no size at all
+        for _ in 0..10 {
+            sizer.record_trade(-1.0);
+        }
+        assert_eq!(sizer.kelly().unwrap().fraction, 0.0);
+        let decision = sizer.size(inputs(None));
+        assert_eq!(decision.size_sol, 0.0);
+        assert!(decision.reason.contains("kelly f*=0.00"), "{}", decision.reason);
+    }
+
+    #[test]
+    fn test_partial_exits_weighted() {
+        let mut sizer = PositionSizer::new(SizingConfig { kelly_min_trades: 2, ..Default::default() });
+        sizer.record_trade(2.0);
+        sizer.record_partial(-1.0, 0.25);
+        sizer.record_partial(-1.0, 0.0);
+
+        // The quarter-size loss weighs a quarter of the full win
+        let kelly = sizer.kelly().unwrap();
+        assert_eq!(kelly.trades, 2);
+        assert!((kelly.win_rate - 0.8).abs() < 1e-12);
+        assert!((kelly.payoff_ratio - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(SizingConfig::default().validate().is_ok());
+        let bad = [
+            SizingConfig { kelly_fraction: Some(1.5), ..Default::default() },
+            SizingConfig { target_volatility: Some(0.0), ..Default::default() },
+            SizingConfig { max_vol_scale: 0.5, ..Default::default() },
+            SizingConfig { kelly_min_trades: 50, kelly_window: 10, ..Default::default() },
+        ];
+        for config in bad {
+            assert!(matches!(config.validate(), Err(ConfigError::InvalidSizingParams(_))));
+        }
+    }
+}
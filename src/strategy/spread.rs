@@ -0,0 +1,419 @@
+//! Cointegration Spread Strategy
+//!
+//! Statistical arbitrage between two correlated assets priced in the same
+//! quote token (e.g. mSOL / jitoSOL / bSOL against SOL, or JUP against SOL).
+//! On log prices:
+//!   y(t) = alpha + beta * x(t) + spread(t)
+//!
+//! The hedge ratio beta is estimated either by rolling OLS over the window or
+//! by a Kalman filter that lets [beta, alpha] drift as a random walk. The
+//! spread is standardized over the same window, and entries are only taken
+//! while an Engle-Granger test (Dickey-Fuller on the spread residuals) rejects
+//! a unit root, i.e. while the pair is cointegrated.
+//!
+//! - Long spread (spread too low): buy Y, sell beta worth of X
+//! - Short spread (spread too high): sell Y, buy beta worth of X
+//! - Exit when |z| falls back to `z_exit`, or blows out past `z_stop`
+
+use serde::{Deserialize, Serialize};
+
+use crate::strategy::params::ConfigError;
+use crate::strategy::regime::{dickey_fuller, AdfResult};
+use crate::strategy::stats::{RingBuffer, RollingCovariance, RollingStats};
+
+/// How the hedge ratio is estimated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HedgeEstimator {
+    /// OLS of y on x over the rolling window
+    #[default]
+    Ols,
+    /// Kalman filter on [beta, alpha]
+    Kalman,
+}
+
+/// Spread strategy configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpreadConfig {
+    pub hedge_estimator: HedgeEstimator,
+    /// Candles in the OLS window, the spread z-score and the cointegration test
+    pub window: usize,
+    /// |z| to enter
+    pub z_entry: f64,
+    /// |z| to take profit
+    pub z_exit: f64,
+    /// |z| beyond which the spread is treated as broken and closed
+    pub z_stop: f64,
+    /// Engle-Granger t-statistic the spread residuals must fall below
+    /// (about -3.34 at 5% for two series with a constant)
+    pub critical_value: f64,
+    /// Kalman drift of [beta, alpha] per candle, as delta / (1 - delta)
+    pub kalman_delta: f64,
+    /// Kalman observation noise variance of the log spread
+    pub kalman_observation_noise: f64,
+}
+
+impl Default for SpreadConfig {
+    fn default() -> Self {
+        Self {
+            hedge_estimator: HedgeEstimator::Ols,
+            window: 100,
+            z_entry: 2.0,
+            z_exit: 0.5,
+            z_stop: 4.0,
+            critical_value: -3.34,
+            kalman_delta: 1e-4,
+            kalman_observation_noise: 1e-4,
+        }
+    }
+}
+
+impl SpreadConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.window < 20 {
+            return Err(ConfigError::InvalidSpreadParams(format!(
+                "window must be >= 20, got {}",
+                self.window
+            )));
+        }
+        if !(self.z_exit >= 0.0 && self.z_exit < self.z_entry && self.z_entry < self.z_stop) {
+            return Err(ConfigError::InvalidSpreadParams(format!(
+                "need 0 <= z_exit < z_entry < z_stop, got {} / {} / {}",
+                self.z_exit, self.z_entry, self.z_stop
+            )));
+        }
+        if self.critical_value >= 0.0 {
+            return Err(ConfigError::InvalidSpreadParams(format!(
+                "critical_value must be < 0, got {}",
+                self.critical_value
+            )));
+        }
+        if !(self.kalman_delta > 0.0 && self.kalman_delta < 1.0) || self.kalman_observation_noise <= 0.0 {
+            return Err(ConfigError::InvalidSpreadParams(format!(
+                "kalman_delta must be in (0, 1) and kalman_observation_noise > 0, got {} and {}",
+                self.kalman_delta, self.kalman_observation_noise
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Direction of an open spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpreadSide {
+    /// Long Y, short X
+    Long,
+    /// Short Y, long X
+    Short,
+}
+
+/// Signal for the two legs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadAction {
+    Enter(SpreadSide),
+    Exit,
+    Hold,
+}
+
+/// Estimates after the latest update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadState {
+    /// Hedge ratio: units of log X per unit of log Y
+    pub beta: f64,
+    pub alpha: f64,
+    /// y - beta * x - alpha on log prices
+    pub spread: f64,
+    /// Spread z-score over the window
+    pub z_score: f64,
+    /// Dickey-Fuller regression of the spread residuals
+    pub cointegration: Option<AdfResult>,
+    /// Residuals reject a unit root at `critical_value`
+    pub cointegrated: bool,
+}
+
+/// Kalman filter on [beta, alpha] with random-walk dynamics
+#[derive(Debug, Clone)]
+struct KalmanHedge {
+    theta: [f64; 2],
+    covariance: [[f64; 2]; 2],
+    drift: f64,
+    observation_noise: f64,
+}
+
+impl KalmanHedge {
+    fn new(delta: f64, observation_noise: f64) -> Self {
+        Self {
+            theta: [0.0, 0.0],
+            covariance: [[1.0, 0.0], [0.0, 1.0]],
+            drift: delta / (1.0 - delta),
+            observation_noise,
+        }
+    }
+
+    /// Observe (x, y), returns the prior [beta, alpha] the observation was compared against
+    fn update(&mut self, x: f64, y: f64) -> [f64; 2] {
+        let prior = self.theta;
+        let h = [x, 1.0];
+
+        // Predict: R = P + Q
+        let mut r = self.covariance;
+        r[0][0] += self.drift;
+        r[1][1] += self.drift;
+
+        // R h' and innovation variance h R h' + Ve
+        let rh = [r[0][0] * h[0] + r[0][1] * h[1], r[1][0] * h[0] + r[1][1] * h[1]];
+        let innovation_variance = h[0] * rh[0] + h[1] * rh[1] + self.observation_noise;
+        let innovation = y - (prior[0] * x + prior[1]);
+        let gain = [rh[0] / innovation_variance, rh[1] / innovation_variance];
+
+        self.theta = [prior[0] + gain[0] * innovation, prior[1] + gain[1] * innovation];
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = r[i][j] - gain[i] * rh[j];
+            }
+        }
+        prior
+    }
+}
+
+#[derive(Debug, Clone)]
+enum HedgeModel {
+    Ols(RollingCovariance),
+    Kalman(KalmanHedge),
+}
+
+/// Pairs / spread strategy on two price series
+#[derive(Debug, Clone)]
+pub struct SpreadStrategy {
+    config: SpreadConfig,
+    hedge: HedgeModel,
+    xs: RingBuffer,
+    ys: RingBuffer,
+    spreads: RollingStats,
+    position: Option<SpreadSide>,
+    state: Option<SpreadState>,
+}
+
+impl SpreadStrategy {
+    pub fn new(config: SpreadConfig) -> Self {
+        let hedge = Self::hedge_model(&config);
+        Self {
+            xs: RingBuffer::new(config.window),
+            ys: RingBuffer::new(config.window),
+            spreads: RollingStats::new(config.window),
+            hedge,
+            config,
+            position: None,
+            state: None,
+        }
+    }
+
+    fn hedge_model(config: &SpreadConfig) -> HedgeModel {
+        match config.hedge_estimator {
+            HedgeEstimator::Ols => HedgeModel::Ols(RollingCovariance::new(config.window)),
+            HedgeEstimator::Kalman => {
+                HedgeModel::Kalman(KalmanHedge::new(config.kalman_delta, config.kalman_observation_noise))
+            }
+        }
+    }
+
+    pub fn config(&self) -> &SpreadConfig {
+        &self.config
+    }
+
+    /// Open spread, None when flat
+    pub fn position(&self) -> Option<SpreadSide> {
+        self.position
+    }
+
+    /// Estimates after the latest update
+    pub fn state(&self) -> Option<SpreadState> {
+        self.state
+    }
+
+    /// Feed one pair of prices, returns the signal once the window is full
+    ///
+    /// The Kalman spread uses the hedge ratio from before this observation so
+    /// that the filter does not absorb the deviation it is measuring.
+    pub fn update(&mut self, y_price: f64, x_price: f64) -> Option<SpreadAction> {
+        if !(y_price > 0.0 && x_price > 0.0 && y_price.is_finite() && x_price.is_finite()) {
+            return None;
+        }
+        let (y, x) = (y_price.ln(), x_price.ln());
+        self.ys.push(y);
+        self.xs.push(x);
+
+        let (beta, alpha) = match &mut self.hedge {
+            HedgeModel::Ols(regression) => {
+                regression.push(x, y);
+                let beta = regression.slope()?;
+                (beta, regression.mean_y()? - beta * regression.mean_x()?)
+            }
+            HedgeModel::Kalman(filter) => {
+                let [beta, alpha] = filter.update(x, y);
+                (beta, alpha)
+            }
+        };
+
+        let spread = y - beta * x - alpha;
+        self.spreads.push(spread);
+        if !self.xs.is_full() || !self.spreads.is_full() {
+            return None;
+        }
+
+        let std_dev = self.spreads.std_dev().filter(|s| *s > 0.0)?;
+        let z_score = (spread - self.spreads.mean()?) / std_dev;
+
+        let residuals: Vec<f64> = self.ys.iter().zip(self.xs.iter()).map(|(y, x)| y - beta * x - alpha).collect();
+        let cointegration = dickey_fuller(&residuals);
+        let cointegrated = cointegration.is_some_and(|r| r.t_stat < self.config.critical_value);
+
+        self.state = Some(SpreadState {
+            beta,
+            alpha,
+            spread,
+            z_score,
+            cointegration,
+            cointegrated,
+        });
+        Some(self.signal(z_score, cointegrated))
+    }
+
+    fn signal(&self, z: f64, cointegrated: bool) -> SpreadAction {
+        let config = &self.config;
+        match self.position {
+            None if !cointegrated || z.abs() >= config.z_stop => SpreadAction::Hold,
+            None if z <= -config.z_entry => SpreadAction::Enter(SpreadSide::Long),
+            None if z >= config.z_entry => SpreadAction::Enter(SpreadSide::Short),
+            None => SpreadAction::Hold,
+            Some(SpreadSide::Long) if z >= -config.z_exit || z <= -config.z_stop => SpreadAction::Exit,
+            Some(SpreadSide::Short) if z <= config.z_exit || z >= config.z_stop => SpreadAction::Exit,
+            Some(_) => SpreadAction::Hold,
+        }
+    }
+
+    /// Record that `action` was executed on both legs
+    pub fn confirm(&mut self, action: SpreadAction) {
+        match action {
+            SpreadAction::Enter(side) => self.position = Some(side),
+            SpreadAction::Exit => self.position = None,
+            SpreadAction::Hold => {}
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.hedge = Self::hedge_model(&self.config);
+        self.xs.clear();
+        self.ys.clear();
+        self.spreads.reset();
+        self.position = None;
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::regime::test_support::Normal;
+
+    /// (y, x) prices: x is a random walk; y = 0.2 + 1.5 x + AR(1) noise when
+    /// cointegrated, an independent random walk otherwise
+    fn prices(n: usize, cointegrated: bool) -> Vec<(f64, f64)> {
+        let mut rng = Normal(7);
+        let (mut x, mut y, mut noise) = (3.0_f64, 4.7_f64, 0.0_f64);
+        (0..n)
+            .map(|_| {
+                x += 0.01 * rng.sample();
+                if cointegrated {
+                    noise = 0.5 * noise + 0.005 * rng.sample();
+                    y = 0.2 + 1.5 * x + noise;
+                } else {
+                    y += 0.01 * rng.sample();
+                }
+                (y.exp(), x.exp())
+            })
+            .collect()
+    }
+
+    fn run(config: SpreadConfig, data: &[(f64, f64)]) -> SpreadStrategy {
+        let mut strategy = SpreadStrategy::new(config);
+        for &(y, x) in data {
+            strategy.update(y, x);
+        }
+        strategy
+    }
+
+    #[test]
+    fn test_hedge_ratio_estimators() {
+        let data = prices(400, true);
+
+        let ols = run(SpreadConfig::default(), &data).state().unwrap();
+        assert!((ols.beta - 1.5).abs() < 0.15, "{:?}", ols);
+        assert!(ols.cointegrated, "{:?}", ols);
+
+        let kalman = SpreadConfig { hedge_estimator: HedgeEstimator::Kalman, ..Default::default() };
+        let kalman = run(kalman, &data).state().unwrap();
+        assert!((kalman.beta - 1.5).abs() < 0.3, "{:?}", kalman);
+    }
+
+    /// Share of full windows in which the pair tested as cointegrated
+    fn cointegrated_share(data: &[(f64, f64)]) -> f64 {
+        let mut strategy = SpreadStrategy::new(SpreadConfig::default());
+        let flags: Vec<bool> = data
+            .iter()
+            .filter_map(|&(y, x)| strategy.update(y, x).map(|_| strategy.state().unwrap().cointegrated))
+            .collect();
+        flags.iter().filter(|c| **c).count() as f64 / flags.len() as f64
+    }
+
+    #[test]
+    fn test_cointegration_gate() {
+        let cointegrated = cointegrated_share(&prices(600, true));
+        let independent = cointegrated_share(&prices(600, false));
+        assert!(cointegrated > 0.95, "{}", cointegrated);
+        assert!(independent < 0.2, "{}", independent);
+
+        // Entries are blocked while the pair is not cointegrated
+        let mut strategy = SpreadStrategy::new(SpreadConfig::default());
+        for (y, x) in prices(600, false) {
+            let action = strategy.update(y, x);
+            if !strategy.state().is_some_and(|s| s.cointegrated) {
+                assert!(!matches!(action, Some(SpreadAction::Enter(_))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_enters_and_exits_on_spread_z() {
+        let mut data = prices(200, true);
+        let mut strategy = SpreadStrategy::new(SpreadConfig::default());
+        for &(y, x) in &data {
+            strategy.update(y, x);
+        }
+        assert_eq!(strategy.position(), None);
+
+        // Y drops 1.5% against X: spread far below its mean, buy Y / sell X
+        let (y, x) = *data.last().unwrap();
+        let action = strategy.update(y * 0.985, x);
+        assert_eq!(action, Some(SpreadAction::Enter(SpreadSide::Long)));
+        strategy.confirm(action.unwrap());
+        assert_eq!(strategy.position(), Some(SpreadSide::Long));
+
+        // Spread converges back
+        data = prices(201, true);
+        let (y, x) = data[200];
+        assert_eq!(strategy.update(y, x), Some(SpreadAction::Exit));
+        strategy.confirm(SpreadAction::Exit);
+        assert_eq!(strategy.position(), None);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(SpreadConfig::default().validate().is_ok());
+        assert!(SpreadConfig { window: 10, ..Default::default() }.validate().is_err());
+        assert!(SpreadConfig { z_exit: 2.5, ..Default::default() }.validate().is_err());
+        assert!(SpreadConfig { z_stop: 1.5, ..Default::default() }.validate().is_err());
+        assert!(SpreadConfig { kalman_delta: 1.0, ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,903 @@
+//! Streaming Statistics
+//!
+//! O(1) rolling-window statistics shared by the z-score gate, the OU
+//! estimator and the indicator library:
+//! - `RingBuffer`: fixed-capacity window that hands back the evicted value
+//! - `KahanSum`: compensated running sum (Neumaier variant)
+//! - `RollingStats`: mean / variance over a window
+//! - `RollingCovariance`: co-moments of a paired series over a window
+//! - `RollingAutocorrelation`: lag-1 autocorrelation of a single series
+//! - `EwmaStats`: exponentially weighted mean / variance
+//! - `RollingMedian`: median and median absolute deviation over a window
+//! - `RollingDifferences`: rolling stats of `x[t] - x[t - lag]` for several lags
+//!
+//! Running sums are kept around a shift (the window mean at the last resync)
+//! so that small variances of large values do not cancel out, and are
+//! recomputed from the window once every `capacity` updates to bound drift.
+//! The resync is O(capacity) once per `capacity` updates, O(1) amortized.
+
+/// Fixed-capacity circular buffer of f64 values
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+    data: Vec<f64>,
+    capacity: usize,
+    /// Index of the oldest value once the buffer is full
+    head: usize,
+}
+
+impl RingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            data: Vec::with_capacity(capacity),
+            capacity,
+            head: 0,
+        }
+    }
+
+    /// Append a value, returns the evicted oldest value when full
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        if self.data.len() < self.capacity {
+            self.data.push(value);
+            return None;
+        }
+        let old = std::mem::replace(&mut self.data[self.head], value);
+        self.head = (self.head + 1) % self.capacity;
+        Some(old)
+    }
+
+    /// Value at position `index`, oldest first
+    pub fn get(&self, index: usize) -> Option<f64> {
+        (index < self.data.len()).then(|| self.data[(self.head + index) % self.capacity])
+    }
+
+    pub fn first(&self) -> Option<f64> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<f64> {
+        self.data.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Values oldest first
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        let (wrapped, oldest) = self.data.split_at(self.head);
+        oldest.iter().chain(wrapped.iter()).copied()
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.iter().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.data.len() == self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.head = 0;
+    }
+}
+
+/// Compensated sum that keeps the rounding error of each addition
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    pub fn add(&mut self, value: f64) {
+        let t = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - t) + value;
+        } else {
+            self.compensation += (value - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Rolling mean and variance over the last `capacity` values
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    window: RingBuffer,
+    shift: f64,
+    sum: KahanSum,
+    sum_sq: KahanSum,
+    since_resync: usize,
+}
+
+impl RollingStats {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: RingBuffer::new(capacity),
+            shift: 0.0,
+            sum: KahanSum::default(),
+            sum_sq: KahanSum::default(),
+            since_resync: 0,
+        }
+    }
+
+    /// Add a value, evicting the oldest once the window is full
+    pub fn push(&mut self, value: f64) {
+        if self.window.is_empty() {
+            self.shift = value;
+        }
+
+        let d = value - self.shift;
+        self.sum.add(d);
+        self.sum_sq.add(d * d);
+
+        if let Some(old) = self.window.push(value) {
+            let d = old - self.shift;
+            self.sum.add(-d);
+            self.sum_sq.add(-d * d);
+        }
+
+        self.since_resync += 1;
+        if self.since_resync >= self.window.capacity() {
+            self.resync();
+        }
+    }
+
+    /// Recompute the running sums around the current window mean
+    fn resync(&mut self) {
+        let n = self.window.len();
+        if n == 0 {
+            return;
+        }
+        self.shift = self.window.iter().sum::<f64>() / n as f64;
+        self.sum.reset();
+        self.sum_sq.reset();
+        for value in self.window.iter() {
+            let d = value - self.shift;
+            self.sum.add(d);
+            self.sum_sq.add(d * d);
+        }
+        self.since_resync = 0;
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        let n = self.window.len();
+        (n > 0).then(|| self.shift + self.sum.value() / n as f64)
+    }
+
+    /// Sum of squared deviations from the mean
+    pub fn m2(&self) -> f64 {
+        let n = self.window.len();
+        if n == 0 {
+            return 0.0;
+        }
+        let sum = self.sum.value();
+        (self.sum_sq.value() - sum * sum / n as f64).max(0.0)
+    }
+
+    /// Variance dividing by n
+    pub fn population_variance(&self) -> Option<f64> {
+        let n = self.window.len();
+        (n > 0).then(|| self.m2() / n as f64)
+    }
+
+    /// Variance dividing by n - 1
+    pub fn sample_variance(&self) -> Option<f64> {
+        let n = self.window.len();
+        (n > 1).then(|| self.m2() / (n - 1) as f64)
+    }
+
+    /// Population standard deviation
+    pub fn std_dev(&self) -> Option<f64> {
+        self.population_variance().map(f64::sqrt)
+    }
+
+    pub fn sample_std_dev(&self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    pub fn last(&self) -> Option<f64> {
+        self.window.last()
+    }
+
+    pub fn window(&self) -> &RingBuffer {
+        &self.window
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.shift = 0.0;
+        self.sum.reset();
+        self.sum_sq.reset();
+        self.since_resync = 0;
+    }
+}
+
+/// Rolling co-moments of a paired series (x, y) over the last `capacity` pairs
+#[derive(Debug, Clone)]
+pub struct RollingCovariance {
+    x: RingBuffer,
+    y: RingBuffer,
+    shift_x: f64,
+    shift_y: f64,
+    sum_x: KahanSum,
+    sum_y: KahanSum,
+    sum_xx: KahanSum,
+    sum_yy: KahanSum,
+    sum_xy: KahanSum,
+    since_resync: usize,
+}
+
+impl RollingCovariance {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            x: RingBuffer::new(capacity),
+            y: RingBuffer::new(capacity),
+            shift_x: 0.0,
+            shift_y: 0.0,
+            sum_x: KahanSum::default(),
+            sum_y: KahanSum::default(),
+            sum_xx: KahanSum::default(),
+            sum_yy: KahanSum::default(),
+            sum_xy: KahanSum::default(),
+            since_resync: 0,
+        }
+    }
+
+    /// Add a pair, evicting the oldest once the window is full
+    pub fn push(&mut self, x: f64, y: f64) {
+        if self.x.is_empty() {
+            self.shift_x = x;
+            self.shift_y = y;
+        }
+
+        self.accumulate(x - self.shift_x, y - self.shift_y, 1.0);
+        if let (Some(old_x), Some(old_y)) = (self.x.push(x), self.y.push(y)) {
+            self.accumulate(old_x - self.shift_x, old_y - self.shift_y, -1.0);
+        }
+
+        self.since_resync += 1;
+        if self.since_resync >= self.x.capacity() {
+            self.resync();
+        }
+    }
+
+    fn accumulate(&mut self, dx: f64, dy: f64, sign: f64) {
+        self.sum_x.add(sign * dx);
+        self.sum_y.add(sign * dy);
+        self.sum_xx.add(sign * dx * dx);
+        self.sum_yy.add(sign * dy * dy);
+        self.sum_xy.add(sign * dx * dy);
+    }
+
+    fn resync(&mut self) {
+        let n = self.x.len();
+        if n == 0 {
+            return;
+        }
+        self.shift_x = self.x.iter().sum::<f64>() / n as f64;
+        self.shift_y = self.y.iter().sum::<f64>() / n as f64;
+        for sum in [
+            &mut self.sum_x,
+            &mut self.sum_y,
+            &mut self.sum_xx,
+            &mut self.sum_yy,
+            &mut self.sum_xy,
+        ] {
+            sum.reset();
+        }
+        for i in 0..n {
+            if let (Some(x), Some(y)) = (self.x.get(i), self.y.get(i)) {
+                self.accumulate(x - self.shift_x, y - self.shift_y, 1.0);
+            }
+        }
+        self.since_resync = 0;
+    }
+
+    pub fn count(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn mean_x(&self) -> Option<f64> {
+        let n = self.count();
+        (n > 0).then(|| self.shift_x + self.sum_x.value() / n as f64)
+    }
+
+    pub fn mean_y(&self) -> Option<f64> {
+        let n = self.count();
+        (n > 0).then(|| self.shift_y + self.sum_y.value() / n as f64)
+    }
+
+    /// Sum of (x - mean_x)^2
+    pub fn m2_x(&self) -> f64 {
+        self.centered(self.sum_xx, self.sum_x, self.sum_x).max(0.0)
+    }
+
+    /// Sum of (y - mean_y)^2
+    pub fn m2_y(&self) -> f64 {
+        self.centered(self.sum_yy, self.sum_y, self.sum_y).max(0.0)
+    }
+
+    /// Sum of (x - mean_x)(y - mean_y)
+    pub fn co_moment(&self) -> f64 {
+        self.centered(self.sum_xy, self.sum_x, self.sum_y)
+    }
+
+    fn centered(&self, product: KahanSum, a: KahanSum, b: KahanSum) -> f64 {
+        let n = self.count();
+        if n == 0 {
+            return 0.0;
+        }
+        product.value() - a.value() * b.value() / n as f64
+    }
+
+    /// Sample covariance (divides by n - 1)
+    pub fn covariance(&self) -> Option<f64> {
+        let n = self.count();
+        (n > 1).then(|| self.co_moment() / (n - 1) as f64)
+    }
+
+    /// Pearson correlation, None if either side has no variance
+    pub fn correlation(&self) -> Option<f64> {
+        let m2_x = self.m2_x();
+        let m2_y = self.m2_y();
+        (m2_x > 0.0 && m2_y > 0.0).then(|| self.co_moment() / (m2_x * m2_y).sqrt())
+    }
+
+    /// OLS slope of y on x
+    pub fn slope(&self) -> Option<f64> {
+        let m2_x = self.m2_x();
+        (m2_x > 0.0).then(|| self.co_moment() / m2_x)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.x.is_full()
+    }
+
+    pub fn reset(&mut self) {
+        self.x.clear();
+        self.y.clear();
+        for sum in [
+            &mut self.sum_x,
+            &mut self.sum_y,
+            &mut self.sum_xx,
+            &mut self.sum_yy,
+            &mut self.sum_xy,
+        ] {
+            sum.reset();
+        }
+        self.since_resync = 0;
+    }
+}
+
+/// Rolling statistics of a series plus its lag-1 co-moments
+///
+/// Over a window of n values the lagged pairs are (v[i], v[i+1]) for the
+/// n - 1 consecutive pairs inside the same window.
+#[derive(Debug, Clone)]
+pub struct RollingAutocorrelation {
+    values: RollingStats,
+    lagged: RollingCovariance,
+}
+
+impl RollingAutocorrelation {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: RollingStats::new(capacity),
+            lagged: RollingCovariance::new(capacity.saturating_sub(1)),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if let Some(prev) = self.values.last() {
+            self.lagged.push(prev, value);
+        }
+        self.values.push(value);
+    }
+
+    /// Statistics of the values themselves
+    pub fn values(&self) -> &RollingStats {
+        &self.values
+    }
+
+    /// Co-moments of (previous, current) pairs
+    pub fn lagged(&self) -> &RollingCovariance {
+        &self.lagged
+    }
+
+    /// Lag-1 autocorrelation
+    pub fn autocorrelation(&self) -> Option<f64> {
+        self.lagged.correlation()
+    }
+
+    pub fn last(&self) -> Option<f64> {
+        self.values.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.values.reset();
+        self.lagged.reset();
+    }
+}
+
+/// Exponentially weighted mean and variance
+///
+/// `alpha = 1 - 0.5^(1 / half_life)`, so an observation's weight halves
+/// every `half_life` updates.
+#[derive(Debug, Clone)]
+pub struct EwmaStats {
+    alpha: f64,
+    half_life: f64,
+    mean: Option<f64>,
+    variance: f64,
+    count: usize,
+}
+
+impl EwmaStats {
+    pub fn new(half_life: f64) -> Self {
+        let half_life = half_life.max(f64::EPSILON);
+        Self {
+            alpha: 1.0 - 0.5_f64.powf(1.0 / half_life),
+            half_life,
+            mean: None,
+            variance: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        match self.mean {
+            None => {
+                self.mean = Some(value);
+                self.variance = 0.0;
+            }
+            Some(mean) => {
+                let d = value - mean;
+                self.mean = Some(mean + self.alpha * d);
+                self.variance = (1.0 - self.alpha) * (self.variance + self.alpha * d * d);
+            }
+        }
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.mean
+    }
+
+    pub fn variance(&self) -> Option<f64> {
+        self.mean.map(|_| self.variance)
+    }
+
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+
+    /// Number of values seen since the last reset
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn reset(&mut self) {
+        self.mean = None;
+        self.variance = 0.0;
+        self.count = 0;
+    }
+}
+
+/// Rolling median and median absolute deviation over the last `capacity` values
+///
+/// Keeps the window sorted: O(log n) search plus an O(n) shift per update,
+/// no allocation after construction.
+#[derive(Debug, Clone)]
+pub struct RollingMedian {
+    window: RingBuffer,
+    sorted: Vec<f64>,
+}
+
+impl RollingMedian {
+    pub fn new(capacity: usize) -> Self {
+        let window = RingBuffer::new(capacity);
+        let sorted = Vec::with_capacity(window.capacity());
+        Self { window, sorted }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if let Some(old) = self.window.push(value) {
+            let index = self.sorted.partition_point(|&v| v < old);
+            if index < self.sorted.len() {
+                self.sorted.remove(index);
+            }
+        }
+        let index = self.sorted.partition_point(|&v| v < value);
+        self.sorted.insert(index, value);
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let n = self.sorted.len();
+        if n == 0 {
+            return None;
+        }
+        Some(if n.is_multiple_of(2) {
+            (self.sorted[n / 2 - 1] + self.sorted[n / 2]) / 2.0
+        } else {
+            self.sorted[n / 2]
+        })
+    }
+
+    /// Median of |x - median|, unscaled
+    pub fn mad(&self) -> Option<f64> {
+        let median = self.median()?;
+        let n = self.sorted.len();
+
+        // Deviations on either side of the median are already sorted,
+        // so merge the two runs up to the middle element
+        let split = self.sorted.partition_point(|&v| v < median);
+        let mut below = self.sorted[..split].iter().rev().map(|v| median - v).peekable();
+        let mut above = self.sorted[split..].iter().map(|v| v - median).peekable();
+
+        let mut prev = 0.0;
+        let mut current = 0.0;
+        for _ in 0..=n / 2 {
+            let next = match (below.peek(), above.peek()) {
+                (Some(b), Some(a)) if b <= a => below.next(),
+                (Some(_), None) => below.next(),
+                _ => above.next(),
+            };
+            let Some(next) = next else { break };
+            prev = current;
+            current = next;
+        }
+
+        Some(if n.is_multiple_of(2) { (prev + current) / 2.0 } else { current })
+    }
+
+    pub fn last(&self) -> Option<f64> {
+        self.window.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sorted.clear();
+    }
+}
+
+/// Rolling statistics of lagged differences `x[t] - x[t - lag]`
+///
+/// Each lag keeps its own `RollingStats` over the last `window` differences,
+/// the basis for variance-ratio and Hurst exponent estimates.
+#[derive(Debug, Clone)]
+pub struct RollingDifferences {
+    history: RingBuffer,
+    lags: Vec<usize>,
+    stats: Vec<RollingStats>,
+}
+
+impl RollingDifferences {
+    /// `lags` are deduplicated and sorted; zero lags are dropped
+    pub fn new(lags: &[usize], window: usize) -> Self {
+        let mut lags: Vec<usize> = lags.iter().copied().filter(|&lag| lag > 0).collect();
+        lags.sort_unstable();
+        lags.dedup();
+
+        let max_lag = lags.last().copied().unwrap_or(1);
+        let stats = lags.iter().map(|_| RollingStats::new(window)).collect();
+        Self {
+            history: RingBuffer::new(max_lag + 1),
+            lags,
+            stats,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.history.push(value);
+        let newest = self.history.len() - 1;
+        for (lag, stats) in self.lags.iter().zip(self.stats.iter_mut()) {
+            if let Some(old) = newest.checked_sub(*lag).and_then(|i| self.history.get(i)) {
+                stats.push(value - old);
+            }
+        }
+    }
+
+    pub fn lags(&self) -> &[usize] {
+        &self.lags
+    }
+
+    /// Statistics of the differences at `lag`, if that lag is tracked
+    pub fn stats(&self, lag: usize) -> Option<&RollingStats> {
+        self.lags.iter().position(|&l| l == lag).map(|i| &self.stats[i])
+    }
+
+    /// (lag, stats) pairs in increasing lag order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &RollingStats)> {
+        self.lags.iter().copied().zip(self.stats.iter())
+    }
+
+    /// True once every lag has a full window of differences
+    pub fn is_full(&self) -> bool {
+        self.stats.iter().all(RollingStats::is_full)
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.stats.iter_mut().for_each(RollingStats::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic noisy series around a large level
+    fn series(n: usize, level: f64) -> Vec<f64> {
+        let mut state = 42u64;
+        (0..n)
+            .map(|i| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let noise = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+                level + (i as f64 / 50.0).sin() * 0.01 + noise * 0.001
+            })
+            .collect()
+    }
+
+    fn naive_mean_m2(window: &[f64]) -> (f64, f64) {
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        (mean, window.iter().map(|v| (v - mean).powi(2)).sum())
+    }
+
+    #[test]
+    fn test_ring_buffer_order_and_eviction() {
+        let mut buffer = RingBuffer::new(3);
+        assert_eq!(buffer.push(1.0), None);
+        assert_eq!(buffer.push(2.0), None);
+        assert_eq!(buffer.push(3.0), None);
+        assert_eq!(buffer.push(4.0), Some(1.0));
+        assert_eq!(buffer.push(5.0), Some(2.0));
+
+        assert_eq!(buffer.to_vec(), vec![3.0, 4.0, 5.0]);
+        assert_eq!(buffer.first(), Some(3.0));
+        assert_eq!(buffer.last(), Some(5.0));
+        assert_eq!(buffer.get(3), None);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.last(), None);
+    }
+
+    #[test]
+    fn test_kahan_sum_keeps_small_terms() {
+        let mut sum = KahanSum::default();
+        sum.add(1e16);
+        for _ in 0..1000 {
+            sum.add(1.0);
+        }
+        sum.add(-1e16);
+        assert_eq!(sum.value(), 1000.0);
+    }
+
+    #[test]
+    fn test_rolling_stats_match_recompute() {
+        let data = series(20_000, 1_000_000.0);
+        let mut stats = RollingStats::new(100);
+
+        for (i, &value) in data.iter().enumerate() {
+            stats.push(value);
+            if i % 997 == 0 || i == data.len() - 1 {
+                let window = &data[(i + 1).saturating_sub(100)..=i];
+                let (mean, m2) = naive_mean_m2(window);
+                assert!((stats.mean().unwrap() - mean).abs() < 1e-9);
+                assert!((stats.m2() - m2).abs() < 1e-9 * m2.max(1e-12) + 1e-12, "m2 drifted at {}", i);
+            }
+        }
+        assert!(stats.is_full());
+        assert_eq!(stats.len(), 100);
+    }
+
+    #[test]
+    fn test_rolling_stats_variants() {
+        let mut stats = RollingStats::new(4);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.sample_variance(), None);
+
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.push(value);
+        }
+        // Window 5, 5, 7, 9: mean 6.5, m2 11
+        assert_eq!(stats.mean(), Some(6.5));
+        assert!((stats.population_variance().unwrap() - 2.75).abs() < 1e-12);
+        assert!((stats.sample_variance().unwrap() - 11.0 / 3.0).abs() < 1e-12);
+
+        stats.reset();
+        assert!(stats.is_empty());
+        assert_eq!(stats.mean(), None);
+    }
+
+    #[test]
+    fn test_autocorrelation_matches_recompute() {
+        let data: Vec<f64> = series(5_000, 4.6);
+        let mut rolling = RollingAutocorrelation::new(60);
+        for &value in &data {
+            rolling.push(value);
+        }
+
+        let window = &data[data.len() - 60..];
+        let (x, y) = (&window[..59], &window[1..]);
+        let mx = x.iter().sum::<f64>() / 59.0;
+        let my = y.iter().sum::<f64>() / 59.0;
+        let cov: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+        let vx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+        let vy: f64 = y.iter().map(|b| (b - my).powi(2)).sum();
+
+        let lagged = rolling.lagged();
+        assert_eq!(lagged.count(), 59);
+        assert!((lagged.co_moment() - cov).abs() < 1e-12);
+        assert!((lagged.m2_x() - vx).abs() < 1e-12);
+        assert!((rolling.autocorrelation().unwrap() - cov / (vx * vy).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_covariance_slope() {
+        let mut cov = RollingCovariance::new(10);
+        for i in 0..25 {
+            let x = i as f64;
+            cov.push(x, 3.0 * x + 1.0);
+        }
+        assert!((cov.slope().unwrap() - 3.0).abs() < 1e-12);
+        assert!((cov.correlation().unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(cov.mean_x(), Some(19.5));
+
+        let mut flat = RollingCovariance::new(10);
+        for i in 0..10 {
+            flat.push(i as f64, 2.0);
+        }
+        assert_eq!(flat.correlation(), None);
+    }
+
+    #[test]
+    fn test_ewma_half_life_weighting() {
+        let mut ewma = EwmaStats::new(1.0); // alpha = 0.5
+        assert_eq!(ewma.mean(), None);
+
+        ewma.push(10.0);
+        assert_eq!(ewma.variance(), Some(0.0));
+        ewma.push(20.0);
+        assert_eq!(ewma.mean(), Some(15.0));
+        // (1 - 0.5) * (0 + 0.5 * 100)
+        assert_eq!(ewma.variance(), Some(25.0));
+
+        // Constant input converges to the level with vanishing variance
+        for _ in 0..200 {
+            ewma.push(50.0);
+        }
+        assert!((ewma.mean().unwrap() - 50.0).abs() < 1e-9);
+        assert!(ewma.std_dev().unwrap() < 1e-9);
+
+        ewma.reset();
+        assert_eq!(ewma.count(), 0);
+    }
+
+    #[test]
+    fn test_rolling_median_and_mad() {
+        let mut median = RollingMedian::new(5);
+        for value in [1.0, 2.0, 3.0, 4.0, 100.0] {
+            median.push(value);
+        }
+        // Deviations 2, 1, 0, 1, 97
+        assert_eq!(median.median(), Some(3.0));
+        assert_eq!(median.mad(), Some(1.0));
+
+        // Evicts 1.0: window 2, 3, 4, 100, 5
+        median.push(5.0);
+        assert_eq!(median.median(), Some(4.0));
+        assert_eq!(median.mad(), Some(1.0));
+
+        let mut even = RollingMedian::new(4);
+        for value in [1.0, 2.0, 4.0, 10.0] {
+            even.push(value);
+        }
+        // Median 3, deviations 2, 1, 1, 7
+        assert_eq!(even.median(), Some(3.0));
+        assert_eq!(even.mad(), Some(1.5));
+    }
+
+    #[test]
+    fn test_rolling_median_matches_sort() {
+        let data = series(2_000, 100.0);
+        let mut rolling = RollingMedian::new(31);
+        for (i, &value) in data.iter().enumerate() {
+            rolling.push(value);
+            if i >= 30 && i % 101 == 0 {
+                let mut window = data[i - 30..=i].to_vec();
+                window.sort_by(|a, b| a.total_cmp(b));
+                let median = window[15];
+                let mut deviations: Vec<f64> = window.iter().map(|v| (v - median).abs()).collect();
+                deviations.sort_by(|a, b| a.total_cmp(b));
+
+                assert_eq!(rolling.median(), Some(median));
+                assert_eq!(rolling.mad(), Some(deviations[15]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_differences() {
+        let mut diffs = RollingDifferences::new(&[3, 1, 0, 1], 4);
+        assert_eq!(diffs.lags(), &[1, 3]);
+
+        for i in 0..10 {
+            diffs.push((i * i) as f64);
+        }
+        assert!(diffs.is_full());
+
+        // Lag 1 window: 6..9 => 2i - 1 = 11, 13, 15, 17
+        assert_eq!(diffs.stats(1).unwrap().mean(), Some(14.0));
+        // Lag 3 window: 6..9 => 6i - 9 = 27, 33, 39, 45
+        assert_eq!(diffs.stats(3).unwrap().mean(), Some(36.0));
+        assert!(diffs.stats(2).is_none());
+
+        diffs.reset();
+        assert!(!diffs.is_full());
+        assert!(diffs.iter().all(|(_, stats)| stats.is_empty()));
+    }
+}
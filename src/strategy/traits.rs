@@ -0,0 +1,156 @@
+//! Strategy Trait
+//!
+//! Common interface for tradeable strategies. The orchestrator, backtester
+//! and paper mode all drive a `Box<dyn Strategy>` the same way:
+//! - `update_tick()` on every price, with the closed candle when one closes
+//! - `confirm_trade()` only after a fill, so failed executions leave state untouched
+//! - `snapshot()` for status output and persistence, `restore()` to resume
+//!   a saved snapshot after a restart
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::strategy::ensemble::{EnsembleStrategy, EnsembleTally};
+use crate::strategy::kalman::KalmanState;
+use crate::strategy::layers::PositionLayers;
+use crate::strategy::mean_reversion::{MeanReversionStrategy, PositionState, TradeAction};
+use crate::strategy::multi_timeframe::TimeframeState;
+use crate::strategy::ou_reversion::OuReversionStrategy;
+use crate::strategy::params::{StrategyConfig, StrategyKind};
+use crate::strategy::regime::Candle;
+
+/// A strategy driven by price ticks and closed candles
+pub trait Strategy: Send + Sync + fmt::Debug {
+    /// Strategy name for logs and snapshots
+    fn name(&self) -> &'static str;
+
+    /// Process one tick; `closed_candle` is set when a strategy-timeframe candle closed
+    /// Returns None while warming up. Does NOT change position state.
+    fn update_tick(&mut self, price: f64, closed_candle: Option<&Candle>) -> Option<TradeAction>;
+
+    /// Record an executed trade - call ONLY after the fill is confirmed
+    fn confirm_trade(&mut self, action: TradeAction, price: f64);
+
+    /// Current position state
+    fn position(&self) -> PositionState;
+
+    /// Whether the strategy has enough data to signal
+    fn is_ready(&self) -> bool;
+
+    /// Drive cooldown and time stops from a simulated clock (replays, backtests)
+    fn set_clock(&mut self, now: Instant);
+
+    /// Scale stop-loss and take-profit distances (1.0 = configured values)
+    fn set_stop_scale(&mut self, scale: f64);
+
+    /// Reset all state for a new session
+    fn reset(&mut self);
+
+    /// Reset daily counters (call at start of each trading day)
+    fn reset_daily(&mut self);
+
+    /// Scale for the size of the next entry (1.0 = full size)
+    fn size_multiplier(&self) -> f64 {
+        1.0
+    }
+
+    /// Serializable view of the current state
+    fn snapshot(&self) -> StrategySnapshot;
+
+    /// Resume the position, stops and daily counters of a saved snapshot
+    /// Indicators are not saved and warm up again.
+    fn restore(&mut self, snapshot: &StrategySnapshot);
+}
+
+/// Point-in-time view of a strategy for status output and persistence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategySnapshot {
+    /// Strategy name
+    pub name: String,
+    /// Whether the strategy has enough data to signal
+    pub ready: bool,
+    pub position: PositionState,
+    /// Seconds since the open position was entered, None when flat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_age_secs: Option<f64>,
+    /// Time stop of the open position in hours, None when flat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_stop_hours: Option<f64>,
+    pub daily_trades: u32,
+    /// Daily P&L in percent
+    pub daily_pnl_pct: f64,
+    /// Primary signal value the strategy trades on (e.g. z-score)
+    pub signal: Option<f64>,
+    /// Strategy-specific indicator values
+    pub indicators: BTreeMap<String, f64>,
+    /// Kalman fair value filter state, when the strategy runs one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kalman: Option<KalmanState>,
+    /// Multi-timeframe entry confirmation state, empty when no rules are configured
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timeframes: Vec<TimeframeState>,
+    /// Fills and partial exits of the open position, when the strategy layers positions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layers: Option<PositionLayers>,
+    /// Member votes behind the signal, when the strategy is an ensemble
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ensemble: Option<EnsembleTally>,
+}
+
+/// Build the strategy selected by `config.kind`
+pub fn build_strategy(config: &StrategyConfig) -> Box<dyn Strategy> {
+    match config.kind {
+        StrategyKind::MeanReversion => Box::new(MeanReversionStrategy::new(config.clone())),
+        StrategyKind::OuReversion => Box::new(OuReversionStrategy::new(config.clone())),
+        StrategyKind::Ensemble => Box::new(EnsembleStrategy::new(config.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_strategy_follows_kind() {
+        let config = StrategyConfig::default();
+        assert_eq!(build_strategy(&config).name(), "mean_reversion");
+
+        let config = config.with_kind(StrategyKind::OuReversion);
+        let strategy = build_strategy(&config);
+        assert_eq!(strategy.name(), "ou_reversion");
+        assert_eq!(strategy.position(), PositionState::Flat);
+        assert!(!strategy.is_ready());
+
+        let config = config.with_kind(StrategyKind::Ensemble);
+        assert_eq!(build_strategy(&config).name(), "ensemble");
+    }
+
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let mut strategy = build_strategy(&StrategyConfig::default());
+        for i in 0..60 {
+            strategy.update_tick(100.0 + (i % 3) as f64, Some(&Candle::new(100.0, 103.0, 99.0, 100.0 + (i % 3) as f64, 1.0)));
+        }
+        strategy.confirm_trade(TradeAction::EnterLong, 101.0);
+
+        let snapshot = strategy.snapshot();
+        assert!(snapshot.ready);
+        assert!(matches!(snapshot.position, PositionState::Long { entry_price, .. } if entry_price == 101.0));
+        assert!(snapshot.indicators.contains_key("z_score"));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let parsed: StrategySnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.name, snapshot.name);
+        // serde_json may round the last bit of an f64
+        assert!(matches!(parsed.position, PositionState::Long { entry_price, .. } if entry_price == 101.0));
+        let (entry_z, parsed_z) = (snapshot.position.stops().unwrap().entry_z, parsed.position.stops().unwrap().entry_z);
+        assert!((entry_z.unwrap() - parsed_z.unwrap()).abs() < 1e-9);
+        assert!(parsed.indicators.keys().eq(snapshot.indicators.keys()));
+        for (key, value) in &snapshot.indicators {
+            assert!((parsed.indicators[key] - value).abs() < 1e-9);
+        }
+    }
+}
@@ -8,20 +8,37 @@
 //! At z_threshold = 2.5:
 //! - Only ~1.2% of data points in a normal distribution
 //! - Extreme deviations revert with 65-75% probability
-
-use crate::strategy::params::StrategyConfig;
+//!
+//! The center and scale come from the configured `ZScoreEstimator`:
+//! - Simple: window mean / population std dev, O(1) via `RollingStats`
+//! - Ewma: exponentially weighted mean / std dev with `ewma_half_life`
+//! - Robust: window median / MAD * 1.4826, so a single spike cannot
+//!   inflate the scale and hide the next one
+//! - Kalman: prior fair value / sqrt(innovation variance) from `KalmanFilter`
+//! - Ou: OU fit of log prices, z = (ln price - mu) / (sigma / sqrt(2 theta));
+//!   reported center is the equilibrium price exp(mu)
+
+use crate::strategy::kalman::{KalmanFilter, KalmanState};
+use crate::strategy::ou_process::{OUParams, OUProcess};
+use crate::strategy::params::{StrategyConfig, ZScoreEstimator};
+use crate::strategy::stats::{EwmaStats, RollingMedian, RollingStats};
+
+/// Scales the MAD to a standard deviation for normally distributed data
+const MAD_TO_STD: f64 = 1.4826;
 
 /// Result of z-score calculation
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ZScoreResult {
     /// Current z-score value
     pub z_score: f64,
-    /// Rolling mean used in calculation
+    /// Center used in calculation (window mean, EWMA mean, median or Kalman fair value)
     pub mean: f64,
-    /// Rolling standard deviation
+    /// Scale used in calculation (std dev, EWMA std dev, scaled MAD or innovation std dev)
     pub std_dev: f64,
     /// Current price
     pub current_price: f64,
+    /// Estimator that produced the center and scale
+    pub estimator: ZScoreEstimator,
 }
 
 impl ZScoreResult {
@@ -46,36 +63,58 @@ impl ZScoreResult {
     }
 }
 
+/// Estimator state beyond the shared price window
+#[derive(Debug, Clone)]
+enum EstimatorState {
+    Simple,
+    Ewma(EwmaStats),
+    Robust(RollingMedian),
+    Kalman(KalmanFilter),
+    Ou(OUProcess),
+}
+
 /// Z-Score calculation gate for mean reversion signals
 #[derive(Debug, Clone)]
 pub struct ZScoreGate {
     /// Configuration for the gate
     config: StrategyConfig,
-    /// Price history buffer
-    price_buffer: Vec<f64>,
+    /// Rolling window of prices with running mean/variance
+    stats: RollingStats,
+    /// Center/scale estimator selected by `config.zscore_estimator`
+    estimator: EstimatorState,
 }
 
 impl ZScoreGate {
     /// Create a new z-score gate with the given configuration
     pub fn new(config: StrategyConfig) -> Self {
-        let capacity = config.lookback_period;
-        Self {
-            config,
-            price_buffer: Vec::with_capacity(capacity),
-        }
+        let stats = RollingStats::new(config.lookback_period);
+        let estimator = match config.zscore_estimator {
+            ZScoreEstimator::Simple => EstimatorState::Simple,
+            ZScoreEstimator::Ewma => EstimatorState::Ewma(EwmaStats::new(config.ewma_half_life)),
+            ZScoreEstimator::Robust => EstimatorState::Robust(RollingMedian::new(config.lookback_period)),
+            ZScoreEstimator::Kalman => EstimatorState::Kalman(KalmanFilter::new(config.kalman.clone())),
+            ZScoreEstimator::Ou => EstimatorState::Ou(OUProcess::new(config.lookback_period, config.ou.dt_minutes)),
+        };
+        Self { config, stats, estimator }
     }
 
     /// Add a new price to the buffer and calculate z-score
     pub fn update(&mut self, price: f64) -> Option<ZScoreResult> {
-        self.price_buffer.push(price);
-
-        // Keep buffer at lookback_period size
-        if self.price_buffer.len() > self.config.lookback_period {
-            self.price_buffer.remove(0);
+        self.stats.push(price);
+        match self.estimator {
+            EstimatorState::Simple => {}
+            EstimatorState::Ewma(ref mut ewma) => ewma.push(price),
+            EstimatorState::Robust(ref mut median) => median.push(price),
+            EstimatorState::Kalman(ref mut kalman) => {
+                kalman.update(price);
+            }
+            EstimatorState::Ou(ref mut ou) => {
+                ou.update(price);
+            }
         }
 
         // Need full buffer for calculation
-        if self.price_buffer.len() < self.config.lookback_period {
+        if !self.is_ready() {
             return None;
         }
 
@@ -84,19 +123,27 @@ impl ZScoreGate {
 
     /// Calculate z-score from current buffer
     pub fn calculate(&self) -> Option<ZScoreResult> {
-        if self.price_buffer.len() < self.config.lookback_period {
+        if !self.is_ready() {
             return None;
         }
 
-        let mean = self.rolling_mean();
-        let std_dev = self.rolling_std(mean);
+        let (mean, std_dev) = match self.estimator {
+            EstimatorState::Simple => (self.stats.mean()?, self.stats.std_dev()?),
+            EstimatorState::Ewma(ref ewma) => (ewma.mean()?, ewma.std_dev()?),
+            EstimatorState::Robust(ref median) => (median.median()?, median.mad()? * MAD_TO_STD),
+            EstimatorState::Kalman(ref kalman) => {
+                let state = kalman.state()?;
+                (state.prior_fair_value, state.volatility())
+            }
+            EstimatorState::Ou(ref ou) => return Self::ou_result(ou, self.stats.last()?),
+        };
 
         // Avoid division by zero
         if std_dev < 1e-10 {
             return None;
         }
 
-        let current_price = *self.price_buffer.last()?;
+        let current_price = self.stats.last()?;
         let z_score = (current_price - mean) / std_dev;
 
         Some(ZScoreResult {
@@ -104,26 +151,27 @@ impl ZScoreGate {
             mean,
             std_dev,
             current_price,
+            estimator: self.config.zscore_estimator,
         })
     }
 
-    /// Calculate rolling mean of price buffer
-    fn rolling_mean(&self) -> f64 {
-        let sum: f64 = self.price_buffer.iter().sum();
-        sum / self.price_buffer.len() as f64
+    /// OU z-score on log prices, None until the fit is valid
+    fn ou_result(ou: &OUProcess, current_price: f64) -> Option<ZScoreResult> {
+        let params = ou.params().filter(|params| params.is_valid())?;
+        let mean = params.mu.exp();
+        let log_std_dev = params.sigma / (2.0 * params.theta).sqrt();
+        Some(ZScoreResult {
+            z_score: params.z_score(current_price.ln()),
+            mean,
+            std_dev: mean * log_std_dev,
+            current_price,
+            estimator: ZScoreEstimator::Ou,
+        })
     }
 
-    /// Calculate rolling standard deviation
-    fn rolling_std(&self, mean: f64) -> f64 {
-        let variance: f64 = self.price_buffer
-            .iter()
-            .map(|&price| {
-                let diff = price - mean;
-                diff * diff
-            })
-            .sum::<f64>() / self.price_buffer.len() as f64;
-
-        variance.sqrt()
+    /// Estimator used for the center and scale
+    pub fn estimator(&self) -> ZScoreEstimator {
+        self.config.zscore_estimator
     }
 
     /// Get the current z-threshold from config
@@ -147,22 +195,45 @@ impl ZScoreGate {
 
     /// Reset the price buffer
     pub fn reset(&mut self) {
-        self.price_buffer.clear();
+        self.stats.reset();
+        match self.estimator {
+            EstimatorState::Simple => {}
+            EstimatorState::Ewma(ref mut ewma) => ewma.reset(),
+            EstimatorState::Robust(ref mut median) => median.reset(),
+            EstimatorState::Kalman(ref mut kalman) => kalman.reset(),
+            EstimatorState::Ou(ref mut ou) => ou.reset(),
+        }
+    }
+
+    /// Kalman filter state when the Kalman estimator is selected
+    pub fn kalman_state(&self) -> Option<KalmanState> {
+        match self.estimator {
+            EstimatorState::Kalman(ref kalman) => kalman.state(),
+            _ => None,
+        }
+    }
+
+    /// OU fit when the OU estimator is selected
+    pub fn ou_params(&self) -> Option<&OUParams> {
+        match self.estimator {
+            EstimatorState::Ou(ref ou) => ou.params(),
+            _ => None,
+        }
     }
 
     /// Get number of prices in buffer
     pub fn buffer_len(&self) -> usize {
-        self.price_buffer.len()
+        self.stats.len()
     }
 
     /// Check if buffer is full
     pub fn is_ready(&self) -> bool {
-        self.price_buffer.len() >= self.config.lookback_period
+        self.stats.len() >= self.config.lookback_period
     }
 
-    /// Get the current price buffer (for testing/debugging)
-    pub fn prices(&self) -> &[f64] {
-        &self.price_buffer
+    /// Get the current price buffer, oldest first (for testing/debugging)
+    pub fn prices(&self) -> Vec<f64> {
+        self.stats.window().to_vec()
     }
 }
 
@@ -276,6 +347,25 @@ mod tests {
         assert_eq!(gate.prices()[0], 2.0);
     }
 
+    #[test]
+    fn test_long_run_matches_full_recompute() {
+        let mut gate = create_test_gate();
+        let mut last = None;
+        for i in 0..5_000 {
+            let price = 150.0 + (i as f64 / 7.0).sin() * 0.5 + (i % 11) as f64 * 0.01;
+            last = gate.update(price);
+        }
+
+        let prices = gate.prices();
+        let mean = prices.iter().sum::<f64>() / prices.len() as f64;
+        let std_dev = (prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / prices.len() as f64).sqrt();
+
+        let result = last.unwrap();
+        assert!((result.mean - mean).abs() < 1e-9);
+        assert!((result.std_dev - std_dev).abs() < 1e-9);
+        assert!((result.z_score - (prices[9] - mean) / std_dev).abs() < 1e-6);
+    }
+
     #[test]
     fn test_reset() {
         let mut gate = create_test_gate();
@@ -297,6 +387,7 @@ mod tests {
             mean: 100.0,
             std_dev: 2.0,
             current_price: 95.0,
+            estimator: ZScoreEstimator::Simple,
         };
 
         assert!(result.is_oversold(2.0));
@@ -304,4 +395,93 @@ mod tests {
         assert!(!result.is_neutral(2.0));
         assert_eq!(result.deviation_magnitude(), 2.5);
     }
+
+    fn gate_with(estimator: ZScoreEstimator) -> ZScoreGate {
+        ZScoreGate::new(StrategyConfig {
+            lookback_period: 20,
+            z_threshold: 2.0,
+            zscore_estimator: estimator,
+            ewma_half_life: 5.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_robust_estimator_ignores_previous_spike() {
+        let mut simple = gate_with(ZScoreEstimator::Simple);
+        let mut robust = gate_with(ZScoreEstimator::Robust);
+
+        // Alternating 100/101 with one spike to 130 in the window
+        for i in 0..20 {
+            let price = if i == 10 { 130.0 } else { 100.0 + (i % 2) as f64 };
+            simple.update(price);
+            robust.update(price);
+        }
+
+        // The spike inflates the simple std dev so a real move looks mild
+        let simple_z = simple.update(97.0).unwrap();
+        let robust_z = robust.update(97.0).unwrap();
+        assert_eq!(robust_z.estimator, ZScoreEstimator::Robust);
+        assert!(!simple_z.is_oversold(2.0));
+        assert!(robust_z.is_oversold(2.0));
+        // Window: 97, 100 x8, 101 x10, 130 => median 101, MAD 0.5
+        assert_eq!(robust_z.mean, 101.0);
+        assert!((robust_z.std_dev - 0.5 * MAD_TO_STD).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ewma_estimator_tracks_recent_level() {
+        let mut simple = gate_with(ZScoreEstimator::Simple);
+        let mut ewma = gate_with(ZScoreEstimator::Ewma);
+
+        // Level shift from 100 to 110 halfway through the window
+        for i in 0..20 {
+            let price = if i < 10 { 100.0 } else { 110.0 } + (i % 2) as f64 * 0.2;
+            simple.update(price);
+            ewma.update(price);
+        }
+
+        let simple_z = simple.calculate().unwrap();
+        let ewma_z = ewma.calculate().unwrap();
+        assert_eq!(ewma_z.estimator, ZScoreEstimator::Ewma);
+        assert!(ewma_z.mean > simple_z.mean);
+        assert!(ewma_z.z_score.abs() < simple_z.z_score.abs());
+
+        // Not ready before the window fills, whatever the estimator
+        ewma.reset();
+        assert!(ewma.update(100.0).is_none());
+    }
+
+    #[test]
+    fn test_kalman_estimator_reports_filter_state() {
+        let mut gate = gate_with(ZScoreEstimator::Kalman);
+        let mut result = None;
+        for i in 0..40 {
+            result = gate.update(100.0 + (i % 3) as f64 * 0.5);
+        }
+
+        let result = result.unwrap();
+        let state = gate.kalman_state().unwrap();
+        assert_eq!(result.estimator, ZScoreEstimator::Kalman);
+        assert_eq!(result.mean, state.prior_fair_value);
+        assert!((result.z_score - state.z_score().unwrap()).abs() < 1e-9);
+
+        assert!(gate_with(ZScoreEstimator::Simple).kalman_state().is_none());
+    }
+
+    #[test]
+    fn test_ou_estimator_uses_log_equilibrium() {
+        let mut gate = gate_with(ZScoreEstimator::Ou);
+        let mut result = None;
+        for i in 0..40 {
+            result = gate.update(100.0 + (i as f64 * 0.5).sin());
+        }
+
+        let result = result.unwrap();
+        let params = gate.ou_params().unwrap();
+        assert_eq!(result.estimator, ZScoreEstimator::Ou);
+        assert!((result.mean - params.mu.exp()).abs() < 1e-9);
+        assert!((result.z_score - params.z_score(result.current_price.ln())).abs() < 1e-12);
+        assert!(gate_with(ZScoreEstimator::Kalman).ou_params().is_none());
+    }
 }